@@ -0,0 +1,126 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use plugx_input::{diff::diff, intern_key, merge::merge, Input};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// The same shape as the derived `Deserialize` of `Input` (without the `fast-deserialize`
+// feature), to compare both in one run:
+#[allow(dead_code)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum UntaggedInput {
+    Bool(bool),
+    Int(isize),
+    Float(f64),
+    Str(String),
+    List(Vec<UntaggedInput>),
+    Map(HashMap<String, UntaggedInput>),
+    Null,
+}
+
+// A list of maps that repeat the same keys, like a list of plugin instances or of routes. Run
+// with `--features interned-keys` (and `fast-deserialize`) to compare the cost of the keys:
+fn large_document(item_count: usize) -> Input {
+    Input::from(
+        (0..item_count)
+            .map(|index| {
+                Input::from(HashMap::from([
+                    ("name", Input::from(format!("item-{index}"))),
+                    ("enabled", Input::from(index % 2 == 0)),
+                    ("weight", Input::from(index as isize)),
+                    (
+                        "options",
+                        Input::from(HashMap::from([
+                            ("timeout", Input::from(30)),
+                            ("retry", Input::from(3)),
+                        ])),
+                    ),
+                ]))
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+// A map of 10k keys where 1% of the values changed, 1% were removed, and 1% were added:
+fn large_map_pair(key_count: usize) -> (Input, Input) {
+    let input = Input::from(
+        (0..key_count)
+            .map(|index| (format!("key-{index}"), large_document(1)))
+            .collect::<HashMap<_, _>>(),
+    );
+    let mut other_input = input.clone();
+    let map = other_input.map_mut();
+    (0..key_count).step_by(100).for_each(|index| {
+        *map.get_mut(format!("key-{index}").as_str()).unwrap() = Input::from(index as isize);
+        map.remove(format!("key-{}", index + 1).as_str());
+        map.insert(intern_key(format!("new-key-{index}")), Input::from(true));
+    });
+    (input, other_input)
+}
+
+fn benchmark(criterion: &mut Criterion) {
+    let json = serde_json::to_string(&large_document(10_000)).unwrap();
+    // Deserialized, so that keys are interned with the `interned-keys` feature:
+    let input = serde_json::from_str::<Input>(&json).unwrap();
+    let mut other_input = input.clone();
+    other_input.list_mut()[5_000]
+        .map_mut()
+        .insert("weight".into(), Input::from(-1));
+
+    criterion.bench_function("deserialize", |bencher| {
+        bencher.iter(|| serde_json::from_str::<Input>(black_box(&json)).unwrap())
+    });
+    // About 4.5 MB of JSON:
+    let large_json = serde_json::to_string(&large_document(50_000)).unwrap();
+    let mut group = criterion.benchmark_group("deserialize_large");
+    group.sample_size(10);
+    group.bench_function("input", |bencher| {
+        bencher.iter(|| serde_json::from_str::<Input>(black_box(&large_json)).unwrap())
+    });
+    group.bench_function("untagged", |bencher| {
+        bencher.iter(|| serde_json::from_str::<UntaggedInput>(black_box(&large_json)).unwrap())
+    });
+    group.finish();
+
+    criterion.bench_function("clone", |bencher| {
+        bencher.iter(|| black_box(&input).clone())
+    });
+    criterion.bench_function("eq", |bencher| {
+        bencher.iter(|| black_box(&input) == black_box(&other_input))
+    });
+
+    // Lists are merged and diffed by searching each item in the other list, so these are smaller:
+    let list_input = large_document(1_000);
+    let mut other_list_input = list_input.clone();
+    other_list_input.list_mut()[500]
+        .map_mut()
+        .insert("weight".into(), Input::from(-1));
+    criterion.bench_function("merge_list", |bencher| {
+        bencher.iter(|| merge(&mut list_input.clone(), black_box(&other_list_input)))
+    });
+    criterion.bench_function("diff_list", |bencher| {
+        bencher.iter(|| {
+            let mut count = 0;
+            diff(
+                black_box(&list_input),
+                black_box(&other_list_input),
+                &mut |_| count += 1,
+            );
+            count
+        })
+    });
+
+    let (input, other_input) = large_map_pair(10_000);
+    criterion.bench_function("diff_map", |bencher| {
+        bencher.iter(|| {
+            let mut count = 0;
+            diff(black_box(&input), black_box(&other_input), &mut |_| {
+                count += 1
+            });
+            count
+        })
+    });
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub(crate) fn encode_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (index, byte)| {
+            value | (*byte as u32) << (16 - 8 * index)
+        });
+        (0..4).for_each(|index| {
+            if index <= chunk.len() {
+                text.push(BASE64_ALPHABET[(value >> (18 - 6 * index) & 0x3f) as usize] as char)
+            } else {
+                text.push('=')
+            }
+        });
+    }
+    text
+}
+
+pub(crate) fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(text.len() / 4 * 3);
+    for chunk in text.as_bytes().chunks(4) {
+        let padding = chunk.iter().rev().take_while(|byte| **byte == b'=').count();
+        if padding > 2 {
+            return None;
+        }
+        let mut value = 0u32;
+        for (index, byte) in chunk[..4 - padding].iter().enumerate() {
+            let sextet = BASE64_ALPHABET.iter().position(|c| c == byte)? as u32;
+            value |= sextet << (18 - 6 * index);
+        }
+        (0..3 - padding).for_each(|index| bytes.push((value >> (16 - 8 * index)) as u8));
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64() {
+        for (bytes, text) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(encode_base64(bytes), text);
+            assert_eq!(decode_base64(text).unwrap(), bytes);
+        }
+        assert!(decode_base64("Zm9").is_none());
+        assert!(decode_base64("Z!==").is_none());
+    }
+}
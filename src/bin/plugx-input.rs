@@ -0,0 +1,159 @@
+use anyhow::{anyhow, bail, Context, Result};
+use plugx_input::{
+    diff::{diff, InputDiff},
+    merge::merge,
+    schema::InputSchemaType,
+    Input,
+};
+use std::{fs, process::ExitCode};
+
+const USAGE: &str = "Usage:
+    plugx-input validate <SCHEMA-FILE> <INPUT-FILE>...
+    plugx-input merge <INPUT-FILE>...
+    plugx-input diff <OLD-INPUT-FILE> <NEW-INPUT-FILE>
+    plugx-input schema doc <SCHEMA-FILE>
+    plugx-input schema json-schema <SCHEMA-FILE>
+
+All files are JSON. `validate` prints the validated input (with default values applied), `merge`
+prints the inputs merged from left to right, and `schema doc` prints a Markdown table of all keys
+with their types, defaults, titles, and descriptions. `validate` and `diff` exit with 1 if the input is
+invalid or if there is any difference.";
+
+fn main() -> ExitCode {
+    let argument_list: Vec<String> = std::env::args().skip(1).collect();
+    match run(&argument_list) {
+        Ok((output, success)) => {
+            if !output.is_empty() {
+                println!("{output}");
+            }
+            if success {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::from(1)
+            }
+        }
+        Err(error) => {
+            eprintln!("{error:#}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(argument_list: &[String]) -> Result<(String, bool)> {
+    let argument_list: Vec<&str> = argument_list.iter().map(String::as_str).collect();
+    match argument_list.as_slice() {
+        ["validate", schema_file, input_file_list @ ..] if !input_file_list.is_empty() => {
+            let schema_type = read_schema_type(schema_file)?;
+            let mut output_list = Vec::new();
+            let mut success = true;
+            for input_file in input_file_list {
+                let mut input = read_input(input_file)?;
+                match schema_type.validate(&mut input, None) {
+                    Ok(_) => output_list.push(to_json(&input)?),
+                    Err(error) => {
+                        success = false;
+                        output_list.push(format!("{input_file}: {error}"))
+                    }
+                }
+            }
+            Ok((output_list.join("\n"), success))
+        }
+        ["merge", input_file_list @ ..] if !input_file_list.is_empty() => {
+            let mut input = read_input(input_file_list[0])?;
+            for input_file in &input_file_list[1..] {
+                merge(&mut input, &read_input(input_file)?);
+            }
+            Ok((to_json(&input)?, true))
+        }
+        ["diff", old_input_file, new_input_file] => {
+            let mut diff_list = Vec::new();
+            diff(
+                &read_input(old_input_file)?,
+                &read_input(new_input_file)?,
+                &mut |diff: InputDiff| diff_list.push(diff.to_string()),
+            );
+            let success = diff_list.is_empty();
+            Ok((diff_list.join("\n"), success))
+        }
+        ["schema", "doc", schema_file] => Ok((read_schema_type(schema_file)?.to_markdown(), true)),
+        ["schema", "json-schema", schema_file] => Ok((
+            to_json(&read_schema_type(schema_file)?.to_json_schema_document())?,
+            true,
+        )),
+        ["help" | "--help" | "-h"] => Ok((USAGE.to_string(), true)),
+        _ => bail!("Invalid arguments\n\n{USAGE}"),
+    }
+}
+
+fn read(file: &str) -> Result<String> {
+    fs::read_to_string(file).with_context(|| format!("Could not read {file:?}"))
+}
+
+fn read_input(file: &str) -> Result<Input> {
+    Input::from_json_str_strict(read(file)?.as_str())
+        .map_err(|error| anyhow!("Could not parse {file:?}: {error}"))
+}
+
+fn read_schema_type(file: &str) -> Result<InputSchemaType> {
+    serde_json::from_str(read(file)?.as_str())
+        .map_err(|error| anyhow!("Could not parse schema {file:?}: {error}"))
+}
+
+fn to_json(input: &Input) -> Result<String> {
+    Ok(serde_json::to_string_pretty(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arguments(dir: &tempdir::TempDir, argument_list: &[&str]) -> Vec<String> {
+        argument_list
+            .iter()
+            .map(|argument| {
+                if argument.ends_with(".json") {
+                    dir.path().join(argument).to_string_lossy().to_string()
+                } else {
+                    argument.to_string()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn run() {
+        let dir = tempdir::TempDir::new("plugx-input").unwrap();
+        fs::write(
+            dir.path().join("schema.json"),
+            r#"{"type": "static_map", "items": {"port": {"schema": {"type": "port"}}, "debug": {"schema": {"type": "boolean"}, "default": false}}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("a.json"), r#"{"port": 80}"#).unwrap();
+        fs::write(dir.path().join("b.json"), r#"{"port": "oops"}"#).unwrap();
+
+        let (output, success) =
+            super::run(&arguments(&dir, &["validate", "schema.json", "a.json"])).unwrap();
+        assert!(success);
+        assert!(output.contains("\"debug\": false"));
+        let (output, success) =
+            super::run(&arguments(&dir, &["validate", "schema.json", "b.json"])).unwrap();
+        assert!(!success);
+        assert!(output.contains("b.json"));
+
+        let (output, _) = super::run(&arguments(&dir, &["merge", "a.json", "b.json"])).unwrap();
+        assert!(output.contains("\"oops\""));
+        let (output, success) =
+            super::run(&arguments(&dir, &["diff", "a.json", "b.json"])).unwrap();
+        assert!(!success);
+        assert!(output.starts_with("port "));
+
+        let (output, _) =
+            super::run(&arguments(&dir, &["schema", "json-schema", "schema.json"])).unwrap();
+        assert!(output.contains("\"$schema\""));
+        let (output, _) = super::run(&arguments(&dir, &["schema", "doc", "schema.json"])).unwrap();
+        assert!(output.contains("| `debug` | boolean | `false` |  |"));
+        assert!(output.contains("| `port` | port number |  |  |"));
+        assert!(super::run(&arguments(&dir, &["merge"])).is_err());
+        assert!(super::run(&arguments(&dir, &["merge", "missing.json"])).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{Input, InputMap};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputMapBuilder {
+    map: InputMap,
+}
+
+impl InputMapBuilder {
+    pub fn new() -> Self {
+        Self {
+            map: InputMap::new(),
+        }
+    }
+
+    pub fn insert<K: Into<String>, V: Into<Input>>(mut self, key: K, value: V) -> Self {
+        self.map.insert(crate::intern_key(key.into()), value.into());
+        self
+    }
+
+    pub fn insert_if_some<K: Into<String>, V: Into<Input>>(
+        self,
+        key: K,
+        maybe_value: Option<V>,
+    ) -> Self {
+        if let Some(value) = maybe_value {
+            self.insert(key, value)
+        } else {
+            self
+        }
+    }
+
+    pub fn insert_if<K: Into<String>, V: Into<Input>>(
+        self,
+        condition: bool,
+        key: K,
+        value: V,
+    ) -> Self {
+        if condition {
+            self.insert(key, value)
+        } else {
+            self
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn build(self) -> Input {
+        Input::Map(self.map)
+    }
+}
+
+impl From<InputMapBuilder> for Input {
+    fn from(builder: InputMapBuilder) -> Self {
+        builder.build()
+    }
+}
+
+impl Input {
+    pub fn map_builder() -> InputMapBuilder {
+        InputMapBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn build() {
+        let maybe_timeout: Option<u16> = None;
+        let input = Input::map_builder()
+            .insert("host", "127.0.0.1")
+            .insert_if_some("port", Some(8080))
+            .insert_if_some("timeout", maybe_timeout)
+            .insert_if(true, "tls", Input::map_builder().insert("enabled", false))
+            .insert_if(false, "debug", true)
+            .build();
+        assert_eq!(
+            input,
+            Input::from(HashMap::from([
+                ("host", Input::from("127.0.0.1")),
+                ("port", Input::from(8080)),
+                ("tls", Input::from(HashMap::from([("enabled", false)]))),
+            ]))
+        );
+    }
+}
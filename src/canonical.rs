@@ -0,0 +1,266 @@
+use crate::Input;
+use std::{collections::hash_map::DefaultHasher, fmt::Write, hash::Hasher};
+
+// RFC 8785 (JSON Canonicalization Scheme) style output. Integers are written exactly even beyond
+// 2^53, and non-finite floats (which JSON can not represent) are written as `null`:
+impl Input {
+    pub fn to_canonical_json(&self) -> String {
+        let mut text = String::new();
+        write_canonical(self, &mut text);
+        text
+    }
+
+    // A hash of `to_canonical_json()` (without building it), so equal inputs have equal
+    // fingerprints regardless of the map order. It's only stable within the same build and may
+    // collide:
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = FingerprintHasher::default();
+        write_canonical(self, &mut hasher);
+        hasher.hasher.finish()
+    }
+
+    // Like `fingerprint()` with a second independent hash, for caches that don't keep the inputs
+    // to compare them. Unlike the canonical JSON, the hash includes the type of every value and
+    // the exact bits of floats, so e.g. `1` and `1.0` (or `NaN` and `null`) differ:
+    #[cfg(feature = "schema")]
+    pub(crate) fn wide_fingerprint(&self) -> u128 {
+        let mut hasher = FingerprintHasher::default();
+        hasher.other_hasher.write_u8(0xff);
+        write_typed(self, &mut hasher);
+        (hasher.hasher.finish() as u128) << 64 | hasher.other_hasher.finish() as u128
+    }
+}
+
+// The canonical JSON is either written to a string or hashed:
+trait CanonicalWrite: Write {
+    fn push_str(&mut self, text: &str) {
+        self.write_str(text).unwrap()
+    }
+
+    fn push(&mut self, character: char) {
+        self.write_char(character).unwrap()
+    }
+}
+
+impl CanonicalWrite for String {}
+
+#[derive(Default)]
+struct FingerprintHasher {
+    hasher: DefaultHasher,
+    other_hasher: DefaultHasher,
+}
+
+impl Write for FingerprintHasher {
+    fn write_str(&mut self, text: &str) -> std::fmt::Result {
+        self.hasher.write(text.as_bytes());
+        self.other_hasher.write(text.as_bytes());
+        Ok(())
+    }
+}
+
+impl CanonicalWrite for FingerprintHasher {}
+
+#[cfg(feature = "schema")]
+impl FingerprintHasher {
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes);
+        self.other_hasher.write(bytes);
+    }
+}
+
+// Every value starts with its type and strings, lists, and maps with their length, so different
+// inputs can't write the same bytes:
+#[cfg(feature = "schema")]
+fn write_typed(input: &Input, hasher: &mut FingerprintHasher) {
+    crate::stack::maybe_grow(|| match input {
+        Input::Null => hasher.write_bytes(&[0]),
+        Input::Bool(boolean) => hasher.write_bytes(&[1, *boolean as u8]),
+        Input::Int(integer) => {
+            hasher.write_bytes(&[2]);
+            hasher.write_bytes(&(*integer as i64).to_le_bytes())
+        }
+        Input::Float(float) => {
+            hasher.write_bytes(&[3]);
+            hasher.write_bytes(&float.to_bits().to_le_bytes())
+        }
+        Input::Str(string) => {
+            hasher.write_bytes(&[4]);
+            write_typed_str(string, hasher)
+        }
+        Input::List(list) => {
+            hasher.write_bytes(&[5]);
+            hasher.write_bytes(&(list.len() as u64).to_le_bytes());
+            list.iter()
+                .for_each(|inner_input| write_typed(inner_input, hasher))
+        }
+        Input::Map(map) => {
+            hasher.write_bytes(&[6]);
+            hasher.write_bytes(&(map.len() as u64).to_le_bytes());
+            let mut key_list: Vec<_> = map.keys().collect();
+            key_list.sort_unstable();
+            key_list.into_iter().for_each(|key| {
+                write_typed_str(key, hasher);
+                write_typed(&map[key], hasher)
+            })
+        }
+    })
+}
+
+#[cfg(feature = "schema")]
+fn write_typed_str(string: &str, hasher: &mut FingerprintHasher) {
+    hasher.write_bytes(&(string.len() as u64).to_le_bytes());
+    hasher.write_bytes(string.as_bytes())
+}
+
+fn write_canonical<W: CanonicalWrite>(input: &Input, text: &mut W) {
+    crate::stack::maybe_grow(|| match input {
+        Input::Null => text.push_str("null"),
+        Input::Bool(boolean) => text.push_str(if *boolean { "true" } else { "false" }),
+        Input::Int(integer) => write!(text, "{integer}").unwrap(),
+        Input::Float(float) => write_float(*float, text),
+        Input::Str(string) => write_string(string, text),
+        Input::List(list) => {
+            text.push('[');
+            list.iter().enumerate().for_each(|(index, inner_input)| {
+                if index > 0 {
+                    text.push(',');
+                }
+                write_canonical(inner_input, text);
+            });
+            text.push(']');
+        }
+        Input::Map(map) => {
+            let mut key_list: Vec<_> = map.keys().collect();
+            // Keys are sorted by their UTF-16 code units (not by their UTF-8 bytes):
+            key_list.sort_by(|key, other_key| key.encode_utf16().cmp(other_key.encode_utf16()));
+            text.push('{');
+            key_list.iter().enumerate().for_each(|(index, key)| {
+                if index > 0 {
+                    text.push(',');
+                }
+                write_string(key, text);
+                text.push(':');
+                write_canonical(&map[*key], text);
+            });
+            text.push('}');
+        }
+    })
+}
+
+fn write_string<W: CanonicalWrite>(string: &str, text: &mut W) {
+    text.push('"');
+    for character in string.chars() {
+        match character {
+            '"' => text.push_str("\\\""),
+            '\\' => text.push_str("\\\\"),
+            '\u{8}' => text.push_str("\\b"),
+            '\u{c}' => text.push_str("\\f"),
+            '\n' => text.push_str("\\n"),
+            '\r' => text.push_str("\\r"),
+            '\t' => text.push_str("\\t"),
+            character if (character as u32) < 0x20 => {
+                write!(text, "\\u{:04x}", character as u32).unwrap()
+            }
+            character => text.push(character),
+        }
+    }
+    text.push('"');
+}
+
+// ECMAScript `Number.prototype.toString()` formatting of the shortest round-trip digits:
+fn write_float<W: CanonicalWrite>(float: f64, text: &mut W) {
+    if !float.is_finite() {
+        return text.push_str("null");
+    }
+    if float == 0.0 {
+        return text.push('0');
+    }
+    if float < 0.0 {
+        text.push('-');
+    }
+    let scientific = format!("{:e}", float.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let digit_count = digits.len() as i32;
+    let point = exponent.parse::<i32>().unwrap() + 1;
+    if digit_count <= point && point <= 21 {
+        text.push_str(&digits);
+        (0..point - digit_count).for_each(|_| text.push('0'));
+    } else if 0 < point && point <= 21 {
+        text.push_str(&digits[..point as usize]);
+        text.push('.');
+        text.push_str(&digits[point as usize..]);
+    } else if -6 < point && point <= 0 {
+        text.push_str("0.");
+        (0..-point).for_each(|_| text.push('0'));
+        text.push_str(&digits);
+    } else {
+        text.push_str(&digits[..1]);
+        if digit_count > 1 {
+            text.push('.');
+            text.push_str(&digits[1..]);
+        }
+        let exponent = point - 1;
+        write!(
+            text,
+            "e{}{}",
+            if exponent < 0 { '-' } else { '+' },
+            exponent.abs()
+        )
+        .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn float(float: f64) -> String {
+        Input::from(float).to_canonical_json()
+    }
+
+    #[test]
+    fn to_canonical_json() {
+        let input = Input::from(HashMap::from([
+            (
+                "b",
+                Input::from([Input::from(1), Input::Null, Input::from(true)]),
+            ),
+            ("a", Input::from("tab\t\"quote\"\u{1}é")),
+            ("\u{fb33}", Input::from(1)),
+            ("\u{1f600}", Input::from(2)),
+        ]));
+        assert_eq!(
+            input.to_canonical_json(),
+            "{\"a\":\"tab\\t\\\"quote\\\"\\u0001é\",\"b\":[1,null,true],\"\u{1f600}\":2,\"\u{fb33}\":1}"
+        );
+    }
+
+    #[test]
+    fn to_canonical_json_float() {
+        assert_eq!(float(1.0), "1");
+        assert_eq!(float(-0.0), "0");
+        assert_eq!(float(2.5), "2.5");
+        assert_eq!(float(-1.5e-7), "-1.5e-7");
+        assert_eq!(float(0.000001), "0.000001");
+        assert_eq!(float(1e21), "1e+21");
+        assert_eq!(float(1e20), "100000000000000000000");
+        assert_eq!(float(123456.789), "123456.789");
+        assert_eq!(float(f64::NAN), "null");
+    }
+
+    #[test]
+    fn fingerprint() {
+        let input = Input::from(HashMap::from([
+            ("a", Input::from(1)),
+            ("b", Input::from(2)),
+        ]));
+        let other_input = Input::from(HashMap::from([
+            ("b", Input::from(2)),
+            ("a", Input::from(1)),
+        ]));
+        assert_eq!(input.fingerprint(), other_input.fingerprint());
+        assert_ne!(input.fingerprint(), Input::from(1).fingerprint());
+    }
+}
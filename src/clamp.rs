@@ -0,0 +1,236 @@
+use crate::{position::InputPosition, Input};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SizeLimits {
+    // Characters:
+    maybe_max_string_len: Option<usize>,
+    maybe_max_list_len: Option<usize>,
+    maybe_max_map_len: Option<usize>,
+}
+
+// A value that `Input::clamp()` truncated:
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputClamp {
+    position: InputPosition,
+    kind: InputClampKind,
+    original_len: usize,
+    clamped_len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputClampKind {
+    String,
+    List,
+    Map,
+}
+
+impl SizeLimits {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn maybe_max_string_len(&self) -> Option<usize> {
+        self.maybe_max_string_len
+    }
+
+    pub fn set_max_string_len(&mut self, max_string_len: usize) {
+        self.maybe_max_string_len = Some(max_string_len);
+    }
+
+    pub fn with_max_string_len(mut self, max_string_len: usize) -> Self {
+        self.set_max_string_len(max_string_len);
+        self
+    }
+
+    pub fn maybe_max_list_len(&self) -> Option<usize> {
+        self.maybe_max_list_len
+    }
+
+    pub fn set_max_list_len(&mut self, max_list_len: usize) {
+        self.maybe_max_list_len = Some(max_list_len);
+    }
+
+    pub fn with_max_list_len(mut self, max_list_len: usize) -> Self {
+        self.set_max_list_len(max_list_len);
+        self
+    }
+
+    pub fn maybe_max_map_len(&self) -> Option<usize> {
+        self.maybe_max_map_len
+    }
+
+    pub fn set_max_map_len(&mut self, max_map_len: usize) {
+        self.maybe_max_map_len = Some(max_map_len);
+    }
+
+    pub fn with_max_map_len(mut self, max_map_len: usize) -> Self {
+        self.set_max_map_len(max_map_len);
+        self
+    }
+}
+
+impl InputClamp {
+    pub fn position(&self) -> &InputPosition {
+        &self.position
+    }
+
+    pub fn kind(&self) -> InputClampKind {
+        self.kind
+    }
+
+    pub fn original_len(&self) -> usize {
+        self.original_len
+    }
+
+    pub fn clamped_len(&self) -> usize {
+        self.clamped_len
+    }
+}
+
+impl Input {
+    // Truncates (nested) strings, lists, and maps that are longer than `limits` instead of
+    // rejecting them, e.g. for best-effort plugin payloads. Lists keep their first items and maps
+    // keep their first keys in sorted order, so it's deterministic:
+    pub fn clamp(&mut self, limits: &SizeLimits) -> Vec<InputClamp> {
+        let mut clamp_list = Vec::new();
+        clamp(self, limits, InputPosition::new(), &mut clamp_list);
+        clamp_list
+    }
+}
+
+fn clamp(
+    input: &mut Input,
+    limits: &SizeLimits,
+    position: InputPosition,
+    clamp_list: &mut Vec<InputClamp>,
+) {
+    crate::stack::maybe_grow(|| match input {
+        Input::Str(string) => {
+            let Some(max_len) = limits.maybe_max_string_len else {
+                return;
+            };
+            let original_len = string.chars().count();
+            if original_len > max_len {
+                *string = string.chars().take(max_len).collect();
+                clamp_list.push(InputClamp {
+                    position,
+                    kind: InputClampKind::String,
+                    original_len,
+                    clamped_len: max_len,
+                })
+            }
+        }
+        Input::List(list) => {
+            if let Some(max_len) = limits.maybe_max_list_len.filter(|max| list.len() > *max) {
+                clamp_list.push(InputClamp {
+                    position: position.clone(),
+                    kind: InputClampKind::List,
+                    original_len: list.len(),
+                    clamped_len: max_len,
+                });
+                list.truncate(max_len);
+            }
+            list.iter_mut()
+                .enumerate()
+                .for_each(|(index, inner_input)| {
+                    clamp(
+                        inner_input,
+                        limits,
+                        position.new_with_index(index),
+                        clamp_list,
+                    )
+                })
+        }
+        Input::Map(map) => {
+            if let Some(max_len) = limits.maybe_max_map_len.filter(|max| map.len() > *max) {
+                clamp_list.push(InputClamp {
+                    position: position.clone(),
+                    kind: InputClampKind::Map,
+                    original_len: map.len(),
+                    clamped_len: max_len,
+                });
+                let mut key_list: Vec<_> = map.keys().cloned().collect();
+                key_list.sort();
+                key_list[max_len..].iter().for_each(|key| {
+                    map.remove(key);
+                });
+            }
+            let mut key_list: Vec<_> = map.keys().cloned().collect();
+            key_list.sort();
+            key_list.into_iter().for_each(|key| {
+                if let Some(inner_input) = map.get_mut(&key) {
+                    clamp(inner_input, limits, position.new_with_key(&key), clamp_list)
+                }
+            })
+        }
+        _ => (),
+    })
+}
+
+impl Display for InputClampKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::String => "string",
+            Self::List => "list",
+            Self::Map => "map",
+        })
+    }
+}
+
+impl Display for InputClamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let unit = match self.kind {
+            InputClampKind::String => "chars",
+            InputClampKind::List | InputClampKind::Map => "items",
+        };
+        if !self.position.is_empty() {
+            write!(f, "{} ", self.position)?;
+        }
+        write!(
+            f,
+            "{} clamped from {} to {} {unit}",
+            self.kind, self.original_len, self.clamped_len
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn clamp() {
+        let mut input: Input = serde_json::from_value(json!({
+            "name": "héllo world",
+            "tags": ["a", "b", "c", "d"],
+            "labels": {"d": 4, "c": 3, "a": 1, "b": 2},
+        }))
+        .unwrap();
+        let limits = SizeLimits::new()
+            .with_max_string_len(5)
+            .with_max_list_len(3)
+            .with_max_map_len(3);
+        let clamp_list = input.clamp(&limits);
+        let expected: Input = serde_json::from_value(json!({
+            "name": "héllo",
+            "tags": ["a", "b", "c"],
+            "labels": {"a": 1, "b": 2, "c": 3},
+        }))
+        .unwrap();
+        assert_eq!(input, expected);
+        assert_eq!(
+            clamp_list
+                .iter()
+                .map(|clamp| clamp.to_string())
+                .collect::<Vec<_>>(),
+            [
+                "labels map clamped from 4 to 3 items",
+                "name string clamped from 11 to 5 chars",
+                "tags list clamped from 4 to 3 items",
+            ]
+        );
+        assert!(input.clamp(&limits).is_empty());
+    }
+}
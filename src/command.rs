@@ -0,0 +1,216 @@
+use crate::{
+    env::variable_name,
+    position::{InputPosition, InputPositionType},
+    schema::InputSchemaType,
+    Input,
+};
+use std::process::Command;
+
+// How `Input::to_argv()` writes flags. Keys of nested positions are joined with `.`, e.g.
+// `--server.port=8080` or `--server.port 8080`:
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputFlagStyle {
+    #[default]
+    Equals,
+    Separate,
+}
+
+impl Input {
+    // Variables for a subprocess in the format of `env::load_matching_schema()`, so it can load
+    // them back with the same prefix and schema. Maps are flattened, strings are passed as they
+    // are, `null`s are skipped, and other values (including lists) are JSON:
+    pub fn to_command_env(&self, prefix: &str) -> Vec<(String, String)> {
+        leaf_list(self)
+            .into_iter()
+            .map(|(position, value)| (variable_name(prefix, &position), value))
+            .collect()
+    }
+
+    // Flags for a subprocess, e.g. `--server.port=8080`. Only values that the schema declares as
+    // public (see `InputSchemaType::is_public_at()`) are passed, since arguments are visible to
+    // other users of the system. Pass the rest via `to_command_env()` instead:
+    pub fn to_argv(
+        &self,
+        schema_type: &InputSchemaType,
+        flag_style: InputFlagStyle,
+    ) -> Vec<String> {
+        leaf_list(self)
+            .into_iter()
+            .filter(|(position, _)| schema_type.is_public_at(position))
+            .flat_map(|(position, value)| {
+                let flag = format!("--{}", flag_name(&position));
+                match flag_style {
+                    InputFlagStyle::Equals => vec![format!("{flag}={value}")],
+                    InputFlagStyle::Separate => vec![flag, value],
+                }
+            })
+            .collect()
+    }
+
+    // The inverse of `to_argv()`: a map of (nested) strings that are coerced by validation.
+    // Arguments that are not flags are ignored:
+    pub fn from_argv<S: AsRef<str>>(argument_list: &[S], flag_style: InputFlagStyle) -> Input {
+        let mut input = Input::new_map();
+        let mut argument_iter = argument_list.iter().map(AsRef::as_ref);
+        while let Some(argument) = argument_iter.next() {
+            let Some(flag) = argument.strip_prefix("--") else {
+                continue;
+            };
+            let maybe_name_value = match flag_style {
+                InputFlagStyle::Equals => flag.split_once('='),
+                InputFlagStyle::Separate => argument_iter.next().map(|value| (flag, value)),
+            };
+            if let Some((name, value)) = maybe_name_value {
+                let mut position = InputPosition::new();
+                name.split('.').for_each(|key| position.add_key(key));
+                input.insert_at(&position, Input::from(value));
+            }
+        }
+        input
+    }
+
+    // Sets `to_command_env()` (with all secrets) and `to_argv()` (without secrets):
+    pub fn apply_to_command(
+        &self,
+        command: &mut Command,
+        schema_type: &InputSchemaType,
+        prefix: &str,
+        flag_style: InputFlagStyle,
+    ) {
+        command
+            .envs(self.to_command_env(prefix))
+            .args(self.to_argv(schema_type, flag_style));
+    }
+}
+
+fn flag_name(position: &InputPosition) -> String {
+    position
+        .iter()
+        .map(|position_type| position_type.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn leaf_list(input: &Input) -> Vec<(InputPosition, String)> {
+    let mut leaf_list = Vec::new();
+    collect_leaf_list(input, InputPosition::new(), &mut leaf_list);
+    leaf_list
+}
+
+// Keys are sorted so that the order of arguments is deterministic:
+fn collect_leaf_list(
+    input: &Input,
+    position: InputPosition,
+    leaf_list: &mut Vec<(InputPosition, String)>,
+) {
+    crate::stack::maybe_grow(|| match input {
+        Input::Null => (),
+        Input::Str(string) => leaf_list.push((position, string.clone())),
+        Input::Map(map) => {
+            let mut key_list: Vec<_> = map.keys().collect();
+            key_list.sort();
+            key_list.into_iter().for_each(|key| {
+                let mut inner_position = position.clone();
+                inner_position.add(InputPositionType::Key(key.clone()));
+                collect_leaf_list(&map[key], inner_position, leaf_list)
+            })
+        }
+        _ => leaf_list.push((position, input.to_canonical_json())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn to_argv() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "server": {"schema": {"type": "static_map", "items": {
+                    "port": {"schema": {"type": "port"}},
+                    "tags": {"schema": {"type": "list", "item_schema": {"type": "string"}}},
+                }}},
+                "token": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+            }
+        }))
+        .unwrap();
+        let input: Input = serde_json::from_value(json!({
+            "server": {"port": 8080, "tags": ["a", "b"]},
+            "token": "s3cr3t",
+        }))
+        .unwrap();
+        assert_eq!(
+            input.to_argv(&schema_type, InputFlagStyle::Equals),
+            ["--server.port=8080", r#"--server.tags=["a","b"]"#]
+        );
+        assert_eq!(
+            input.to_command_env("APP_"),
+            [
+                ("APP_SERVER__PORT".to_string(), "8080".to_string()),
+                ("APP_SERVER__TAGS".to_string(), r#"["a","b"]"#.to_string()),
+                ("APP_TOKEN".to_string(), "s3cr3t".to_string()),
+            ]
+        );
+        let argument_list = input.to_argv(&schema_type, InputFlagStyle::Separate);
+        assert_eq!(argument_list[..2], ["--server.port", "8080"]);
+        assert_eq!(
+            Input::from_argv(&argument_list, InputFlagStyle::Separate),
+            serde_json::from_value::<Input>(json!({
+                "server": {"port": "8080", "tags": r#"["a","b"]"#},
+            }))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn to_argv_without_secrets() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "alt": {"schema": {"type": "either", "schema_list": [
+                    {"type": "string"},
+                    {"type": "secret", "schema": {"type": "string"}},
+                ]}},
+                "tree": {"schema": {
+                    "type": "recursive",
+                    "name": "tree",
+                    "schema": {"type": "static_map", "items": {
+                        "password": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                        "name": {"schema": {"type": "string"}},
+                        "tree": {"schema": {"type": "ref", "name": "tree"}},
+                    }},
+                }},
+                "nested": {"schema": {"type": "static_map", "items": {
+                    "user": {"schema": {"type": "string"}},
+                    "token": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                }}},
+            },
+            "additional_items": {"schema": {"type": "any"}},
+        }))
+        .unwrap();
+        let input: Input = serde_json::from_value(json!({
+            "alt": "hunter4",
+            "tree": {"name": "root", "password": "hunter3", "tree": {"password": "hunter5"}},
+            "nested": {"user": "admin", "token": "hunter6"},
+            "extra": "value",
+        }))
+        .unwrap();
+        assert_eq!(
+            input.to_argv(&schema_type, InputFlagStyle::Equals),
+            ["--extra=value", "--nested.user=admin", "--tree.name=root"]
+        );
+        // With undeclared positions:
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {"name": {"schema": {"type": "string"}}},
+        }))
+        .unwrap();
+        assert_eq!(
+            input.to_argv(&schema_type, InputFlagStyle::Equals),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,377 @@
+use crate::{position::InputPosition, Input, InputMap};
+use serde::de::{
+    self, value::BorrowedStrDeserializer, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess,
+    SeqAccess, VariantAccess, Visitor,
+};
+use serde::Deserialize;
+use std::fmt::Display;
+use thiserror::Error;
+
+// A serde error with the position of the value that could not be deserialized (serde's own
+// messages only mention field names):
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{position} {description}")]
+pub struct InputDeserializeError {
+    position: InputPosition,
+    description: String,
+    // Errors get the position of the innermost value they pass through:
+    has_position: bool,
+}
+
+impl InputDeserializeError {
+    pub fn position(&self) -> &InputPosition {
+        &self.position
+    }
+
+    pub fn description(&self) -> &String {
+        &self.description
+    }
+
+    fn at(mut self, position: &InputPosition) -> Self {
+        if !self.has_position {
+            self.position = position.clone();
+            self.has_position = true;
+        }
+        self
+    }
+}
+
+impl de::Error for InputDeserializeError {
+    fn custom<T: Display>(description: T) -> Self {
+        Self {
+            position: InputPosition::new(),
+            description: description.to_string(),
+            has_position: false,
+        }
+    }
+}
+
+impl Input {
+    // Like `T::deserialize(input)` (e.g. with `serde_json::from_value()`), but errors carry the
+    // position of the failed value:
+    pub fn deserialize_into<'de, T: Deserialize<'de>>(
+        &'de self,
+    ) -> Result<T, InputDeserializeError> {
+        T::deserialize(InputDeserializer {
+            input: self,
+            position: InputPosition::new(),
+        })
+    }
+}
+
+pub(crate) struct InputDeserializer<'de> {
+    input: &'de Input,
+    position: InputPosition,
+}
+
+struct InputSeqAccess<'de> {
+    iter: std::iter::Enumerate<std::slice::Iter<'de, Input>>,
+    position: InputPosition,
+}
+
+struct InputMapAccess<'de> {
+    iter: <&'de InputMap as IntoIterator>::IntoIter,
+    maybe_value: Option<(&'de Input, InputPosition)>,
+    position: InputPosition,
+}
+
+struct InputEnumAccess<'de> {
+    variant: &'de str,
+    maybe_value: Option<&'de Input>,
+    position: InputPosition,
+}
+
+impl<'de> InputDeserializer<'de> {
+    pub(crate) fn new(input: &'de Input, position: InputPosition) -> Self {
+        Self { input, position }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for InputDeserializer<'de> {
+    type Error = InputDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let result = crate::stack::maybe_grow(|| match self.input {
+            Input::Null => visitor.visit_unit(),
+            Input::Bool(boolean) => visitor.visit_bool(*boolean),
+            Input::Int(integer) => visitor.visit_i64(*integer as i64),
+            Input::Float(float) => visitor.visit_f64(*float),
+            Input::Str(string) => visitor.visit_borrowed_str(string),
+            Input::List(list) => visitor.visit_seq(InputSeqAccess {
+                iter: list.iter().enumerate(),
+                position: self.position.clone(),
+            }),
+            Input::Map(map) => visitor.visit_map(InputMapAccess {
+                iter: map.iter(),
+                maybe_value: None,
+                position: self.position.clone(),
+            }),
+        });
+        result.map_err(|error| error.at(&self.position))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.input.is_null() {
+            visitor.visit_none()
+        } else {
+            let position = self.position.clone();
+            visitor
+                .visit_some(self)
+                .map_err(|error| error.at(&position))
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let position = self.position.clone();
+        visitor
+            .visit_newtype_struct(self)
+            .map_err(|error| error.at(&position))
+    }
+
+    // Unit variants are strings and other variants are maps with one key (like `serde_json`):
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let enum_access = match self.input {
+            Input::Str(variant) => InputEnumAccess {
+                variant,
+                maybe_value: None,
+                position: self.position.clone(),
+            },
+            Input::Map(map) if map.len() == 1 => {
+                let (variant, value) = map.iter().next().unwrap();
+                InputEnumAccess {
+                    variant,
+                    maybe_value: Some(value),
+                    position: self.position.new_with_key(variant),
+                }
+            }
+            input => {
+                return Err(<InputDeserializeError as de::Error>::invalid_type(
+                    unexpected(input),
+                    &"string or map with one key",
+                )
+                .at(&self.position))
+            }
+        };
+        visitor
+            .visit_enum(enum_access)
+            .map_err(|error| error.at(&self.position))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> SeqAccess<'de> for InputSeqAccess<'de> {
+    type Error = InputDeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.iter
+            .next()
+            .map(|(index, input)| {
+                seed.deserialize(InputDeserializer::new(
+                    input,
+                    self.position.new_with_index(index),
+                ))
+            })
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+impl<'de> MapAccess<'de> for InputMapAccess<'de> {
+    type Error = InputDeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.iter.next() else {
+            return Ok(None);
+        };
+        let position = self.position.new_with_key(key);
+        let key = seed
+            .deserialize(BorrowedStrDeserializer::<InputDeserializeError>::new(key))
+            .map_err(|error| error.at(&position))?;
+        self.maybe_value = Some((value, position));
+        Ok(Some(key))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (value, position) = self
+            .maybe_value
+            .take()
+            .ok_or_else(|| de::Error::custom("value is missing"))?;
+        seed.deserialize(InputDeserializer::new(value, position))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+impl<'de> EnumAccess<'de> for InputEnumAccess<'de> {
+    type Error = InputDeserializeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), Self::Error> {
+        let variant = seed
+            .deserialize(self.variant.into_deserializer())
+            .map_err(|error: InputDeserializeError| error.at(&self.position))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for InputEnumAccess<'de> {
+    type Error = InputDeserializeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.maybe_value {
+            None | Some(Input::Null) => Ok(()),
+            Some(input) => Err(<InputDeserializeError as de::Error>::invalid_type(
+                unexpected(input),
+                &"unit variant",
+            )
+            .at(&self.position)),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self.value_deserializer()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_seq(self.value_deserializer()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_map(self.value_deserializer()?, visitor)
+    }
+}
+
+impl<'de> InputEnumAccess<'de> {
+    fn value_deserializer(self) -> Result<InputDeserializer<'de>, InputDeserializeError> {
+        match self.maybe_value {
+            Some(input) => Ok(InputDeserializer::new(input, self.position)),
+            None => Err(<InputDeserializeError as de::Error>::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"variant with a value",
+            )
+            .at(&self.position)),
+        }
+    }
+}
+
+fn unexpected(input: &Input) -> de::Unexpected<'_> {
+    match input {
+        Input::Null => de::Unexpected::Unit,
+        Input::Bool(boolean) => de::Unexpected::Bool(*boolean),
+        Input::Int(integer) => de::Unexpected::Signed(*integer as i64),
+        Input::Float(float) => de::Unexpected::Float(*float),
+        Input::Str(string) => de::Unexpected::Str(string),
+        Input::List(_) => de::Unexpected::Seq,
+        Input::Map(_) => de::Unexpected::Map,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Config {
+        name: String,
+        maybe_port: Option<u16>,
+        server_list: Vec<Server>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Server {
+        Local,
+        Remote { host: String, port: u16 },
+    }
+
+    #[test]
+    fn deserialize_into() {
+        let input: Input = serde_json::from_value(json!({
+            "name": "app",
+            "maybe_port": 80,
+            "server_list": ["local", {"remote": {"host": "example.com", "port": 8080}}],
+        }))
+        .unwrap();
+        assert_eq!(
+            input.deserialize_into::<Config>().unwrap(),
+            Config {
+                name: "app".to_string(),
+                maybe_port: Some(80),
+                server_list: vec![
+                    Server::Local,
+                    Server::Remote {
+                        host: "example.com".to_string(),
+                        port: 8080
+                    }
+                ],
+            }
+        );
+
+        for (value, error_position) in [
+            (
+                json!({"name": "app", "server_list": [{"remote": {"host": "a", "port": 70000}}]}),
+                "[server_list][0][remote][port]",
+            ),
+            (
+                json!({"name": "app", "maybe_port": "80", "server_list": []}),
+                "maybe_port",
+            ),
+            (
+                json!({"name": "app", "server_list": ["cloud"]}),
+                "[server_list][0]",
+            ),
+            (
+                json!({"name": "app", "server_list": [], "user": "root"}),
+                "user",
+            ),
+            (json!({"server_list": []}), ""),
+        ] {
+            let input: Input = serde_json::from_value(value).unwrap();
+            let error = input.deserialize_into::<Config>().unwrap_err();
+            assert_eq!(error.position().to_string(), error_position, "{error}");
+        }
+    }
+}
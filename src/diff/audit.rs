@@ -0,0 +1,172 @@
+use crate::diff::InputDiff;
+use serde::Serialize;
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// Called after each written batch with the number of bytes written to the current writer. A
+// returned writer replaces the current one (e.g. after renaming a full file):
+pub type AuditLogRotateHook<W> = Box<dyn FnMut(&mut W, u64) -> io::Result<Option<W>> + Send>;
+
+// Appends one JSON line per batch of diffs with the time, the actor (e.g. `admin` or
+// `reload-signal`), the rendered diffs, and the diffs themselves (which can be deserialized):
+pub struct AuditLog<W: Write> {
+    writer: W,
+    written_length: u64,
+    maybe_rotate_hook: Option<AuditLogRotateHook<W>>,
+}
+
+#[derive(Debug, Serialize)]
+struct AuditLogRecord<'a> {
+    // Milliseconds since the Unix epoch:
+    timestamp: u128,
+    actor: &'a str,
+    rendered_list: Vec<String>,
+    diff_list: &'a [InputDiff],
+}
+
+impl AuditLog<File> {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map(Self::new)
+    }
+}
+
+impl<W: Write> AuditLog<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            written_length: 0,
+            maybe_rotate_hook: None,
+        }
+    }
+
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+
+    // Bytes written to the current writer since it was set:
+    pub fn written_length(&self) -> u64 {
+        self.written_length
+    }
+
+    pub fn set_rotate_hook<F>(&mut self, rotate_hook: F)
+    where
+        F: FnMut(&mut W, u64) -> io::Result<Option<W>> + Send + 'static,
+    {
+        self.maybe_rotate_hook = Some(Box::new(rotate_hook));
+    }
+
+    pub fn with_rotate_hook<F>(mut self, rotate_hook: F) -> Self
+    where
+        F: FnMut(&mut W, u64) -> io::Result<Option<W>> + Send + 'static,
+    {
+        self.set_rotate_hook(rotate_hook);
+        self
+    }
+
+    pub fn append(&mut self, actor: &str, diff_list: &[InputDiff]) -> io::Result<()> {
+        self.append_at(SystemTime::now(), actor, diff_list)
+    }
+
+    // Empty batches are not written:
+    pub fn append_at(
+        &mut self,
+        time: SystemTime,
+        actor: &str,
+        diff_list: &[InputDiff],
+    ) -> io::Result<()> {
+        if diff_list.is_empty() {
+            return Ok(());
+        }
+        let record = AuditLogRecord {
+            timestamp: time
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis(),
+            actor,
+            rendered_list: diff_list.iter().map(ToString::to_string).collect(),
+            diff_list,
+        };
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+        self.writer.flush()?;
+        self.written_length += line.len() as u64;
+        if let Some(rotate_hook) = self.maybe_rotate_hook.as_mut() {
+            if let Some(writer) = rotate_hook(&mut self.writer, self.written_length)? {
+                self.writer = writer;
+                self.written_length = 0;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diff::diff, Input};
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    #[test]
+    fn append() {
+        let mut diff_list = Vec::new();
+        diff(
+            &Input::from(HashMap::from([("port", 80)])),
+            &Input::from(HashMap::from([("port", 8080)])),
+            &mut |diff| diff_list.push(diff),
+        );
+        let mut audit_log = AuditLog::new(Vec::new());
+        audit_log
+            .append_at(UNIX_EPOCH, "admin", diff_list.as_slice())
+            .unwrap();
+        audit_log.append("admin", &[]).unwrap();
+        let text = String::from_utf8(audit_log.writer().clone()).unwrap();
+        assert_eq!(text.lines().count(), 1);
+        let record: serde_json::Value = serde_json::from_str(text.trim()).unwrap();
+        assert_eq!(record["timestamp"], 0);
+        assert_eq!(record["actor"], "admin");
+        assert_eq!(
+            record["rendered_list"][0],
+            "port value `80` increased by 8000 to new value `8080`"
+        );
+        assert_eq!(
+            serde_json::from_value::<Vec<InputDiff>>(record["diff_list"].clone()).unwrap(),
+            diff_list
+        );
+
+        // Rotates when the second batch is written:
+        let max_length = audit_log.written_length();
+        let rotated_list = Arc::new(Mutex::new(Vec::new()));
+        let hook_rotated_list = rotated_list.clone();
+        audit_log.set_rotate_hook(move |writer: &mut Vec<u8>, length| {
+            if length <= max_length {
+                return Ok(None);
+            }
+            hook_rotated_list
+                .lock()
+                .unwrap()
+                .push(std::mem::take(writer));
+            Ok(Some(Vec::new()))
+        });
+        audit_log
+            .append_at(UNIX_EPOCH, "admin", diff_list.as_slice())
+            .unwrap();
+        assert_eq!(rotated_list.lock().unwrap()[0].len() as u64, 2 * max_length);
+        assert_eq!(audit_log.written_length(), 0);
+    }
+}
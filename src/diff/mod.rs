@@ -0,0 +1,750 @@
+#[cfg(feature = "json")]
+mod audit;
+
+#[cfg(feature = "schema")]
+mod schema;
+
+#[cfg(feature = "json")]
+pub use audit::{AuditLog, AuditLogRotateHook};
+#[cfg(feature = "schema")]
+pub use schema::diff_with_schema;
+
+use crate::{
+    eq::is_unchanged,
+    patch::{InputPatch, InputPatchOperation},
+    position::InputPosition,
+    record_metric, Input,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputDiff {
+    input: Arc<Input>,
+    position: InputPosition,
+    // Missing values are skipped so that `null` values (e.g. an added `null`) round-trip:
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_some",
+        default
+    )]
+    maybe_old_value: Option<Input>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_some",
+        default
+    )]
+    maybe_new_value: Option<Input>,
+    action: InputDiffAction,
+    // e.g. `+30s` or `−256MiB` for durations and sizes (see `diff_with_schema()`):
+    #[serde(
+        rename = "unit_delta",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    maybe_unit_delta: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InputDiffAction {
+    Added,
+    Removed,
+    // `delta` is set for integer to integer and float to float updates (except for `NaN`):
+    Updated { delta: Option<InputNumberDelta> },
+    TypeChanged { old_type: String, new_type: String },
+}
+
+// The raw old and new numbers of an update, so that consumers (e.g. alerts on thresholds) don't
+// have to parse the description:
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum InputNumberDelta {
+    Int { old: isize, new: isize },
+    Float { old: f64, new: f64 },
+}
+
+fn deserialize_some<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Input>, D::Error> {
+    Input::deserialize(deserializer).map(Some)
+}
+
+impl InputDiff {
+    // e.g. for diffs from other systems. The input is the old value (or `null` for added values)
+    // unless it's set with `with_input()`:
+    pub fn new(
+        position: InputPosition,
+        action: InputDiffAction,
+        maybe_old_value: Option<Input>,
+        maybe_new_value: Option<Input>,
+    ) -> Self {
+        let input = Arc::new(maybe_old_value.clone().unwrap_or_default());
+        Self::new_with_input(input, position, maybe_old_value, maybe_new_value, action)
+    }
+
+    pub(crate) fn new_with_input(
+        input: Arc<Input>,
+        position: InputPosition,
+        maybe_old_value: Option<Input>,
+        maybe_new_value: Option<Input>,
+        action: InputDiffAction,
+    ) -> Self {
+        Self {
+            input,
+            position,
+            maybe_old_value,
+            maybe_new_value,
+            action,
+            maybe_unit_delta: None,
+        }
+    }
+
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    pub fn position(&self) -> &InputPosition {
+        &self.position
+    }
+
+    pub fn maybe_old_value(&self) -> Option<&Input> {
+        self.maybe_old_value.as_ref()
+    }
+
+    pub fn maybe_new_value(&self) -> Option<&Input> {
+        self.maybe_new_value.as_ref()
+    }
+
+    pub fn action(&self) -> &InputDiffAction {
+        &self.action
+    }
+
+    pub fn maybe_unit_delta(&self) -> Option<&String> {
+        self.maybe_unit_delta.as_ref()
+    }
+
+    pub fn set_input<I: Into<Input>>(&mut self, input: I) {
+        self.input = Arc::new(input.into());
+    }
+
+    pub fn with_input<I: Into<Input>>(mut self, input: I) -> Self {
+        self.set_input(input);
+        self
+    }
+
+    pub fn set_position(&mut self, position: InputPosition) {
+        self.position = position;
+    }
+
+    pub fn with_position(mut self, position: InputPosition) -> Self {
+        self.set_position(position);
+        self
+    }
+
+    pub fn set_old_value<I: Into<Input>>(&mut self, old_value: I) {
+        self.maybe_old_value = Some(old_value.into());
+    }
+
+    pub fn with_old_value<I: Into<Input>>(mut self, old_value: I) -> Self {
+        self.set_old_value(old_value);
+        self
+    }
+
+    pub fn set_new_value<I: Into<Input>>(&mut self, new_value: I) {
+        self.maybe_new_value = Some(new_value.into());
+    }
+
+    pub fn with_new_value<I: Into<Input>>(mut self, new_value: I) -> Self {
+        self.set_new_value(new_value);
+        self
+    }
+
+    pub fn set_action(&mut self, action: InputDiffAction) {
+        self.action = action;
+    }
+
+    pub fn with_action(mut self, action: InputDiffAction) -> Self {
+        self.set_action(action);
+        self
+    }
+
+    pub fn set_unit_delta<D: ToString>(&mut self, unit_delta: D) {
+        self.maybe_unit_delta = Some(unit_delta.to_string());
+    }
+
+    pub fn with_unit_delta<D: ToString>(mut self, unit_delta: D) -> Self {
+        self.set_unit_delta(unit_delta);
+        self
+    }
+}
+
+impl Display for InputDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let position = if self.position.is_empty() {
+            "".to_string()
+        } else {
+            format!("{} ", self.position)
+        };
+        let description = &self.action;
+        let text = match (
+            self.maybe_old_value.as_ref(),
+            self.maybe_new_value.as_ref(),
+            self.maybe_unit_delta.as_ref(),
+        ) {
+            (Some(old_value), Some(new_value), Some(unit_delta)) => format!(
+                "{position}value `{old_value}` changed by {unit_delta} to new value `{new_value}`"
+            ),
+            (Some(old_value), Some(new_value), None) => {
+                format!("{position}value `{old_value}` {description} to new value `{new_value}`")
+            }
+            (Some(old_value), None, _) => {
+                format!("{position}value `{old_value}` {description}")
+            }
+            (None, Some(new_value), _) => {
+                format!("{position}value `{new_value}` {description}")
+            }
+            (None, None, _) => format!("{position}hasn't changed"),
+        };
+        f.write_str(text.as_str())
+    }
+}
+
+impl Display for InputDiffAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added => f.write_str("added"),
+            Self::Removed => f.write_str("removed"),
+            Self::Updated { delta: None } => f.write_str("updated"),
+            Self::Updated { delta: Some(delta) } => Display::fmt(delta, f),
+            Self::TypeChanged { old_type, new_type } => {
+                write!(f, "changed from {old_type} type to {new_type} type")
+            }
+        }
+    }
+}
+
+impl InputNumberDelta {
+    pub fn is_increase(&self) -> bool {
+        match self {
+            Self::Int { old, new } => old < new,
+            Self::Float { old, new } => old < new,
+        }
+    }
+
+    pub fn is_decrease(&self) -> bool {
+        match self {
+            Self::Int { old, new } => old > new,
+            Self::Float { old, new } => old > new,
+        }
+    }
+
+    // `abs_diff()` can't overflow (e.g. from `isize::MIN` to `isize::MAX`):
+    pub fn maybe_int_amount(&self) -> Option<usize> {
+        match self {
+            Self::Int { old, new } => Some(old.abs_diff(*new)),
+            Self::Float { .. } => None,
+        }
+    }
+
+    pub fn float_amount(&self) -> f64 {
+        match self {
+            Self::Int { old, new } => old.abs_diff(*new) as f64,
+            Self::Float { old, new } => (new - old).abs(),
+        }
+    }
+}
+
+impl Display for InputNumberDelta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let direction = if self.is_increase() {
+            "increased"
+        } else {
+            "decreased"
+        };
+        match self.maybe_int_amount() {
+            Some(amount) => write!(f, "{direction} by {amount}"),
+            // The distance to infinity is not useful:
+            None if !self.float_amount().is_finite() => f.write_str(direction),
+            None => write!(f, "{direction} by {}", self.float_amount()),
+        }
+    }
+}
+
+pub fn diff<F>(input_1: &Input, input_2: &Input, for_each_function: &mut F)
+where
+    F: FnMut(InputDiff),
+{
+    diff_with_position(input_1, input_2, for_each_function, InputPosition::new());
+    #[cfg(feature = "metrics")]
+    crate::metrics::finish();
+}
+
+pub fn diff_with_position<F>(
+    input_1: &Input,
+    input_2: &Input,
+    for_each_function: &mut F,
+    position: InputPosition,
+) where
+    F: FnMut(InputDiff),
+{
+    record_metric!(diff_visited_count);
+    crate::stack::maybe_grow(|| diff_level(input_1, input_2, for_each_function, position))
+}
+
+fn diff_level<F>(
+    input_1: &Input,
+    input_2: &Input,
+    for_each_function: &mut F,
+    position: InputPosition,
+) where
+    F: FnMut(InputDiff),
+{
+    // Maps and lists are not compared upfront since walking their keys and items finds the same
+    // differences (and comparing every level of deep inputs again would be quadratic):
+    let is_same_container =
+        (input_1.is_map() && input_2.is_map()) || (input_1.is_list() && input_2.is_list());
+    if !is_same_container && is_unchanged(input_1, input_2) {
+        return;
+    }
+    // All diffs of one level share a single copy of `input_1` that is made for the first diff:
+    let mut maybe_input = None;
+    let mut shared_input = || {
+        maybe_input
+            .get_or_insert_with(|| Arc::new(copy(input_1)))
+            .clone()
+    };
+    if input_1.is_map() && input_2.is_map() {
+        let (old_map, new_map) = (input_1.as_map(), input_2.as_map());
+        for (key, old_value) in old_map.iter() {
+            let new_position = position.new_with_key(key);
+            if let Some(new_value) = new_map.get(key) {
+                diff_with_position(old_value, new_value, for_each_function, new_position);
+            } else {
+                let diff = InputDiff {
+                    input: shared_input(),
+                    position: new_position,
+                    maybe_old_value: Some(copy(old_value)),
+                    maybe_new_value: None,
+                    action: InputDiffAction::Removed,
+                    maybe_unit_delta: None,
+                };
+                for_each_function(diff);
+            }
+        }
+        for (key, new_value) in new_map.iter() {
+            if !old_map.contains_key(key) {
+                let diff = InputDiff {
+                    input: shared_input(),
+                    position: position.new_with_key(key),
+                    maybe_old_value: None,
+                    maybe_new_value: Some(copy(new_value)),
+                    action: InputDiffAction::Added,
+                    maybe_unit_delta: None,
+                };
+                for_each_function(diff);
+            }
+        }
+    } else if input_1.is_list() && input_2.is_list() {
+        let (old_list, new_list) = (input_1.as_list(), input_2.as_list());
+        // Single items are either equal (and have no differences) or changed:
+        if old_list.len() == 1 && new_list.len() == 1 {
+            return diff_with_position(
+                &old_list[0],
+                &new_list[0],
+                for_each_function,
+                position.new_with_index(0),
+            );
+        }
+        let (mut added_index_list, mut removed_index_list) = (Vec::new(), Vec::new());
+        for (old_index, inner_input) in old_list.iter().enumerate() {
+            if !new_list.contains(inner_input) {
+                removed_index_list.push(old_index)
+            }
+        }
+        for (new_index, inner_input) in new_list.iter().enumerate() {
+            if !old_list.contains(inner_input) {
+                added_index_list.push(new_index)
+            }
+        }
+        for added_index in &added_index_list {
+            let added_index = *added_index;
+            let new_position = position.new_with_index(added_index);
+            if removed_index_list.contains(&added_index) {
+                diff_with_position(
+                    old_list.get(added_index).unwrap(),
+                    new_list.get(added_index).unwrap(),
+                    for_each_function,
+                    new_position,
+                );
+            } else {
+                let diff = InputDiff {
+                    input: shared_input(),
+                    position: new_position,
+                    maybe_old_value: None,
+                    maybe_new_value: Some(copy(new_list.get(added_index).unwrap())),
+                    action: InputDiffAction::Added,
+                    maybe_unit_delta: None,
+                };
+                for_each_function(diff);
+            }
+        }
+        for removed_index in removed_index_list {
+            let new_position = position.new_with_index(removed_index);
+            if !added_index_list.contains(&removed_index) {
+                let diff = InputDiff {
+                    input: shared_input(),
+                    position: new_position,
+                    maybe_old_value: Some(copy(old_list.get(removed_index).unwrap())),
+                    maybe_new_value: None,
+                    action: InputDiffAction::Removed,
+                    maybe_unit_delta: None,
+                };
+                for_each_function(diff);
+            }
+        }
+    } else {
+        let action = match (input_1.as_leaf(), input_2.as_leaf()) {
+            (Some(old), Some(new)) if old.kind() == new.kind() => InputDiffAction::Updated {
+                delta: old.number_delta(&new),
+            },
+            _ => InputDiffAction::TypeChanged {
+                old_type: input_1.type_name(),
+                new_type: input_2.type_name(),
+            },
+        };
+        let input = shared_input();
+        let diff = InputDiff {
+            maybe_old_value: Some(copy(input.as_ref())),
+            input,
+            position,
+            maybe_new_value: Some(copy(input_2)),
+            action,
+            maybe_unit_delta: None,
+        };
+        for_each_function(diff);
+    }
+}
+
+pub fn to_patch(input_1: &Input, input_2: &Input) -> InputPatch {
+    let mut patch = InputPatch::new();
+    to_patch_with_position(input_1, input_2, &mut patch, InputPosition::new());
+    patch
+}
+
+pub fn to_patch_with_position(
+    input_1: &Input,
+    input_2: &Input,
+    patch: &mut InputPatch,
+    position: InputPosition,
+) {
+    crate::stack::maybe_grow(|| to_patch_level(input_1, input_2, patch, position))
+}
+
+fn to_patch_level(
+    input_1: &Input,
+    input_2: &Input,
+    patch: &mut InputPatch,
+    position: InputPosition,
+) {
+    if is_unchanged(input_1, input_2) {
+        return;
+    }
+    if input_1.is_map() && input_2.is_map() {
+        let (old_map, new_map) = (input_1.as_map(), input_2.as_map());
+        for (key, old_value) in old_map.iter() {
+            let new_position = position.new_with_key(key);
+            if let Some(new_value) = new_map.get(key) {
+                to_patch_with_position(old_value, new_value, patch, new_position);
+            } else {
+                patch.add_operation(InputPatchOperation::Remove {
+                    position: new_position,
+                    old_value: old_value.clone(),
+                });
+            }
+        }
+        for (key, new_value) in new_map.iter() {
+            if !old_map.contains_key(key) {
+                patch.add_operation(InputPatchOperation::Add {
+                    position: position.new_with_key(key),
+                    value: new_value.clone(),
+                });
+            }
+        }
+    } else if input_1.is_list() && input_2.is_list() {
+        // Unlike `diff`, lists are compared index by index, so applying the patch always
+        // reproduces `input_2`:
+        let (old_list, new_list) = (input_1.as_list(), input_2.as_list());
+        let common_length = old_list.len().min(new_list.len());
+        for index in 0..common_length {
+            to_patch_with_position(
+                &old_list[index],
+                &new_list[index],
+                patch,
+                position.new_with_index(index),
+            );
+        }
+        for (index, new_value) in new_list.iter().enumerate().skip(common_length) {
+            patch.add_operation(InputPatchOperation::Add {
+                position: position.new_with_index(index),
+                value: new_value.clone(),
+            });
+        }
+        for (index, old_value) in old_list.iter().enumerate().skip(common_length).rev() {
+            patch.add_operation(InputPatchOperation::Remove {
+                position: position.new_with_index(index),
+                old_value: old_value.clone(),
+            });
+        }
+    } else {
+        patch.add_operation(InputPatchOperation::Replace {
+            position,
+            old_value: input_1.clone(),
+            new_value: input_2.clone(),
+        });
+    }
+}
+
+// Every value that diffing copies goes through here so that it can be counted:
+fn copy(input: &Input) -> Input {
+    record_metric!(diff_cloned_count, crate::metrics::node_count(input));
+    input.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::{enable_logging, info};
+    use crate::position::InputPositionType;
+    use std::collections::HashMap;
+
+    fn diff_to_list(old_input: &Input, new_input: &Input, print: bool) -> Vec<InputDiff> {
+        enable_logging();
+
+        let mut diff_list = Vec::new();
+        diff(old_input, new_input, &mut |diff| {
+            if print {
+                info(format!("\t{diff}"));
+            }
+            diff_list.push(diff)
+        });
+        diff_list
+    }
+
+    #[test]
+    fn serde() {
+        let old_input: Input =
+            serde_json::from_value(serde_json::json!({"a": 1, "b": "x", "c": [1.5]})).unwrap();
+        let new_input: Input =
+            serde_json::from_value(serde_json::json!({"a": 2, "b": 1, "d": null})).unwrap();
+        for diff in diff_to_list(&old_input, &new_input, false) {
+            let json = serde_json::to_string(&diff).unwrap();
+            assert_eq!(serde_json::from_str::<InputDiff>(&json).unwrap(), diff);
+        }
+    }
+
+    #[test]
+    fn functionality() {
+        enable_logging();
+
+        let old_input = Input::from(true);
+        let mut new_input = Input::from(true);
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert!(diff_list.is_empty());
+        *new_input.bool_mut() = false;
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert!(!diff_list.is_empty());
+
+        let old_input = Input::from(100);
+        let mut new_input = Input::from(100);
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert!(diff_list.is_empty());
+        *new_input.int_mut() = -100;
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert!(!diff_list.is_empty());
+        assert_eq!(
+            diff_list[0].action.to_string(),
+            "decreased by 200".to_string()
+        );
+
+        let old_input = Input::from(-1.5);
+        let mut new_input = Input::from(-1.5);
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert!(diff_list.is_empty());
+        *new_input.float_mut() = 3.0;
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert!(!diff_list.is_empty());
+        assert_eq!(
+            diff_list[0].action.to_string(),
+            "increased by 4.5".to_string()
+        );
+
+        let diff_list = diff_to_list(&Input::from(isize::MIN), &Input::from(isize::MAX), false);
+        assert_eq!(
+            diff_list[0].action.to_string(),
+            format!("increased by {}", usize::MAX)
+        );
+
+        assert_eq!(
+            diff_list[0].action,
+            InputDiffAction::Updated {
+                delta: Some(InputNumberDelta::Int {
+                    old: isize::MIN,
+                    new: isize::MAX
+                })
+            }
+        );
+        let diff_list = diff_to_list(&Input::from(1), &Input::from("1"), false);
+        assert_eq!(
+            diff_list[0].action.to_string(),
+            "changed from integer type to string type"
+        );
+
+        let nan_input = Input::from(f64::NAN);
+        assert!(diff_to_list(&nan_input, &Input::from(f64::NAN), false).is_empty());
+        let diff_list = diff_to_list(&new_input, &nan_input, false);
+        assert_eq!(diff_list[0].action.to_string(), "updated".to_string());
+        let diff_list = diff_to_list(&new_input, &Input::from(f64::INFINITY), false);
+        assert_eq!(diff_list[0].action.to_string(), "increased".to_string());
+
+        let old_input = Input::from("foo");
+        let mut new_input = Input::from("foo");
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert!(diff_list.is_empty());
+        *new_input.str_mut() = "bar".to_string();
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert!(!diff_list.is_empty());
+        assert_eq!(
+            diff_list[0].action,
+            InputDiffAction::Updated { delta: None }
+        );
+
+        let old_input = Input::from(Vec::from([
+            Input::from(true),
+            Input::from(10),
+            Input::from(0.5),
+            Input::from("foo"),
+        ]));
+        let mut new_input = Input::from(Vec::from([
+            Input::from(true),
+            Input::from(10),
+            Input::from(0.5),
+            Input::from("foo"),
+        ]));
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert!(diff_list.is_empty());
+        *new_input.list_mut()[0].bool_mut() = false;
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert_eq!(diff_list.len(), 1);
+        assert_eq!(
+            diff_list[0].position.clone()[0],
+            InputPositionType::Index(0)
+        );
+        *new_input.list_mut()[1].int_mut() = 20;
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert_eq!(diff_list.len(), 2);
+        assert_eq!(
+            diff_list[1].position.clone()[0],
+            InputPositionType::Index(1)
+        );
+        *new_input.list_mut()[2].float_mut() = -0.5;
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert_eq!(diff_list.len(), 3);
+        assert_eq!(
+            diff_list[2].position.clone()[0],
+            InputPositionType::Index(2)
+        );
+        *new_input.list_mut()[3].str_mut() = "bar".to_string();
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert_eq!(diff_list.len(), 4);
+        assert_eq!(
+            diff_list[3].position.clone()[0],
+            InputPositionType::Index(3)
+        );
+
+        let old_input = Input::from(HashMap::from([(
+            "foo".to_string(),
+            Input::from(Vec::from([
+                Input::from(true),
+                Input::from(10),
+                Input::from(0.5),
+                Input::from("foo"),
+            ])),
+        )]));
+        let mut new_input = Input::from(HashMap::from([(
+            "foo".to_string(),
+            Input::from(Vec::from([
+                Input::from(true),
+                Input::from(10),
+                Input::from(0.5),
+                Input::from("foo"),
+            ])),
+        )]));
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert!(diff_list.is_empty());
+        *new_input.map_mut().get_mut("foo").unwrap().list_mut()[1].int_mut() = 100;
+        let diff_list = diff_to_list(&old_input, &new_input, false);
+        assert!(!diff_list.is_empty());
+        assert_eq!(
+            diff_list[0].position.clone()[0],
+            InputPositionType::Key("foo".into())
+        );
+        assert_eq!(
+            diff_list[0].position.clone()[1],
+            InputPositionType::Index(1)
+        );
+    }
+
+    #[test]
+    fn print() {
+        enable_logging();
+
+        let json = serde_json::json!({"foo": {"bar": {"baz": [-10, 1.5, false, "bar"]}}});
+        let input: Input = serde_json::from_value(json.clone()).unwrap();
+        let mut new_input = input.clone();
+        let list = new_input
+            .map_mut()
+            .get_mut("foo")
+            .unwrap()
+            .map_mut()
+            .get_mut("bar")
+            .unwrap()
+            .map_mut()
+            .get_mut("baz")
+            .unwrap()
+            .list_mut();
+        *list[0].int_mut() = 10;
+        *list[1].float_mut() = -1.5;
+        list[2] = Input::from("new string");
+        list.remove(3);
+        info(format!("Original data: {json}"));
+        info(format!(
+            "Updated data:  {}",
+            serde_json::to_string(&new_input).unwrap()
+        ));
+        info("Updates:");
+        diff_to_list(&input, &new_input, true);
+    }
+
+    #[test]
+    fn new() {
+        let diff = InputDiff::new(
+            InputPosition::new().new_with_key("timeout"),
+            InputDiffAction::Updated { delta: None },
+            Some(Input::from("30s")),
+            Some(Input::from("1m")),
+        )
+        .with_unit_delta("+30s");
+        assert_eq!(diff.input(), &Input::from("30s"));
+        assert_eq!(
+            diff.to_string(),
+            "timeout value `\"30s\"` changed by +30s to new value `\"1m\"`"
+        );
+        let diff = diff
+            .with_action(InputDiffAction::Added)
+            .with_input(Input::new_map());
+        assert_eq!(diff.action(), &InputDiffAction::Added);
+        assert!(diff.input().is_map());
+    }
+}
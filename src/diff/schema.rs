@@ -0,0 +1,201 @@
+use crate::{
+    diff::{diff, InputDiff, InputDiffAction},
+    schema::InputSchemaType,
+    Input,
+};
+
+// Like `diff::diff()`, but both inputs are validated first (if they are valid) so
+// representation-only differences (e.g. `"8080"` and `8080`) are not reported, and updates of
+// `string` values that both are durations (e.g. `30s` and `1m`) or sizes (e.g. `512MiB` and
+// `256MiB`) get a unit delta (e.g. `+30s` and `−256MiB`). Values of other schemas (e.g. secrets)
+// are never interpreted. Changes of `volatile` items are skipped:
+pub fn diff_with_schema<F>(
+    input_1: &Input,
+    input_2: &Input,
+    schema_type: &InputSchemaType,
+    for_each_function: &mut F,
+) where
+    F: FnMut(InputDiff),
+{
+    let validated = |input: &Input| {
+        let mut validated_input = input.clone();
+        schema_type
+            .validate(&mut validated_input, None)
+            .map_or_else(|_| input.clone(), |_| validated_input)
+    };
+    diff(&validated(input_1), &validated(input_2), &mut |mut diff| {
+        if schema_type.is_volatile_at(&diff.position) {
+            return;
+        }
+        if matches!(diff.action, InputDiffAction::Updated { delta: None })
+            && matches!(
+                schema_type.subschema(&diff.position),
+                Some(InputSchemaType::String(_))
+            )
+        {
+            if let (Some(Input::Str(old)), Some(Input::Str(new))) =
+                (diff.maybe_old_value.as_ref(), diff.maybe_new_value.as_ref())
+            {
+                diff.maybe_unit_delta = unit_delta(old, new)
+            }
+        }
+        for_each_function(diff)
+    })
+}
+
+fn unit_delta(old: &str, new: &str) -> Option<String> {
+    if let (Some(old), Some(new)) = (parse_duration(old), parse_duration(new)) {
+        return Some(signed(new - old, format_duration));
+    }
+    let (old, new) = (parse_size(old)?, parse_size(new)?);
+    Some(signed(new as f64 - old as f64, |bytes| {
+        format_size(bytes as u128)
+    }))
+}
+
+fn signed<F: Fn(f64) -> String>(delta: f64, format: F) -> String {
+    format!(
+        "{}{}",
+        if delta < 0.0 { "\u{2212}" } else { "+" },
+        format(delta.abs())
+    )
+}
+
+const DURATION_UNIT_LIST: [(&str, f64); 8] = [
+    ("ns", 1e-9),
+    ("us", 1e-6),
+    ("µs", 1e-6),
+    ("ms", 1e-3),
+    ("s", 1.0),
+    ("m", 60.0),
+    ("h", 3600.0),
+    ("d", 86400.0),
+];
+
+// A sequence of numbers with units, e.g. `1h30m` or `1.5s`:
+fn parse_duration(text: &str) -> Option<f64> {
+    let mut rest = text.trim();
+    let mut seconds = 0.0;
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let number_length = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .filter(|length| *length > 0)?;
+        let number: f64 = rest[..number_length].parse().ok()?;
+        rest = &rest[number_length..];
+        let unit_length = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let multiplier = DURATION_UNIT_LIST
+            .iter()
+            .find(|(unit, _)| *unit == &rest[..unit_length])?
+            .1;
+        seconds += number * multiplier;
+        rest = &rest[unit_length..];
+    }
+    Some(seconds)
+}
+
+fn format_duration(seconds: f64) -> String {
+    if seconds.fract() != 0.0 || seconds == 0.0 {
+        return if seconds < 1.0 && seconds > 0.0 {
+            format!("{}ms", seconds * 1e3)
+        } else {
+            format!("{seconds}s")
+        };
+    }
+    let mut seconds = seconds as u64;
+    let mut text = String::new();
+    for (unit, unit_seconds) in [("d", 86400), ("h", 3600), ("m", 60), ("s", 1)] {
+        if seconds >= unit_seconds {
+            text.push_str(format!("{}{unit}", seconds / unit_seconds).as_str());
+            seconds %= unit_seconds;
+        }
+    }
+    text
+}
+
+const SIZE_UNIT_LIST: [(&str, u128); 9] = [
+    ("B", 1),
+    ("KB", 1000),
+    ("MB", 1000_u128.pow(2)),
+    ("GB", 1000_u128.pow(3)),
+    ("TB", 1000_u128.pow(4)),
+    ("KiB", 1024),
+    ("MiB", 1024_u128.pow(2)),
+    ("GiB", 1024_u128.pow(3)),
+    ("TiB", 1024_u128.pow(4)),
+];
+
+// An integer with a unit, e.g. `256MiB` or `1 GB`:
+fn parse_size(text: &str) -> Option<u128> {
+    let text = text.trim();
+    let number_length = text
+        .find(|c: char| !c.is_ascii_digit())
+        .filter(|length| *length > 0)?;
+    let number: u128 = text[..number_length].parse().ok()?;
+    let unit = text[number_length..].trim_start();
+    let multiplier = SIZE_UNIT_LIST.iter().find(|(name, _)| *name == unit)?.1;
+    number.checked_mul(multiplier)
+}
+
+// The biggest binary, or else decimal, unit that divides the size:
+fn format_size(bytes: u128) -> String {
+    let (unit, multiplier) = SIZE_UNIT_LIST[5..]
+        .iter()
+        .rev()
+        .chain(SIZE_UNIT_LIST[..5].iter().rev())
+        .find(|(_, multiplier)| bytes != 0 && bytes.is_multiple_of(*multiplier))
+        .unwrap_or(&SIZE_UNIT_LIST[0]);
+    format!("{}{unit}", bytes / multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_with_schema() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "timeout": {"schema": {"type": "string"}},
+                "cache": {"schema": {"type": "string"}},
+                "port": {"schema": {"type": "integer"}},
+                "token": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                "updated_at": {"schema": {"type": "integer"}, "volatile": true},
+            }
+        }))
+        .unwrap();
+        let old_input: Input = serde_json::from_value(json!({
+            "timeout": "30s", "cache": "512MiB", "port": 8080, "token": "1s", "updated_at": 1
+        }))
+        .unwrap();
+        let new_input: Input = serde_json::from_value(json!({
+            "timeout": "1m", "cache": "256MiB", "port": "8080", "token": "2s", "updated_at": 2
+        }))
+        .unwrap();
+        let mut diff_list = Vec::new();
+        super::diff_with_schema(&old_input, &new_input, &schema_type, &mut |diff| {
+            diff_list.push((
+                diff.position().to_string(),
+                diff.maybe_unit_delta().cloned(),
+            ))
+        });
+        diff_list.sort();
+        assert_eq!(
+            diff_list,
+            [
+                ("cache".to_string(), Some("\u{2212}256MiB".to_string())),
+                ("timeout".to_string(), Some("+30s".to_string())),
+                ("token".to_string(), None),
+            ]
+        );
+        assert_eq!(unit_delta("1h", "1h30m").as_deref(), Some("+30m"));
+        assert_eq!(unit_delta("1GB", "1500MB").as_deref(), Some("+500MB"));
+        assert_eq!(unit_delta("fast", "slow"), None);
+    }
+}
@@ -0,0 +1,339 @@
+use crate::{Input, InputKey};
+use std::fmt::{Display, Formatter, Result, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayOptions {
+    pretty: bool,
+    indent: usize,
+    sort_keys: bool,
+    maybe_max_depth: Option<usize>,
+    maybe_max_width: Option<usize>,
+    maybe_max_len: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct InputDisplay<'a> {
+    input: &'a Input,
+    options: DisplayOptions,
+}
+
+impl DisplayOptions {
+    pub fn new() -> Self {
+        Self {
+            pretty: false,
+            indent: 2,
+            sort_keys: false,
+            maybe_max_depth: None,
+            maybe_max_width: None,
+            maybe_max_len: None,
+        }
+    }
+
+    pub fn new_pretty() -> Self {
+        Self::new().with_pretty(true).with_sort_keys(true)
+    }
+
+    pub fn pretty(&self) -> bool {
+        self.pretty
+    }
+
+    pub fn set_pretty(&mut self, pretty: bool) {
+        self.pretty = pretty;
+    }
+
+    pub fn with_pretty(mut self, pretty: bool) -> Self {
+        self.set_pretty(pretty);
+        self
+    }
+
+    pub fn indent(&self) -> usize {
+        self.indent
+    }
+
+    pub fn set_indent(&mut self, indent: usize) {
+        self.indent = indent;
+    }
+
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.set_indent(indent);
+        self
+    }
+
+    pub fn sort_keys(&self) -> bool {
+        self.sort_keys
+    }
+
+    pub fn set_sort_keys(&mut self, sort_keys: bool) {
+        self.sort_keys = sort_keys;
+    }
+
+    pub fn with_sort_keys(mut self, sort_keys: bool) -> Self {
+        self.set_sort_keys(sort_keys);
+        self
+    }
+
+    pub fn maybe_max_depth(&self) -> Option<usize> {
+        self.maybe_max_depth
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.maybe_max_depth = Some(max_depth);
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.set_max_depth(max_depth);
+        self
+    }
+
+    pub fn maybe_max_width(&self) -> Option<usize> {
+        self.maybe_max_width
+    }
+
+    pub fn set_max_width(&mut self, max_width: usize) {
+        self.maybe_max_width = Some(max_width);
+    }
+
+    pub fn with_max_width(mut self, max_width: usize) -> Self {
+        self.set_max_width(max_width);
+        self
+    }
+
+    pub fn maybe_max_len(&self) -> Option<usize> {
+        self.maybe_max_len
+    }
+
+    pub fn set_max_len(&mut self, max_len: usize) {
+        self.maybe_max_len = Some(max_len);
+    }
+
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.set_max_len(max_len);
+        self
+    }
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> InputDisplay<'a> {
+    pub fn new(input: &'a Input, options: DisplayOptions) -> Self {
+        Self { input, options }
+    }
+
+    pub fn input(&self) -> &Input {
+        self.input
+    }
+
+    pub fn options(&self) -> &DisplayOptions {
+        &self.options
+    }
+}
+
+impl Input {
+    pub fn display_with(&self, options: DisplayOptions) -> InputDisplay<'_> {
+        InputDisplay::new(self, options)
+    }
+
+    pub fn display_compact(&self, max_len: usize) -> InputDisplay<'_> {
+        self.display_with(DisplayOptions::new().with_max_len(max_len))
+    }
+}
+
+impl Display for InputDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write_input(f, self.input, &self.options, 0)
+    }
+}
+
+fn write_input(
+    f: &mut Formatter<'_>,
+    input: &Input,
+    options: &DisplayOptions,
+    depth: usize,
+) -> Result {
+    match input {
+        Input::List(list) => {
+            if list.is_empty() {
+                return f.write_str("[]");
+            }
+            if options
+                .maybe_max_len
+                .is_some_and(|max_len| list.len() > max_len)
+            {
+                return write!(f, "[… {} items …]", human_count(list.len()));
+            }
+            if options
+                .maybe_max_depth
+                .is_some_and(|max_depth| depth >= max_depth)
+            {
+                return f.write_str("[...]");
+            }
+            f.write_char('[')?;
+            let items = list.iter().map(|item| (None, item));
+            write_items(f, items, list.len(), options, depth)?;
+            f.write_char(']')
+        }
+        Input::Map(map) => {
+            if map.is_empty() {
+                return f.write_str("{}");
+            }
+            if options
+                .maybe_max_len
+                .is_some_and(|max_len| map.len() > max_len)
+            {
+                return write!(f, "{{… {} items …}}", human_count(map.len()));
+            }
+            if options
+                .maybe_max_depth
+                .is_some_and(|max_depth| depth >= max_depth)
+            {
+                return f.write_str("{...}");
+            }
+            f.write_char('{')?;
+            let mut items: Vec<_> = map.iter().collect();
+            if options.sort_keys {
+                items.sort_by_key(|(key, _)| *key);
+            }
+            let items = items.into_iter().map(|(key, value)| (Some(key), value));
+            write_items(f, items, map.len(), options, depth)?;
+            f.write_char('}')
+        }
+        // Everything else is a leaf:
+        _ => input
+            .as_leaf()
+            .map_or(Ok(()), |leaf| leaf.write(f, options.maybe_max_len)),
+    }
+}
+
+fn write_items<'a, I>(
+    f: &mut Formatter<'_>,
+    items: I,
+    length: usize,
+    options: &DisplayOptions,
+    depth: usize,
+) -> Result
+where
+    I: Iterator<Item = (Option<&'a InputKey>, &'a Input)>,
+{
+    let shown = options.maybe_max_width.unwrap_or(length).min(length);
+    let hidden = length - shown;
+    for (index, (maybe_key, value)) in items.take(shown).enumerate() {
+        if index > 0 {
+            f.write_char(',')?;
+        }
+        write_separator(f, options, index == 0, depth + 1)?;
+        if let Some(key) = maybe_key {
+            write!(f, "{key:?}: ")?;
+        }
+        write_input(f, value, options, depth + 1)?;
+    }
+    if hidden > 0 {
+        if shown > 0 {
+            f.write_char(',')?;
+        }
+        write_separator(f, options, shown == 0, depth + 1)?;
+        write!(f, "... ({hidden} more)")?;
+    }
+    if options.pretty {
+        write_separator(f, options, false, depth)?;
+    }
+    Ok(())
+}
+
+fn write_separator(
+    f: &mut Formatter<'_>,
+    options: &DisplayOptions,
+    first: bool,
+    depth: usize,
+) -> Result {
+    if options.pretty {
+        write!(f, "\n{:width$}", "", width = depth * options.indent)
+    } else if !first {
+        f.write_char(' ')
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn human_count(count: usize) -> String {
+    if count < 1_000 {
+        count.to_string()
+    } else if count < 1_000_000 {
+        format!("{:.1}k", count as f64 / 1_000.0)
+    } else {
+        format!("{:.1}M", count as f64 / 1_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn compact() {
+        let input = Input::from(HashMap::from([("foo", Input::from([1, 2]))]));
+        assert_eq!(format!("{input}"), "{\"foo\": [1, 2]}");
+        assert_eq!(format!("{}", Input::from([1, 2, 3])), "[1, 2, 3]");
+        assert_eq!(format!("{}", Input::new_list()), "[]");
+        assert_eq!(format!("{}", Input::new_map()), "{}");
+    }
+
+    #[test]
+    fn pretty() {
+        let input = Input::from(HashMap::from([
+            ("foo", Input::from([1, 2])),
+            ("bar", Input::from(HashMap::from([("baz", "qux")]))),
+            ("empty", Input::new_list()),
+        ]));
+        assert_eq!(
+            format!("{input:#}"),
+            "{\n  \"bar\": {\n    \"baz\": \"qux\"\n  },\n  \"empty\": [],\n  \"foo\": [\n    1,\n    2\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn truncate() {
+        let input = Input::from(HashMap::from([(
+            "foo",
+            Input::from([Input::from([1]), Input::from(2), Input::from(3)]),
+        )]));
+        let options = DisplayOptions::new().with_max_width(2).with_max_depth(2);
+        assert_eq!(
+            input.display_with(options).to_string(),
+            "{\"foo\": [[...], 2, ... (1 more)]}"
+        );
+    }
+
+    #[test]
+    fn display_compact() {
+        let input = Input::from(HashMap::from([
+            ("blob", Input::from("x".repeat(1234))),
+            ("list", Input::from(vec![0; 1234])),
+            ("small", Input::from([1, 2])),
+        ]));
+        assert_eq!(
+            Input::from("x".repeat(1234)).display_compact(4).to_string(),
+            "\"xxxx…\" (… 1.2k chars …)"
+        );
+        assert_eq!(
+            Input::from(vec![0; 1234]).display_compact(4).to_string(),
+            "[… 1.2k items …]"
+        );
+        let text = input
+            .display_with(DisplayOptions::new_pretty().with_max_len(4))
+            .to_string();
+        assert!(text.contains("\"xxxx…\" (… 1.2k chars …)"));
+        assert!(text.contains("[… 1.2k items …]"));
+        assert!(text.contains("\"small\": [\n"));
+        assert_eq!(
+            Input::from(HashMap::from([("a", 1), ("b", 2)]))
+                .display_compact(1)
+                .to_string(),
+            "{… 2 items …}"
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{utf8::InputUtf8Policy, Input, InputMap};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputDotenvError {
+    #[error("Could not read {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Line {line_number}: {description}")]
+    Syntax {
+        line_number: usize,
+        description: String,
+    },
+    #[error("Line {line_number}: key `{key}` conflicts with a previously defined key")]
+    Conflict { line_number: usize, key: String },
+    #[error("Line {line_number}: invalid UTF-8")]
+    Utf8 { line_number: usize },
+}
+
+impl Input {
+    pub fn from_dotenv<P: AsRef<Path>>(
+        path: P,
+        nesting_separator: &str,
+    ) -> Result<Input, InputDotenvError> {
+        Self::from_dotenv_with_policy(path, nesting_separator, InputUtf8Policy::Error)
+    }
+
+    pub fn from_dotenv_with_policy<P: AsRef<Path>>(
+        path: P,
+        nesting_separator: &str,
+        policy: InputUtf8Policy,
+    ) -> Result<Input, InputDotenvError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|source| InputDotenvError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_dotenv_bytes(&bytes, nesting_separator, policy)
+    }
+
+    pub fn from_dotenv_bytes(
+        bytes: &[u8],
+        nesting_separator: &str,
+        policy: InputUtf8Policy,
+    ) -> Result<Input, InputDotenvError> {
+        let text = policy
+            .decode_lines(bytes)
+            .map_err(|line_number| InputDotenvError::Utf8 { line_number })?;
+        let mut input = Self::from_dotenv_str(text.as_str(), nesting_separator)?;
+        policy.restore_bytes(&mut input);
+        Ok(input)
+    }
+
+    // An empty `nesting_separator` disables nesting. All values are strings:
+    pub fn from_dotenv_str(text: &str, nesting_separator: &str) -> Result<Input, InputDotenvError> {
+        let mut input = Input::new_map();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let line = line
+                .strip_prefix("export ")
+                .map(str::trim_start)
+                .unwrap_or(line);
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| InputDotenvError::Syntax {
+                    line_number,
+                    description: "expected `KEY=VALUE`".into(),
+                })?;
+            let key = key.trim();
+            if key.is_empty() || key.chars().any(char::is_whitespace) {
+                return Err(InputDotenvError::Syntax {
+                    line_number,
+                    description: format!("invalid key `{key}`"),
+                });
+            }
+            let value = parse_value(value.trim(), line_number)?;
+            let key_list: Vec<&str> = if nesting_separator.is_empty() {
+                vec![key]
+            } else {
+                key.split(nesting_separator).collect()
+            };
+            insert(&mut input, &key_list, value).map_err(|_| InputDotenvError::Conflict {
+                line_number,
+                key: key.to_string(),
+            })?;
+        }
+        Ok(input)
+    }
+}
+
+fn parse_value(value: &str, line_number: usize) -> Result<String, InputDotenvError> {
+    let unterminated = || InputDotenvError::Syntax {
+        line_number,
+        description: "unterminated quoted value".into(),
+    };
+    if let Some(rest) = value.strip_prefix('\'') {
+        let end = rest.find('\'').ok_or_else(unterminated)?;
+        Ok(rest[..end].to_string())
+    } else if let Some(rest) = value.strip_prefix('"') {
+        let mut result = String::new();
+        let mut chars = rest.chars();
+        loop {
+            match chars.next().ok_or_else(unterminated)? {
+                '"' => break,
+                '\\' => match chars.next().ok_or_else(unterminated)? {
+                    'n' => result.push('\n'),
+                    'r' => result.push('\r'),
+                    't' => result.push('\t'),
+                    character => result.push(character),
+                },
+                character => result.push(character),
+            }
+        }
+        Ok(result)
+    } else {
+        // Inline comments need a preceding whitespace, e.g. `KEY=value # comment`:
+        let value = value
+            .find(" #")
+            .map(|index| &value[..index])
+            .unwrap_or(value);
+        Ok(value.trim_end().to_string())
+    }
+}
+
+fn insert(input: &mut Input, key_list: &[&str], value: String) -> Result<(), ()> {
+    let map: &mut InputMap = if let Input::Map(map) = input {
+        map
+    } else {
+        return Err(());
+    };
+    match key_list {
+        [key] => {
+            if map.get(*key).is_some_and(Input::is_map) {
+                return Err(());
+            }
+            map.insert(crate::intern_key(*key), Input::from(value));
+            Ok(())
+        }
+        [key, rest @ ..] => insert(
+            map.entry(crate::intern_key(*key))
+                .or_insert_with(Input::new_map),
+            rest,
+            value,
+        ),
+        [] => Err(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_dotenv_str() {
+        let text = r#"
+# comment
+export HTTP__PORT=8080
+HTTP__HOST = localhost # inline comment
+GREETING="hello \"world\"\n"
+RAW='no $expansion # here'
+EMPTY=
+"#;
+        let input = Input::from_dotenv_str(text, "__").unwrap();
+        let expected: Input = serde_json::from_value(json!({
+            "HTTP": {"PORT": "8080", "HOST": "localhost"},
+            "GREETING": "hello \"world\"\n",
+            "RAW": "no $expansion # here",
+            "EMPTY": ""
+        }))
+        .unwrap();
+        assert_eq!(input, expected);
+        assert!(Input::from_dotenv_str("A__B=1", "")
+            .unwrap()
+            .as_map()
+            .contains_key("A__B"));
+    }
+
+    #[test]
+    fn error() {
+        assert!(matches!(
+            Input::from_dotenv_str("FOO", "__"),
+            Err(InputDotenvError::Syntax { line_number: 1, .. })
+        ));
+        assert!(matches!(
+            Input::from_dotenv_str("FOO=\"bar", "__"),
+            Err(InputDotenvError::Syntax { .. })
+        ));
+        assert!(matches!(
+            Input::from_dotenv_str("A=1\nA__B=2", "__"),
+            Err(InputDotenvError::Conflict { line_number: 2, .. })
+        ));
+        assert!(matches!(
+            Input::from_dotenv("/non/existent/.env", "__"),
+            Err(InputDotenvError::Io { .. })
+        ));
+        assert!(matches!(
+            Input::from_dotenv_bytes(b"A=1\nB=\xff", "__", InputUtf8Policy::Error),
+            Err(InputDotenvError::Utf8 { line_number: 2 })
+        ));
+        let input = Input::from_dotenv_bytes(
+            b"A=1\nB=\xff\nC=\"\xff\\n\" # comment\nD=/w==",
+            "__",
+            InputUtf8Policy::Bytes,
+        )
+        .unwrap();
+        assert_eq!(input.as_map()["B"], Input::new_bytes(b"\xff"));
+        assert_eq!(input.as_map()["C"], Input::new_bytes(b"\xff\n"));
+        assert_eq!(input.as_map()["D"], Input::from("/w=="));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    position::InputPosition,
+    provenance::{InputSource, Provenance},
+    schema::InputSchemaType,
+    utf8::InputUtf8Policy,
+    Input,
+};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum InputEnvError {
+    #[error("Variable `{name}` is not valid UTF-8")]
+    Utf8 { name: String },
+}
+
+// Separates the keys of nested positions in variable names (e.g. `APP_SERVER__PORT`):
+pub const NESTING_SEPARATOR: &str = "__";
+
+// Only reads the variables of the positions declared by the schema (see
+// `InputSchemaType::path_list()`), so unrelated variables with the same prefix don't end up in
+// dynamic maps. Values are strings and are coerced by validation. Fails at the first variable that
+// is not valid UTF-8:
+pub fn load_matching_schema(
+    schema_type: &InputSchemaType,
+    prefix: &str,
+) -> Result<Input, InputEnvError> {
+    load_matching_schema_with_policy(schema_type, prefix, InputUtf8Policy::Error)
+}
+
+// Like `load_matching_schema()`, but variables that are not valid UTF-8 are handled by `policy`:
+pub fn load_matching_schema_with_policy(
+    schema_type: &InputSchemaType,
+    prefix: &str,
+    policy: InputUtf8Policy,
+) -> Result<Input, InputEnvError> {
+    let mut maybe_error = None;
+    let (input, _) = load_with_provenance(schema_type, prefix, |name| {
+        let value = std::env::var_os(name)?;
+        let maybe_value = policy.decode_value(value.as_encoded_bytes());
+        if maybe_value.is_none() && maybe_error.is_none() {
+            maybe_error = Some(InputEnvError::Utf8 {
+                name: name.to_string(),
+            })
+        }
+        maybe_value
+    });
+    maybe_error.map_or(Ok(input), Err)
+}
+
+pub fn load_matching_schema_with<F: FnMut(&str) -> Option<String>>(
+    schema_type: &InputSchemaType,
+    prefix: &str,
+    lookup: F,
+) -> Input {
+    load_matching_schema_with_provenance(schema_type, prefix, lookup).0
+}
+
+// Like `load_matching_schema_with()` with the variable name of every loaded value
+// (`InputSource::Env`):
+pub fn load_matching_schema_with_provenance<F: FnMut(&str) -> Option<String>>(
+    schema_type: &InputSchemaType,
+    prefix: &str,
+    mut lookup: F,
+) -> (Input, Provenance) {
+    load_with_provenance(schema_type, prefix, |name| lookup(name).map(Input::from))
+}
+
+fn load_with_provenance<F: FnMut(&str) -> Option<Input>>(
+    schema_type: &InputSchemaType,
+    prefix: &str,
+    mut lookup: F,
+) -> (Input, Provenance) {
+    let mut input = Input::new_map();
+    let mut provenance = Provenance::new();
+    schema_type
+        .path_list()
+        .into_iter()
+        .filter(|position| !position.is_empty())
+        .for_each(|position| {
+            let name = variable_name(prefix, &position);
+            if let Some(value) = lookup(name.as_str()) {
+                input.insert_at(&position, value);
+                provenance.set_source(position, InputSource::Env(name));
+            }
+        });
+    (input, provenance)
+}
+
+// The prefix as is followed by the upper-case keys with `-` and `.` replaced by `_`, e.g.
+// `APP_SERVER__MAX_CONNECTIONS` for `[server][max-connections]` with prefix `APP_`:
+pub fn variable_name(prefix: &str, position: &InputPosition) -> String {
+    let key_list: Vec<_> = position
+        .iter()
+        .map(|position_type| {
+            position_type
+                .to_string()
+                .to_uppercase()
+                .replace(['-', '.'], "_")
+        })
+        .collect();
+    format!("{prefix}{}", key_list.join(NESTING_SEPARATOR))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn load_matching_schema() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "server": {"schema": {"type": "static_map", "items": {
+                    "max-connections": {"schema": {"type": "integer"}},
+                    "host": {"schema": {"type": "string"}},
+                }}},
+                "headers": {"schema": {"type": "dynamic_map"}},
+            }
+        }))
+        .unwrap();
+        let variable_map = HashMap::from([
+            ("APP_SERVER__MAX_CONNECTIONS", "10"),
+            ("APP_SERVER__TIMEOUT", "5"),
+            ("APP_HEADERS__ACCEPT", "*/*"),
+            ("APP_LOG", "debug"),
+        ]);
+        let input = load_matching_schema_with(&schema_type, "APP_", |name| {
+            variable_map.get(name).map(ToString::to_string)
+        });
+        assert_eq!(
+            input,
+            serde_json::from_value::<Input>(json!({"server": {"max-connections": "10"}})).unwrap()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn load_matching_schema_with_invalid_utf8() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {"token": {"schema": {"type": "binary"}}}
+        }))
+        .unwrap();
+        std::env::set_var("PLUGX_INPUT_UTF8_TOKEN", OsStr::from_bytes(b"\xff"));
+        assert_eq!(
+            super::load_matching_schema(&schema_type, "PLUGX_INPUT_UTF8_"),
+            Err(InputEnvError::Utf8 {
+                name: "PLUGX_INPUT_UTF8_TOKEN".to_string()
+            })
+        );
+        let input = load_matching_schema_with_policy(
+            &schema_type,
+            "PLUGX_INPUT_UTF8_",
+            InputUtf8Policy::Bytes,
+        )
+        .unwrap();
+        assert_eq!(input.as_map()["token"], Input::new_bytes(b"\xff"));
+    }
+}
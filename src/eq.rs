@@ -0,0 +1,203 @@
+#[cfg(feature = "schema")]
+use crate::schema::InputSchemaType;
+use crate::Input;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct InputEqOptions {
+    float_tolerance: f64,
+    ignore_list_order: bool,
+    nan_equal: bool,
+}
+
+impl InputEqOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn float_tolerance(&self) -> f64 {
+        self.float_tolerance
+    }
+
+    pub fn set_float_tolerance(&mut self, float_tolerance: f64) {
+        self.float_tolerance = float_tolerance.abs();
+    }
+
+    pub fn with_float_tolerance(mut self, float_tolerance: f64) -> Self {
+        self.set_float_tolerance(float_tolerance);
+        self
+    }
+
+    pub fn ignore_list_order(&self) -> bool {
+        self.ignore_list_order
+    }
+
+    pub fn set_ignore_list_order(&mut self, ignore_list_order: bool) {
+        self.ignore_list_order = ignore_list_order;
+    }
+
+    pub fn with_ignore_list_order(mut self, ignore_list_order: bool) -> Self {
+        self.set_ignore_list_order(ignore_list_order);
+        self
+    }
+
+    pub fn nan_equal(&self) -> bool {
+        self.nan_equal
+    }
+
+    pub fn set_nan_equal(&mut self, nan_equal: bool) {
+        self.nan_equal = nan_equal;
+    }
+
+    pub fn with_nan_equal(mut self, nan_equal: bool) -> Self {
+        self.set_nan_equal(nan_equal);
+        self
+    }
+}
+
+// A yes/no answer that returns at the first difference (unlike `diff::diff()` which reports all of
+// them). With a tolerance, integers and floats are compared as numbers (e.g. `1` and `1.0`):
+impl Input {
+    pub fn deep_eq_with_tolerance(&self, other: &Input, float_tolerance: f64) -> bool {
+        self.deep_eq_with_options(
+            other,
+            &InputEqOptions::new().with_float_tolerance(float_tolerance),
+        )
+    }
+
+    pub fn deep_eq_with_options(&self, other: &Input, options: &InputEqOptions) -> bool {
+        crate::stack::maybe_grow(|| deep_eq(self, other, options))
+    }
+}
+
+// `==` where `NaN` is equal to `NaN`, for checking whether a value changed (e.g. by diffs,
+// patches, and reloads), since `NaN != NaN` would make every value that contains it a change:
+pub(crate) fn is_unchanged(input: &Input, other: &Input) -> bool {
+    input.deep_eq_with_options(other, &InputEqOptions::new().with_nan_equal(true))
+}
+
+#[cfg(feature = "schema")]
+impl Input {
+    // Compares the inputs after validation, so representation-only differences (e.g. `"8080"` and
+    // `8080`, or `"yes"` and `true`) and missing defaults don't count. An input that is not valid
+    // is compared as it is:
+    pub fn equivalent(&self, other: &Input, schema_type: &InputSchemaType) -> bool {
+        let validated = |input: &Input| {
+            let mut validated_input = input.clone();
+            schema_type
+                .validate(&mut validated_input, None)
+                .map_or_else(|_| input.clone(), |_| validated_input)
+        };
+        validated(self) == validated(other)
+    }
+}
+
+fn deep_eq(input: &Input, other: &Input, options: &InputEqOptions) -> bool {
+    match (input, other) {
+        (Input::Float(float), Input::Float(other_float)) => {
+            (options.nan_equal && float.is_nan() && other_float.is_nan())
+                || float_eq(*float, *other_float, options.float_tolerance)
+        }
+        (Input::Int(integer), Input::Float(float)) | (Input::Float(float), Input::Int(integer))
+            if options.float_tolerance > 0.0 =>
+        {
+            float_eq(*integer as f64, *float, options.float_tolerance)
+        }
+        (Input::List(list), Input::List(other_list)) => {
+            if list.len() != other_list.len() {
+                false
+            } else if options.ignore_list_order {
+                // Every item of `other_list` can only be matched once:
+                let mut matched_list = vec![false; other_list.len()];
+                list.iter().all(|inner_input| {
+                    other_list
+                        .iter()
+                        .enumerate()
+                        .position(|(index, other_inner_input)| {
+                            !matched_list[index]
+                                && inner_input.deep_eq_with_options(other_inner_input, options)
+                        })
+                        .map(|index| matched_list[index] = true)
+                        .is_some()
+                })
+            } else {
+                list.iter()
+                    .zip(other_list)
+                    .all(|(inner_input, other_inner_input)| {
+                        inner_input.deep_eq_with_options(other_inner_input, options)
+                    })
+            }
+        }
+        (Input::Map(map), Input::Map(other_map)) => {
+            map.len() == other_map.len()
+                && map.iter().all(|(key, inner_input)| {
+                    other_map.get(key).is_some_and(|other_inner_input| {
+                        inner_input.deep_eq_with_options(other_inner_input, options)
+                    })
+                })
+        }
+        _ => input == other,
+    }
+}
+
+fn float_eq(float: f64, other_float: f64, float_tolerance: f64) -> bool {
+    float == other_float || (float - other_float).abs() <= float_tolerance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn deep_eq_with_options() {
+        let input: Input =
+            serde_json::from_value(json!({"ratio": 0.1, "count": 1, "tags": ["a", "b", "a"]}))
+                .unwrap();
+        let other_input: Input = serde_json::from_value(
+            json!({"ratio": 0.1000001, "count": 1.0, "tags": ["a", "a", "b"]}),
+        )
+        .unwrap();
+        assert!(input.deep_eq_with_tolerance(&input, 0.0));
+        assert!(!input.deep_eq_with_tolerance(&other_input, 0.001));
+        let options = InputEqOptions::new()
+            .with_float_tolerance(0.001)
+            .with_ignore_list_order(true);
+        assert!(input.deep_eq_with_options(&other_input, &options));
+        assert!(!input.deep_eq_with_options(&other_input, &options.with_float_tolerance(1e-9)));
+
+        let list: Input = serde_json::from_value(json!(["a", "a", "b"])).unwrap();
+        let other_list: Input = serde_json::from_value(json!(["a", "b", "b"])).unwrap();
+        assert!(!list.deep_eq_with_options(&other_list, &options));
+
+        let nan_list = Input::from([Input::from(f64::NAN)]);
+        assert!(nan_list != nan_list.clone());
+        assert!(nan_list.deep_eq_with_options(
+            &nan_list.clone(),
+            &InputEqOptions::new().with_nan_equal(true)
+        ));
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn equivalent() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "integer"}},
+                "enabled": {"schema": {"type": "boolean"}},
+                "workers": {"schema": {"type": "integer"}, "default": 4},
+            }
+        }))
+        .unwrap();
+        let input: Input = serde_json::from_value(json!({"port": 8080, "enabled": true})).unwrap();
+        let other_input: Input =
+            serde_json::from_value(json!({"port": "8080", "enabled": "yes", "workers": 4}))
+                .unwrap();
+        assert!(input.equivalent(&other_input, &schema_type));
+        let other_input: Input =
+            serde_json::from_value(json!({"port": "8081", "enabled": "yes"})).unwrap();
+        assert!(!input.equivalent(&other_input, &schema_type));
+        let invalid_input: Input = serde_json::from_value(json!({"port": "http"})).unwrap();
+        assert!(invalid_input.equivalent(&invalid_input.clone(), &schema_type));
+    }
+}
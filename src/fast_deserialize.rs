@@ -0,0 +1,137 @@
+use crate::{Input, InputMap};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::fmt::Formatter;
+
+// The derived `untagged` implementation buffers every value and then tries each variant on the
+// buffer in order. This visitor builds the value directly with the same result: integers that
+// don't fit in `isize` become floats, and `char`s and UTF-8 bytes become strings. See
+// `benches/large_document.rs` for the difference:
+impl<'de> Deserialize<'de> for Input {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::stack::maybe_grow(|| deserializer.deserialize_any(InputVisitor))
+    }
+}
+
+// Sizes from `size_hint()` are not trusted for preallocation beyond this:
+const MAX_PREALLOCATION: usize = 4096;
+
+struct InputVisitor;
+
+impl<'de> Visitor<'de> for InputVisitor {
+    type Value = Input;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("null, boolean, integer, float, string, list, or map")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Input, E> {
+        Ok(Input::from(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Input, E> {
+        Ok(isize::try_from(value).map_or(Input::from(value as f64), Input::from))
+    }
+
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<Input, E> {
+        Ok(isize::try_from(value).map_or(Input::from(value as f64), Input::from))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Input, E> {
+        Ok(isize::try_from(value).map_or(Input::from(value as f64), Input::from))
+    }
+
+    fn visit_u128<E: de::Error>(self, value: u128) -> Result<Input, E> {
+        Ok(isize::try_from(value).map_or(Input::from(value as f64), Input::from))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Input, E> {
+        Ok(Input::from(value))
+    }
+
+    fn visit_char<E: de::Error>(self, value: char) -> Result<Input, E> {
+        Ok(Input::from(value.to_string()))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Input, E> {
+        Ok(Input::from(value))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Input, E> {
+        Ok(Input::from(value))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Input, E> {
+        std::str::from_utf8(value)
+            .map(Input::from)
+            .map_err(|_| de::Error::invalid_type(de::Unexpected::Bytes(value), &self))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Input, E> {
+        Ok(Input::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Input, E> {
+        Ok(Input::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Input, D::Error> {
+        Input::deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Input, D::Error> {
+        Input::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Input, A::Error> {
+        let mut list: Vec<Input> =
+            Vec::with_capacity(seq.size_hint().unwrap_or_default().min(MAX_PREALLOCATION));
+        while let Some(inner_input) = seq.next_element()? {
+            list.push(inner_input)
+        }
+        Ok(Input::from(list))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map_access: A) -> Result<Input, A::Error> {
+        let mut map = InputMap::new();
+        #[cfg(not(feature = "sorted-maps"))]
+        map.reserve(
+            map_access
+                .size_hint()
+                .unwrap_or_default()
+                .min(MAX_PREALLOCATION),
+        );
+        while let Some((key, inner_input)) = map_access.next_entry()? {
+            map.insert(crate::intern_key::<String>(key), inner_input);
+        }
+        Ok(Input::from(map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize() {
+        let input: Input = serde_json::from_value(json!({
+            "a": [1, -2, 1.5, "x", null, true],
+            "b": {"c": 18446744073709551615u64},
+        }))
+        .unwrap();
+        assert_eq!(input.as_map()["a"].as_list()[1], Input::from(-2));
+        assert_eq!(
+            input.as_map()["b"].as_map()["c"],
+            Input::from(18446744073709551615u64 as f64)
+        );
+        assert_eq!(
+            input.deserialize_into::<Input>().unwrap(),
+            input,
+            "from `Input` itself"
+        );
+        assert!(serde_json::from_str::<Input>("{\"a\": }").is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{position::InputPosition, Input};
+use thiserror::Error;
+
+// How non-finite floats (`NaN`, `inf`, and `-inf`) are handled. Most formats can't represent them
+// (e.g. `serde_json` silently serializes them as `null`):
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputFloatPolicy {
+    #[default]
+    Allow,
+    Reject,
+    // Replaced with `"NaN"`, `"inf"`, or `"-inf"` which float schemas parse back:
+    String,
+}
+
+#[derive(Debug, Clone, Error)]
+#[error("{position} non-finite float `{float}` is not allowed")]
+pub struct InputFloatError {
+    position: InputPosition,
+    float: f64,
+}
+
+impl InputFloatError {
+    pub fn position(&self) -> &InputPosition {
+        &self.position
+    }
+
+    pub fn float(&self) -> f64 {
+        self.float
+    }
+}
+
+impl Input {
+    pub fn try_from_float(float: f64, policy: InputFloatPolicy) -> Result<Input, InputFloatError> {
+        let mut input = Input::from(float);
+        input.apply_float_policy(policy)?;
+        Ok(input)
+    }
+
+    // Applies `policy` to all floats inside the input. On error, floats before the failed one
+    // are kept as they are (`Reject` doesn't change anything):
+    pub fn apply_float_policy(&mut self, policy: InputFloatPolicy) -> Result<(), InputFloatError> {
+        if policy == InputFloatPolicy::Allow {
+            return Ok(());
+        }
+        apply_float_policy(self, policy, InputPosition::new())
+    }
+
+    pub fn has_non_finite_float(&self) -> bool {
+        match self {
+            Self::Float(float) => !float.is_finite(),
+            Self::List(list) => list.iter().any(Self::has_non_finite_float),
+            Self::Map(map) => map.values().any(Self::has_non_finite_float),
+            _ => false,
+        }
+    }
+}
+
+fn apply_float_policy(
+    input: &mut Input,
+    policy: InputFloatPolicy,
+    position: InputPosition,
+) -> Result<(), InputFloatError> {
+    crate::stack::maybe_grow(|| match input {
+        Input::Float(float) if !float.is_finite() => match policy {
+            InputFloatPolicy::Reject => Err(InputFloatError {
+                position,
+                float: *float,
+            }),
+            InputFloatPolicy::String => {
+                *input = Input::from(float.to_string());
+                Ok(())
+            }
+            InputFloatPolicy::Allow => Ok(()),
+        },
+        Input::List(list) => list
+            .iter_mut()
+            .enumerate()
+            .try_for_each(|(index, inner_input)| {
+                apply_float_policy(inner_input, policy, position.new_with_index(index))
+            }),
+        Input::Map(map) => map.iter_mut().try_for_each(|(key, inner_input)| {
+            apply_float_policy(inner_input, policy, position.new_with_key(key))
+        }),
+        _ => Ok(()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn apply_float_policy() {
+        let input = Input::from(HashMap::from([(
+            "ratio",
+            Input::from([
+                Input::from(1.5),
+                Input::from(f64::NAN),
+                Input::from(f64::NEG_INFINITY),
+            ]),
+        )]));
+        assert!(input.has_non_finite_float());
+
+        let mut allowed_input = input.clone();
+        allowed_input
+            .apply_float_policy(InputFloatPolicy::Allow)
+            .unwrap();
+        assert!(allowed_input.has_non_finite_float());
+
+        let error = input
+            .clone()
+            .apply_float_policy(InputFloatPolicy::Reject)
+            .unwrap_err();
+        assert_eq!(error.position().to_string(), "[ratio][1]");
+
+        let mut string_input = input;
+        string_input
+            .apply_float_policy(InputFloatPolicy::String)
+            .unwrap();
+        assert_eq!(
+            string_input,
+            Input::from(HashMap::from([(
+                "ratio",
+                Input::from([Input::from(1.5), Input::from("NaN"), Input::from("-inf")])
+            )]))
+        );
+
+        assert!(Input::try_from_float(f64::INFINITY, InputFloatPolicy::Reject).is_err());
+        assert_eq!(
+            Input::try_from_float(2.0, InputFloatPolicy::Reject).unwrap(),
+            Input::from(2.0)
+        );
+    }
+}
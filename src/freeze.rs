@@ -0,0 +1,179 @@
+use crate::{
+    merge::merge,
+    patch::{InputPatch, InputPatchError},
+    position::{InputPosition, InputPositionPattern},
+    Input,
+};
+use std::ops::Deref;
+use thiserror::Error;
+
+// Merges and patches through the guard don't modify values at (or inside) positions that match
+// the frozen patterns, e.g. security-critical settings that a host has set before plugins add
+// their overlays. The input can still be read through the guard:
+#[derive(Debug)]
+pub struct InputFreezeGuard<'a> {
+    input: &'a mut Input,
+    pattern_list: Vec<InputPositionPattern>,
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InputFreezeError {
+    #[error("{position} is frozen")]
+    Frozen { position: InputPosition },
+    #[error(transparent)]
+    Patch(#[from] InputPatchError),
+}
+
+impl Input {
+    pub fn freeze<P: Into<InputPositionPattern>, I: IntoIterator<Item = P>>(
+        &mut self,
+        pattern_list: I,
+    ) -> InputFreezeGuard<'_> {
+        InputFreezeGuard {
+            input: self,
+            pattern_list: pattern_list.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl InputFreezeGuard<'_> {
+    pub fn pattern_list(&self) -> &Vec<InputPositionPattern> {
+        &self.pattern_list
+    }
+
+    // True if the position or any of its ancestors matches a frozen pattern:
+    pub fn is_frozen(&self, position: &InputPosition) -> bool {
+        let mut maybe_position = Some(position.clone());
+        while let Some(position) = maybe_position {
+            if self
+                .pattern_list
+                .iter()
+                .any(|pattern| pattern.matches(&position))
+            {
+                return true;
+            }
+            maybe_position = position.parent();
+        }
+        false
+    }
+
+    // Like `merge::merge()` but frozen values of `to_be_merged_input` are skipped, and so are
+    // non-map values that would replace the parents of frozen values. Returns skipped positions:
+    pub fn merge(&mut self, to_be_merged_input: &Input) -> Vec<InputPosition> {
+        let mut skipped_list = Vec::new();
+        if let Some(unfrozen_input) =
+            self.unfrozen(to_be_merged_input, InputPosition::new(), &mut skipped_list)
+        {
+            merge(self.input, &unfrozen_input);
+        }
+        skipped_list
+    }
+
+    // Nothing is applied if any operation is (inside) a frozen value or replaces or removes a
+    // parent of a frozen value:
+    pub fn apply_patch(&mut self, patch: &InputPatch) -> Result<(), InputFreezeError> {
+        if let Some(operation) = patch.operation_list().iter().find(|operation| {
+            self.pattern_list
+                .iter()
+                .any(|pattern| pattern.overlaps(operation.position()))
+        }) {
+            return Err(InputFreezeError::Frozen {
+                position: operation.position().clone(),
+            });
+        }
+        Ok(patch.apply(self.input)?)
+    }
+
+    fn unfrozen(
+        &self,
+        to_be_merged_input: &Input,
+        position: InputPosition,
+        skipped_list: &mut Vec<InputPosition>,
+    ) -> Option<Input> {
+        crate::stack::maybe_grow(|| {
+            if self
+                .pattern_list
+                .iter()
+                .any(|pattern| pattern.matches(&position))
+            {
+                skipped_list.push(position);
+                return None;
+            }
+            if !self
+                .pattern_list
+                .iter()
+                .any(|pattern| pattern.overlaps(&position))
+            {
+                return Some(to_be_merged_input.clone());
+            }
+            // A parent of frozen values, so only maps are merged into it:
+            let Input::Map(to_be_merged_map) = to_be_merged_input else {
+                skipped_list.push(position);
+                return None;
+            };
+            Some(Input::from(
+                to_be_merged_map
+                    .iter()
+                    .filter_map(|(key, inner_to_be_merged_input)| {
+                        self.unfrozen(
+                            inner_to_be_merged_input,
+                            position.new_with_key(key),
+                            skipped_list,
+                        )
+                        .map(|inner_input| (key.clone(), inner_input))
+                    })
+                    .collect::<std::collections::HashMap<_, _>>(),
+            ))
+        })
+    }
+}
+
+impl Deref for InputFreezeGuard<'_> {
+    type Target = Input;
+
+    fn deref(&self) -> &Self::Target {
+        self.input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patch::InputPatchOperation;
+    use serde_json::json;
+
+    #[test]
+    fn freeze() {
+        let mut input: Input =
+            serde_json::from_value(json!({"security": {"tls": true, "level": 1}, "port": 80}))
+                .unwrap();
+        let mut guard = input.freeze(["security.tls"]);
+        let overlay: Input = serde_json::from_value(json!({
+            "security": {"tls": false, "level": 2},
+            "port": 8080,
+        }))
+        .unwrap();
+        let skipped_list = guard.merge(&overlay);
+        assert_eq!(skipped_list.len(), 1);
+        assert_eq!(skipped_list[0].to_string(), "[security][tls]");
+        let overlay: Input = serde_json::from_value(json!({"security": false})).unwrap();
+        assert_eq!(guard.merge(&overlay)[0].to_string(), "security");
+        let patch = InputPatch::new().with_operation(InputPatchOperation::Remove {
+            position: InputPosition::new().new_with_key("security"),
+            old_value: guard.as_map()["security"].clone(),
+        });
+        assert!(matches!(
+            guard.apply_patch(&patch),
+            Err(InputFreezeError::Frozen { .. })
+        ));
+        assert!(guard.is_frozen(
+            &InputPosition::new()
+                .new_with_key("security")
+                .new_with_key("tls")
+        ));
+        let expected: Input =
+            serde_json::from_value(json!({"security": {"tls": true, "level": 2}, "port": 8080}))
+                .unwrap();
+        assert_eq!(input, expected);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::Input;
+use std::{collections::HashMap, io::Read, sync::Mutex, time::Duration};
+use thiserror::Error;
+
+#[derive(Debug)]
+pub struct InputHttpSource {
+    timeout: Duration,
+    max_size: usize,
+    etag_cache: bool,
+    // url -> (ETag, input)
+    cache: Mutex<HashMap<String, (String, Input)>>,
+}
+
+#[derive(Debug, Error)]
+pub enum InputHttpError {
+    #[error("Could not fetch {url:?}: {source}")]
+    Request {
+        url: String,
+        source: Box<ureq::Error>,
+    },
+    #[error("Could not read response body of {url:?}: {source}")]
+    Read { url: String, source: std::io::Error },
+    #[error("Response body of {url:?} is bigger than {max_size} bytes")]
+    TooLarge { url: String, max_size: usize },
+    #[error("Could not parse response body of {url:?}: {source}")]
+    Parse { url: String, source: anyhow::Error },
+    // A `304 Not Modified` response without a cached input to use instead:
+    #[error("{url:?} responded with `304 Not Modified` but there is no cached input of it")]
+    NotModified { url: String },
+}
+
+impl Default for InputHttpSource {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            max_size: 1024 * 1024,
+            etag_cache: false,
+            cache: Default::default(),
+        }
+    }
+}
+
+impl InputHttpSource {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.set_timeout(timeout);
+        self
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    pub fn set_max_size(&mut self, max_size: usize) {
+        self.max_size = max_size;
+    }
+
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.set_max_size(max_size);
+        self
+    }
+
+    pub fn etag_cache(&self) -> bool {
+        self.etag_cache
+    }
+
+    pub fn set_etag_cache(&mut self, etag_cache: bool) {
+        self.etag_cache = etag_cache;
+    }
+
+    pub fn with_etag_cache(mut self, etag_cache: bool) -> Self {
+        self.set_etag_cache(etag_cache);
+        self
+    }
+
+    // `format` parses the response body, e.g. `|bytes| Ok(serde_json::from_slice(bytes)?)`:
+    pub fn fetch<F>(&self, url: &str, format: F) -> Result<Input, InputHttpError>
+    where
+        F: FnOnce(&[u8]) -> anyhow::Result<Input>,
+    {
+        let agent = ureq::AgentBuilder::new().timeout(self.timeout).build();
+        let mut request = agent.get(url);
+        let maybe_cached = if self.etag_cache {
+            self.cache
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .get(url)
+                .cloned()
+        } else {
+            None
+        };
+        if let Some((etag, _)) = maybe_cached.as_ref() {
+            request = request.set("If-None-Match", etag);
+        }
+        let response = request.call().map_err(|source| InputHttpError::Request {
+            url: url.to_string(),
+            source: Box::new(source),
+        })?;
+        if response.status() == 304 {
+            return maybe_cached.map(|(_, input)| input).ok_or_else(|| {
+                InputHttpError::NotModified {
+                    url: url.to_string(),
+                }
+            });
+        }
+        let maybe_etag = response.header("ETag").map(String::from);
+        let mut body = Vec::new();
+        response
+            .into_reader()
+            .take(self.max_size as u64 + 1)
+            .read_to_end(&mut body)
+            .map_err(|source| InputHttpError::Read {
+                url: url.to_string(),
+                source,
+            })?;
+        if body.len() > self.max_size {
+            return Err(InputHttpError::TooLarge {
+                url: url.to_string(),
+                max_size: self.max_size,
+            });
+        }
+        let input = format(&body).map_err(|source| InputHttpError::Parse {
+            url: url.to_string(),
+            source,
+        })?;
+        if let (true, Some(etag)) = (self.etag_cache, maybe_etag) {
+            self.cache
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .insert(url.to_string(), (etag, input.clone()));
+        }
+        Ok(input)
+    }
+}
+
+impl Input {
+    pub fn from_url<F>(url: &str, format: F) -> Result<Input, InputHttpError>
+    where
+        F: FnOnce(&[u8]) -> anyhow::Result<Input>,
+    {
+        InputHttpSource::new().fetch(url, format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    // Serves the given responses in order and returns the request header lines it received:
+    fn serve(response_list: Vec<String>) -> (String, thread::JoinHandle<Vec<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/config.json", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            response_list
+                .into_iter()
+                .map(|response| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let header_list: Vec<String> = BufReader::new(stream.try_clone().unwrap())
+                        .lines()
+                        .map(|line| line.unwrap())
+                        .take_while(|line| !line.is_empty())
+                        .collect();
+                    stream.write_all(response.as_bytes()).unwrap();
+                    header_list
+                })
+                .collect()
+        });
+        (url, handle)
+    }
+
+    fn json(bytes: &[u8]) -> anyhow::Result<Input> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    #[test]
+    fn from_url() {
+        let body = r#"{"port": 8080}"#;
+        let (url, handle) = serve(vec![
+            format!("HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len()),
+            "HTTP/1.1 304 Not Modified\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+            format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len()),
+        ]);
+        let source = InputHttpSource::new().with_etag_cache(true);
+        let input = source.fetch(&url, json).unwrap();
+        assert_eq!(input.as_map().get("port"), Some(&Input::from(8080)));
+        assert_eq!(source.fetch(&url, json).unwrap(), input);
+        assert!(matches!(
+            InputHttpSource::new().with_max_size(4).fetch(&url, json),
+            Err(InputHttpError::TooLarge { .. })
+        ));
+        let request_list = handle.join().unwrap();
+        assert!(!request_list[0]
+            .iter()
+            .any(|header| header.to_lowercase().starts_with("if-none-match")));
+        assert!(request_list[1]
+            .iter()
+            .any(|header| header.to_lowercase() == "if-none-match: \"v1\""));
+    }
+
+    #[test]
+    fn not_modified_without_cache() {
+        let (url, handle) = serve(vec![
+            "HTTP/1.1 304 Not Modified\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                .to_string(),
+        ]);
+        assert!(matches!(
+            InputHttpSource::new()
+                .with_etag_cache(true)
+                .fetch(&url, json),
+            Err(InputHttpError::NotModified { .. })
+        ));
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,363 @@
+use crate::{
+    position::InputPosition,
+    resolution::{chain_text, ResolutionError, ResolutionGuard, DEFAULT_MAX_DEPTH},
+    Input,
+};
+use std::{
+    fmt::{Debug, Formatter},
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+pub const INCLUDE_KEY: &str = "$include";
+
+pub type InputIncludeLoader = Box<dyn Fn(&Path) -> anyhow::Result<Input> + Send + Sync>;
+
+// The host may provide its own loader, otherwise `load_by_extension()` is used. Included paths
+// (after following symlinks) must be inside the base directory:
+pub struct InputIncludeResolver {
+    base_dir: PathBuf,
+    loader: InputIncludeLoader,
+    max_depth: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum InputIncludeError {
+    #[error("{position} could not include {path:?}: {source}")]
+    Load {
+        position: InputPosition,
+        path: PathBuf,
+        source: anyhow::Error,
+    },
+    // Chains are canonical paths of the including files, with the failed one at the end:
+    #[error("{position} includes {path:?} which is already being included ({})", chain_text(.chain))]
+    Cycle {
+        position: InputPosition,
+        path: PathBuf,
+        chain: Vec<PathBuf>,
+    },
+    #[error("{position} exceeds the maximum include depth {max_depth} ({})", chain_text(.chain))]
+    Depth {
+        position: InputPosition,
+        max_depth: usize,
+        chain: Vec<PathBuf>,
+    },
+    #[error("{position} includes {path:?} which is outside of the base directory {base_dir:?}")]
+    Escape {
+        position: InputPosition,
+        path: PathBuf,
+        base_dir: PathBuf,
+    },
+    #[error("{position} expected a string path for `{INCLUDE_KEY}`, got `{input}`")]
+    Invalid {
+        position: InputPosition,
+        input: Input,
+    },
+}
+
+impl InputIncludeResolver {
+    pub fn new<F>(loader: F) -> Self
+    where
+        F: Fn(&Path) -> anyhow::Result<Input> + Send + Sync + 'static,
+    {
+        Self {
+            base_dir: PathBuf::from("."),
+            loader: Box::new(loader),
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    pub fn base_dir(&self) -> &PathBuf {
+        &self.base_dir
+    }
+
+    pub fn set_base_dir<P: Into<PathBuf>>(&mut self, base_dir: P) {
+        self.base_dir = base_dir.into();
+    }
+
+    pub fn with_base_dir<P: Into<PathBuf>>(mut self, base_dir: P) -> Self {
+        self.set_base_dir(base_dir);
+        self
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.set_max_depth(max_depth);
+        self
+    }
+
+    pub fn resolve(&self, input: &mut Input) -> Result<(), InputIncludeError> {
+        self.resolve_with_guard(input, &mut ResolutionGuard::new())
+    }
+
+    // For resolvers that include inputs themselves (e.g. from other sources) with the same guard:
+    pub fn resolve_with_guard(
+        &self,
+        input: &mut Input,
+        guard: &mut ResolutionGuard<PathBuf>,
+    ) -> Result<(), InputIncludeError> {
+        guard.set_max_depth(self.max_depth);
+        self.resolve_with_position(input, InputPosition::new(), &self.base_dir, guard)
+    }
+
+    fn resolve_with_position(
+        &self,
+        input: &mut Input,
+        position: InputPosition,
+        dir: &Path,
+        guard: &mut ResolutionGuard<PathBuf>,
+    ) -> Result<(), InputIncludeError> {
+        if let Some(include) = include_of(input) {
+            let path = if let Input::Str(path) = include {
+                dir.join(path)
+            } else {
+                return Err(InputIncludeError::Invalid {
+                    position,
+                    input: include.clone(),
+                });
+            };
+            let canonical_path = path
+                .canonicalize()
+                .map_err(|error| InputIncludeError::Load {
+                    position: position.clone(),
+                    path: path.clone(),
+                    source: error.into(),
+                })?;
+            let base_dir = self
+                .base_dir
+                .canonicalize()
+                .unwrap_or_else(|_| self.base_dir.clone());
+            if !canonical_path.starts_with(&base_dir) {
+                return Err(InputIncludeError::Escape {
+                    position,
+                    path,
+                    base_dir,
+                });
+            }
+            let included = guard
+                .resolve(canonical_path.clone(), |guard| {
+                    let mut included = (self.loader)(&canonical_path).map_err(|source| {
+                        InputIncludeError::Load {
+                            position: position.clone(),
+                            path: path.clone(),
+                            source,
+                        }
+                    })?;
+                    // Nested includes are relative to the file that contains them:
+                    let included_dir = canonical_path
+                        .parent()
+                        .map(Path::to_path_buf)
+                        .unwrap_or_else(|| dir.to_path_buf());
+                    self.resolve_with_position(
+                        &mut included,
+                        position.clone(),
+                        &included_dir,
+                        guard,
+                    )
+                    .map(|_| included)
+                })
+                .map_err(|error| {
+                    let chain = error.chain().iter().chain([error.key()]).cloned().collect();
+                    match error {
+                        ResolutionError::Cycle { .. } => InputIncludeError::Cycle {
+                            position: position.clone(),
+                            path,
+                            chain,
+                        },
+                        ResolutionError::Depth { max_depth, .. } => InputIncludeError::Depth {
+                            position: position.clone(),
+                            max_depth,
+                            chain,
+                        },
+                    }
+                })??;
+            *input = included;
+            return Ok(());
+        }
+        match input {
+            Input::Map(map) => map.iter_mut().try_for_each(|(key, inner_input)| {
+                self.resolve_with_position(inner_input, position.new_with_key(key), dir, guard)
+            }),
+            Input::List(list) => {
+                list.iter_mut()
+                    .enumerate()
+                    .try_for_each(|(index, inner_input)| {
+                        self.resolve_with_position(
+                            inner_input,
+                            position.new_with_index(index),
+                            dir,
+                            guard,
+                        )
+                    })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for InputIncludeResolver {
+    fn default() -> Self {
+        Self::new(load_by_extension)
+    }
+}
+
+// Parses the file in the format of its extension, for the formats whose features are enabled:
+pub fn load_by_extension(path: &Path) -> anyhow::Result<Input> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    #[allow(unused_variables)]
+    let text = std::fs::read_to_string(path)?;
+    match extension.as_str() {
+        #[cfg(feature = "json")]
+        "json" => Ok(Input::from_json_str_strict(text.as_str())?),
+        #[cfg(feature = "relaxed-json")]
+        "json5" => Ok(Input::from_json5_str(text.as_str())?),
+        #[cfg(feature = "toml")]
+        "toml" => Ok(::toml::from_str(text.as_str())?),
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => Ok(serde_yaml::from_str(text.as_str())?),
+        #[cfg(feature = "ini")]
+        "ini" => Ok(Input::from_ini_str(text.as_str())?),
+        _ => anyhow::bail!("unsupported file extension {extension:?}"),
+    }
+}
+
+fn include_of(input: &Input) -> Option<&Input> {
+    match input {
+        Input::Map(map) if map.len() == 1 => map.get(INCLUDE_KEY),
+        _ => None,
+    }
+}
+
+impl Debug for InputIncludeResolver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputIncludeResolver")
+            .field("base_dir", &self.base_dir)
+            .field("max_depth", &self.max_depth)
+            .finish()
+    }
+}
+
+impl Input {
+    pub fn resolve_includes(
+        &mut self,
+        resolver: &InputIncludeResolver,
+    ) -> Result<(), InputIncludeError> {
+        resolver.resolve(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+
+    fn resolver(base_dir: &Path) -> InputIncludeResolver {
+        InputIncludeResolver::new(|path| {
+            Ok(serde_json::from_str(fs::read_to_string(path)?.as_str())?)
+        })
+        .with_base_dir(base_dir)
+    }
+
+    #[test]
+    fn resolve_includes() {
+        let dir = tempdir::TempDir::new("plugx-input").unwrap();
+        fs::create_dir(dir.path().join("plugins")).unwrap();
+        fs::write(
+            dir.path().join("plugins/http.json"),
+            r#"{"port": 80, "tls": {"$include": "tls.json"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("plugins/tls.json"), r#"{"enabled": true}"#).unwrap();
+        let mut input: Input =
+            serde_json::from_value(json!({"plugins": [{"$include": "plugins/http.json"}]}))
+                .unwrap();
+        input.resolve_includes(&resolver(dir.path())).unwrap();
+        assert_eq!(
+            input,
+            serde_json::from_value(json!({"plugins": [{"port": 80, "tls": {"enabled": true}}]}))
+                .unwrap()
+        );
+
+        let mut input: Input =
+            serde_json::from_value(json!({"foo": {"$include": "missing.json"}})).unwrap();
+        let error = input.resolve_includes(&resolver(dir.path())).unwrap_err();
+        assert!(matches!(error, InputIncludeError::Load { .. }));
+        assert!(error.to_string().starts_with("foo "));
+    }
+
+    #[test]
+    fn cycle() {
+        let dir = tempdir::TempDir::new("plugx-input").unwrap();
+        fs::write(
+            dir.path().join("a.json"),
+            r#"{"b": {"$include": "b.json"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("b.json"),
+            r#"{"a": {"$include": "a.json"}}"#,
+        )
+        .unwrap();
+        let mut input: Input = serde_json::from_value(json!({"$include": "a.json"})).unwrap();
+        let error = input.resolve_includes(&resolver(dir.path())).unwrap_err();
+        assert!(
+            matches!(&error, InputIncludeError::Cycle { chain, .. } if chain.len() == 3 && chain[0] == chain[2])
+        );
+        assert_eq!(error.to_string().split(' ').next(), Some("[b][a]"));
+    }
+
+    #[test]
+    fn escape() {
+        let dir = tempdir::TempDir::new("plugx-input").unwrap();
+        fs::create_dir(dir.path().join("base")).unwrap();
+        fs::write(dir.path().join("secret.json"), r#"{"password": "hunter2"}"#).unwrap();
+        let absolute_path = dir.path().join("secret.json");
+        for path in ["../secret.json", absolute_path.to_str().unwrap()] {
+            let mut input: Input =
+                serde_json::from_value(json!({"foo": {"$include": path}})).unwrap();
+            let error = input
+                .resolve_includes(&resolver(&dir.path().join("base")))
+                .unwrap_err();
+            assert!(matches!(error, InputIncludeError::Escape { .. }));
+            assert!(error.to_string().starts_with("foo "));
+        }
+    }
+
+    #[cfg(all(feature = "json", feature = "toml"))]
+    #[test]
+    fn load_by_extension() {
+        let dir = tempdir::TempDir::new("plugx-input").unwrap();
+        fs::write(
+            dir.path().join("a.toml"),
+            "b = { \"$include\" = \"b.json\" }",
+        )
+        .unwrap();
+        fs::write(dir.path().join("b.json"), r#"{"enabled": true}"#).unwrap();
+        fs::write(dir.path().join("c.txt"), "").unwrap();
+        let resolver = InputIncludeResolver::default().with_base_dir(dir.path());
+        let mut input: Input = serde_json::from_value(json!({"$include": "a.toml"})).unwrap();
+        input.resolve_includes(&resolver).unwrap();
+        assert_eq!(
+            input,
+            serde_json::from_value(json!({"b": {"enabled": true}})).unwrap()
+        );
+
+        let mut input: Input = serde_json::from_value(json!({"$include": "c.txt"})).unwrap();
+        assert!(matches!(
+            input.resolve_includes(&resolver).unwrap_err(),
+            InputIncludeError::Load { .. }
+        ));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{utf8::InputUtf8Policy, Input, InputMap};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InputIniError {
+    #[error("Line {line_number}: {description}")]
+    Syntax {
+        line_number: usize,
+        description: String,
+    },
+    #[error("Line {line_number}: invalid UTF-8")]
+    Utf8 { line_number: usize },
+}
+
+impl Input {
+    pub fn from_ini_bytes(bytes: &[u8], policy: InputUtf8Policy) -> Result<Input, InputIniError> {
+        let text = policy
+            .decode_lines(bytes)
+            .map_err(|line_number| InputIniError::Utf8 { line_number })?;
+        let mut input = Self::from_ini_str(text.as_str())?;
+        policy.restore_bytes(&mut input);
+        Ok(input)
+    }
+
+    // Keys before the first section are placed at the top level. Repeated keys become lists and
+    // repeated sections are merged together. All values are strings:
+    pub fn from_ini_str(text: &str) -> Result<Input, InputIniError> {
+        let mut root = InputMap::new();
+        let mut maybe_section: Option<String> = None;
+        let mut line_iter = text.lines().enumerate();
+        while let Some((index, line)) = line_iter.next() {
+            let line_number = index + 1;
+            let mut line = line.trim().to_string();
+            // systemd-style line continuation:
+            while line.ends_with('\\') {
+                line.pop();
+                if let Some((_, next_line)) = line_iter.next() {
+                    line.push(' ');
+                    line.push_str(next_line.trim());
+                } else {
+                    break;
+                }
+            }
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[') {
+                let section = section
+                    .strip_suffix(']')
+                    .map(str::trim)
+                    .filter(|section| !section.is_empty())
+                    .ok_or_else(|| InputIniError::Syntax {
+                        line_number,
+                        description: format!("invalid section header `{line}`"),
+                    })?;
+                match root
+                    .entry(crate::intern_key(section))
+                    .or_insert_with(Input::new_map)
+                {
+                    Input::Map(_) => (),
+                    _ => {
+                        return Err(InputIniError::Syntax {
+                            line_number,
+                            description: format!(
+                                "section `{section}` conflicts with a top-level key"
+                            ),
+                        })
+                    }
+                }
+                maybe_section = Some(section.to_string());
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| InputIniError::Syntax {
+                line_number,
+                description: "expected `key=value`".into(),
+            })?;
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(InputIniError::Syntax {
+                    line_number,
+                    description: "empty key".into(),
+                });
+            }
+            let map = if let Some(section) = maybe_section.as_ref() {
+                root.get_mut(section.as_str()).unwrap().map_mut()
+            } else {
+                &mut root
+            };
+            let value = Input::from(unquote(value.trim()));
+            match map.get_mut(key) {
+                Some(Input::List(list)) => list.push(value),
+                Some(Input::Map(_)) => {
+                    return Err(InputIniError::Syntax {
+                        line_number,
+                        description: format!("key `{key}` conflicts with a section"),
+                    })
+                }
+                Some(previous) => *previous = Input::from([previous.clone(), value]),
+                None => {
+                    map.insert(crate::intern_key(key), value);
+                }
+            }
+        }
+        Ok(Input::from(root))
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    if value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')))
+    {
+        &value[1..value.len() - 1]
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_ini_str() {
+        let text = r#"
+name = my-service
+; comment
+[Service]
+ExecStart=/usr/bin/foo \
+    --verbose
+Environment="A=1"
+Environment=B=2
+
+[Install]
+WantedBy = multi-user.target
+[Service]
+Restart=always
+"#;
+        let expected: Input = serde_json::from_value(json!({
+            "name": "my-service",
+            "Service": {
+                "ExecStart": "/usr/bin/foo  --verbose",
+                "Environment": ["A=1", "B=2"],
+                "Restart": "always"
+            },
+            "Install": {"WantedBy": "multi-user.target"}
+        }))
+        .unwrap();
+        assert_eq!(Input::from_ini_str(text).unwrap(), expected);
+    }
+
+    #[test]
+    fn error() {
+        assert!(Input::from_ini_str("[section").is_err());
+        assert!(Input::from_ini_str("key").is_err());
+        assert_eq!(
+            Input::from_ini_str("a=1\n[a]"),
+            Err(InputIniError::Syntax {
+                line_number: 2,
+                description: "section `a` conflicts with a top-level key".into()
+            })
+        );
+        assert_eq!(
+            Input::from_ini_bytes(b"[a]\nb = \xff", InputUtf8Policy::Bytes)
+                .unwrap()
+                .as_map()["a"]
+                .as_map()["b"],
+            Input::new_bytes(b"\xff")
+        );
+        assert_eq!(
+            Input::from_ini_bytes(b"[\xff]", InputUtf8Policy::Bytes),
+            Err(InputIniError::Utf8 { line_number: 1 })
+        );
+    }
+}
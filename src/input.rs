@@ -1,13 +1,18 @@
-use serde::{Deserialize, Serialize};
-use std::{
-    collections::HashMap,
-    fmt::{Debug, Display, Formatter},
+use crate::{
+    display::DisplayOptions,
+    position::{InputPosition, InputPositionType},
 };
-
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg(not(feature = "fast-deserialize"))]
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt::{Debug, Display, Formatter};
+
+// With the `fast-deserialize` feature, `Deserialize` is implemented in `fast_deserialize.rs`:
+#[derive(Debug, Default, Serialize)]
+#[cfg_attr(not(feature = "fast-deserialize"), derive(Deserialize))]
 #[serde(
     untagged,
-    expecting = "expecting boolean, integer, float, string, list, or map"
+    expecting = "expecting null, boolean, integer, float, string, list, or map"
 )]
 pub enum Input {
     Bool(bool),
@@ -15,12 +20,80 @@ pub enum Input {
     Float(f64),
     Str(String),
     List(Vec<Input>),
-    Map(HashMap<String, Input>),
+    // Keys are interned while deserializing with the `interned-keys` feature (see `InputKey`):
+    #[cfg_attr(
+        all(feature = "interned-keys", not(feature = "fast-deserialize")),
+        serde(deserialize_with = "deserialize_interned_map")
+    )]
+    Map(InputMap),
+    #[default]
+    Null,
+}
+
+// `HashMap` by default. With the `sorted-maps` feature, maps are `BTreeMap`s so that everything
+// that iterates them (serialization, `Display`, diffs, patches, and merges) is in key order. Code
+// that has to build with and without the feature should only use methods that both have:
+#[cfg(not(feature = "sorted-maps"))]
+pub type InputMap = std::collections::HashMap<InputKey, Input>;
+#[cfg(feature = "sorted-maps")]
+pub type InputMap = std::collections::BTreeMap<InputKey, Input>;
+
+// Keys of maps and positions. With the `interned-keys` feature, keys are `Arc<str>`s and equal
+// keys that are created via `intern_key()` (e.g. while deserializing) share one allocation, so
+// large documents with the same keys in every item of a list take less memory and equal keys are
+// compared by their address first. Like `sorted-maps`, the feature changes a public type:
+#[cfg(not(feature = "interned-keys"))]
+pub type InputKey = String;
+#[cfg(feature = "interned-keys")]
+pub type InputKey = std::sync::Arc<str>;
+
+#[cfg(not(feature = "interned-keys"))]
+pub fn intern_key<K: AsRef<str> + Into<String>>(key: K) -> InputKey {
+    key.into()
+}
+
+// Interned keys are kept per thread. Keys that are not used anymore are dropped whenever the
+// number of keys has doubled:
+#[cfg(feature = "interned-keys")]
+pub fn intern_key<K: AsRef<str> + Into<String>>(key: K) -> InputKey {
+    use std::{cell::RefCell, collections::HashSet, sync::Arc};
+
+    thread_local! {
+        static KEY_SET: RefCell<(HashSet<Arc<str>>, usize)> =
+            RefCell::new((HashSet::new(), INITIAL_KEY_COUNT));
+    }
+    const INITIAL_KEY_COUNT: usize = 1024;
+
+    KEY_SET.with(|key_set| {
+        let (key_set, max_key_count) = &mut *key_set.borrow_mut();
+        if let Some(interned_key) = key_set.get(key.as_ref()) {
+            return interned_key.clone();
+        }
+        if key_set.len() >= *max_key_count {
+            key_set.retain(|interned_key| Arc::strong_count(interned_key) > 1);
+            *max_key_count = (key_set.len() * 2).max(INITIAL_KEY_COUNT);
+        }
+        let interned_key: Arc<str> = Arc::from(key.as_ref());
+        key_set.insert(interned_key.clone());
+        interned_key
+    })
+}
+
+#[cfg(all(feature = "interned-keys", not(feature = "fast-deserialize")))]
+fn deserialize_interned_map<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<InputMap, D::Error> {
+    Ok(
+        std::collections::HashMap::<String, Input>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(key, input)| (intern_key(key), input))
+            .collect(),
+    )
 }
 
 impl Input {
     pub fn new_map() -> Self {
-        Self::Map(HashMap::new())
+        Self::Map(InputMap::new())
     }
 
     pub fn new_list() -> Self {
@@ -31,6 +104,22 @@ impl Input {
         Self::Str(String::new())
     }
 
+    pub fn new_null() -> Self {
+        Self::Null
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    pub fn from_option<T: Into<Input>>(maybe_value: Option<T>) -> Self {
+        maybe_value.map(|value| value.into()).unwrap_or_default()
+    }
+
+    pub fn from_option_or_default<T: Into<Input> + Default>(maybe_value: Option<T>) -> Self {
+        maybe_value.unwrap_or_default().into()
+    }
+
     pub fn is_bool(&self) -> bool {
         matches!(self, Self::Bool(_))
     }
@@ -127,9 +216,9 @@ impl Input {
         }
     }
 
-    pub fn into_str(self) -> String {
-        if let Self::Str(value) = self {
-            value
+    pub fn into_str(mut self) -> String {
+        if let Self::Str(value) = &mut self {
+            std::mem::take(value)
         } else {
             panic!("Expected Input to be a string. You should call `.is_<TYPE>()` before calling any `into_<TYPE>()` method")
         }
@@ -155,9 +244,9 @@ impl Input {
         }
     }
 
-    pub fn into_list(self) -> Vec<Input> {
-        if let Self::List(value) = self {
-            value
+    pub fn into_list(mut self) -> Vec<Input> {
+        if let Self::List(value) = &mut self {
+            std::mem::take(value)
         } else {
             panic!("Expected Input to be a list. You should call `.is_<TYPE>()` before calling any `into_<TYPE>()` method")
         }
@@ -175,7 +264,7 @@ impl Input {
         matches!(self, Self::Map(_))
     }
 
-    pub fn as_map(&self) -> &HashMap<String, Input> {
+    pub fn as_map(&self) -> &InputMap {
         if let Self::Map(value) = self {
             value
         } else {
@@ -183,15 +272,15 @@ impl Input {
         }
     }
 
-    pub fn into_map(self) -> HashMap<String, Input> {
-        if let Self::Map(value) = self {
-            value
+    pub fn into_map(mut self) -> InputMap {
+        if let Self::Map(value) = &mut self {
+            std::mem::take(value)
         } else {
             panic!("Expected Input to be a map. You should call `.is_<TYPE>()` before calling any `into_<TYPE>()` method")
         }
     }
 
-    pub fn map_mut(&mut self) -> &mut HashMap<String, Input> {
+    pub fn map_mut(&mut self) -> &mut InputMap {
         if let Self::Map(value) = self {
             value
         } else {
@@ -199,6 +288,65 @@ impl Input {
         }
     }
 
+    pub fn get_at(&self, position: &InputPosition) -> Option<&Input> {
+        position
+            .iter()
+            .try_fold(self, |input, position_type| match (input, position_type) {
+                (Self::Map(map), InputPositionType::Key(key)) => map.get(key),
+                (Self::List(list), InputPositionType::Index(index)) => list.get(*index),
+                _ => None,
+            })
+    }
+
+    pub fn get_at_mut(&mut self, position: &InputPosition) -> Option<&mut Input> {
+        position
+            .iter()
+            .try_fold(self, |input, position_type| match (input, position_type) {
+                (Self::Map(map), InputPositionType::Key(key)) => map.get_mut(key),
+                (Self::List(list), InputPositionType::Index(index)) => list.get_mut(*index),
+                _ => None,
+            })
+    }
+
+    // Like `serde_json::Value::pointer()`: RFC 6901 JSON Pointers (e.g. `/a/b/0`) where `~1` is
+    // `/` and `~0` is `~` in keys, and list indexes have no leading `+` or `0`s:
+    pub fn pointer(&self, pointer: &str) -> Option<&Input> {
+        pointer_token_iter(pointer)?.try_fold(self, |input, token| match input {
+            Self::Map(map) => map.get(&*token),
+            Self::List(list) => pointer_index(&token).and_then(|index| list.get(index)),
+            _ => None,
+        })
+    }
+
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Input> {
+        pointer_token_iter(pointer)?.try_fold(self, |input, token| match input {
+            Self::Map(map) => map.get_mut(&*token),
+            Self::List(list) => pointer_index(&token).and_then(|index| list.get_mut(index)),
+            _ => None,
+        })
+    }
+
+    // Like `get_at_mut()` but missing map keys are inserted as maps. Returns `false` if the
+    // position goes through a value that is not a map (or a list with that index):
+    pub(crate) fn insert_at(&mut self, position: &InputPosition, value: Input) -> bool {
+        let maybe_inner_input =
+            position
+                .iter()
+                .try_fold(self, |input, position_type| match (input, position_type) {
+                    (Self::Map(map), InputPositionType::Key(key)) => {
+                        Some(map.entry(key.clone()).or_insert_with(Self::new_map))
+                    }
+                    (Self::List(list), InputPositionType::Index(index)) => list.get_mut(*index),
+                    _ => None,
+                });
+        if let Some(inner_input) = maybe_inner_input {
+            *inner_input = value;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn type_name(&self) -> String {
         match self {
             Self::Bool(_) => Self::bool_type_name(),
@@ -207,6 +355,7 @@ impl Input {
             Self::Str(_) => Self::str_type_name(),
             Self::List(_) => Self::list_type_name(),
             Self::Map(_) => Self::map_type_name(),
+            Self::Null => Self::null_type_name(),
         }
     }
 
@@ -233,46 +382,174 @@ impl Input {
     pub fn bool_type_name() -> String {
         "boolean".to_string()
     }
+
+    pub fn null_type_name() -> String {
+        "null".to_string()
+    }
 }
 
-impl Display for Input {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+// `Clone` and `PartialEq` are implemented by hand to not overflow the stack for deeply nested
+// inputs (see `stack::maybe_grow()`):
+impl Clone for Input {
+    fn clone(&self) -> Self {
         match self {
-            Self::Bool(value) => write!(f, "{value}"),
-            Self::Int(value) => write!(f, "{value}"),
-            Self::Float(value) => write!(f, "{value}"),
-            Self::Str(value) => write!(f, "{value:?}"),
-            Self::List(value) => {
-                write!(f, "[")?;
-                let length = value.len();
-                for (index, inner_value) in value.iter().enumerate() {
-                    write!(f, "{inner_value}")?;
-                    if index < length - 1 {
-                        write!(f, ", ")?;
-                    }
-                }
-                write!(f, "]")
-            }
-            Self::Map(value) => {
-                write!(f, "{{")?;
-                let length = value.len();
-                for (index, (key, value)) in value.iter().enumerate() {
-                    write!(f, "{key:?}: {value}")?;
-                    if index < length - 1 {
-                        write!(f, ", ")?;
-                    }
-                }
-                write!(f, "}}")
+            Self::Bool(boolean) => Self::Bool(*boolean),
+            Self::Int(integer) => Self::Int(*integer),
+            Self::Float(float) => Self::Float(*float),
+            Self::Str(string) => Self::Str(string.clone()),
+            Self::List(list) => Self::List(crate::stack::maybe_grow(|| list.clone())),
+            Self::Map(map) => Self::Map(crate::stack::maybe_grow(|| map.clone())),
+            Self::Null => Self::Null,
+        }
+    }
+}
+
+impl PartialEq for Input {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Bool(boolean), Self::Bool(other_boolean)) => boolean == other_boolean,
+            (Self::Int(integer), Self::Int(other_integer)) => integer == other_integer,
+            (Self::Float(float), Self::Float(other_float)) => float == other_float,
+            (Self::Str(string), Self::Str(other_string)) => string == other_string,
+            (Self::List(list), Self::List(other_list)) => {
+                crate::stack::maybe_grow(|| list == other_list)
             }
+            (Self::Map(map), Self::Map(other_map)) => crate::stack::maybe_grow(|| map == other_map),
+            (Self::Null, Self::Null) => true,
+            _ => false,
         }
     }
 }
 
+impl Display for Input {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let options = if f.alternate() {
+            DisplayOptions::new_pretty()
+        } else {
+            DisplayOptions::new()
+        };
+        Display::fmt(&self.display_with(options), f)
+    }
+}
+
+// `None` for non-empty pointers that don't start with `/`:
+fn pointer_token_iter(pointer: &str) -> Option<impl Iterator<Item = String> + '_> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return None;
+    }
+    Some(
+        pointer
+            .split('/')
+            .skip(1)
+            .map(|token| token.replace("~1", "/").replace("~0", "~")),
+    )
+}
+
+fn pointer_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.starts_with('0') && token.len() != 1) {
+        return None;
+    }
+    token.parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    #[test]
+    fn from_option() {
+        assert_eq!(Input::from_option(Some(10)), Input::from(10));
+        assert_eq!(Input::from_option(None::<isize>), Input::Null);
+        assert_eq!(Input::from_option_or_default(None::<isize>), Input::from(0));
+        assert_eq!(
+            Input::from_option_or_default(None::<String>),
+            Input::new_str()
+        );
+        assert_eq!(Input::default(), Input::new_null());
+    }
+
+    #[test]
+    fn get_at() {
+        let mut input = Input::from(HashMap::from([("foo", Input::from([1, 2]))]));
+        let position = InputPosition::new().new_with_key("foo").new_with_index(1);
+        assert_eq!(input.get_at(&position), Some(&Input::from(2)));
+        assert_eq!(input.get_at(&InputPosition::new()), Some(&input.clone()));
+        assert_eq!(input.get_at(&position.new_with_key("bar")), None);
+        *input.get_at_mut(&position).unwrap() = Input::from(3);
+        assert_eq!(input.get_at(&position), Some(&Input::from(3)));
+    }
+
+    #[test]
+    fn pointer() {
+        let json = serde_json::json!({"a/b": {"c~d": [0, 1]}, "": {"": 2}, "l": [0, 1]});
+        let mut input: Input = serde_json::from_value(json.clone()).unwrap();
+        [
+            "",
+            "/a~1b",
+            "/a~1b/c~0d/1",
+            "/",
+            "//",
+            "/l/01",
+            "/l/+1",
+            "/l/2",
+            "/a",
+            "x",
+            "/l/-",
+        ]
+        .into_iter()
+        .for_each(|pointer| {
+            assert_eq!(
+                input.pointer(pointer),
+                json.pointer(pointer)
+                    .map(|value| serde_json::from_value::<Input>(value.clone()).unwrap())
+                    .as_ref(),
+                "{pointer}"
+            )
+        });
+        *input.pointer_mut("/l/1").unwrap() = Input::from(3);
+        assert_eq!(input.pointer("/l/1"), Some(&Input::from(3)));
+    }
+
+    #[cfg(feature = "interned-keys")]
+    #[test]
+    fn interned_keys() {
+        let input: Input = serde_json::from_str(r#"[{"name": 1}, {"name": 2}]"#).unwrap();
+        let key_list: Vec<_> = input
+            .as_list()
+            .iter()
+            .flat_map(|inner_input| inner_input.as_map().keys())
+            .collect();
+        assert!(std::sync::Arc::ptr_eq(key_list[0], key_list[1]));
+        let position = InputPosition::new().new_with_key("name");
+        let Some(InputPositionType::Key(key)) = position.last() else {
+            panic!("Expected a key")
+        };
+        assert!(std::sync::Arc::ptr_eq(key, key_list[0]));
+    }
+
+    #[cfg(feature = "sorted-maps")]
+    #[test]
+    fn sorted_maps() {
+        let input = Input::from(HashMap::from([("c", 3), ("a", 1), ("b", 2), ("aa", 0)]));
+        assert!(input
+            .as_map()
+            .keys()
+            .map(|key| &**key)
+            .eq(["a", "aa", "b", "c"]));
+        assert_eq!(
+            serde_json::to_string(&input).unwrap(),
+            r#"{"a":1,"aa":0,"b":2,"c":3}"#
+        );
+        assert_eq!(input.to_string(), r#"{"a": 1, "aa": 0, "b": 2, "c": 3}"#);
+        let other_input = Input::from(HashMap::from([("d", 4), ("b", 3)]));
+        let mut position_list = Vec::new();
+        crate::diff::diff(&input, &other_input, &mut |diff| {
+            position_list.push(diff.position().to_string())
+        });
+        assert_eq!(position_list, ["a", "aa", "b", "c", "d"]);
+    }
+
     #[test]
     fn serde() {
         let de_result = serde_json::from_str::<Input>("true");
@@ -312,6 +589,11 @@ mod tests {
         assert!(de_result.is_ok());
         assert_eq!(Input::Str("3.14".to_string()), de_result.unwrap());
 
+        let de_result = serde_json::from_str::<Input>("null");
+        assert!(de_result.is_ok());
+        assert_eq!(Input::Null, de_result.unwrap());
+        assert_eq!(serde_json::to_string(&Input::Null).unwrap(), "null");
+
         let de_result = serde_json::from_str::<Input>("[false, 0, 0.0, \"hello\", [[]], {}]");
         assert!(de_result.is_ok());
         let list = de_result.unwrap();
@@ -324,7 +606,7 @@ mod tests {
                     Input::from(0.0),
                     Input::from("hello".to_string()),
                     Input::from([Input::List([].to_vec())].to_vec()),
-                    Input::from(Input::new_map())
+                    Input::new_map()
                 ]
                 .to_vec()
             ),
@@ -338,14 +620,14 @@ mod tests {
         let map = de_result.unwrap();
         assert!(map.is_map());
         assert_eq!(
-            Input::Map(HashMap::from([
-                ("foo".to_string(), Input::Int(0)),
-                ("bar".to_string(), Input::Float(0.0)),
-                ("baz".to_string(), Input::Bool(false)),
+            Input::Map(InputMap::from([
+                ("foo".into(), Input::Int(0)),
+                ("bar".into(), Input::Float(0.0)),
+                ("baz".into(), Input::Bool(false)),
                 (
-                    "qux".to_string(),
-                    Input::Map(HashMap::from([(
-                        "hello".to_string(),
+                    "qux".into(),
+                    Input::Map(InputMap::from([(
+                        "hello".into(),
                         Input::Str("world".to_string())
                     )]))
                 ),
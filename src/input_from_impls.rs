@@ -1,5 +1,11 @@
-use crate::Input;
-use std::collections::HashMap;
+use crate::{Input, InputKey};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    num::TryFromIntError,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 impl From<&Input> for Input {
     fn from(value: &Input) -> Self {
@@ -13,24 +19,12 @@ impl From<bool> for Input {
     }
 }
 
-impl From<Option<bool>> for Input {
-    fn from(value: Option<bool>) -> Self {
-        Self::Bool(value.unwrap_or_default())
-    }
-}
-
 impl From<isize> for Input {
     fn from(value: isize) -> Self {
         Self::Int(value)
     }
 }
 
-impl From<Option<isize>> for Input {
-    fn from(value: Option<isize>) -> Self {
-        Self::Int(value.unwrap_or_default())
-    }
-}
-
 impl From<i8> for Input {
     fn from(value: i8) -> Self {
         Self::Int(value as isize)
@@ -67,15 +61,33 @@ impl From<u32> for Input {
     }
 }
 
-impl From<f64> for Input {
-    fn from(value: f64) -> Self {
-        Self::Float(value)
+impl TryFrom<i64> for Input {
+    type Error = TryFromIntError;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        isize::try_from(value).map(Self::Int)
     }
 }
 
-impl From<Option<f64>> for Input {
-    fn from(value: Option<f64>) -> Self {
-        Self::Float(value.unwrap_or_default())
+impl TryFrom<u64> for Input {
+    type Error = TryFromIntError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        isize::try_from(value).map(Self::Int)
+    }
+}
+
+impl TryFrom<usize> for Input {
+    type Error = TryFromIntError;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        isize::try_from(value).map(Self::Int)
+    }
+}
+
+impl From<f64> for Input {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
     }
 }
 
@@ -91,51 +103,93 @@ impl From<&str> for Input {
     }
 }
 
-impl From<Option<&str>> for Input {
-    fn from(value: Option<&str>) -> Self {
-        Self::Str(value.unwrap_or_default().to_string())
-    }
-}
-
 impl From<String> for Input {
     fn from(value: String) -> Self {
         Self::Str(value)
     }
 }
 
-impl From<Option<String>> for Input {
-    fn from(value: Option<String>) -> Self {
-        Self::Str(value.unwrap_or_default())
+impl From<&String> for Input {
+    fn from(value: &String) -> Self {
+        Self::Str(value.to_string())
     }
 }
 
-impl From<&String> for Input {
-    fn from(value: &String) -> Self {
+impl From<char> for Input {
+    fn from(value: char) -> Self {
         Self::Str(value.to_string())
     }
 }
 
-impl From<Option<&String>> for Input {
-    fn from(value: Option<&String>) -> Self {
-        Self::Str(value.cloned().unwrap_or_default())
+impl From<&Path> for Input {
+    fn from(value: &Path) -> Self {
+        Self::Str(value.to_string_lossy().to_string())
     }
 }
 
-impl<T: Into<Input>> From<Vec<T>> for Input {
-    fn from(value: Vec<T>) -> Self {
-        Self::List(value.into_iter().map(|i| i.into()).collect())
+impl From<PathBuf> for Input {
+    fn from(value: PathBuf) -> Self {
+        Self::from(value.as_path())
     }
 }
 
-impl<T: Into<Input>> From<Option<Vec<T>>> for Input {
-    fn from(value: Option<Vec<T>>) -> Self {
-        Self::List(
-            value
-                .unwrap_or_default()
-                .into_iter()
-                .map(|i| i.into())
-                .collect(),
-        )
+impl From<&PathBuf> for Input {
+    fn from(value: &PathBuf) -> Self {
+        Self::from(value.as_path())
+    }
+}
+
+impl From<IpAddr> for Input {
+    fn from(value: IpAddr) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<Ipv4Addr> for Input {
+    fn from(value: Ipv4Addr) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<Ipv6Addr> for Input {
+    fn from(value: Ipv6Addr) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<SocketAddr> for Input {
+    fn from(value: SocketAddr) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+// Durations are stored as (fractional) seconds:
+impl From<Duration> for Input {
+    fn from(value: Duration) -> Self {
+        Self::Float(value.as_secs_f64())
+    }
+}
+
+// `None` is converted to `Input::Null`. This replaces the former `From<Option<bool>>`,
+// `From<Option<isize>>`, `From<Option<f64>>`, `From<Option<&str>>`, `From<Option<String>>`,
+// `From<Option<&String>>`, and `From<Option<Vec<T>>>` impls that converted `None` to the default
+// value of the type: they overlap with this impl, and trait impls can't be marked `#[deprecated]`.
+// Use `Input::from_option_or_default()` to keep the old behavior:
+impl<T: Into<Input>> From<Option<T>> for Input {
+    fn from(value: Option<T>) -> Self {
+        value.map(|inner| inner.into()).unwrap_or(Self::Null)
+    }
+}
+
+impl From<()> for Input {
+    fn from(_: ()) -> Self {
+        Self::Null
+    }
+}
+
+impl<T: Into<Input>> From<Vec<T>> for Input {
+    fn from(value: Vec<T>) -> Self {
+        Self::List(value.into_iter().map(|i| i.into()).collect())
     }
 }
 
@@ -151,26 +205,13 @@ impl<T: Into<Input> + Clone> From<&[T]> for Input {
     }
 }
 
-impl<T: Into<Input> + Clone> From<Option<&[T]>> for Input {
-    fn from(value: Option<&[T]>) -> Self {
-        Self::List(
-            value
-                .unwrap_or_default()
-                .iter()
-                .cloned()
-                .map(|i| i.into())
-                .collect(),
-        )
-    }
-}
-
 impl<I: Into<Input>> FromIterator<I> for Input {
     fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
         Self::List(iter.into_iter().map(|i| i.into()).collect())
     }
 }
 
-impl<K: Into<String>, V: Into<Input>> From<HashMap<K, V>> for Input {
+impl<K: Into<InputKey>, V: Into<Input>> From<HashMap<K, V>> for Input {
     fn from(value: HashMap<K, V>) -> Self {
         Self::Map(
             value
@@ -181,11 +222,10 @@ impl<K: Into<String>, V: Into<Input>> From<HashMap<K, V>> for Input {
     }
 }
 
-impl<K: Into<String>, V: Into<Input>> From<Option<HashMap<K, V>>> for Input {
-    fn from(value: Option<HashMap<K, V>>) -> Self {
+impl<K: Into<InputKey>, V: Into<Input>> From<BTreeMap<K, V>> for Input {
+    fn from(value: BTreeMap<K, V>) -> Self {
         Self::Map(
             value
-                .unwrap_or_default()
                 .into_iter()
                 .map(|(k, v)| (k.into(), v.into()))
                 .collect(),
@@ -193,7 +233,19 @@ impl<K: Into<String>, V: Into<Input>> From<Option<HashMap<K, V>>> for Input {
     }
 }
 
-impl<K: Into<String>, V: Into<Input>> FromIterator<(K, V)> for Input {
+impl<T: Into<Input>> From<HashSet<T>> for Input {
+    fn from(value: HashSet<T>) -> Self {
+        Self::List(value.into_iter().map(|i| i.into()).collect())
+    }
+}
+
+impl<T: Into<Input>> From<BTreeSet<T>> for Input {
+    fn from(value: BTreeSet<T>) -> Self {
+        Self::List(value.into_iter().map(|i| i.into()).collect())
+    }
+}
+
+impl<K: Into<InputKey>, V: Into<Input>> FromIterator<(K, V)> for Input {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
         Self::Map(
             iter.into_iter()
@@ -202,3 +254,67 @@ impl<K: Into<String>, V: Into<Input>> FromIterator<(K, V)> for Input {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from() {
+        assert_eq!(Input::from('x'), Input::from("x"));
+        assert_eq!(Input::from(Path::new("/tmp")), Input::from("/tmp"));
+        assert_eq!(
+            Input::from(PathBuf::from("foo/bar")),
+            Input::from("foo/bar")
+        );
+        assert_eq!(
+            Input::from(IpAddr::from([127, 0, 0, 1])),
+            Input::from("127.0.0.1")
+        );
+        assert_eq!(
+            Input::from(SocketAddr::from(([127, 0, 0, 1], 8080))),
+            Input::from("127.0.0.1:8080")
+        );
+        assert_eq!(Input::from(Duration::from_millis(1500)), Input::from(1.5));
+        assert_eq!(
+            Input::from(BTreeMap::from([("foo", 1)])),
+            Input::from(HashMap::from([("foo", 1)]))
+        );
+        assert_eq!(Input::from(HashSet::from([1])), Input::from([1]));
+        assert_eq!(Input::from(BTreeSet::from([1, 2])), Input::from([1, 2]));
+    }
+
+    #[test]
+    fn from_option() {
+        assert_eq!(Input::from(Some(true)), Input::from(true));
+        assert_eq!(Input::from(None::<bool>), Input::Null);
+        assert_eq!(Input::from(None::<&str>), Input::Null);
+        assert_eq!(Input::from(Some(vec![1, 2])), Input::from([1, 2]));
+        assert_eq!(Input::from(()), Input::Null);
+    }
+
+    // The types whose `None` used to be converted to their default value:
+    #[test]
+    fn from_none_of_former_default_types() {
+        assert_eq!(Input::from(None::<isize>), Input::Null);
+        assert_eq!(Input::from(None::<f64>), Input::Null);
+        assert_eq!(Input::from(None::<String>), Input::Null);
+        assert_eq!(Input::from(None::<&String>), Input::Null);
+        assert_eq!(Input::from(None::<Vec<isize>>), Input::Null);
+        assert_eq!(
+            Input::from_option_or_default(None::<Vec<isize>>),
+            Input::new_list()
+        );
+        assert_eq!(
+            Input::from_option_or_default(None::<bool>),
+            Input::from(false)
+        );
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(Input::try_from(10usize), Ok(Input::from(10)));
+        assert_eq!(Input::try_from(-10i64), Ok(Input::from(-10)));
+        assert!(Input::try_from(u64::MAX).is_err());
+    }
+}
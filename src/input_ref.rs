@@ -0,0 +1,538 @@
+use crate::{
+    diff::{diff_with_position, InputDiff, InputDiffAction},
+    position::{InputPosition, InputPositionType},
+    Input,
+};
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::{borrow::Cow, collections::HashMap, fmt::Formatter, sync::Arc};
+
+// A borrowed view of an `Input`. Strings are borrowed from the `Input` or (when the format allows
+// it, e.g. JSON strings without escapes) from the deserialized text. Only lists and maps allocate:
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(untagged)]
+pub enum InputRef<'a> {
+    Bool(bool),
+    Int(isize),
+    Float(f64),
+    Str(Cow<'a, str>),
+    List(Vec<InputRef<'a>>),
+    Map(HashMap<Cow<'a, str>, InputRef<'a>>),
+    #[default]
+    Null,
+}
+
+impl<'a> InputRef<'a> {
+    pub fn is_map(&self) -> bool {
+        matches!(self, Self::Map(_))
+    }
+
+    pub fn is_list(&self) -> bool {
+        matches!(self, Self::List(_))
+    }
+
+    pub fn is_str(&self) -> bool {
+        matches!(self, Self::Str(_))
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    pub fn maybe_str(&self) -> Option<&str> {
+        if let Self::Str(string) = self {
+            Some(string)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, position: &InputPosition) -> Option<&InputRef<'a>> {
+        position
+            .iter()
+            .try_fold(self, |input, position_type| match (input, position_type) {
+                (Self::Map(map), InputPositionType::Key(key)) => map.get(&**key),
+                (Self::List(list), InputPositionType::Index(index)) => list.get(*index),
+                _ => None,
+            })
+    }
+
+    pub fn type_name(&self) -> String {
+        match self {
+            Self::Bool(_) => Input::bool_type_name(),
+            Self::Int(_) => Input::int_type_name(),
+            Self::Float(_) => Input::float_type_name(),
+            Self::Str(_) => Input::str_type_name(),
+            Self::List(_) => Input::list_type_name(),
+            Self::Map(_) => Input::map_type_name(),
+            Self::Null => Input::null_type_name(),
+        }
+    }
+
+    pub fn to_input(&self) -> Input {
+        match self {
+            Self::Bool(boolean) => Input::Bool(*boolean),
+            Self::Int(integer) => Input::Int(*integer),
+            Self::Float(float) => Input::Float(*float),
+            Self::Str(string) => Input::Str(string.to_string()),
+            Self::List(list) => Input::List(list.iter().map(Self::to_input).collect()),
+            Self::Map(map) => Input::Map(
+                map.iter()
+                    .map(|(key, inner_input)| (crate::intern_key(&**key), inner_input.to_input()))
+                    .collect(),
+            ),
+            Self::Null => Input::Null,
+        }
+    }
+
+    // Same diffs as `diff::diff()`. Only the values of diffs (and the input of the level that
+    // has them) are converted to owned `Input`s:
+    pub fn diff<F>(&self, other: &InputRef<'_>, for_each_function: &mut F)
+    where
+        F: FnMut(InputDiff),
+    {
+        self.diff_with_position(other, for_each_function, InputPosition::new())
+    }
+
+    pub fn diff_with_position<F>(
+        &self,
+        other: &InputRef<'_>,
+        for_each_function: &mut F,
+        position: InputPosition,
+    ) where
+        F: FnMut(InputDiff),
+    {
+        if self == other {
+            return;
+        }
+        if let (Self::Map(old_map), InputRef::Map(new_map)) = (self, other) {
+            let mut maybe_input = None;
+            for (key, old_value) in old_map {
+                let new_position = position.new_with_key(key);
+                if let Some(new_value) = new_map.get(key) {
+                    old_value.diff_with_position(new_value, for_each_function, new_position);
+                } else {
+                    for_each_function(InputDiff::new_with_input(
+                        maybe_input
+                            .get_or_insert_with(|| Arc::new(self.to_input()))
+                            .clone(),
+                        new_position,
+                        Some(old_value.to_input()),
+                        None,
+                        InputDiffAction::Removed,
+                    ));
+                }
+            }
+            for (key, new_value) in new_map {
+                if !old_map.contains_key(key) {
+                    for_each_function(InputDiff::new_with_input(
+                        maybe_input
+                            .get_or_insert_with(|| Arc::new(self.to_input()))
+                            .clone(),
+                        position.new_with_key(key),
+                        None,
+                        Some(new_value.to_input()),
+                        InputDiffAction::Added,
+                    ));
+                }
+            }
+        } else if let (Self::List(old_list), InputRef::List(new_list)) = (self, other) {
+            let mut maybe_input = None;
+            let removed_index_list: Vec<_> = (0..old_list.len())
+                .filter(|index| !new_list.contains(&old_list[*index]))
+                .collect();
+            let added_index_list: Vec<_> = (0..new_list.len())
+                .filter(|index| !old_list.contains(&new_list[*index]))
+                .collect();
+            for added_index in added_index_list.iter().copied() {
+                let new_position = position.new_with_index(added_index);
+                if removed_index_list.contains(&added_index) {
+                    old_list[added_index].diff_with_position(
+                        &new_list[added_index],
+                        for_each_function,
+                        new_position,
+                    );
+                } else {
+                    for_each_function(InputDiff::new_with_input(
+                        maybe_input
+                            .get_or_insert_with(|| Arc::new(self.to_input()))
+                            .clone(),
+                        new_position,
+                        None,
+                        Some(new_list[added_index].to_input()),
+                        InputDiffAction::Added,
+                    ));
+                }
+            }
+            for removed_index in removed_index_list {
+                if !added_index_list.contains(&removed_index) {
+                    for_each_function(InputDiff::new_with_input(
+                        maybe_input
+                            .get_or_insert_with(|| Arc::new(self.to_input()))
+                            .clone(),
+                        position.new_with_index(removed_index),
+                        Some(old_list[removed_index].to_input()),
+                        None,
+                        InputDiffAction::Removed,
+                    ));
+                }
+            }
+        } else {
+            // Other changes are diffs of the whole values:
+            diff_with_position(
+                &self.to_input(),
+                &other.to_input(),
+                for_each_function,
+                position,
+            )
+        }
+    }
+}
+
+impl<'a> From<&'a Input> for InputRef<'a> {
+    fn from(input: &'a Input) -> Self {
+        match input {
+            Input::Bool(boolean) => Self::Bool(*boolean),
+            Input::Int(integer) => Self::Int(*integer),
+            Input::Float(float) => Self::Float(*float),
+            Input::Str(string) => Self::Str(Cow::Borrowed(string)),
+            Input::List(list) => Self::List(list.iter().map(Self::from).collect()),
+            Input::Map(map) => Self::Map(
+                map.iter()
+                    .map(|(key, inner_input)| (Cow::Borrowed(&**key), Self::from(inner_input)))
+                    .collect(),
+            ),
+            Input::Null => Self::Null,
+        }
+    }
+}
+
+impl From<&InputRef<'_>> for Input {
+    fn from(input_ref: &InputRef<'_>) -> Self {
+        input_ref.to_input()
+    }
+}
+
+impl Input {
+    pub fn as_input_ref(&self) -> InputRef<'_> {
+        InputRef::from(self)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for InputRef<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(InputRefVisitor)
+    }
+}
+
+struct InputRefVisitor;
+
+impl<'de> Visitor<'de> for InputRefVisitor {
+    type Value = InputRef<'de>;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("expecting null, boolean, integer, float, string, list, or map")
+    }
+
+    fn visit_bool<E>(self, boolean: bool) -> Result<Self::Value, E> {
+        Ok(InputRef::Bool(boolean))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, integer: i64) -> Result<Self::Value, E> {
+        isize::try_from(integer)
+            .map(InputRef::Int)
+            .map_err(|_| E::custom(format!("integer {integer} is out of range")))
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, integer: u64) -> Result<Self::Value, E> {
+        isize::try_from(integer)
+            .map(InputRef::Int)
+            .map_err(|_| E::custom(format!("integer {integer} is out of range")))
+    }
+
+    fn visit_f64<E>(self, float: f64) -> Result<Self::Value, E> {
+        Ok(InputRef::Float(float))
+    }
+
+    fn visit_borrowed_str<E>(self, string: &'de str) -> Result<Self::Value, E> {
+        Ok(InputRef::Str(Cow::Borrowed(string)))
+    }
+
+    fn visit_str<E>(self, string: &str) -> Result<Self::Value, E> {
+        Ok(InputRef::Str(Cow::Owned(string.to_string())))
+    }
+
+    fn visit_string<E>(self, string: String) -> Result<Self::Value, E> {
+        Ok(InputRef::Str(Cow::Owned(string)))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(InputRef::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(InputRef::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        InputRef::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut list = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(inner_input) = seq.next_element()? {
+            list.push(inner_input);
+        }
+        Ok(InputRef::List(list))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map_access: A) -> Result<Self::Value, A::Error> {
+        let mut map = HashMap::with_capacity(map_access.size_hint().unwrap_or_default());
+        while let Some(InputRefKey(key)) = map_access.next_key()? {
+            map.insert(key, map_access.next_value()?);
+        }
+        Ok(InputRef::Map(map))
+    }
+}
+
+// `Cow<str>` itself always deserializes to `Cow::Owned`:
+struct InputRefKey<'a>(Cow<'a, str>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for InputRefKey<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl<'de> Visitor<'de> for KeyVisitor {
+            type Value = InputRefKey<'de>;
+
+            fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                f.write_str("expecting string")
+            }
+
+            fn visit_borrowed_str<E>(self, key: &'de str) -> Result<Self::Value, E> {
+                Ok(InputRefKey(Cow::Borrowed(key)))
+            }
+
+            fn visit_str<E>(self, key: &str) -> Result<Self::Value, E> {
+                Ok(InputRefKey(Cow::Owned(key.to_string())))
+            }
+
+            fn visit_string<E>(self, key: String) -> Result<Self::Value, E> {
+                Ok(InputRefKey(Cow::Owned(key)))
+            }
+        }
+
+        deserializer.deserialize_str(KeyVisitor)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl crate::schema::InputSchemaType {
+    // Lists, dynamic maps, and static maps are validated on the borrowed tree one item at a time
+    // (like `schema::validate_json_stream()`) and only other values (and lists with `item_map` and
+    // dynamic maps with `format`) are copied to be validated. So coerced values and defaults are
+    // not visible to the caller:
+    pub fn validate_ref(
+        &self,
+        input_ref: &InputRef,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), crate::schema::InputSchemaError> {
+        crate::stack::maybe_grow(|| {
+            validate_ref_level(self, input_ref, maybe_position.unwrap_or_default())
+        })
+    }
+}
+
+#[cfg(feature = "schema")]
+fn validate_ref_level(
+    schema_type: &crate::schema::InputSchemaType,
+    input_ref: &InputRef,
+    position: InputPosition,
+) -> Result<(), crate::schema::InputSchemaError> {
+    use crate::schema::InputSchemaType;
+
+    match (schema_type, input_ref) {
+        (InputSchemaType::List(list), InputRef::List(item_list))
+            if list.maybe_item_map().is_none() =>
+        {
+            check_size(
+                list.maybe_size(),
+                item_list.len(),
+                InputSchemaType::new_list(),
+                &position,
+            )?;
+            item_list
+                .iter()
+                .enumerate()
+                .try_for_each(|(index, inner_input_ref)| {
+                    list.item_schema()
+                        .validate_ref(inner_input_ref, Some(position.new_with_index(index)))
+                })
+        }
+        (InputSchemaType::DynamicMap(dynamic_map), InputRef::Map(map))
+            if dynamic_map.maybe_format().is_none() =>
+        {
+            check_size(
+                dynamic_map.maybe_size(),
+                map.len(),
+                InputSchemaType::new_dynamic_map(),
+                &position,
+            )?;
+            map.iter().try_for_each(|(key, inner_input_ref)| {
+                dynamic_map
+                    .item_schema()
+                    .validate_ref(inner_input_ref, Some(position.new_with_key(key)))
+            })
+        }
+        // Lists and maps of items are validated first and replaced with `null` to validate the
+        // rest of the map with `any` for them, so missing and unknown keys and defaults are still
+        // checked:
+        (InputSchemaType::StaticMap(static_map), InputRef::Map(map)) => {
+            let mut input_map = HashMap::with_capacity(map.len());
+            let mut walked_key_list = Vec::new();
+            for (key, inner_input_ref) in map {
+                match static_map
+                    .items()
+                    .get(key.as_ref())
+                    .filter(|_| inner_input_ref.is_map() || inner_input_ref.is_list())
+                {
+                    Some(schema) => {
+                        schema
+                            .schema_type()
+                            .validate_ref(inner_input_ref, Some(position.new_with_key(key)))?;
+                        input_map.insert(key.to_string(), Input::Null);
+                        walked_key_list.push(key);
+                    }
+                    None => {
+                        input_map.insert(key.to_string(), inner_input_ref.to_input());
+                    }
+                }
+            }
+            if walked_key_list.is_empty() {
+                return schema_type.validate(&mut Input::from(input_map), Some(position));
+            }
+            let mut static_map = static_map.clone();
+            walked_key_list.into_iter().for_each(|key| {
+                if let Some(schema) = static_map.items_mut().get_mut(key.as_ref()) {
+                    schema.set_schema_type(InputSchemaType::new_any())
+                }
+            });
+            InputSchemaType::StaticMap(static_map)
+                .validate(&mut Input::from(input_map), Some(position))
+        }
+        _ => schema_type.validate(&mut input_ref.to_input(), Some(position)),
+    }
+}
+
+#[cfg(feature = "schema")]
+fn check_size(
+    maybe_size: Option<&crate::schema::InputSchemaTypeSize>,
+    size: usize,
+    schema_type: crate::schema::InputSchemaType,
+    position: &InputPosition,
+) -> Result<(), crate::schema::InputSchemaError> {
+    match maybe_size {
+        Some(expected_size)
+            if expected_size.maybe_max().is_some_and(|max| size > max)
+                || expected_size.maybe_min().is_some_and(|min| size < min) =>
+        {
+            Err(crate::schema::InputSchemaError::Size {
+                position: position.clone(),
+                schema_type,
+                expected_size: *expected_size,
+                size,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::diff;
+
+    #[test]
+    fn deserialize_borrowed() {
+        let json = r#"{"name": "foo", "escaped": "a\"b", "list": [1, 2.5, null, true]}"#;
+        let input_ref: InputRef = serde_json::from_str(json).unwrap();
+        let map = if let InputRef::Map(map) = &input_ref {
+            map
+        } else {
+            panic!("{input_ref:?}")
+        };
+        assert!(matches!(map["name"], InputRef::Str(Cow::Borrowed("foo"))));
+        assert!(matches!(map["escaped"], InputRef::Str(Cow::Owned(_))));
+        assert!(map.keys().all(|key| matches!(key, Cow::Borrowed(_))));
+        let input: Input = serde_json::from_str(json).unwrap();
+        assert_eq!(input_ref.to_input(), input);
+        assert_eq!(input.as_input_ref(), input_ref);
+        assert_eq!(
+            input_ref.get(&InputPosition::new().new_with_key("list").new_with_index(1)),
+            Some(&InputRef::Float(2.5))
+        );
+    }
+
+    #[test]
+    fn diff_ref() {
+        let old_input: Input = serde_json::from_str(
+            r#"{"same": {"a": [1, 2]}, "removed": 1, "updated": "x", "list": [1, 2]}"#,
+        )
+        .unwrap();
+        let new_input: Input = serde_json::from_str(
+            r#"{"same": {"a": [1, 2]}, "added": 2, "updated": "y", "list": [1, 3]}"#,
+        )
+        .unwrap();
+        let mut expected_list = Vec::new();
+        diff(&old_input, &new_input, &mut |diff| {
+            expected_list.push(diff.to_string())
+        });
+        let mut diff_list = Vec::new();
+        old_input
+            .as_input_ref()
+            .diff(&new_input.as_input_ref(), &mut |diff| {
+                diff_list.push(diff.to_string())
+            });
+        expected_list.sort();
+        diff_list.sort();
+        assert_eq!(diff_list.len(), 4);
+        assert_eq!(diff_list, expected_list);
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn validate_ref() {
+        let schema_type: crate::schema::InputSchemaType =
+            serde_json::from_value(serde_json::json!({
+                "type": "static_map",
+                "items": {
+                    "name": {"schema": {"type": "string"}},
+                    "timeout": {"schema": {"type": "integer"}, "default": 30},
+                    "routes": {"schema": {"type": "list", "size": {"max": 2}, "item_schema": {
+                        "type": "static_map",
+                        "items": {"port": {"schema": {"type": "port"}}},
+                    }}},
+                }
+            }))
+            .unwrap();
+        for (json, maybe_position) in [
+            (r#"{"name": "app", "routes": [{"port": "8080"}]}"#, None),
+            (
+                r#"{"name": "app", "routes": [{"port": 80}, {"port": "http"}]}"#,
+                Some("[routes][1][port]"),
+            ),
+            (r#"{"name": "app", "routes": [{}, {}, {}]}"#, Some("routes")),
+            (r#"{"routes": []}"#, Some("name")),
+        ] {
+            let input_ref: InputRef = serde_json::from_str(json).unwrap();
+            let result = schema_type.validate_ref(&input_ref, None);
+            assert_eq!(
+                result.map_err(|error| error.position().to_string()).err(),
+                maybe_position.map(ToString::to_string),
+                "{json}"
+            );
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::Input;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use std::{cell::RefCell, collections::HashMap, fmt::Formatter};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputJsonError {
+    #[error("Duplicate key `{key}` at line {line} column {column}")]
+    DuplicateKey {
+        key: String,
+        line: usize,
+        column: usize,
+    },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Input {
+    // Unlike `serde_json::from_str()` which keeps the last value of duplicate keys, this fails at
+    // the first duplicate key. The location is where the parser was when the key was seen:
+    pub fn from_json_str_strict(text: &str) -> Result<Input, InputJsonError> {
+        let maybe_duplicate_key = RefCell::new(None);
+        let mut deserializer = serde_json::Deserializer::from_str(text);
+        let result = StrictInput {
+            maybe_duplicate_key: &maybe_duplicate_key,
+        }
+        .deserialize(&mut deserializer)
+        .and_then(|input| deserializer.end().map(|_| input));
+        result.map_err(|error| match maybe_duplicate_key.take() {
+            Some(key) => InputJsonError::DuplicateKey {
+                key,
+                line: error.line(),
+                column: error.column(),
+            },
+            None => InputJsonError::Json(error),
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct StrictInput<'a> {
+    maybe_duplicate_key: &'a RefCell<Option<String>>,
+}
+
+impl<'de> DeserializeSeed<'de> for StrictInput<'_> {
+    type Value = Input;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Input, D::Error> {
+        crate::stack::maybe_grow(|| deserializer.deserialize_any(self))
+    }
+}
+
+impl<'de> Visitor<'de> for StrictInput<'_> {
+    type Value = Input;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("null, boolean, integer, float, string, list, or map")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Input, E> {
+        Ok(Input::from(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Input, E> {
+        Ok(isize::try_from(value).map_or(Input::from(value as f64), Input::from))
+    }
+
+    // Like `Input`'s own `Deserialize`, integers that don't fit in `isize` become floats:
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Input, E> {
+        Ok(isize::try_from(value).map_or(Input::from(value as f64), Input::from))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Input, E> {
+        Ok(Input::from(value))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Input, E> {
+        Ok(Input::from(value))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Input, E> {
+        Ok(Input::from(value))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Input, E> {
+        Ok(Input::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Input, A::Error> {
+        let mut list = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(inner_input) = seq.next_element_seed(self)? {
+            list.push(inner_input)
+        }
+        Ok(Input::from(list))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map_access: A) -> Result<Input, A::Error> {
+        let mut map = HashMap::with_capacity(map_access.size_hint().unwrap_or_default());
+        while let Some(key) = map_access.next_key::<String>()? {
+            if map.contains_key(&key) {
+                let error = de::Error::custom(format!("duplicate key `{key}`"));
+                *self.maybe_duplicate_key.borrow_mut() = Some(key);
+                return Err(error);
+            }
+            let inner_input = map_access.next_value_seed(self)?;
+            map.insert(key, inner_input);
+        }
+        Ok(Input::from(map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_json_str_strict() {
+        let text = r#"{"a": [1, -2, 1.5, "x", null, true], "b": {"c": 18446744073709551615}}"#;
+        assert_eq!(
+            Input::from_json_str_strict(text).unwrap(),
+            serde_json::from_str::<Input>(text).unwrap()
+        );
+        assert_eq!(
+            Input::from_json_str_strict("{}").unwrap(),
+            serde_json::from_value::<Input>(json!({})).unwrap()
+        );
+
+        let text = "{\n  \"a\": 1,\n  \"b\": {\"c\": 1, \"c\": 2}\n}";
+        match Input::from_json_str_strict(text) {
+            Err(InputJsonError::DuplicateKey { key, line, .. }) => {
+                assert_eq!(key, "c");
+                assert_eq!(line, 3);
+            }
+            result => panic!("{result:?}"),
+        }
+        assert!(matches!(
+            Input::from_json_str_strict("{\"a\": 1} 2"),
+            Err(InputJsonError::Json(_))
+        ));
+    }
+}
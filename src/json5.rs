@@ -0,0 +1,49 @@
+use crate::Input;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputJson5Error {
+    #[error(transparent)]
+    Json5(#[from] ::json5::Error),
+}
+
+impl Input {
+    // Accepts comments, trailing commas, unquoted keys, single-quoted strings, and other JSON5
+    // extensions that hand-written configuration files tend to have:
+    pub fn from_json5_str(text: &str) -> Result<Input, InputJson5Error> {
+        Ok(::json5::from_str(text)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_json5_str() {
+        let text = r#"
+        // comment
+        {
+            name: 'app',
+            port: 8080,
+            ratio: .5,
+            tags: ["a", "b",],
+            /* block comment */
+            "nested": {enabled: true, maybe: null},
+        }
+        "#;
+        assert_eq!(
+            Input::from_json5_str(text).unwrap(),
+            serde_json::from_value::<Input>(json!({
+                "name": "app",
+                "port": 8080,
+                "ratio": 0.5,
+                "tags": ["a", "b"],
+                "nested": {"enabled": true, "maybe": null},
+            }))
+            .unwrap()
+        );
+        assert!(Input::from_json5_str("{a: }").is_err());
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{
+    merge::merge,
+    position::InputPosition,
+    provenance::{InputSource, Provenance},
+    Input,
+};
+#[cfg(feature = "schema")]
+use crate::{
+    position::InputPositionType,
+    schema::{InputSchemaError, InputSchemaType},
+};
+use std::fmt::{Display, Formatter};
+
+// Named inputs (e.g. `defaults`, `config.toml`, `env`, `cli`) that are merged in order, so later
+// layers override earlier ones (see `merge::merge`):
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputLayers {
+    layer_list: Vec<(String, Input)>,
+    #[cfg(feature = "schema")]
+    maybe_schema_type: Option<InputSchemaType>,
+}
+
+// Where the effective value of a position comes from (e.g. for an `explain` command):
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputExplanation {
+    position: InputPosition,
+    maybe_value: Option<Input>,
+    maybe_layer_name: Option<String>,
+    maybe_default: Option<Input>,
+    maybe_coerced_from: Option<Input>,
+    #[cfg(feature = "schema")]
+    maybe_error: Option<InputSchemaError>,
+}
+
+impl InputLayers {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn layer_list(&self) -> &Vec<(String, Input)> {
+        &self.layer_list
+    }
+
+    pub fn layer_list_mut(&mut self) -> &mut Vec<(String, Input)> {
+        &mut self.layer_list
+    }
+
+    pub fn add_layer<N: ToString, I: Into<Input>>(&mut self, name: N, input: I) {
+        self.layer_list.push((name.to_string(), input.into()));
+    }
+
+    pub fn with_layer<N: ToString, I: Into<Input>>(mut self, name: N, input: I) -> Self {
+        self.add_layer(name, input);
+        self
+    }
+
+    #[cfg(feature = "schema")]
+    pub fn maybe_schema_type(&self) -> Option<&InputSchemaType> {
+        self.maybe_schema_type.as_ref()
+    }
+
+    #[cfg(feature = "schema")]
+    pub fn maybe_schema_type_mut(&mut self) -> &mut Option<InputSchemaType> {
+        &mut self.maybe_schema_type
+    }
+
+    #[cfg(feature = "schema")]
+    pub fn set_schema_type<S: Into<InputSchemaType>>(&mut self, schema_type: S) {
+        *self.maybe_schema_type_mut() = Some(schema_type.into());
+    }
+
+    #[cfg(feature = "schema")]
+    pub fn with_schema_type<S: Into<InputSchemaType>>(mut self, schema_type: S) -> Self {
+        self.set_schema_type(schema_type);
+        self
+    }
+
+    // All layers merged in order (without validation). An empty stack is an empty map:
+    pub fn merged(&self) -> Input {
+        let mut layer_iter = self.layer_list.iter();
+        let mut input = layer_iter
+            .next()
+            .map_or_else(Input::new_map, |(_, input)| input.clone());
+        layer_iter.for_each(|(_, layer_input)| merge(&mut input, layer_input));
+        input
+    }
+
+    // The sources of `merged()` (`InputSource::Layer` of the layer names):
+    pub fn provenance(&self) -> Provenance {
+        let mut provenance = Provenance::new();
+        self.layer_list.iter().for_each(|(name, input)| {
+            provenance.merge(
+                input,
+                &Provenance::new_with_source(InputSource::Layer(name.clone())),
+            )
+        });
+        provenance
+    }
+
+    // The provider is the last layer that contains the position. With a schema, the value is the
+    // validated one so defaults and coercions (e.g. `"8080"` to `8080`) are applied. If the merged
+    // input is not valid, the error is kept and the value is the merged (unvalidated) one:
+    pub fn explain(&self, position: &InputPosition) -> InputExplanation {
+        let merged = self.merged();
+        let maybe_raw_value = merged.get_at(position).cloned();
+        let maybe_layer_name = self
+            .layer_list
+            .iter()
+            .rev()
+            .find(|(_, input)| input.get_at(position).is_some())
+            .map(|(name, _)| name.clone());
+        let explanation = InputExplanation {
+            position: position.clone(),
+            maybe_value: maybe_raw_value,
+            maybe_layer_name,
+            maybe_default: None,
+            maybe_coerced_from: None,
+            #[cfg(feature = "schema")]
+            maybe_error: None,
+        };
+        #[cfg(feature = "schema")]
+        let explanation = match self.maybe_schema_type() {
+            Some(schema_type) => explanation.validated(schema_type, merged),
+            None => explanation,
+        };
+        explanation
+    }
+}
+
+impl InputExplanation {
+    pub fn position(&self) -> &InputPosition {
+        &self.position
+    }
+
+    // `None` if no layer (or default) provides the position:
+    pub fn maybe_value(&self) -> Option<&Input> {
+        self.maybe_value.as_ref()
+    }
+
+    // `None` if the value comes from the schema default:
+    pub fn maybe_layer_name(&self) -> Option<&String> {
+        self.maybe_layer_name.as_ref()
+    }
+
+    pub fn maybe_default(&self) -> Option<&Input> {
+        self.maybe_default.as_ref()
+    }
+
+    // The merged value before validation if validation changed it:
+    pub fn maybe_coerced_from(&self) -> Option<&Input> {
+        self.maybe_coerced_from.as_ref()
+    }
+
+    #[cfg(feature = "schema")]
+    pub fn maybe_error(&self) -> Option<&InputSchemaError> {
+        self.maybe_error.as_ref()
+    }
+}
+
+#[cfg(feature = "schema")]
+impl InputExplanation {
+    fn validated(mut self, schema_type: &InputSchemaType, mut merged: Input) -> Self {
+        self.maybe_default = default_at(schema_type, &self.position);
+        match schema_type.validate(&mut merged, None) {
+            Ok(()) => {
+                let maybe_value = merged.get_at(&self.position).cloned();
+                self.maybe_coerced_from = std::mem::replace(&mut self.maybe_value, maybe_value)
+                    .filter(|raw_value| Some(raw_value) != self.maybe_value.as_ref());
+            }
+            Err(error) => self.maybe_error = Some(error),
+        }
+        self
+    }
+}
+
+// The default of the innermost item schema, or the value inside the default of a parent (e.g. the
+// default of `server` is `{"port": 80}` for `[server][port]`):
+#[cfg(feature = "schema")]
+fn default_at(schema_type: &InputSchemaType, position: &InputPosition) -> Option<Input> {
+    let mut schema_type = schema_type;
+    let mut maybe_default: Option<&Input> = None;
+    for position_type in position.iter() {
+        let maybe_inner_default =
+            maybe_default.and_then(|default| match (default, position_type) {
+                (Input::Map(map), InputPositionType::Key(key)) => map.get(key),
+                (Input::List(list), InputPositionType::Index(index)) => list.get(*index),
+                _ => None,
+            });
+        while let InputSchemaType::Secret(secret) = schema_type {
+            schema_type = secret.inner_schema();
+        }
+        match (schema_type, position_type) {
+            (InputSchemaType::StaticMap(static_map), InputPositionType::Key(key)) => {
+                let schema = static_map.items().get(&**key)?;
+                schema_type = schema.schema_type();
+                maybe_default = schema.maybe_default().or(maybe_inner_default);
+            }
+            (InputSchemaType::DynamicMap(dynamic_map), InputPositionType::Key(_)) => {
+                schema_type = dynamic_map.item_schema();
+                maybe_default = maybe_inner_default;
+            }
+            (InputSchemaType::KeyValue(key_value), InputPositionType::Key(_)) => {
+                schema_type = key_value.value_schema();
+                maybe_default = maybe_inner_default;
+            }
+            (InputSchemaType::List(list), InputPositionType::Index(_)) => {
+                schema_type = list.item_schema();
+                maybe_default = maybe_inner_default;
+            }
+            _ => return None,
+        }
+    }
+    maybe_default.cloned()
+}
+
+impl Display for InputExplanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let position = if self.position.is_empty() {
+            "<root>".to_string()
+        } else {
+            self.position.to_string()
+        };
+        match (&self.maybe_value, &self.maybe_layer_name) {
+            (Some(value), Some(layer_name)) => {
+                write!(f, "{position} = {value} (from layer `{layer_name}`)")?
+            }
+            (Some(value), None) => write!(f, "{position} = {value} (from default)")?,
+            (None, _) => write!(f, "{position} is not set")?,
+        }
+        if let Some(coerced_from) = &self.maybe_coerced_from {
+            write!(f, ", coerced from {coerced_from}")?;
+        }
+        if let Some(default) = &self.maybe_default {
+            write!(f, ", default is {default}")?;
+        }
+        #[cfg(feature = "schema")]
+        if let Some(error) = &self.maybe_error {
+            write!(f, ", invalid: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(value: serde_json::Value) -> Input {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn explain() {
+        let layers = InputLayers::new()
+            .with_layer("defaults", input(json!({"host": "localhost", "port": 80})))
+            .with_layer("env", input(json!({"port": "8080"})));
+        assert_eq!(
+            layers.merged(),
+            input(json!({"host": "localhost", "port": "8080"}))
+        );
+        let position = InputPosition::new().new_with_key("port");
+        let explanation = layers.explain(&position);
+        assert_eq!(explanation.maybe_value(), Some(&Input::from("8080")));
+        assert_eq!(
+            explanation.maybe_layer_name().map(String::as_str),
+            Some("env")
+        );
+        assert_eq!(
+            layers
+                .merged()
+                .provenance_at(&layers.provenance(), &position),
+            Some(&InputSource::Layer("env".to_string()))
+        );
+        let explanation = layers.explain(&InputPosition::new().new_with_key("user"));
+        assert_eq!(explanation.maybe_value(), None);
+        assert_eq!(explanation.maybe_layer_name(), None);
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn explain_with_schema() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "integer"}, "default": 80},
+                "workers": {"schema": {"type": "integer"}, "default": 4},
+            }
+        }))
+        .unwrap();
+        let layers = InputLayers::new()
+            .with_layer("file", input(json!({"port": 8000})))
+            .with_layer("env", input(json!({"port": "8080"})))
+            .with_schema_type(schema_type);
+
+        let explanation = layers.explain(&InputPosition::new().new_with_key("port"));
+        assert_eq!(explanation.maybe_value(), Some(&Input::from(8080)));
+        assert_eq!(
+            explanation.maybe_layer_name().map(String::as_str),
+            Some("env")
+        );
+        assert_eq!(explanation.maybe_default(), Some(&Input::from(80)));
+        assert_eq!(explanation.maybe_coerced_from(), Some(&Input::from("8080")));
+        assert_eq!(
+            explanation.to_string(),
+            "port = 8080 (from layer `env`), coerced from \"8080\", default is 80"
+        );
+
+        let explanation = layers.explain(&InputPosition::new().new_with_key("workers"));
+        assert_eq!(explanation.maybe_value(), Some(&Input::from(4)));
+        assert_eq!(explanation.maybe_layer_name(), None);
+        assert_eq!(explanation.maybe_coerced_from(), None);
+
+        let layers = layers.with_layer("cli", input(json!({"port": "http"})));
+        let explanation = layers.explain(&InputPosition::new().new_with_key("port"));
+        assert_eq!(explanation.maybe_value(), Some(&Input::from("http")));
+        assert!(explanation.maybe_error().is_some());
+    }
+}
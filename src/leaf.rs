@@ -0,0 +1,123 @@
+use crate::{diff::InputNumberDelta, display::human_count, Input};
+use std::fmt::{Display, Formatter};
+
+// A scalar value (anything but a list or a map). Merges replace leaves, diffs report leaves of the
+// same kind as updated (with a delta for numbers), and `Display` writes them with these methods,
+// so a new scalar variant of `Input` only needs arms here:
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputLeaf<'a> {
+    Null,
+    Bool(bool),
+    Int(isize),
+    Float(f64),
+    Str(&'a str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputLeafKind {
+    Null,
+    Bool,
+    Int,
+    Float,
+    Str,
+}
+
+impl Input {
+    // `None` for lists and maps:
+    pub fn as_leaf(&self) -> Option<InputLeaf<'_>> {
+        match self {
+            Self::Null => Some(InputLeaf::Null),
+            Self::Bool(value) => Some(InputLeaf::Bool(*value)),
+            Self::Int(value) => Some(InputLeaf::Int(*value)),
+            Self::Float(value) => Some(InputLeaf::Float(*value)),
+            Self::Str(value) => Some(InputLeaf::Str(value)),
+            Self::List(_) | Self::Map(_) => None,
+        }
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.as_leaf().is_some()
+    }
+}
+
+impl InputLeaf<'_> {
+    pub fn kind(&self) -> InputLeafKind {
+        match self {
+            Self::Null => InputLeafKind::Null,
+            Self::Bool(_) => InputLeafKind::Bool,
+            Self::Int(_) => InputLeafKind::Int,
+            Self::Float(_) => InputLeafKind::Float,
+            Self::Str(_) => InputLeafKind::Str,
+        }
+    }
+
+    // The raw numbers of an update of a number to another number of the same kind. `NaN` is
+    // neither bigger nor smaller, so it has no delta:
+    pub fn number_delta(&self, new: &Self) -> Option<InputNumberDelta> {
+        match (self, new) {
+            (Self::Int(old), Self::Int(new)) => Some(InputNumberDelta::Int {
+                old: *old,
+                new: *new,
+            }),
+            (Self::Float(old), Self::Float(new)) => {
+                (!old.is_nan() && !new.is_nan()).then_some(InputNumberDelta::Float {
+                    old: *old,
+                    new: *new,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    // Strings longer than `maybe_max_len` characters are truncated:
+    pub(crate) fn write(
+        &self,
+        f: &mut Formatter<'_>,
+        maybe_max_len: Option<usize>,
+    ) -> std::fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::Str(value) => match maybe_max_len {
+                Some(max_len) if value.chars().count() > max_len => {
+                    let truncated: String = value.chars().take(max_len).collect();
+                    let length = human_count(value.chars().count());
+                    write!(f, "{:?} (… {length} chars …)", format!("{truncated}…"))
+                }
+                _ => write!(f, "{value:?}"),
+            },
+        }
+    }
+}
+
+impl Display for InputLeaf<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write(f, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_leaf() {
+        assert_eq!(Input::from(1).as_leaf(), Some(InputLeaf::Int(1)));
+        assert_eq!(
+            Input::from("a").as_leaf().unwrap().kind(),
+            InputLeafKind::Str
+        );
+        assert_eq!(Input::new_map().as_leaf(), None);
+        assert_eq!(
+            InputLeaf::Int(1).number_delta(&InputLeaf::Int(2)),
+            Some(InputNumberDelta::Int { old: 1, new: 2 })
+        );
+        assert_eq!(
+            InputLeaf::Float(f64::NAN).number_delta(&InputLeaf::Float(1.0)),
+            None
+        );
+        assert_eq!(InputLeaf::Str("a").to_string(), "\"a\"");
+    }
+}
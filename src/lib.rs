@@ -1,12 +1,55 @@
 #![doc = include_str!("../README.md")]
+// Error types carry the offending `Input` values by design:
+#![allow(clippy::result_large_err)]
 
 extern crate core;
 
+pub mod builder;
+pub mod clamp;
+#[cfg(feature = "schema")]
+pub mod command;
+pub mod deserialize;
 pub mod diff;
+pub mod display;
+pub mod dotenv;
+#[cfg(feature = "schema")]
+pub mod env;
+pub mod eq;
+pub mod float;
+pub mod freeze;
+pub mod include;
+#[cfg(feature = "ini")]
+pub mod ini;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "relaxed-json")]
+pub mod json5;
+pub mod layers;
+pub mod leaf;
 pub mod merge;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod migration;
+pub mod naming;
+pub mod patch;
 pub mod position;
+pub mod prelude;
+pub mod profile;
+pub mod provenance;
+pub mod resolution;
+pub mod section;
+pub mod shared;
+#[cfg(feature = "signed")]
+pub mod signed;
+pub mod subscription;
+#[cfg(feature = "test-util")]
+pub mod testing;
+pub mod utf8;
 #[doc(inline)]
-pub use input::Input;
+pub use input::{intern_key, Input, InputKey, InputMap};
+pub use input_ref::InputRef;
+#[cfg(feature = "http")]
+pub mod http;
 #[cfg(feature = "schema")]
 pub mod schema;
 
@@ -14,6 +57,12 @@ pub mod ext {
     pub extern crate anyhow;
 }
 
+mod base64;
+mod canonical;
+#[cfg(feature = "fast-deserialize")]
+mod fast_deserialize;
 mod input;
 mod input_from_impls;
+mod input_ref;
 mod logging;
+mod stack;
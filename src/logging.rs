@@ -1,3 +1,7 @@
+// Maximum length of strings and collections in trace messages:
+#[cfg(any(feature = "logging", feature = "tracing"))]
+pub(crate) const TRACE_MAX_LEN: usize = 64;
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! is_debug_level_enabled {
@@ -30,6 +34,22 @@ macro_rules! is_trace_level_enabled {
     }};
 }
 
+// Adds to a counter of `metrics::InputMetrics` of the current thread if `metrics` is enabled:
+#[doc(hidden)]
+#[macro_export]
+macro_rules! record_metric {
+    ($field:ident) => {
+        $crate::record_metric!($field, 1)
+    };
+    ($field:ident, $count:expr) => {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "metrics")] {
+                $crate::metrics::record(|metrics| metrics.$field += $count)
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 pub fn enable_logging() {
     cfg_if::cfg_if! {
@@ -1,4 +1,4 @@
-use crate::{is_trace_level_enabled, position::InputPosition, Input};
+use crate::{position::InputPosition, record_metric, Input};
 use cfg_if::cfg_if;
 
 macro_rules! trace_merge {
@@ -7,18 +7,18 @@ macro_rules! trace_merge {
             if #[cfg(feature = "tracing")] {
                 tracing::trace!(
                     position = %$input_position,
-                    old_value = %$input,
+                    old_value = %$input.display_compact(crate::logging::TRACE_MAX_LEN),
                     from = %$to_be_merged_input_position,
-                    new_value = %$to_be_merged_input,
+                    new_value = %$to_be_merged_input.display_compact(crate::logging::TRACE_MAX_LEN),
                     $action
                 );
             } else if #[cfg(feature = "logging")] {
                 log::trace!(
                     "position={:?} old_value={:?} from={:?} new_value={:?} message={:?}",
                     $input_position.to_string(),
-                    $input.to_string(),
+                    $input.display_compact(crate::logging::TRACE_MAX_LEN).to_string(),
                     $to_be_merged_input_position.to_string(),
-                    $to_be_merged_input.to_string(),
+                    $to_be_merged_input.display_compact(crate::logging::TRACE_MAX_LEN).to_string(),
                     $action,
                 );
             }
@@ -26,13 +26,49 @@ macro_rules! trace_merge {
     };
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InputMergeOptions {
+    // Map keys that only differ in case (e.g. `Port` and `PORT`) are merged into the existing key:
+    case_insensitive_keys: bool,
+}
+
+impl InputMergeOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn case_insensitive_keys(&self) -> bool {
+        self.case_insensitive_keys
+    }
+
+    pub fn set_case_insensitive_keys(&mut self, case_insensitive_keys: bool) {
+        self.case_insensitive_keys = case_insensitive_keys;
+    }
+
+    pub fn with_case_insensitive_keys(mut self, case_insensitive_keys: bool) -> Self {
+        self.set_case_insensitive_keys(case_insensitive_keys);
+        self
+    }
+}
+
 pub fn merge(input: &mut Input, to_be_merged_input: &Input) {
-    merge_with_positions(
+    merge_with_options(input, to_be_merged_input, &InputMergeOptions::default())
+}
+
+pub fn merge_with_options(
+    input: &mut Input,
+    to_be_merged_input: &Input,
+    options: &InputMergeOptions,
+) {
+    merge_with_positions_and_options(
         input,
         InputPosition::new(),
         to_be_merged_input,
         InputPosition::new(),
-    )
+        options,
+    );
+    #[cfg(feature = "metrics")]
+    crate::metrics::finish();
 }
 
 pub fn merge_with_positions(
@@ -41,51 +77,48 @@ pub fn merge_with_positions(
     to_be_merged_input: &Input,
     to_be_merged_input_position: InputPosition,
 ) {
-    if input.is_map() {
-        merge_map(
-            input,
-            input_position,
-            to_be_merged_input,
-            to_be_merged_input_position,
-        )
-    } else if input.is_list() {
-        merge_list(
-            input,
-            input_position,
-            to_be_merged_input,
-            to_be_merged_input_position,
-        )
-    } else if input.is_str() {
-        merge_str(
-            input,
-            input_position,
-            to_be_merged_input,
-            to_be_merged_input_position,
-        )
-    } else if input.is_float() {
-        merge_float(
-            input,
-            input_position,
-            to_be_merged_input,
-            to_be_merged_input_position,
-        )
-    } else if input.is_int() {
-        merge_int(
+    merge_with_positions_and_options(
+        input,
+        input_position,
+        to_be_merged_input,
+        to_be_merged_input_position,
+        &InputMergeOptions::default(),
+    )
+}
+
+fn merge_with_positions_and_options(
+    input: &mut Input,
+    input_position: InputPosition,
+    to_be_merged_input: &Input,
+    to_be_merged_input_position: InputPosition,
+    options: &InputMergeOptions,
+) {
+    record_metric!(merge_visited_count);
+    // Every variant is matched (no `_`) so that a new variant doesn't compile instead of
+    // panicking at runtime:
+    crate::stack::maybe_grow(|| match input {
+        Input::Map(_) => merge_map(
             input,
             input_position,
             to_be_merged_input,
             to_be_merged_input_position,
-        )
-    } else if input.is_bool() {
-        merge_bool(
+            options,
+        ),
+        Input::List(_) => merge_list(
             input,
             input_position,
             to_be_merged_input,
             to_be_merged_input_position,
-        )
-    } else {
-        unreachable!("{input:?}!!!")
-    }
+        ),
+        Input::Bool(_) | Input::Int(_) | Input::Float(_) | Input::Str(_) | Input::Null => {
+            merge_leaf(
+                input,
+                input_position,
+                to_be_merged_input,
+                to_be_merged_input_position,
+            )
+        }
+    })
 }
 
 fn merge_map(
@@ -93,8 +126,9 @@ fn merge_map(
     input_position: InputPosition,
     to_be_merged_input: &Input,
     to_be_merged_input_position: InputPosition,
+    options: &InputMergeOptions,
 ) {
-    if !(to_be_merged_input.is_map() && input.is_map()) {
+    let (Input::Map(map), Input::Map(to_be_merged_map)) = (&mut *input, to_be_merged_input) else {
         trace_merge!(
             to_be_merged_input_position,
             to_be_merged_input,
@@ -102,21 +136,28 @@ fn merge_map(
             input,
             "replaced"
         );
-        *input = to_be_merged_input.clone();
+        *input = copy(to_be_merged_input);
         return;
-    }
-    let map = input.map_mut();
-    let to_be_merged_map = to_be_merged_input.as_map();
-    for (key, inner_to_be_merged_input) in to_be_merged_map {
-        if let Some(inner_input) = map.get_mut(key) {
-            merge_with_positions(
+    };
+    for (key, inner_to_be_merged_input) in to_be_merged_map.iter() {
+        let maybe_key = if map.contains_key(key) || !options.case_insensitive_keys {
+            Some(key.clone())
+        } else {
+            let lowercase_key = key.to_lowercase();
+            map.keys()
+                .find(|existing_key| existing_key.to_lowercase() == lowercase_key)
+                .cloned()
+        };
+        if let Some(inner_input) = maybe_key.and_then(|existing_key| map.get_mut(&existing_key)) {
+            merge_with_positions_and_options(
                 inner_input,
                 input_position.new_with_key(key),
                 inner_to_be_merged_input,
                 to_be_merged_input_position.new_with_key(key),
+                options,
             );
         } else {
-            map.insert(key.clone(), inner_to_be_merged_input.clone());
+            map.insert(key.clone(), copy(inner_to_be_merged_input));
         }
     }
 }
@@ -135,32 +176,40 @@ fn merge_list(
             input,
             "replaced"
         );
-        *input = to_be_merged_input.clone();
+        *input = copy(to_be_merged_input);
         return;
     }
-    let mut _input_clone = input.clone();
-    let list = input.list_mut();
-    let to_be_merged_list = to_be_merged_input.as_list();
+    // The list as it is after every appended item, only for logging (and not counted as a copy
+    // of merging):
+    #[cfg(any(feature = "logging", feature = "tracing"))]
+    let mut maybe_input_clone = crate::is_trace_level_enabled!().then(|| input.clone());
+    let (Input::List(list), Input::List(to_be_merged_list)) = (input, to_be_merged_input) else {
+        return;
+    };
+    // `_index` is only used when logging is enabled:
+    #[allow(clippy::unused_enumerate_index)]
     for (_index, inner_to_be_merged_input) in to_be_merged_list.iter().enumerate() {
         if !list.contains(inner_to_be_merged_input) {
-            if is_trace_level_enabled!() {
-                _input_clone
-                    .list_mut()
-                    .push(inner_to_be_merged_input.clone());
+            #[cfg(any(feature = "logging", feature = "tracing"))]
+            if let Some(input_clone) = &mut maybe_input_clone {
+                if let Input::List(input_clone_list) = input_clone {
+                    input_clone_list.push(inner_to_be_merged_input.clone());
+                }
                 trace_merge!(
                     _to_be_merged_input_position.new_with_index(_index),
                     inner_to_be_merged_input,
                     _input_position,
-                    _input_clone,
+                    input_clone,
                     "appended"
                 );
             }
-            list.push(inner_to_be_merged_input.clone());
+            list.push(copy(inner_to_be_merged_input));
         }
     }
 }
 
-fn merge_str(
+// Leaves (see `InputLeaf`) are replaced:
+fn merge_leaf(
     input: &mut Input,
     _input_position: InputPosition,
     to_be_merged_input: &Input,
@@ -174,70 +223,38 @@ fn merge_str(
             input,
             "replaced"
         );
-        *input = to_be_merged_input.clone();
+        *input = copy(to_be_merged_input);
     }
 }
 
-fn merge_float(
-    input: &mut Input,
-    _input_position: InputPosition,
-    to_be_merged_input: &Input,
-    _to_be_merged_input_position: InputPosition,
-) {
-    if input != to_be_merged_input {
-        trace_merge!(
-            _to_be_merged_input_position,
-            to_be_merged_input,
-            _input_position,
-            input,
-            "replaced"
-        );
-        *input = to_be_merged_input.clone();
-    }
-}
-
-fn merge_int(
-    input: &mut Input,
-    _input_position: InputPosition,
-    to_be_merged_input: &Input,
-    _to_be_merged_input_position: InputPosition,
-) {
-    if input != to_be_merged_input {
-        trace_merge!(
-            _to_be_merged_input_position,
-            to_be_merged_input,
-            _input_position,
-            input,
-            "replaced"
-        );
-        *input = to_be_merged_input.clone();
-    }
-}
-
-fn merge_bool(
-    input: &mut Input,
-    _input_position: InputPosition,
-    to_be_merged_input: &Input,
-    _to_be_merged_input_position: InputPosition,
-) {
-    if input != to_be_merged_input {
-        trace_merge!(
-            _to_be_merged_input_position,
-            to_be_merged_input,
-            _input_position,
-            input,
-            "replaced"
-        );
-        *input = to_be_merged_input.clone();
-    }
+// Every value that merging copies goes through here so that it can be counted:
+fn copy(input: &Input) -> Input {
+    record_metric!(merge_cloned_count, crate::metrics::node_count(input));
+    input.clone()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::logging::enable_logging;
+    use serde_json::json;
     use std::collections::HashMap;
 
+    #[test]
+    fn case_insensitive_keys() {
+        let mut input: Input =
+            serde_json::from_value(json!({"Server": {"Port": 80, "host": "localhost"}})).unwrap();
+        let to_be_merged_input: Input =
+            serde_json::from_value(json!({"SERVER": {"port": 8080}, "Log": "debug"})).unwrap();
+        let options = InputMergeOptions::new().with_case_insensitive_keys(true);
+        merge_with_options(&mut input, &to_be_merged_input, &options);
+        let expected: Input = serde_json::from_value(
+            json!({"Server": {"Port": 8080, "host": "localhost"}, "Log": "debug"}),
+        )
+        .unwrap();
+        assert_eq!(input, expected);
+    }
+
     #[test]
     fn it_works() {
         enable_logging();
@@ -251,7 +268,7 @@ mod tests {
                     "list",
                     Input::from([Input::from(1), Input::from(2), Input::from(3)]),
                 ),
-                ("number", Input::from(3.14)),
+                ("number", Input::from(2.5)),
             ])),
         );
         let mut input = Input::from(map);
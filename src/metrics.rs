@@ -0,0 +1,196 @@
+use crate::Input;
+use std::{
+    cell::Cell,
+    sync::{Mutex, RwLock},
+};
+
+// Counted per `merge::merge()`, `diff::diff()`, and `ValidationCache::validate()` call (on the
+// calling thread). A "node" is any value, so copying `{"a": [1, 2]}` counts 4 nodes:
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InputMetrics {
+    pub(crate) merge_visited_count: usize,
+    pub(crate) merge_cloned_count: usize,
+    pub(crate) diff_visited_count: usize,
+    pub(crate) diff_cloned_count: usize,
+    pub(crate) validation_cache_hit_count: usize,
+    pub(crate) validation_cache_miss_count: usize,
+}
+
+pub type InputMetricsObserver = Box<dyn Fn(&InputMetrics) + Send + Sync>;
+
+const EMPTY: InputMetrics = InputMetrics {
+    merge_visited_count: 0,
+    merge_cloned_count: 0,
+    diff_visited_count: 0,
+    diff_cloned_count: 0,
+    validation_cache_hit_count: 0,
+    validation_cache_miss_count: 0,
+};
+
+static OBSERVER: RwLock<Option<InputMetricsObserver>> = RwLock::new(None);
+static TOTAL: Mutex<InputMetrics> = Mutex::new(EMPTY);
+
+thread_local! {
+    static CURRENT: Cell<InputMetrics> = const { Cell::new(EMPTY) };
+}
+
+impl InputMetrics {
+    pub fn merge_visited_count(&self) -> usize {
+        self.merge_visited_count
+    }
+
+    pub fn merge_cloned_count(&self) -> usize {
+        self.merge_cloned_count
+    }
+
+    pub fn diff_visited_count(&self) -> usize {
+        self.diff_visited_count
+    }
+
+    pub fn diff_cloned_count(&self) -> usize {
+        self.diff_cloned_count
+    }
+
+    pub fn validation_cache_hit_count(&self) -> usize {
+        self.validation_cache_hit_count
+    }
+
+    pub fn validation_cache_miss_count(&self) -> usize {
+        self.validation_cache_miss_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &EMPTY
+    }
+
+    fn add(&mut self, other: &InputMetrics) {
+        self.merge_visited_count += other.merge_visited_count;
+        self.merge_cloned_count += other.merge_cloned_count;
+        self.diff_visited_count += other.diff_visited_count;
+        self.diff_cloned_count += other.diff_cloned_count;
+        self.validation_cache_hit_count += other.validation_cache_hit_count;
+        self.validation_cache_miss_count += other.validation_cache_miss_count;
+    }
+}
+
+// Called with the metrics of every `merge::merge()`, `diff::diff()`, and
+// `ValidationCache::validate()` call, on the calling thread:
+pub fn set_observer(observer: InputMetricsObserver) {
+    *OBSERVER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(observer);
+}
+
+pub fn remove_observer() -> Option<InputMetricsObserver> {
+    OBSERVER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take()
+}
+
+// Sum of all calls (on all threads) since the start or since the last `reset_total()`:
+pub fn total() -> InputMetrics {
+    *TOTAL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn reset_total() -> InputMetrics {
+    std::mem::take(
+        &mut *TOTAL
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()),
+    )
+}
+
+pub(crate) fn record<F: FnOnce(&mut InputMetrics)>(function: F) {
+    CURRENT.with(|current| {
+        let mut metrics = current.get();
+        function(&mut metrics);
+        current.set(metrics);
+    })
+}
+
+pub(crate) fn finish() {
+    let metrics = CURRENT.with(|current| current.replace(EMPTY));
+    if metrics.is_empty() {
+        return;
+    }
+    TOTAL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .add(&metrics);
+    if let Some(observer) = OBSERVER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+    {
+        observer(&metrics)
+    }
+}
+
+pub(crate) fn node_count(input: &Input) -> usize {
+    let mut count = 0;
+    let mut input_list = vec![input];
+    while let Some(input) = input_list.pop() {
+        count += 1;
+        match input {
+            Input::List(list) => input_list.extend(list),
+            Input::Map(map) => input_list.extend(map.values()),
+            _ => (),
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diff::diff, merge::merge};
+    use serde_json::json;
+    use std::{
+        sync::{Arc, Mutex},
+        thread::{self, ThreadId},
+    };
+
+    #[test]
+    fn observer() {
+        // Other tests may merge and diff on other threads at the same time:
+        let metrics_list: Arc<Mutex<Vec<(ThreadId, InputMetrics)>>> = Default::default();
+        let observer_metrics_list = metrics_list.clone();
+        set_observer(Box::new(move |metrics| {
+            observer_metrics_list
+                .lock()
+                .unwrap()
+                .push((thread::current().id(), *metrics))
+        }));
+        let mut input: Input = serde_json::from_value(json!({"a": {"b": 1}})).unwrap();
+        let other_input: Input = serde_json::from_value(json!({"a": {"c": [1, 2]}})).unwrap();
+        merge(&mut input, &other_input);
+        let mut diff_count = 0;
+        diff(&other_input, &input, &mut |_| diff_count += 1);
+        let mut list: Input = serde_json::from_value(json!([1, 2, 3])).unwrap();
+        merge(&mut list, &serde_json::from_value(json!([3, 4])).unwrap());
+        remove_observer();
+
+        let metrics_list: Vec<_> = metrics_list
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(thread_id, _)| thread_id == &thread::current().id())
+            .map(|(_, metrics)| *metrics)
+            .collect();
+        assert_eq!(metrics_list.len(), 3);
+        // The root and `a` are visited, `c` (with its 2 items) is added:
+        assert_eq!(metrics_list[0].merge_visited_count(), 2);
+        assert_eq!(metrics_list[0].merge_cloned_count(), 3);
+        assert_eq!(metrics_list[0].diff_visited_count(), 0);
+        // `b` is added, the diff copies the parent `a` (4 nodes) and `b`:
+        assert_eq!(diff_count, 1);
+        assert_eq!(metrics_list[1].diff_visited_count(), 3);
+        assert_eq!(metrics_list[1].diff_cloned_count(), 5);
+        // Only the appended `4` is copied, not the list:
+        assert_eq!(metrics_list[2].merge_cloned_count(), 1);
+        assert!(total().merge_visited_count() >= 2);
+    }
+}
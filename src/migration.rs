@@ -0,0 +1,329 @@
+use crate::{
+    merge::merge,
+    position::{InputPosition, InputPositionPattern, InputPositionPatternType, InputPositionType},
+    Input,
+};
+use std::fmt::{Display, Formatter};
+
+// Declared renames and moves of keys between schema versions, applied to old inputs before they
+// are validated with the new schema (see `schema::compat_check_migrated()`), so both old and new
+// inputs stay valid:
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InputMigration {
+    step_list: Vec<InputMigrationStep>,
+}
+
+// Wildcards (`*` and `**`) of `to_path` are replaced by the segments that the wildcards of
+// `from_path` matched (in the same order), e.g. `servers.*.host_name` to `servers.*.hostname`.
+// Only map keys are moved (not list items) and missing maps of `to_path` are created:
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputMigrationStep {
+    // If the input already has a value at `to_path` (e.g. a new input), it's kept and the old
+    // value is removed:
+    RenameKey {
+        from_path: InputPositionPattern,
+        to_path: InputPositionPattern,
+    },
+    // The old value is merged into the value at `to_path` (values at `to_path` win):
+    MoveSubtree {
+        from_path: InputPositionPattern,
+        to_path: InputPositionPattern,
+    },
+}
+
+pub fn rename_key<F: Into<InputPositionPattern>, T: Into<InputPositionPattern>>(
+    from_path: F,
+    to_path: T,
+) -> InputMigrationStep {
+    InputMigrationStep::RenameKey {
+        from_path: from_path.into(),
+        to_path: to_path.into(),
+    }
+}
+
+pub fn move_subtree<F: Into<InputPositionPattern>, T: Into<InputPositionPattern>>(
+    from_path: F,
+    to_path: T,
+) -> InputMigrationStep {
+    InputMigrationStep::MoveSubtree {
+        from_path: from_path.into(),
+        to_path: to_path.into(),
+    }
+}
+
+impl InputMigration {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn step_list(&self) -> &Vec<InputMigrationStep> {
+        &self.step_list
+    }
+
+    pub fn step_list_mut(&mut self) -> &mut Vec<InputMigrationStep> {
+        &mut self.step_list
+    }
+
+    pub fn add_step(&mut self, step: InputMigrationStep) {
+        self.step_list_mut().push(step);
+    }
+
+    pub fn with_step(mut self, step: InputMigrationStep) -> Self {
+        self.add_step(step);
+        self
+    }
+
+    pub fn with_rename_key<F: Into<InputPositionPattern>, T: Into<InputPositionPattern>>(
+        self,
+        from_path: F,
+        to_path: T,
+    ) -> Self {
+        self.with_step(rename_key(from_path, to_path))
+    }
+
+    pub fn with_move_subtree<F: Into<InputPositionPattern>, T: Into<InputPositionPattern>>(
+        self,
+        from_path: F,
+        to_path: T,
+    ) -> Self {
+        self.with_step(move_subtree(from_path, to_path))
+    }
+
+    // Steps are applied in order. Returns the old and new positions of moved values:
+    pub fn apply(&self, input: &mut Input) -> Vec<(InputPosition, InputPosition)> {
+        self.step_list
+            .iter()
+            .flat_map(|step| step.apply(input))
+            .collect()
+    }
+}
+
+impl InputMigrationStep {
+    pub fn from_path(&self) -> &InputPositionPattern {
+        match self {
+            Self::RenameKey { from_path, .. } | Self::MoveSubtree { from_path, .. } => from_path,
+        }
+    }
+
+    pub fn to_path(&self) -> &InputPositionPattern {
+        match self {
+            Self::RenameKey { to_path, .. } | Self::MoveSubtree { to_path, .. } => to_path,
+        }
+    }
+
+    // Returns the old and new positions of moved values:
+    pub fn apply(&self, input: &mut Input) -> Vec<(InputPosition, InputPosition)> {
+        let from_path: Vec<_> = self.from_path().iter().cloned().collect();
+        let mut match_list = Vec::new();
+        find(
+            input,
+            &from_path,
+            InputPosition::new(),
+            Vec::new(),
+            &mut match_list,
+        );
+        match_list
+            .into_iter()
+            .filter_map(|(from_position, capture_list)| {
+                let to_position = to_position(self.to_path(), capture_list)?;
+                self.move_value(input, &from_position, &to_position)
+                    .then_some((from_position, to_position))
+            })
+            .collect()
+    }
+
+    fn move_value(
+        &self,
+        input: &mut Input,
+        from_position: &InputPosition,
+        to_position: &InputPosition,
+    ) -> bool {
+        if from_position == to_position {
+            return false;
+        }
+        let (Some(parent_position), Some(InputPositionType::Key(key))) =
+            (from_position.parent(), from_position.last())
+        else {
+            return false;
+        };
+        let Some(Input::Map(parent_map)) = input.get_at_mut(&parent_position) else {
+            return false;
+        };
+        let Some(mut value) = parent_map.remove(key) else {
+            return false;
+        };
+        let maybe_to_value = input.get_at(to_position).cloned();
+        match (self, maybe_to_value) {
+            (Self::RenameKey { .. }, Some(_)) => return true,
+            (Self::MoveSubtree { .. }, Some(to_value)) => merge(&mut value, &to_value),
+            (_, None) => (),
+        }
+        if input.insert_at(to_position, value.clone()) {
+            return true;
+        }
+        // Restored if `to_path` goes through a value that is not a map:
+        if let Some(Input::Map(parent_map)) = input.get_at_mut(&parent_position) {
+            parent_map.insert(key.clone(), value);
+        }
+        false
+    }
+}
+
+// Positions of `input` that match `pattern` (sorted by keys), with the segments that each
+// wildcard matched:
+fn find(
+    input: &Input,
+    pattern: &[InputPositionPatternType],
+    position: InputPosition,
+    capture_list: Vec<Vec<InputPositionType>>,
+    match_list: &mut Vec<(InputPosition, Vec<Vec<InputPositionType>>)>,
+) {
+    crate::stack::maybe_grow(|| {
+        let Some((pattern_type, rest)) = pattern.split_first() else {
+            match_list.push((position, capture_list));
+            return;
+        };
+        if pattern_type == &InputPositionPatternType::AnyDeep {
+            let mut descendant_list = Vec::new();
+            collect_descendant_list(input, Vec::new(), &mut descendant_list);
+            return descendant_list
+                .into_iter()
+                .for_each(|(segment_list, inner_input)| {
+                    let mut inner_position = position.clone();
+                    segment_list
+                        .iter()
+                        .for_each(|position_type| inner_position.add(position_type.clone()));
+                    let mut capture_list = capture_list.clone();
+                    capture_list.push(segment_list);
+                    find(inner_input, rest, inner_position, capture_list, match_list)
+                });
+        }
+        inner_list(input)
+            .into_iter()
+            .filter(|(position_type, _)| pattern_type.matches(position_type))
+            .for_each(|(position_type, inner_input)| {
+                let mut capture_list = capture_list.clone();
+                if pattern_type == &InputPositionPatternType::Any {
+                    capture_list.push(vec![position_type.clone()]);
+                }
+                let mut inner_position = position.clone();
+                inner_position.add(position_type);
+                find(inner_input, rest, inner_position, capture_list, match_list)
+            })
+    })
+}
+
+// `input` itself (with no segments) and all of its nested values, for `**`:
+fn collect_descendant_list<'a>(
+    input: &'a Input,
+    segment_list: Vec<InputPositionType>,
+    descendant_list: &mut Vec<(Vec<InputPositionType>, &'a Input)>,
+) {
+    crate::stack::maybe_grow(|| {
+        descendant_list.push((segment_list.clone(), input));
+        inner_list(input)
+            .into_iter()
+            .for_each(|(position_type, inner_input)| {
+                let mut inner_segment_list = segment_list.clone();
+                inner_segment_list.push(position_type);
+                collect_descendant_list(inner_input, inner_segment_list, descendant_list)
+            })
+    })
+}
+
+fn inner_list(input: &Input) -> Vec<(InputPositionType, &Input)> {
+    match input {
+        Input::Map(map) => {
+            let mut inner_list: Vec<_> = map
+                .iter()
+                .map(|(key, inner_input)| (InputPositionType::Key(key.clone()), inner_input))
+                .collect();
+            inner_list.sort_by_key(|(position_type, _)| position_type.to_string());
+            inner_list
+        }
+        Input::List(list) => list
+            .iter()
+            .enumerate()
+            .map(|(index, inner_input)| (InputPositionType::Index(index), inner_input))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// `None` if `to_path` has more wildcards than `from_path`:
+fn to_position(
+    to_path: &InputPositionPattern,
+    capture_list: Vec<Vec<InputPositionType>>,
+) -> Option<InputPosition> {
+    let mut capture_iter = capture_list.into_iter();
+    let mut position = InputPosition::new();
+    for pattern_type in to_path.iter() {
+        match pattern_type {
+            InputPositionPatternType::Key(key) => position.add_key(key),
+            InputPositionPatternType::Index(index) => position.add_index(*index),
+            InputPositionPatternType::Any | InputPositionPatternType::AnyDeep => capture_iter
+                .next()?
+                .into_iter()
+                .for_each(|position_type| position.add(position_type)),
+        }
+    }
+    Some(position)
+}
+
+impl Display for InputMigrationStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RenameKey { from_path, to_path } => write!(f, "rename {from_path} to {to_path}"),
+            Self::MoveSubtree { from_path, to_path } => write!(f, "move {from_path} to {to_path}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn apply() {
+        let migration = InputMigration::new()
+            .with_rename_key("servers.*.host_name", "servers.*.hostname")
+            .with_move_subtree("tls", "security.tls");
+        let mut input: Input = serde_json::from_value(json!({
+            "servers": {
+                "a": {"host_name": "old.example.com"},
+                "b": {"host_name": "old.example.com", "hostname": "new.example.com"},
+            },
+            "tls": {"cert": "a.pem", "key": "a.key"},
+            "security": {"tls": {"key": "b.key"}},
+        }))
+        .unwrap();
+        let moved_list: Vec<_> = migration
+            .apply(&mut input)
+            .into_iter()
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect();
+        assert_eq!(moved_list.len(), 3);
+        let expected: Input = serde_json::from_value(json!({
+            "servers": {
+                "a": {"hostname": "old.example.com"},
+                "b": {"hostname": "new.example.com"},
+            },
+            "security": {"tls": {"cert": "a.pem", "key": "b.key"}},
+        }))
+        .unwrap();
+        assert_eq!(input, expected);
+        // New inputs are kept as they are:
+        let mut new_input = expected.clone();
+        assert!(migration.apply(&mut new_input).is_empty());
+        assert_eq!(new_input, expected);
+
+        let mut input: Input =
+            serde_json::from_value(json!({"a": {"b": {"timeout_ms": 1}}, "timeout_ms": 2}))
+                .unwrap();
+        rename_key("**.timeout_ms", "**.timeout").apply(&mut input);
+        let expected: Input =
+            serde_json::from_value(json!({"a": {"b": {"timeout": 1}}, "timeout": 2})).unwrap();
+        assert_eq!(input, expected);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{position::InputPosition, Input, InputKey};
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamingConvention {
+    // `max_connections`:
+    Snake,
+    // `max-connections`:
+    Kebab,
+    // `maxConnections`:
+    Camel,
+    // `MaxConnections`:
+    Pascal,
+    // `MAX_CONNECTIONS`:
+    ScreamingSnake,
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InputNamingError {
+    #[error("{position}: keys `{key}` and `{other_key}` are both `{normalized_key}` after normalization")]
+    Conflict {
+        position: InputPosition,
+        key: String,
+        other_key: String,
+        normalized_key: String,
+    },
+}
+
+impl NamingConvention {
+    // Words are separated by `_`, `-`, `.`, spaces, and case changes (`HTTPServer` is `http` and
+    // `server`):
+    pub fn convert(&self, key: &str) -> String {
+        let word_list = split_words(key);
+        match self {
+            Self::Snake => word_list.join("_"),
+            Self::Kebab => word_list.join("-"),
+            Self::ScreamingSnake => word_list.join("_").to_uppercase(),
+            Self::Camel | Self::Pascal => word_list
+                .iter()
+                .enumerate()
+                .map(|(index, word)| {
+                    if index == 0 && self == &Self::Camel {
+                        word.clone()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Input {
+    // Renames all (nested) map keys. Keys that end up the same (e.g. `max_connections` and
+    // `maxConnections`) are an error and leave the input partially renamed:
+    pub fn normalize_keys(&mut self, convention: NamingConvention) -> Result<(), InputNamingError> {
+        normalize_keys(self, convention, InputPosition::new())
+    }
+}
+
+fn normalize_keys(
+    input: &mut Input,
+    convention: NamingConvention,
+    position: InputPosition,
+) -> Result<(), InputNamingError> {
+    crate::stack::maybe_grow(|| match input {
+        Input::Map(map) => {
+            let mut normalized_map: HashMap<String, (InputKey, Input)> =
+                HashMap::with_capacity(map.len());
+            for (key, mut inner_input) in std::mem::take(map) {
+                let normalized_key = convention.convert(&key);
+                normalize_keys(
+                    &mut inner_input,
+                    convention,
+                    position.new_with_key(&normalized_key),
+                )?;
+                if let Some((other_key, _)) = normalized_map.get(&normalized_key) {
+                    return Err(InputNamingError::Conflict {
+                        position,
+                        key: other_key.to_string(),
+                        other_key: key.to_string(),
+                        normalized_key,
+                    });
+                }
+                normalized_map.insert(normalized_key, (key, inner_input));
+            }
+            *map = normalized_map
+                .into_iter()
+                .map(|(normalized_key, (_, inner_input))| {
+                    (crate::intern_key(normalized_key), inner_input)
+                })
+                .collect();
+            Ok(())
+        }
+        Input::List(list) => list
+            .iter_mut()
+            .enumerate()
+            .try_for_each(|(index, inner_input)| {
+                normalize_keys(inner_input, convention, position.new_with_index(index))
+            }),
+        _ => Ok(()),
+    })
+}
+
+fn split_words(key: &str) -> Vec<String> {
+    let char_list: Vec<char> = key.chars().collect();
+    let mut word_list = Vec::new();
+    let mut word = String::new();
+    for (index, character) in char_list.iter().enumerate() {
+        if "_-. ".contains(*character) {
+            if !word.is_empty() {
+                word_list.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        if character.is_uppercase() && !word.is_empty() && index > 0 {
+            let previous = char_list[index - 1];
+            let is_acronym_end = previous.is_uppercase()
+                && char_list
+                    .get(index + 1)
+                    .is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase() || previous.is_ascii_digit() || is_acronym_end {
+                word_list.push(std::mem::take(&mut word));
+            }
+        }
+        word.extend(character.to_lowercase());
+    }
+    if !word.is_empty() {
+        word_list.push(word);
+    }
+    word_list
+}
+
+fn capitalize(word: &str) -> String {
+    let mut char_iter = word.chars();
+    char_iter
+        .next()
+        .map(|first| first.to_uppercase().chain(char_iter).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn convert() {
+        for key in [
+            "maxConnections",
+            "max-connections",
+            "max_connections",
+            "MaxConnections",
+            "MAX_CONNECTIONS",
+        ] {
+            assert_eq!(NamingConvention::Snake.convert(key), "max_connections");
+            assert_eq!(NamingConvention::Kebab.convert(key), "max-connections");
+            assert_eq!(NamingConvention::Camel.convert(key), "maxConnections");
+            assert_eq!(NamingConvention::Pascal.convert(key), "MaxConnections");
+            assert_eq!(
+                NamingConvention::ScreamingSnake.convert(key),
+                "MAX_CONNECTIONS"
+            );
+        }
+        assert_eq!(NamingConvention::Snake.convert("HTTPServer"), "http_server");
+        assert_eq!(
+            NamingConvention::Snake.convert("ipv4Address"),
+            "ipv4_address"
+        );
+    }
+
+    #[test]
+    fn normalize_keys() {
+        let mut input: Input = serde_json::from_value(
+            json!({"maxConnections": 1, "tls-config": {"CertFile": "a"}, "list": [{"a-b": 1}]}),
+        )
+        .unwrap();
+        input.normalize_keys(NamingConvention::Snake).unwrap();
+        let expected: Input = serde_json::from_value(
+            json!({"max_connections": 1, "tls_config": {"cert_file": "a"}, "list": [{"a_b": 1}]}),
+        )
+        .unwrap();
+        assert_eq!(input, expected);
+
+        let mut input: Input =
+            serde_json::from_value(json!({"a": {"max_connections": 1, "maxConnections": 2}}))
+                .unwrap();
+        let error = input.normalize_keys(NamingConvention::Snake).unwrap_err();
+        assert!(matches!(
+            error,
+            InputNamingError::Conflict { ref position, .. } if position.to_string() == "a"
+        ));
+    }
+}
@@ -0,0 +1,383 @@
+use crate::{
+    eq::is_unchanged,
+    position::{InputPosition, InputPositionType},
+    Input,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct InputPatch {
+    operation_list: Vec<InputPatchOperation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "operation")]
+pub enum InputPatchOperation {
+    Add {
+        position: InputPosition,
+        value: Input,
+    },
+    Remove {
+        position: InputPosition,
+        old_value: Input,
+    },
+    Replace {
+        position: InputPosition,
+        old_value: Input,
+        new_value: Input,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InputPatchError {
+    #[error("{position} could not be found")]
+    NotFound { position: InputPosition },
+    #[error("{position} is expected to be `{expected}`, but it is `{found}`")]
+    Conflict {
+        position: InputPosition,
+        expected: Input,
+        found: Input,
+    },
+    #[error("{position} could not be added to `{parent}`")]
+    Invalid {
+        position: InputPosition,
+        parent: Input,
+    },
+}
+
+impl InputPatch {
+    pub fn new() -> Self {
+        Self {
+            operation_list: Vec::new(),
+        }
+    }
+
+    pub fn operation_list(&self) -> &Vec<InputPatchOperation> {
+        &self.operation_list
+    }
+
+    pub fn operation_list_mut(&mut self) -> &mut Vec<InputPatchOperation> {
+        &mut self.operation_list
+    }
+
+    pub fn add_operation(&mut self, operation: InputPatchOperation) {
+        self.operation_list.push(operation);
+    }
+
+    pub fn with_operation(mut self, operation: InputPatchOperation) -> Self {
+        self.add_operation(operation);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operation_list.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.operation_list.len()
+    }
+
+    pub fn then(mut self, other: InputPatch) -> Self {
+        self.operation_list.extend(other.operation_list);
+        self
+    }
+
+    pub fn invert(&self) -> Self {
+        Self {
+            operation_list: self
+                .operation_list
+                .iter()
+                .rev()
+                .map(|operation| operation.invert())
+                .collect(),
+        }
+    }
+
+    pub fn apply(&self, input: &mut Input) -> Result<(), InputPatchError> {
+        let mut new_input = input.clone();
+        self.operation_list
+            .iter()
+            .try_for_each(|operation| operation.apply(&mut new_input))?;
+        *input = new_input;
+        Ok(())
+    }
+}
+
+impl InputPatchOperation {
+    pub fn position(&self) -> &InputPosition {
+        match self {
+            Self::Add { position, .. }
+            | Self::Remove { position, .. }
+            | Self::Replace { position, .. } => position,
+        }
+    }
+
+    pub fn invert(&self) -> Self {
+        match self.clone() {
+            Self::Add { position, value } => Self::Remove {
+                position,
+                old_value: value,
+            },
+            Self::Remove {
+                position,
+                old_value,
+            } => Self::Add {
+                position,
+                value: old_value,
+            },
+            Self::Replace {
+                position,
+                old_value,
+                new_value,
+            } => Self::Replace {
+                position,
+                old_value: new_value,
+                new_value: old_value,
+            },
+        }
+    }
+
+    pub fn apply(&self, input: &mut Input) -> Result<(), InputPatchError> {
+        match self {
+            Self::Add { position, value } => {
+                let (parent, last) = parent_and_last(input, position)?;
+                match (&mut *parent, last) {
+                    (Input::Map(map), InputPositionType::Key(key)) if !map.contains_key(key) => {
+                        map.insert(key.clone(), value.clone());
+                    }
+                    (Input::List(list), InputPositionType::Index(index))
+                        if *index <= list.len() =>
+                    {
+                        list.insert(*index, value.clone());
+                    }
+                    _ => {
+                        return Err(InputPatchError::Invalid {
+                            position: position.clone(),
+                            parent: parent.clone(),
+                        })
+                    }
+                }
+            }
+            Self::Remove {
+                position,
+                old_value,
+            } => {
+                check_value(input, position, old_value)?;
+                let (parent, last) = parent_and_last(input, position)?;
+                let removed = match (&mut *parent, last) {
+                    (Input::Map(map), InputPositionType::Key(key)) => map.remove(key).is_some(),
+                    (Input::List(list), InputPositionType::Index(index)) if *index < list.len() => {
+                        list.remove(*index);
+                        true
+                    }
+                    _ => false,
+                };
+                if !removed {
+                    return Err(InputPatchError::Invalid {
+                        position: position.clone(),
+                        parent: parent.clone(),
+                    });
+                }
+            }
+            Self::Replace {
+                position,
+                old_value,
+                new_value,
+            } => {
+                check_value(input, position, old_value)?;
+                *input
+                    .get_at_mut(position)
+                    .ok_or_else(|| InputPatchError::NotFound {
+                        position: position.clone(),
+                    })? = new_value.clone();
+            }
+        };
+        Ok(())
+    }
+}
+
+fn check_value(
+    input: &Input,
+    position: &InputPosition,
+    expected: &Input,
+) -> Result<(), InputPatchError> {
+    let found = input
+        .get_at(position)
+        .ok_or_else(|| InputPatchError::NotFound {
+            position: position.clone(),
+        })?;
+    if !is_unchanged(found, expected) {
+        return Err(InputPatchError::Conflict {
+            position: position.clone(),
+            expected: expected.clone(),
+            found: found.clone(),
+        });
+    }
+    Ok(())
+}
+
+fn parent_and_last<'a, 'b>(
+    input: &'a mut Input,
+    position: &'b InputPosition,
+) -> Result<(&'a mut Input, &'b InputPositionType), InputPatchError> {
+    let not_found = || InputPatchError::NotFound {
+        position: position.clone(),
+    };
+    let parent_position = position.parent().ok_or_else(not_found)?;
+    let last = position.last().ok_or_else(not_found)?;
+    let parent = input.get_at_mut(&parent_position).ok_or_else(not_found)?;
+    Ok((parent, last))
+}
+
+impl Input {
+    pub fn apply_patch(&mut self, patch: &InputPatch) -> Result<(), InputPatchError> {
+        patch.apply(self)
+    }
+}
+
+impl FromIterator<InputPatchOperation> for InputPatch {
+    fn from_iter<T: IntoIterator<Item = InputPatchOperation>>(iter: T) -> Self {
+        Self {
+            operation_list: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for InputPatch {
+    type Item = InputPatchOperation;
+    type IntoIter = std::vec::IntoIter<InputPatchOperation>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.operation_list.into_iter()
+    }
+}
+
+impl Display for InputPatchOperation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Add { position, value } => write!(f, "add {position} `{value}`"),
+            Self::Remove {
+                position,
+                old_value,
+            } => write!(f, "remove {position} `{old_value}`"),
+            Self::Replace {
+                position,
+                old_value,
+                new_value,
+            } => write!(f, "replace {position} `{old_value}` with `{new_value}`"),
+        }
+    }
+}
+
+impl Display for InputPatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let length = self.operation_list.len();
+        for (index, operation) in self.operation_list.iter().enumerate() {
+            write!(f, "{operation}")?;
+            if index < length - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::to_patch;
+    use serde_json::json;
+
+    fn input(json: serde_json::Value) -> Input {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn apply() {
+        let old = input(json!({"foo": {"bar": [1, 2, 3], "baz": true}, "qux": "hello"}));
+        let new = input(json!({"foo": {"bar": [1, 20]}, "qux": 10, "new": null}));
+        let patch = to_patch(&old, &new);
+        let mut patched = old.clone();
+        patched.apply_patch(&patch).unwrap();
+        assert_eq!(patched, new);
+        patched.apply_patch(&patch.invert()).unwrap();
+        assert_eq!(patched, old);
+
+        let newer = input(json!({"foo": {"bar": []}}));
+        let mut patched = old.clone();
+        patched
+            .apply_patch(&patch.clone().then(to_patch(&new, &newer)))
+            .unwrap();
+        assert_eq!(patched, newer);
+    }
+
+    #[test]
+    fn conflict() {
+        let mut old = input(json!({"foo": 1}));
+        let patch = to_patch(&old, &input(json!({"foo": 2})));
+        let mut changed = input(json!({"foo": 3}));
+        assert!(matches!(
+            changed.apply_patch(&patch),
+            Err(InputPatchError::Conflict { .. })
+        ));
+        assert_eq!(changed, input(json!({"foo": 3})));
+        old.apply_patch(&patch).unwrap();
+        assert!(matches!(
+            old.apply_patch(&InputPatch::new().with_operation(InputPatchOperation::Add {
+                position: InputPosition::new().new_with_key("foo"),
+                value: Input::from(1),
+            })),
+            Err(InputPatchError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_position() {
+        let mut root = input(json!({"foo": [1]}));
+        for operation in [
+            InputPatchOperation::Remove {
+                position: InputPosition::new(),
+                old_value: root.clone(),
+            },
+            InputPatchOperation::Remove {
+                position: InputPosition::new().new_with_key("foo").new_with_index(1),
+                old_value: Input::from(1),
+            },
+            InputPatchOperation::Replace {
+                position: InputPosition::new().new_with_key("bar"),
+                old_value: Input::from(1),
+                new_value: Input::from(2),
+            },
+        ] {
+            assert!(matches!(
+                root.apply_patch(&InputPatch::new().with_operation(operation)),
+                Err(InputPatchError::NotFound { .. })
+            ));
+        }
+        assert_eq!(root, input(json!({"foo": [1]})));
+    }
+
+    #[test]
+    fn nan() {
+        // Only `foo` changed, the `NaN` of `bar` is the same:
+        let mut old = input(json!({"foo": 1}));
+        old.map_mut().insert("bar".into(), Input::from(f64::NAN));
+        let mut new = old.clone();
+        new.map_mut().insert("foo".into(), Input::from(10));
+        let patch = to_patch(&old, &new);
+        assert_eq!(patch.len(), 1);
+        let mut patched = old.clone();
+        patched.apply_patch(&patch).unwrap();
+        assert_eq!(patched.as_map()["foo"], Input::from(10));
+        assert!(patched.as_map()["bar"].as_float().is_nan());
+    }
+
+    #[test]
+    fn serde() {
+        let patch = to_patch(&input(json!({"foo": 1})), &input(json!({"foo": 2})));
+        let json = serde_json::to_string(&patch).unwrap();
+        assert_eq!(serde_json::from_str::<InputPatch>(&json).unwrap(), patch);
+    }
+}
@@ -1,17 +1,58 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize, Serializer};
 use std::{
-    fmt::{Display, Formatter, Result},
+    fmt::{Debug, Display, Formatter, Result},
     ops::Index,
+    str::FromStr,
+    sync::Arc,
 };
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+// Deserializes from the serialized form (`{"inner": [{"Key": "a"}, {"Index": 0}]}`), a list of
+// keys and indexes (`["a", 0]`), or the displayed form (`"[a][0]"` or `"a"`). Positions share
+// their parents, so the positions of all levels of a deep walk only take linear memory and
+// `new_with_key()` and `new_with_index()` don't copy:
+#[derive(Clone, Deserialize)]
+#[serde(try_from = "InputPositionForm")]
 pub struct InputPosition {
-    inner: Vec<InputPositionType>,
+    maybe_last: Option<Arc<InputPositionNode>>,
+    len: usize,
+}
+
+struct InputPositionNode {
+    maybe_parent: Option<Arc<InputPositionNode>>,
+    position_type: InputPositionType,
+}
+
+#[derive(Serialize)]
+#[serde(rename = "InputPosition")]
+struct InputPositionInner<'a> {
+    inner: Vec<&'a InputPositionType>,
+}
+
+#[derive(Deserialize)]
+#[serde(
+    untagged,
+    expecting = "Expecting a position like `[a][0]` or `[\"a\", 0]`"
+)]
+enum InputPositionForm {
+    Inner { inner: Vec<InputPositionType> },
+    List(Vec<InputPositionSegment>),
+    Str(String),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum InputPositionSegment {
+    Index(usize),
+    Key(String),
+    Type(InputPositionType),
 }
 
 impl InputPosition {
     pub fn new() -> Self {
-        Self { inner: Vec::new() }
+        Self {
+            maybe_last: None,
+            len: 0,
+        }
     }
 
     pub fn new_with_index(&self, index: usize) -> Self {
@@ -27,19 +68,110 @@ impl InputPosition {
     }
 
     pub fn add_index(&mut self, index: usize) {
-        self.inner.push(InputPositionType::Index(index))
+        self.add(InputPositionType::Index(index))
     }
 
     pub fn add_key(&mut self, key: &str) {
-        self.inner.push(InputPositionType::Key(key.to_string()))
+        self.add(InputPositionType::Key(crate::intern_key(key)))
+    }
+
+    pub fn new_with_position(&self, position: &InputPosition) -> Self {
+        let mut new = self.clone();
+        new.add_position(position);
+        new
+    }
+
+    pub fn add_position(&mut self, position: &InputPosition) {
+        position
+            .iter()
+            .for_each(|position_type| self.add(position_type.clone()))
     }
 
     pub fn add<T: Into<InputPositionType>>(&mut self, key_or_index: T) {
-        self.inner.push(key_or_index.into())
+        self.maybe_last = Some(Arc::new(InputPositionNode {
+            maybe_parent: self.maybe_last.take(),
+            position_type: key_or_index.into(),
+        }));
+        self.len += 1;
     }
 
     pub fn is_empty(&self) -> bool {
-        self.inner.is_empty()
+        self.len == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &InputPositionType> {
+        self.type_list().into_iter()
+    }
+
+    pub fn last(&self) -> Option<&InputPositionType> {
+        self.maybe_last.as_ref().map(|node| &node.position_type)
+    }
+
+    pub fn parent(&self) -> Option<Self> {
+        self.maybe_last.as_ref().map(|node| Self {
+            maybe_last: node.maybe_parent.clone(),
+            len: self.len - 1,
+        })
+    }
+
+    fn type_list(&self) -> Vec<&InputPositionType> {
+        let mut type_list = Vec::with_capacity(self.len);
+        let mut maybe_node = self.maybe_last.as_deref();
+        while let Some(node) = maybe_node {
+            type_list.push(&node.position_type);
+            maybe_node = node.maybe_parent.as_deref();
+        }
+        type_list.reverse();
+        type_list
+    }
+}
+
+impl FromIterator<InputPositionType> for InputPosition {
+    fn from_iter<T: IntoIterator<Item = InputPositionType>>(iter: T) -> Self {
+        let mut position = Self::new();
+        iter.into_iter()
+            .for_each(|position_type| position.add(position_type));
+        position
+    }
+}
+
+// Long chains of parents are dropped one at a time instead of recursively:
+impl Drop for InputPositionNode {
+    fn drop(&mut self) {
+        let mut maybe_parent = self.maybe_parent.take();
+        while let Some(parent) = maybe_parent {
+            maybe_parent = Arc::into_inner(parent).and_then(|mut node| node.maybe_parent.take());
+        }
+    }
+}
+
+impl PartialEq for InputPosition {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && (self.maybe_last.as_ref().map(Arc::as_ptr)
+                == other.maybe_last.as_ref().map(Arc::as_ptr)
+                || self.iter().eq(other.iter()))
+    }
+}
+
+impl Debug for InputPosition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("InputPosition")
+            .field("inner", &self.type_list())
+            .finish()
+    }
+}
+
+impl Serialize for InputPosition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        InputPositionInner {
+            inner: self.type_list(),
+        }
+        .serialize(serializer)
     }
 }
 
@@ -47,6 +179,17 @@ pub fn new() -> InputPosition {
     InputPosition::new()
 }
 
+// Keys and indexes of a position, e.g. `position!["servers", 0usize, "port"]`:
+#[macro_export]
+macro_rules! position {
+    ($($key_or_index:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut position = $crate::position::InputPosition::new();
+        $(position.add($key_or_index);)*
+        position
+    }};
+}
+
 impl Default for InputPosition {
     fn default() -> Self {
         Self::new()
@@ -55,27 +198,81 @@ impl Default for InputPosition {
 
 impl Display for InputPosition {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        if self.inner.len() == 1 {
-            f.write_str(self.inner[0].to_string().as_str())
+        if self.len == 1 {
+            f.write_str(self.iter().next().unwrap().to_string().as_str())
         } else {
-            self.inner
-                .iter()
+            self.iter()
                 .try_for_each(|position_type| f.write_str(format!("[{position_type}]").as_str()))
         }
     }
 }
 
+// Numeric segments are indexes, so map keys like `8080` don't round-trip and neither do keys
+// that contain `[` or `]`:
+impl FromStr for InputPosition {
+    type Err = String;
+
+    fn from_str(text: &str) -> std::result::Result<Self, Self::Err> {
+        let segment_type = |segment: &str| {
+            segment
+                .parse()
+                .map(InputPositionType::Index)
+                .unwrap_or_else(|_| InputPositionType::Key(crate::intern_key(segment)))
+        };
+        if !text.starts_with('[') {
+            return Ok(if text.is_empty() {
+                Self::new()
+            } else {
+                Self::from_iter([segment_type(text)])
+            });
+        }
+        let mut position = Self::new();
+        let mut rest = text;
+        while !rest.is_empty() {
+            let (segment, next_rest) = rest
+                .strip_prefix('[')
+                .and_then(|rest| rest.split_once(']'))
+                .ok_or_else(|| format!("Invalid position `{text}`"))?;
+            position.add(segment_type(segment));
+            rest = next_rest;
+        }
+        Ok(position)
+    }
+}
+
+impl TryFrom<InputPositionForm> for InputPosition {
+    type Error = String;
+
+    fn try_from(form: InputPositionForm) -> std::result::Result<Self, Self::Error> {
+        match form {
+            InputPositionForm::Inner { inner } => Ok(Self::from_iter(inner)),
+            InputPositionForm::List(segment_list) => Ok(segment_list
+                .into_iter()
+                .map(|segment| match segment {
+                    InputPositionSegment::Index(index) => InputPositionType::Index(index),
+                    InputPositionSegment::Key(key) => {
+                        InputPositionType::Key(crate::intern_key(key))
+                    }
+                    InputPositionSegment::Type(position_type) => position_type,
+                })
+                .collect()),
+            InputPositionForm::Str(text) => text.parse(),
+        }
+    }
+}
+
+// Walks the parents, so it takes linear time:
 impl Index<usize> for InputPosition {
     type Output = InputPositionType;
 
     fn index(&self, index: usize) -> &Self::Output {
-        self.inner.index(index)
+        self.type_list()[index]
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum InputPositionType {
-    Key(String),
+    Key(crate::InputKey),
     Index(usize),
 }
 
@@ -93,13 +290,13 @@ impl From<usize> for InputPositionType {
 
 impl From<&str> for InputPositionType {
     fn from(key: &str) -> Self {
-        Self::Key(key.to_string())
+        Self::Key(crate::intern_key(key))
     }
 }
 
 impl From<String> for InputPositionType {
     fn from(key: String) -> Self {
-        Self::Key(key)
+        Self::Key(crate::intern_key(key))
     }
 }
 
@@ -115,6 +312,180 @@ impl Display for InputPositionType {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InputPositionPattern {
+    inner: Vec<InputPositionPatternType>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputPositionPatternType {
+    Key(String),
+    Index(usize),
+    // `*`
+    Any,
+    // `**`
+    AnyDeep,
+}
+
+impl InputPositionPattern {
+    // Segments are separated by `.`, e.g. `plugins.*.port` or `plugins.**`. Keys in brackets are
+    // matched as they are, so they can contain `.` or be `*` (e.g. `hosts.[example.com].port`):
+    pub fn new(pattern: &str) -> Self {
+        let mut inner = Vec::new();
+        let mut segment = String::new();
+        let mut in_brackets = false;
+        for character in pattern.chars() {
+            match character {
+                ']' if in_brackets => {
+                    inner.push(InputPositionPatternType::new_literal(&segment));
+                    segment.clear();
+                    in_brackets = false;
+                }
+                _ if in_brackets => segment.push(character),
+                '.' | '[' => {
+                    if !segment.is_empty() {
+                        inner.push(InputPositionPatternType::new(&segment));
+                        segment.clear();
+                    }
+                    in_brackets = character == '[';
+                }
+                _ => segment.push(character),
+            }
+        }
+        // An unterminated bracket is part of the last key:
+        if in_brackets {
+            segment.insert(0, '[');
+        }
+        if !segment.is_empty() {
+            inner.push(InputPositionPatternType::new(&segment));
+        }
+        Self { inner }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &InputPositionPatternType> {
+        self.inner.iter()
+    }
+
+    pub fn matches(&self, position: &InputPosition) -> bool {
+        matches(&self.inner, &position.type_list())
+    }
+
+    // Also true if `position` is an ancestor or a descendant of a matching position, which is
+    // what matters when the value at `position` has changed:
+    pub fn overlaps(&self, position: &InputPosition) -> bool {
+        overlaps(&self.inner, &position.type_list())
+    }
+}
+
+fn matches(pattern: &[InputPositionPatternType], position: &[&InputPositionType]) -> bool {
+    match pattern.first() {
+        None => position.is_empty(),
+        Some(InputPositionPatternType::AnyDeep) => {
+            (0..=position.len()).any(|index| matches(&pattern[1..], &position[index..]))
+        }
+        Some(pattern_type) => position.first().is_some_and(|position_type| {
+            pattern_type.matches(position_type) && matches(&pattern[1..], &position[1..])
+        }),
+    }
+}
+
+fn overlaps(pattern: &[InputPositionPatternType], position: &[&InputPositionType]) -> bool {
+    match (pattern.first(), position.first()) {
+        (None, _) | (_, None) | (Some(InputPositionPatternType::AnyDeep), _) => true,
+        (Some(pattern_type), Some(position_type)) => {
+            pattern_type.matches(position_type) && overlaps(&pattern[1..], &position[1..])
+        }
+    }
+}
+
+impl InputPositionPatternType {
+    pub fn new(segment: &str) -> Self {
+        match segment {
+            "*" => Self::Any,
+            "**" => Self::AnyDeep,
+            _ => Self::new_literal(segment),
+        }
+    }
+
+    // A segment in brackets, which is never a wildcard:
+    pub fn new_literal(segment: &str) -> Self {
+        segment
+            .parse()
+            .map(Self::Index)
+            .unwrap_or_else(|_| Self::Key(segment.to_string()))
+    }
+
+    pub fn matches(&self, position_type: &InputPositionType) -> bool {
+        match (self, position_type) {
+            (Self::Any | Self::AnyDeep, _) => true,
+            (Self::Key(pattern_key), InputPositionType::Key(key)) => **pattern_key == **key,
+            (Self::Index(pattern_index), InputPositionType::Index(index)) => pattern_index == index,
+            // Numeric segments are ambiguous, e.g. `ports.8080` may refer to a map key:
+            (Self::Index(pattern_index), InputPositionType::Key(key)) => {
+                pattern_index.to_string() == **key
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<&str> for InputPositionPattern {
+    fn from(pattern: &str) -> Self {
+        Self::new(pattern)
+    }
+}
+
+impl From<&InputPosition> for InputPositionPattern {
+    fn from(position: &InputPosition) -> Self {
+        Self {
+            inner: position
+                .iter()
+                .map(|position_type| match position_type {
+                    InputPositionType::Key(key) => InputPositionPatternType::Key(key.to_string()),
+                    InputPositionType::Index(index) => InputPositionPatternType::Index(*index),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Display for InputPositionPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(
+            self.inner
+                .iter()
+                .map(|pattern_type| pattern_type.to_string())
+                .collect::<Vec<_>>()
+                .join(".")
+                .as_str(),
+        )
+    }
+}
+
+impl Display for InputPositionPatternType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Key(key)
+                if key.is_empty() || key.contains(['.', '[']) || key == "*" || key == "**" =>
+            {
+                write!(f, "[{key}]")
+            }
+            Self::Key(key) => f.write_str(key),
+            Self::Index(index) => write!(f, "{index}"),
+            Self::Any => f.write_str("*"),
+            Self::AnyDeep => f.write_str("**"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,5 +500,91 @@ mod tests {
         assert_eq!(pos[0], InputPositionType::new(0));
         assert_eq!(pos[1], InputPositionType::new("foo"));
         assert_eq!(format!("{pos}"), "[0][foo]".to_string());
+        assert_eq!(pos.len(), 2);
+        assert_eq!(crate::position![0usize, "foo"], pos);
+        assert_eq!(pos.last(), Some(&InputPositionType::new("foo")));
+        assert_eq!(pos.parent(), Some(new().new_with_index(0)));
+        assert_eq!(new().parent(), None);
+        assert_eq!(
+            new()
+                .new_with_key("foo")
+                .new_with_position(&new().new_with_index(1)),
+            new().new_with_key("foo").new_with_index(1)
+        );
+    }
+
+    #[test]
+    fn serde() {
+        let position = new().new_with_key("foo").new_with_index(1);
+        let json = serde_json::to_value(&position).unwrap();
+        assert_eq!(
+            serde_json::from_value::<InputPosition>(json).unwrap(),
+            position
+        );
+        for json in [
+            serde_json::json!(["foo", 1]),
+            serde_json::json!([{"Key": "foo"}, {"Index": 1}]),
+            serde_json::json!("[foo][1]"),
+        ] {
+            assert_eq!(
+                serde_json::from_value::<InputPosition>(json).unwrap(),
+                position
+            );
+        }
+        assert_eq!(
+            serde_json::from_value::<InputPosition>(serde_json::json!("foo")).unwrap(),
+            new().new_with_key("foo")
+        );
+        assert_eq!("".parse::<InputPosition>().unwrap(), new());
+        assert!("[foo][1".parse::<InputPosition>().is_err());
+        assert!(serde_json::from_value::<InputPosition>(serde_json::json!([-1])).is_err());
+    }
+
+    #[test]
+    fn pattern() {
+        let position = new()
+            .new_with_key("plugins")
+            .new_with_key("a")
+            .new_with_key("port");
+        assert!(InputPositionPattern::new("plugins.a.*").matches(&position));
+        assert!(InputPositionPattern::new("plugins.**").matches(&position));
+        assert!(InputPositionPattern::new("**.port").matches(&position));
+        assert!(!InputPositionPattern::new("plugins.b.*").matches(&position));
+        assert!(!InputPositionPattern::new("plugins.a").matches(&position));
+        assert!(InputPositionPattern::new("plugins.a").overlaps(&position));
+        assert!(InputPositionPattern::new("plugins.a.port.*").overlaps(&position));
+        assert!(!InputPositionPattern::new("plugins.b").overlaps(&position));
+        assert!(InputPositionPattern::new("list.0")
+            .matches(&new().new_with_key("list").new_with_index(0)));
+        assert_eq!(
+            InputPositionPattern::new("plugins.*.port").to_string(),
+            "plugins.*.port"
+        );
+        assert!(InputPositionPattern::from(&position).matches(&position));
+    }
+
+    #[test]
+    fn pattern_with_brackets() {
+        let position = new()
+            .new_with_key("hosts")
+            .new_with_key("example.com")
+            .new_with_key("*");
+        for pattern in ["hosts.[example.com].[*]", "hosts[example.com][*]", "**.[*]"] {
+            assert!(InputPositionPattern::new(pattern).matches(&position));
+        }
+        assert!(!InputPositionPattern::new("hosts.example.com.*").matches(&position));
+        assert!(
+            !InputPositionPattern::new("hosts.[example.com].[*]").matches(
+                &new()
+                    .new_with_key("hosts")
+                    .new_with_key("example.com")
+                    .new_with_key("a")
+            )
+        );
+        let pattern = InputPositionPattern::from(&position);
+        assert_eq!(pattern.to_string(), "hosts.[example.com].[*]");
+        assert_eq!(InputPositionPattern::new(&pattern.to_string()), pattern);
+        assert!(InputPositionPattern::new("list.[0]")
+            .matches(&new().new_with_key("list").new_with_index(0)));
     }
 }
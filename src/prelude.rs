@@ -0,0 +1,75 @@
+// The types, functions, and macros that most hosts and plugins use, via
+// `use plugx_input::prelude::*;`:
+#[cfg(feature = "schema")]
+pub use crate::schema::{
+    merge_with_schema, InputSchema, InputSchemaError, InputSchemaType, SchemaSet, ValidationReport,
+};
+pub use crate::{
+    builder::InputMapBuilder,
+    deserialize::InputDeserializeError,
+    diff::{diff, InputDiff, InputDiffAction},
+    freeze::{InputFreezeError, InputFreezeGuard},
+    include::{InputIncludeError, InputIncludeResolver},
+    merge::{merge, merge_with_options, InputMergeOptions},
+    migration::InputMigration,
+    patch::{InputPatch, InputPatchError, InputPatchOperation},
+    position,
+    position::{InputPosition, InputPositionPattern, InputPositionType},
+    resolution::{ResolutionError, ResolutionGuard},
+    Input, InputRef,
+};
+
+// Short names of the same items without the `Input` prefix, for code that only works with
+// inputs (e.g. `Schema` and `Position`):
+#[cfg(feature = "schema")]
+pub use crate::schema::{
+    InputSchema as Schema, InputSchemaError as SchemaError, InputSchemaType as SchemaType,
+};
+pub use crate::{
+    builder::InputMapBuilder as MapBuilder,
+    deserialize::InputDeserializeError as DeserializeError,
+    diff::{InputDiff as Diff, InputDiffAction as DiffAction},
+    freeze::{InputFreezeError as FreezeError, InputFreezeGuard as FreezeGuard},
+    include::{InputIncludeError as IncludeError, InputIncludeResolver as IncludeResolver},
+    merge::InputMergeOptions as MergeOptions,
+    migration::InputMigration as Migration,
+    patch::{
+        InputPatch as Patch, InputPatchError as PatchError, InputPatchOperation as PatchOperation,
+    },
+    position::{
+        InputPosition as Position, InputPositionPattern as PositionPattern,
+        InputPositionType as PositionType,
+    },
+};
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn prelude() {
+        let mut input = InputMapBuilder::new().insert("port", 80).build();
+        merge(
+            &mut input,
+            &InputMapBuilder::new().insert("port", 8080).build(),
+        );
+        assert_eq!(input.get_at(&position!["port"]), Some(&Input::from(8080)));
+        let position: Position = position!["port"];
+        assert_eq!(position, InputPosition::new().new_with_key("port"));
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn short_names() {
+        let schema = Schema::new().with_schema_type(SchemaType::new_port());
+        schema
+            .schema_type()
+            .validate(&mut Input::from(8080), None)
+            .unwrap();
+        let error: SchemaError = schema
+            .schema_type()
+            .validate(&mut Input::from("http"), None)
+            .unwrap_err();
+        assert_eq!(error.position(), &Position::new());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{merge::merge_with_positions, position::InputPosition, Input};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputProfileStrategy {
+    // Profile values are merged recursively over the base (see `merge::merge`):
+    #[default]
+    Deep,
+    // Every top-level key of the profile replaces the same key of the base:
+    Shallow,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputProfileOptions {
+    profiles_key: String,
+    strategy: InputProfileStrategy,
+}
+
+impl Default for InputProfileOptions {
+    fn default() -> Self {
+        Self {
+            profiles_key: "profiles".into(),
+            strategy: Default::default(),
+        }
+    }
+}
+
+impl InputProfileOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn profiles_key(&self) -> &String {
+        &self.profiles_key
+    }
+
+    pub fn set_profiles_key<K: ToString>(&mut self, profiles_key: K) {
+        self.profiles_key = profiles_key.to_string();
+    }
+
+    pub fn with_profiles_key<K: ToString>(mut self, profiles_key: K) -> Self {
+        self.set_profiles_key(profiles_key);
+        self
+    }
+
+    pub fn strategy(&self) -> InputProfileStrategy {
+        self.strategy
+    }
+
+    pub fn set_strategy(&mut self, strategy: InputProfileStrategy) {
+        self.strategy = strategy;
+    }
+
+    pub fn with_strategy(mut self, strategy: InputProfileStrategy) -> Self {
+        self.set_strategy(strategy);
+        self
+    }
+}
+
+impl Input {
+    pub fn apply_profile(&mut self, profile_name: &str) -> bool {
+        self.apply_profile_with_options(profile_name, &InputProfileOptions::default())
+    }
+
+    // The profiles subtree is always stripped. Returns `false` if the profile is not defined:
+    pub fn apply_profile_with_options(
+        &mut self,
+        profile_name: &str,
+        options: &InputProfileOptions,
+    ) -> bool {
+        let map = if let Input::Map(map) = self {
+            map
+        } else {
+            return false;
+        };
+        let maybe_profile = match map.remove(options.profiles_key().as_str()) {
+            Some(mut profile_map) if profile_map.is_map() => {
+                profile_map.map_mut().remove(profile_name)
+            }
+            _ => None,
+        };
+        let profile = if let Some(profile) = maybe_profile {
+            profile
+        } else {
+            return false;
+        };
+        match (options.strategy(), profile) {
+            (InputProfileStrategy::Shallow, profile) if profile.is_map() => {
+                map.extend(profile.into_map())
+            }
+            (_, profile) => merge_with_positions(
+                self,
+                InputPosition::new(),
+                &profile,
+                InputPosition::new()
+                    .new_with_key(options.profiles_key())
+                    .new_with_key(profile_name),
+            ),
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(json: serde_json::Value) -> Input {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn apply_profile() {
+        let base = input(json!({
+            "server": {"host": "localhost", "port": 8080},
+            "profiles": {"production": {"server": {"host": "example.com"}}}
+        }));
+
+        let mut deep = base.clone();
+        assert!(deep.apply_profile("production"));
+        assert_eq!(
+            deep,
+            input(json!({"server": {"host": "example.com", "port": 8080}}))
+        );
+
+        let mut shallow = base.clone();
+        assert!(shallow.apply_profile_with_options(
+            "production",
+            &InputProfileOptions::new().with_strategy(InputProfileStrategy::Shallow)
+        ));
+        assert_eq!(shallow, input(json!({"server": {"host": "example.com"}})));
+
+        let mut unknown = base.clone();
+        assert!(!unknown.apply_profile("staging"));
+        assert_eq!(
+            unknown,
+            input(json!({"server": {"host": "localhost", "port": 8080}}))
+        );
+    }
+}
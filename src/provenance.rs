@@ -0,0 +1,172 @@
+#[cfg(feature = "schema")]
+use crate::schema::InputSchemaError;
+use crate::{position::InputPosition, Input};
+use std::{
+    fmt::{Display, Formatter},
+    path::PathBuf,
+};
+
+// Where a value was set (e.g. to say "set from `APP_SERVER__PORT`" in error messages):
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Env(String),
+    Layer(String),
+    Other(String),
+}
+
+// A side-table of the sources of an input, kept next to it (`Input` itself has no room for
+// metadata). A source covers its position and every position inside it unless an inner position
+// has its own source:
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Provenance {
+    source_list: Vec<(InputPosition, InputSource)>,
+}
+
+impl Provenance {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // Every value of a loaded input comes from the same source (e.g. a file):
+    pub fn new_with_source(source: InputSource) -> Self {
+        Self::new().with_source(InputPosition::new(), source)
+    }
+
+    pub fn source_list(&self) -> &Vec<(InputPosition, InputSource)> {
+        &self.source_list
+    }
+
+    // Replaces the sources of the position and of the positions inside it:
+    pub fn set_source(&mut self, position: InputPosition, source: InputSource) {
+        self.source_list
+            .retain(|(other_position, _)| !starts_with(other_position, &position));
+        self.source_list.push((position, source));
+    }
+
+    pub fn with_source(mut self, position: InputPosition, source: InputSource) -> Self {
+        self.set_source(position, source);
+        self
+    }
+
+    // The source of the position itself or of its innermost parent that has one:
+    pub fn source_at(&self, position: &InputPosition) -> Option<&InputSource> {
+        self.source_list
+            .iter()
+            .filter(|(other_position, _)| starts_with(position, other_position))
+            .max_by_key(|(other_position, _)| other_position.len())
+            .map(|(_, source)| source)
+    }
+
+    // Follows `merge::merge(input, to_be_merged_input)`: maps are merged key by key and every
+    // other value (including lists) is attributed to `to_be_merged_input`:
+    pub fn merge(&mut self, to_be_merged_input: &Input, to_be_merged_provenance: &Provenance) {
+        self.merge_at(
+            InputPosition::new(),
+            to_be_merged_input,
+            to_be_merged_provenance,
+        )
+    }
+
+    fn merge_at(
+        &mut self,
+        position: InputPosition,
+        to_be_merged_input: &Input,
+        to_be_merged_provenance: &Provenance,
+    ) {
+        crate::stack::maybe_grow(|| match to_be_merged_input {
+            Input::Map(map) if !map.is_empty() => map.iter().for_each(|(key, inner_input)| {
+                self.merge_at(
+                    position.new_with_key(key),
+                    inner_input,
+                    to_be_merged_provenance,
+                )
+            }),
+            _ => match to_be_merged_provenance.source_at(&position) {
+                Some(source) => self.set_source(position, source.clone()),
+                None => self
+                    .source_list
+                    .retain(|(other_position, _)| !starts_with(other_position, &position)),
+            },
+        })
+    }
+
+    // Appends `(set from ...)` to the error message if the failed position has a source:
+    #[cfg(feature = "schema")]
+    pub fn describe_error(&self, error: &InputSchemaError) -> String {
+        match self.source_at(error.position()) {
+            Some(source) => format!("{error} (set from {source})"),
+            None => error.to_string(),
+        }
+    }
+}
+
+impl Input {
+    // `None` if the input has no value at the position:
+    pub fn provenance_at<'a>(
+        &self,
+        provenance: &'a Provenance,
+        position: &InputPosition,
+    ) -> Option<&'a InputSource> {
+        self.get_at(position)
+            .and_then(|_| provenance.source_at(position))
+    }
+}
+
+fn starts_with(position: &InputPosition, prefix: &InputPosition) -> bool {
+    position.len() >= prefix.len() && position.iter().zip(prefix.iter()).all(|(a, b)| a == b)
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "file `{}`", path.display()),
+            Self::Env(name) => write!(f, "`{name}`"),
+            Self::Layer(name) => write!(f, "layer `{name}`"),
+            Self::Other(name) => f.write_str(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge() {
+        let file_input: Input =
+            serde_json::from_value(json!({"server": {"host": "localhost", "port": 80}})).unwrap();
+        let env_input: Input = serde_json::from_value(json!({"server": {"port": "8080"}})).unwrap();
+        let port_position = InputPosition::new()
+            .new_with_key("server")
+            .new_with_key("port");
+        let env_provenance = Provenance::new().with_source(
+            port_position.clone(),
+            InputSource::Env("APP_SERVER__PORT".to_string()),
+        );
+        let mut input = file_input.clone();
+        let mut provenance =
+            Provenance::new_with_source(InputSource::File(PathBuf::from("app.json")));
+        crate::merge::merge(&mut input, &env_input);
+        provenance.merge(&env_input, &env_provenance);
+
+        assert_eq!(
+            input.provenance_at(&provenance, &port_position),
+            Some(&InputSource::Env("APP_SERVER__PORT".to_string()))
+        );
+        let host_position = InputPosition::new()
+            .new_with_key("server")
+            .new_with_key("host");
+        assert_eq!(
+            input
+                .provenance_at(&provenance, &host_position)
+                .map(ToString::to_string),
+            Some("file `app.json`".to_string())
+        );
+        assert_eq!(
+            input.provenance_at(&provenance, &InputPosition::new().new_with_key("user")),
+            None
+        );
+    }
+}
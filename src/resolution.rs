@@ -0,0 +1,188 @@
+use std::fmt::{Debug, Display, Formatter};
+
+// The chain of values (e.g. include paths or ref names) that are being resolved inside each
+// other, for cycle detection with a depth budget. Used by `include::InputIncludeResolver` and
+// `ref` schemas, and available to other resolvers:
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolutionGuard<K> {
+    chain: Vec<K>,
+    max_depth: usize,
+    // e.g. recursive schemas that follow the same ref in nested values:
+    allow_cycles: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolutionError<K> {
+    Cycle {
+        key: K,
+        chain: Vec<K>,
+    },
+    Depth {
+        key: K,
+        chain: Vec<K>,
+        max_depth: usize,
+    },
+}
+
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+impl<K> Default for ResolutionGuard<K> {
+    fn default() -> Self {
+        Self {
+            chain: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            allow_cycles: false,
+        }
+    }
+}
+
+impl<K: Clone + PartialEq> ResolutionGuard<K> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.set_max_depth(max_depth);
+        self
+    }
+
+    pub fn allow_cycles(&self) -> bool {
+        self.allow_cycles
+    }
+
+    pub fn set_allow_cycles(&mut self, allow_cycles: bool) {
+        self.allow_cycles = allow_cycles;
+    }
+
+    pub fn with_allow_cycles(mut self, allow_cycles: bool) -> Self {
+        self.set_allow_cycles(allow_cycles);
+        self
+    }
+
+    // Outermost first:
+    pub fn chain(&self) -> &Vec<K> {
+        &self.chain
+    }
+
+    pub fn depth(&self) -> usize {
+        self.chain.len()
+    }
+
+    // Should be followed by `leave()` once `key` is resolved (also if resolving it fails):
+    pub fn enter(&mut self, key: K) -> Result<(), ResolutionError<K>> {
+        if self.chain.len() >= self.max_depth {
+            return Err(ResolutionError::Depth {
+                key,
+                chain: self.chain.clone(),
+                max_depth: self.max_depth,
+            });
+        }
+        if !self.allow_cycles && self.chain.contains(&key) {
+            return Err(ResolutionError::Cycle {
+                key,
+                chain: self.chain.clone(),
+            });
+        }
+        self.chain.push(key);
+        Ok(())
+    }
+
+    pub fn leave(&mut self) -> Option<K> {
+        self.chain.pop()
+    }
+
+    // Runs `resolve` between `enter()` and `leave()`:
+    pub fn resolve<T, F: FnOnce(&mut Self) -> T>(
+        &mut self,
+        key: K,
+        resolve: F,
+    ) -> Result<T, ResolutionError<K>> {
+        self.enter(key)?;
+        let result = resolve(self);
+        self.leave();
+        Ok(result)
+    }
+}
+
+impl<K> ResolutionError<K> {
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Cycle { key, .. } | Self::Depth { key, .. } => key,
+        }
+    }
+
+    // The chain before `key`, outermost first:
+    pub fn chain(&self) -> &Vec<K> {
+        match self {
+            Self::Cycle { chain, .. } | Self::Depth { chain, .. } => chain,
+        }
+    }
+}
+
+impl<K: Debug> ResolutionError<K> {
+    // The chain with `key` at the end, e.g. `"a.json" -> "b.json" -> "a.json"`:
+    pub fn chain_text(&self) -> String {
+        chain_text(self.chain().iter().chain([self.key()]))
+    }
+}
+
+pub fn chain_text<K: Debug, I: IntoIterator<Item = K>>(chain: I) -> String {
+    chain
+        .into_iter()
+        .map(|key| format!("{key:?}"))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+impl<K: Debug> Display for ResolutionError<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cycle { key, .. } => {
+                write!(
+                    f,
+                    "{key:?} is already being resolved ({})",
+                    self.chain_text()
+                )
+            }
+            Self::Depth { max_depth, .. } => write!(
+                f,
+                "exceeds the maximum depth {max_depth} ({})",
+                self.chain_text()
+            ),
+        }
+    }
+}
+
+impl<K: Debug> std::error::Error for ResolutionError<K> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enter() {
+        let mut guard = ResolutionGuard::new().with_max_depth(3);
+        guard.enter("a").unwrap();
+        guard.enter("b").unwrap();
+        let error = guard.enter("a").unwrap_err();
+        assert!(matches!(error, ResolutionError::Cycle { .. }));
+        assert_eq!(error.chain_text(), r#""a" -> "b" -> "a""#);
+        guard.enter("c").unwrap();
+        assert!(matches!(
+            guard.enter("d"),
+            Err(ResolutionError::Depth { max_depth: 3, .. })
+        ));
+        assert_eq!(guard.leave(), Some("c"));
+        let mut guard = guard.with_allow_cycles(true);
+        assert_eq!(guard.resolve("a", |guard| guard.depth()), Ok(3));
+        assert_eq!(guard.depth(), 2);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{position::InputPosition, schema::InputSchemaError};
+
+const POSITION: &str = "\x1b[1;36m";
+const EXPECTED: &str = "\x1b[33m";
+const RECEIVED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+impl InputSchemaError {
+    // Like `Display`, but the position is cyan, the expected type or constraint is yellow, and
+    // the received value is red, e.g. for terminal-based hosts. Colors are skipped if `NO_COLOR`
+    // is set:
+    pub fn render_colored(&self) -> String {
+        let color = std::env::var_os("NO_COLOR").is_none();
+        let paint = |code: &str, text: String| {
+            if color {
+                format!("{code}{text}{RESET}")
+            } else {
+                text
+            }
+        };
+        // The root (an empty position) is skipped:
+        let position = |position: &InputPosition| {
+            if position.is_empty() {
+                String::new()
+            } else {
+                format!("{} ", paint(POSITION, position.to_string()))
+            }
+        };
+        match self {
+            Self::Type {
+                position: input_position,
+                expected_type,
+                input_type,
+            } => format!(
+                "{}Expected `{}` type, got `{}`",
+                position(input_position),
+                paint(EXPECTED, expected_type.clone()),
+                paint(RECEIVED, input_type.clone())
+            ),
+            Self::Schema {
+                position: input_position,
+                schema_type,
+                input,
+            } => format!(
+                "{}Expected {}, got `{}`{}",
+                position(input_position),
+                paint(EXPECTED, schema_type.to_string()),
+                paint(RECEIVED, input.to_string()),
+                super::suggest::enum_hint(schema_type, input)
+            ),
+            Self::Size {
+                position: input_position,
+                schema_type,
+                expected_size,
+                size,
+            } => format!(
+                "{}Expected {schema_type} with {}, but the input size is {}",
+                position(input_position),
+                paint(EXPECTED, expected_size.to_string()),
+                paint(RECEIVED, size.to_string())
+            ),
+            Self::Range {
+                position: input_position,
+                schema_type,
+                expected_range,
+                input,
+            } => format!(
+                "{}Expected {schema_type} with {}, but the input is {}",
+                position(input_position),
+                paint(EXPECTED, expected_range.to_string()),
+                paint(RECEIVED, input.to_string())
+            ),
+            Self::NotFound {
+                position: input_position,
+                schema_type,
+            } => format!(
+                "{}is not set (expected {})",
+                position(input_position),
+                paint(EXPECTED, schema_type.to_string())
+            ),
+            Self::Invalid {
+                description,
+                position: input_position,
+                input,
+            } => format!(
+                "{}{description} ({})",
+                position(input_position),
+                paint(RECEIVED, input.to_string())
+            ),
+            Self::Aborted {
+                position: input_position,
+                description,
+            } => format!(
+                "{}validation aborted: {description}",
+                position(input_position)
+            ),
+            Self::Internal {
+                position: input_position,
+                description,
+            } => format!("{}internal error: {description}", position(input_position)),
+            Self::Context { context, source } => {
+                format!("{context}: {}", source.render_colored())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        schema::{InputSchemaType, InputSchemaTypeRange},
+        Input,
+    };
+
+    #[test]
+    fn render_colored() {
+        let error = InputSchemaError::Range {
+            position: InputPosition::new().new_with_key("port"),
+            schema_type: InputSchemaType::new_integer(),
+            expected_range: InputSchemaTypeRange::from(1024..65535),
+            input: Input::from(80),
+        };
+        let text = error.render_colored();
+        if std::env::var_os("NO_COLOR").is_none() {
+            assert!(text.starts_with(format!("{POSITION}port{RESET} Expected").as_str()));
+            assert!(text.ends_with(format!("{RECEIVED}80{RESET}").as_str()));
+        } else {
+            assert_eq!(text, error.to_string());
+        }
+    }
+}
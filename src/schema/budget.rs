@@ -0,0 +1,178 @@
+use crate::{
+    position::InputPosition,
+    schema::{InputSchemaError, InputSchemaType},
+    Input,
+};
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+// Limits of `InputSchemaType::validate_with_options()` for documents of untrusted plugins:
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ValidationOptions {
+    maybe_deadline: Option<Instant>,
+    // The maximum number of validated values (a value is validated once per schema, e.g. once per
+    // tried `either` branch):
+    maybe_node_budget: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Budget {
+    maybe_deadline: Option<Instant>,
+    maybe_remaining_node_count: Option<usize>,
+    is_exceeded: bool,
+}
+
+thread_local! {
+    static CURRENT: Cell<Option<Budget>> = const { Cell::new(None) };
+}
+
+impl ValidationOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn maybe_deadline(&self) -> Option<Instant> {
+        self.maybe_deadline
+    }
+
+    pub fn set_deadline(&mut self, deadline: Instant) {
+        self.maybe_deadline = Some(deadline)
+    }
+
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.set_deadline(deadline);
+        self
+    }
+
+    // The deadline is the timeout from now:
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn maybe_node_budget(&self) -> Option<usize> {
+        self.maybe_node_budget
+    }
+
+    pub fn set_node_budget(&mut self, node_budget: usize) {
+        self.maybe_node_budget = Some(node_budget)
+    }
+
+    pub fn with_node_budget(mut self, node_budget: usize) -> Self {
+        self.set_node_budget(node_budget);
+        self
+    }
+}
+
+impl InputSchemaType {
+    // Like `validate()`, but fails with `InputSchemaError::Aborted` once the deadline has passed
+    // or the node budget is used up. Limits are checked before validating every value, so a
+    // single value (e.g. a huge string) is never interrupted:
+    pub fn validate_with_options(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+        options: &ValidationOptions,
+    ) -> Result<(), InputSchemaError> {
+        let budget = Budget {
+            maybe_deadline: options.maybe_deadline,
+            maybe_remaining_node_count: options.maybe_node_budget,
+            is_exceeded: false,
+        };
+        let maybe_outer_budget = CURRENT.with(|current| current.replace(Some(budget)));
+        let result = self.validate(input, maybe_position.clone());
+        let budget = CURRENT.with(|current| current.replace(maybe_outer_budget));
+        match result {
+            // e.g. `either` may have tried another branch after the budget was exceeded:
+            Err(error) if budget.is_some_and(|budget| budget.is_exceeded) => {
+                Err(match error.inner() {
+                    InputSchemaError::Aborted { .. } => error,
+                    _ => aborted_error(options.maybe_deadline, error.position().clone()),
+                })
+            }
+            result => result,
+        }
+    }
+}
+
+// Called before validating every value:
+pub(crate) fn check(maybe_position: Option<&InputPosition>) -> Result<(), InputSchemaError> {
+    CURRENT.with(|current| {
+        let Some(mut budget) = current.get() else {
+            return Ok(());
+        };
+        if !budget.is_exceeded {
+            budget.is_exceeded = budget
+                .maybe_deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+                || budget.maybe_remaining_node_count == Some(0);
+            budget.maybe_remaining_node_count = budget
+                .maybe_remaining_node_count
+                .map(|count| count.saturating_sub(1));
+            current.set(Some(budget));
+        }
+        if budget.is_exceeded {
+            Err(aborted_error(
+                budget.maybe_deadline,
+                maybe_position.cloned().unwrap_or_default(),
+            ))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+fn aborted_error(maybe_deadline: Option<Instant>, position: InputPosition) -> InputSchemaError {
+    InputSchemaError::Aborted {
+        position,
+        description: if maybe_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            "deadline has passed".to_string()
+        } else {
+            "node budget is used up".to_string()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate_with_options() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "list",
+            "item_schema": {"type": "either", "schema_list": [{"type": "integer"}, {"type": "string"}]}
+        }))
+        .unwrap();
+        let mut input = Input::from((0..100).map(Input::from).collect::<Vec<_>>());
+        schema_type
+            .validate_with_options(
+                &mut input.clone(),
+                None,
+                &ValidationOptions::new().with_node_budget(1000),
+            )
+            .unwrap();
+        let error = schema_type
+            .validate_with_options(
+                &mut input,
+                None,
+                &ValidationOptions::new().with_node_budget(10),
+            )
+            .unwrap_err();
+        assert!(matches!(error, InputSchemaError::Aborted { .. }));
+        assert!(error.to_string().contains("node budget"));
+        let error = schema_type
+            .validate_with_options(
+                &mut input,
+                None,
+                &ValidationOptions::new().with_deadline(Instant::now()),
+            )
+            .unwrap_err();
+        assert_eq!(error.position(), &InputPosition::new());
+        assert!(error.to_string().contains("deadline"));
+        // Without options nothing is limited:
+        schema_type.validate(&mut input, None).unwrap();
+    }
+}
@@ -0,0 +1,347 @@
+use crate::{
+    position::InputPosition,
+    schema::{InputSchemaError, InputSchemaType},
+    Input,
+};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+// Remembers the validated maps and lists (at any depth) of one schema by their
+// `Input::fingerprint()`, e.g. for reloads that mostly don't change anything or for many plugin
+// instances that share the same (default) sections. Entries are keyed by the schema type of the
+// subtree, so an identical subtree is only validated once per place in the schema. The least
+// recently used entry is dropped once there are more than `capacity` entries. Only successful
+// validations are cached, since errors include the position. Note that schemas which check the
+// environment (e.g. `fs`) are not re-checked and side effects of validation (e.g. unknown keys of
+// `ValidationReport`) are not repeated for cached subtrees. Validations with the same cache are
+// serialized:
+#[derive(Debug)]
+pub struct ValidationCache {
+    // Boxed so that the addresses of all schema types (the keys of entries) don't change:
+    schema_type: Box<InputSchemaType>,
+    schema_type_set: Arc<HashSet<usize>>,
+    capacity: usize,
+    state: Mutex<ValidationCacheState>,
+}
+
+// Schema types by their address with the wide fingerprint of the input:
+type ValidationCacheKey = (usize, u128);
+
+#[derive(Debug, Default)]
+struct ValidationCacheState {
+    entry_map: HashMap<ValidationCacheKey, ValidationCacheEntry>,
+    // Keys by their last use, so the least recently used one is the first:
+    key_map: BTreeMap<u64, ValidationCacheKey>,
+    last_used: u64,
+    capacity: usize,
+}
+
+#[derive(Debug)]
+struct ValidationCacheEntry {
+    // The validated input, `None` if validation didn't change it:
+    maybe_input: Option<Input>,
+    last_used: u64,
+}
+
+// The schema types of the cache with its state while it validates on this thread:
+struct ActiveValidationCache {
+    schema_type_set: Arc<HashSet<usize>>,
+    state: ValidationCacheState,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<ActiveValidationCache>> = const { RefCell::new(None) };
+}
+
+impl ValidationCache {
+    pub fn new(schema_type: InputSchemaType, capacity: usize) -> Self {
+        let schema_type = Box::new(schema_type);
+        let mut schema_type_set = HashSet::new();
+        collect_schema_type_set(&schema_type, &mut schema_type_set);
+        Self {
+            schema_type,
+            schema_type_set: Arc::new(schema_type_set),
+            capacity,
+            state: Mutex::new(ValidationCacheState {
+                capacity,
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub fn schema_type(&self) -> &InputSchemaType {
+        &self.schema_type
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.lock().entry_map.len()
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entry_map.clear();
+        state.key_map.clear();
+    }
+
+    // Like `InputSchemaType::validate()`. Hits and misses of every cached subtree are counted in
+    // `metrics::InputMetrics`:
+    pub fn validate(&self, input: &mut Input) -> Result<(), InputSchemaError> {
+        let mut state = self.lock();
+        let maybe_outer_cache = CURRENT.with(|current| {
+            current.replace(Some(ActiveValidationCache {
+                schema_type_set: self.schema_type_set.clone(),
+                state: std::mem::take(&mut *state),
+            }))
+        });
+        let result = self.schema_type.validate(input, None);
+        if let Some(cache) = CURRENT.with(|current| current.replace(maybe_outer_cache)) {
+            *state = cache.state
+        }
+        #[cfg(feature = "metrics")]
+        crate::metrics::finish();
+        result
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ValidationCacheState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ValidationCacheState {
+    fn get(&mut self, key: &ValidationCacheKey) -> Option<Option<Input>> {
+        self.last_used += 1;
+        let last_used = self.last_used;
+        let entry = self.entry_map.get_mut(key)?;
+        self.key_map.remove(&entry.last_used);
+        self.key_map.insert(last_used, *key);
+        entry.last_used = last_used;
+        Some(entry.maybe_input.clone())
+    }
+
+    fn insert(&mut self, key: ValidationCacheKey, maybe_input: Option<Input>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.last_used += 1;
+        let last_used = self.last_used;
+        if let Some(entry) = self.entry_map.insert(
+            key,
+            ValidationCacheEntry {
+                maybe_input,
+                last_used,
+            },
+        ) {
+            self.key_map.remove(&entry.last_used);
+        }
+        self.key_map.insert(last_used, key);
+        if self.entry_map.len() > self.capacity {
+            if let Some((_, least_recently_used)) = self.key_map.pop_first() {
+                self.entry_map.remove(&least_recently_used);
+            }
+        }
+    }
+}
+
+// Called by `InputSchemaType::validate()` for every value. Scalars are always validated:
+pub(crate) fn validate<F>(
+    schema_type: &InputSchemaType,
+    input: &mut Input,
+    maybe_position: Option<InputPosition>,
+    validate_function: F,
+) -> Result<(), InputSchemaError>
+where
+    F: FnOnce(&mut Input, Option<InputPosition>) -> Result<(), InputSchemaError>,
+{
+    let Some((key, maybe_hit)) = lookup(schema_type, input) else {
+        return validate_function(input, maybe_position);
+    };
+    if let Some(maybe_input) = maybe_hit {
+        if let Some(validated_input) = maybe_input {
+            *input = validated_input
+        }
+        return Ok(());
+    }
+    validate_function(input, maybe_position)?;
+    insert(
+        key,
+        (input.wide_fingerprint() != key.1).then(|| input.clone()),
+    );
+    Ok(())
+}
+
+// Like `validate()` for `InputSchemaType::validate_cow()`:
+pub(crate) fn validate_cow<'a, F>(
+    schema_type: &InputSchemaType,
+    input: &'a Input,
+    validate_function: F,
+) -> Result<Cow<'a, Input>, InputSchemaError>
+where
+    F: FnOnce(&'a Input) -> Result<Cow<'a, Input>, InputSchemaError>,
+{
+    let Some((key, maybe_hit)) = lookup(schema_type, input) else {
+        return validate_function(input);
+    };
+    if let Some(maybe_input) = maybe_hit {
+        return Ok(maybe_input.map_or(Cow::Borrowed(input), Cow::Owned));
+    }
+    let validated_input = validate_function(input)?;
+    insert(
+        key,
+        match &validated_input {
+            Cow::Borrowed(_) => None,
+            Cow::Owned(validated_input) => Some(validated_input.clone()),
+        },
+    );
+    Ok(validated_input)
+}
+
+// `None` if the input is not cached, otherwise the key with the cached validated input if any:
+fn lookup(
+    schema_type: &InputSchemaType,
+    input: &Input,
+) -> Option<(ValidationCacheKey, Option<Option<Input>>)> {
+    if !input.is_map() && !input.is_list() {
+        return None;
+    }
+    let schema_type_address = schema_type as *const InputSchemaType as usize;
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .filter(|cache| cache.schema_type_set.contains(&schema_type_address))?;
+        let key = (schema_type_address, input.wide_fingerprint());
+        let maybe_hit = current
+            .borrow_mut()
+            .as_mut()
+            .and_then(|cache| cache.state.get(&key));
+        if maybe_hit.is_some() {
+            crate::record_metric!(validation_cache_hit_count);
+        } else {
+            crate::record_metric!(validation_cache_miss_count);
+        }
+        Some((key, maybe_hit))
+    })
+}
+
+fn insert(key: ValidationCacheKey, maybe_input: Option<Input>) {
+    CURRENT.with(|current| {
+        if let Some(cache) = current.borrow_mut().as_mut() {
+            cache.state.insert(key, maybe_input)
+        }
+    })
+}
+
+fn collect_schema_type_set(schema_type: &InputSchemaType, schema_type_set: &mut HashSet<usize>) {
+    crate::stack::maybe_grow(|| {
+        schema_type_set.insert(schema_type as *const InputSchemaType as usize);
+        schema_type
+            .child_list()
+            .into_iter()
+            .for_each(|schema_type| collect_schema_type_set(schema_type, schema_type_set))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "dynamic_map",
+            "item_schema": {"type": "static_map", "items": {
+                "port": {"schema": {"type": "port"}},
+                "debug": {"schema": {"type": "boolean"}, "default": false},
+            }},
+        }))
+        .unwrap();
+        let cache = ValidationCache::new(schema_type, 3);
+        let mut input: Input =
+            serde_json::from_value(json!({"a": {"port": "8080"}, "b": {"port": 80}})).unwrap();
+        cache.validate(&mut input).unwrap();
+        let expected: Input = serde_json::from_value(json!({
+            "a": {"port": 8080, "debug": false},
+            "b": {"port": 80, "debug": false},
+        }))
+        .unwrap();
+        assert_eq!(input, expected);
+        // The root and both plugin instances:
+        assert_eq!(cache.entry_count(), 3);
+
+        // Other instances with the same sections are hits, even at other keys:
+        let mut input: Input =
+            serde_json::from_value(json!({"c": {"port": "8080"}, "b": {"port": 80}})).unwrap();
+        cache.validate(&mut input).unwrap();
+        assert_eq!(input.as_map()["c"], expected.as_map()["a"]);
+        // The new root replaced the least recently used entry (the first root):
+        assert_eq!(cache.entry_count(), 3);
+        let mut state = cache.lock();
+        assert_eq!(state.key_map.len(), 3);
+        let first_root =
+            serde_json::from_value::<Input>(json!({"a": {"port": "8080"}, "b": {"port": 80}}))
+                .unwrap()
+                .wide_fingerprint();
+        assert!(!state.entry_map.keys().any(|(_, key)| *key == first_root));
+        drop(state);
+
+        assert!(cache
+            .validate(&mut Input::from(HashMap::from([(
+                "d",
+                Input::from("http")
+            )])))
+            .is_err());
+        state = cache.lock();
+        assert_eq!(state.entry_map.len(), 3);
+        #[cfg(feature = "metrics")]
+        assert!(crate::metrics::total().validation_cache_hit_count() >= 2);
+    }
+
+    #[test]
+    fn int_and_float() {
+        let schema_type = InputSchemaType::List(
+            crate::schema::InputSchemaTypeList::default().with_item_schema(
+                InputSchemaType::Integer(
+                    crate::schema::InputSchemaTypeInteger::default().with_deny_floats(true),
+                ),
+            ),
+        );
+        let cache = ValidationCache::new(schema_type, 8);
+        cache
+            .validate(&mut Input::from(vec![Input::from(1)]))
+            .unwrap();
+        assert!(cache
+            .validate(&mut Input::from(vec![Input::from(1.0)]))
+            .is_err());
+
+        let schema_type = InputSchemaType::List(
+            crate::schema::InputSchemaTypeList::default()
+                .with_item_schema(InputSchemaType::new_float()),
+        );
+        let cache = ValidationCache::new(schema_type, 8);
+        let mut input = Input::from(vec![Input::from(1.0)]);
+        cache.validate(&mut input).unwrap();
+        let mut input = Input::from(vec![Input::from(1)]);
+        cache.validate(&mut input).unwrap();
+        assert!(input.as_list()[0].is_float());
+    }
+
+    #[test]
+    fn capacity() {
+        let cache = ValidationCache::new(InputSchemaType::new_list(), 0);
+        cache
+            .validate(&mut Input::from(vec![Input::from(1)]))
+            .unwrap();
+        assert_eq!(cache.entry_count(), 0);
+    }
+}
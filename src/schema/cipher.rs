@@ -0,0 +1,539 @@
+use crate::{
+    base64::{decode_base64, encode_base64},
+    position::InputPosition,
+    schema::{types::recursive::RefScope, InputSchemaType},
+    Input,
+};
+use thiserror::Error;
+
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+pub trait SecretCipher {
+    fn encrypt(&self, plaintext: &[u8]) -> anyhow::Result<Vec<u8>>;
+
+    fn decrypt(&self, ciphertext: &[u8]) -> anyhow::Result<Vec<u8>>;
+}
+
+#[derive(Debug, Error)]
+pub enum SecretCipherError {
+    #[error("{position} could not be encrypted: {source}")]
+    Encrypt {
+        position: InputPosition,
+        source: anyhow::Error,
+    },
+    #[error("{position} could not be decrypted: {source}")]
+    Decrypt {
+        position: InputPosition,
+        source: anyhow::Error,
+    },
+    #[error("{position} is not a valid `{ENCRYPTED_PREFIX}<base64>` value")]
+    Encoding { position: InputPosition },
+    #[error("{position} could not follow a `ref` schema: {description}")]
+    Ref {
+        position: InputPosition,
+        description: String,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+// Scalar secret values are encrypted from their string form (e.g. `8080` as `"8080"`), so they are
+// strings after decryption until they are validated again. Lists and maps are handled per item:
+impl Input {
+    pub fn encrypt_secrets<C: SecretCipher + ?Sized>(
+        &mut self,
+        schema_type: &InputSchemaType,
+        cipher: &C,
+    ) -> Result<(), SecretCipherError> {
+        transform(
+            self,
+            schema_type,
+            cipher,
+            Direction::Encrypt,
+            InputPosition::new(),
+            &mut RefScope::default(),
+        )
+    }
+
+    pub fn decrypt_secrets<C: SecretCipher + ?Sized>(
+        &mut self,
+        schema_type: &InputSchemaType,
+        cipher: &C,
+    ) -> Result<(), SecretCipherError> {
+        transform(
+            self,
+            schema_type,
+            cipher,
+            Direction::Decrypt,
+            InputPosition::new(),
+            &mut RefScope::default(),
+        )
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        matches!(self, Input::Str(string) if string.starts_with(ENCRYPTED_PREFIX))
+    }
+}
+
+fn transform<'a, C: SecretCipher + ?Sized>(
+    input: &mut Input,
+    schema_type: &'a InputSchemaType,
+    cipher: &C,
+    direction: Direction,
+    position: InputPosition,
+    scope: &mut RefScope<'a>,
+) -> Result<(), SecretCipherError> {
+    match schema_type {
+        InputSchemaType::Secret(_) => transform_leaves(input, cipher, direction, position),
+        InputSchemaType::List(list) if input.is_list() => input
+            .list_mut()
+            .iter_mut()
+            .enumerate()
+            .try_for_each(|(index, inner_input)| {
+                transform(
+                    inner_input,
+                    list.item_schema(),
+                    cipher,
+                    direction,
+                    position.new_with_index(index),
+                    scope,
+                )
+            }),
+        InputSchemaType::DynamicMap(dynamic_map) if input.is_map() => input
+            .map_mut()
+            .iter_mut()
+            .try_for_each(|(key, inner_input)| {
+                transform(
+                    inner_input,
+                    dynamic_map.item_schema(),
+                    cipher,
+                    direction,
+                    position.new_with_key(key),
+                    scope,
+                )
+            }),
+        InputSchemaType::StaticMap(static_map) if input.is_map() => input
+            .map_mut()
+            .iter_mut()
+            .try_for_each(|(key, inner_input)| {
+                if let Some(inner_schema) = static_map.item_schema(key) {
+                    transform(
+                        inner_input,
+                        inner_schema.schema_type(),
+                        cipher,
+                        direction,
+                        position.new_with_key(key),
+                        scope,
+                    )
+                } else {
+                    Ok(())
+                }
+            }),
+        InputSchemaType::KeyValue(key_value) if input.is_map() => input
+            .map_mut()
+            .iter_mut()
+            .try_for_each(|(key, inner_input)| {
+                transform(
+                    inner_input,
+                    key_value.value_schema(),
+                    cipher,
+                    direction,
+                    position.new_with_key(key),
+                    scope,
+                )
+            }),
+        // Values of `"key=value"` strings, before they are validated to a map:
+        InputSchemaType::KeyValue(key_value) if input.is_list() => input
+            .list_mut()
+            .iter_mut()
+            .enumerate()
+            .try_for_each(|(index, item)| {
+                let Some((key, value)) = (match item {
+                    Input::Str(string) => string.split_once('='),
+                    _ => None,
+                }) else {
+                    return Ok(());
+                };
+                let key = key.to_string();
+                let mut value = Input::from(value);
+                transform(
+                    &mut value,
+                    key_value.value_schema(),
+                    cipher,
+                    direction,
+                    position.new_with_index(index).new_with_key(&key),
+                    scope,
+                )?;
+                let value = if value.is_str() {
+                    value.into_str()
+                } else {
+                    value.to_string()
+                };
+                *item = Input::from(format!("{key}={value}"));
+                Ok(())
+            }),
+        InputSchemaType::Interval(interval) if input.is_map() => input
+            .map_mut()
+            .iter_mut()
+            .try_for_each(|(key, inner_input)| {
+                transform(
+                    inner_input,
+                    interval.item_schema(),
+                    cipher,
+                    direction,
+                    position.new_with_key(key),
+                    scope,
+                )
+            }),
+        // Bounds of `"MIN..MAX"` strings, before they are validated to a map:
+        InputSchemaType::Interval(interval) if input.is_str() => {
+            let Some((min, max)) = input.as_str().split_once("..") else {
+                return Ok(());
+            };
+            let mut bound_list =
+                [("min", min), ("max", max)].map(|(key, bound)| (key, Input::from(bound)));
+            for (key, bound) in bound_list.iter_mut() {
+                transform(
+                    bound,
+                    interval.item_schema(),
+                    cipher,
+                    direction,
+                    position.new_with_key(key),
+                    scope,
+                )?;
+            }
+            let [min, max] = bound_list.map(|(_, bound)| {
+                if bound.is_str() {
+                    bound.into_str()
+                } else {
+                    bound.to_string()
+                }
+            });
+            *input = Input::from(format!("{min}..{max}"));
+            Ok(())
+        }
+        InputSchemaType::WeightedList(weighted_list) if input.is_list() => input
+            .list_mut()
+            .iter_mut()
+            .enumerate()
+            .try_for_each(|(index, item)| match item {
+                Input::Map(map) => map.get_mut("value").map_or(Ok(()), |value| {
+                    transform(
+                        value,
+                        weighted_list.value_schema(),
+                        cipher,
+                        direction,
+                        position.new_with_index(index).new_with_key("value"),
+                        scope,
+                    )
+                }),
+                _ => Ok(()),
+            }),
+        InputSchemaType::Recursive(recursive) => scope.with_recursive(recursive, |scope| {
+            transform(
+                input,
+                recursive.inner_schema(),
+                cipher,
+                direction,
+                position.clone(),
+                scope,
+            )
+        }),
+        InputSchemaType::Ref(reference) => scope
+            .with_ref(reference.name(), |scope, schema_type| {
+                transform(
+                    input,
+                    schema_type,
+                    cipher,
+                    direction,
+                    position.clone(),
+                    scope,
+                )
+            })
+            .map_err(|description| SecretCipherError::Ref {
+                position: position.clone(),
+                description,
+            })?,
+        InputSchemaType::Either(either) => match direction {
+            Direction::Encrypt => {
+                if let Some(schema_type) = either
+                    .schema_list()
+                    .iter()
+                    .find(|schema_type| scope.is_valid(schema_type, input))
+                {
+                    transform(input, schema_type, cipher, direction, position, scope)
+                } else {
+                    Ok(())
+                }
+            }
+            // Encrypted values do not validate, so every encrypted value is decrypted:
+            Direction::Decrypt => transform_leaves(input, cipher, direction, position),
+        },
+        _ => Ok(()),
+    }
+}
+
+fn transform_leaves<C: SecretCipher + ?Sized>(
+    input: &mut Input,
+    cipher: &C,
+    direction: Direction,
+    position: InputPosition,
+) -> Result<(), SecretCipherError> {
+    match input {
+        Input::List(list) => list
+            .iter_mut()
+            .enumerate()
+            .try_for_each(|(index, inner_input)| {
+                transform_leaves(
+                    inner_input,
+                    cipher,
+                    direction,
+                    position.new_with_index(index),
+                )
+            }),
+        Input::Map(map) => map.iter_mut().try_for_each(|(key, inner_input)| {
+            transform_leaves(inner_input, cipher, direction, position.new_with_key(key))
+        }),
+        Input::Null => Ok(()),
+        _ if direction == Direction::Encrypt => {
+            if input.is_encrypted() {
+                return Ok(());
+            }
+            let plaintext = match input {
+                Input::Str(string) => string.clone(),
+                _ => input.to_string(),
+            };
+            let ciphertext = cipher
+                .encrypt(plaintext.as_bytes())
+                .map_err(|source| SecretCipherError::Encrypt { position, source })?;
+            *input = Input::from(format!("{ENCRYPTED_PREFIX}{}", encode_base64(&ciphertext)));
+            Ok(())
+        }
+        Input::Str(string) => {
+            let encoded = if let Some(encoded) = string.strip_prefix(ENCRYPTED_PREFIX) {
+                encoded
+            } else {
+                return Ok(());
+            };
+            let ciphertext = decode_base64(encoded).ok_or_else(|| SecretCipherError::Encoding {
+                position: position.clone(),
+            })?;
+            let plaintext =
+                cipher
+                    .decrypt(&ciphertext)
+                    .map_err(|source| SecretCipherError::Decrypt {
+                        position: position.clone(),
+                        source,
+                    })?;
+            *input = Input::from(
+                String::from_utf8(plaintext)
+                    .map_err(|_| SecretCipherError::Encoding { position })?,
+            );
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // Not a real cipher, just reversible:
+    struct XorCipher(u8);
+
+    impl SecretCipher for XorCipher {
+        fn encrypt(&self, plaintext: &[u8]) -> anyhow::Result<Vec<u8>> {
+            Ok(plaintext.iter().map(|byte| byte ^ self.0).collect())
+        }
+
+        fn decrypt(&self, ciphertext: &[u8]) -> anyhow::Result<Vec<u8>> {
+            self.encrypt(ciphertext)
+        }
+    }
+
+    #[test]
+    fn encrypt_secrets() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "user": {"schema": {"type": "string"}},
+                "password": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                "pin": {"schema": {"type": "secret", "schema": {"type": "integer"}}},
+            }
+        }))
+        .unwrap();
+        let original: Input =
+            serde_json::from_value(json!({"user": "admin", "password": "hunter2", "pin": 1234}))
+                .unwrap();
+        let cipher = XorCipher(42);
+        let mut input = original.clone();
+        input.encrypt_secrets(&schema_type, &cipher).unwrap();
+        assert_eq!(input.as_map()["user"], Input::from("admin"));
+        assert!(input.as_map()["password"].is_encrypted());
+        assert!(input.as_map()["pin"].is_encrypted());
+        let encrypted = input.clone();
+        input.encrypt_secrets(&schema_type, &cipher).unwrap();
+        assert_eq!(input, encrypted);
+
+        input.decrypt_secrets(&schema_type, &cipher).unwrap();
+        assert_eq!(input.as_map()["pin"], Input::from("1234"));
+        schema_type.validate(&mut input, None).unwrap();
+        assert_eq!(input, original);
+
+        let mut invalid: Input =
+            serde_json::from_value(json!({"password": "enc:v1:!!!!"})).unwrap();
+        assert!(matches!(
+            invalid.decrypt_secrets(&schema_type, &cipher),
+            Err(SecretCipherError::Encoding { .. })
+        ));
+    }
+
+    #[test]
+    fn encrypt_additional_items() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {"user": {"schema": {"type": "string"}}},
+            "additional_items": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+        }))
+        .unwrap();
+        let cipher = XorCipher(42);
+        let original: Input =
+            serde_json::from_value(json!({"user": "admin", "token": "hunter2"})).unwrap();
+        let mut input = original.clone();
+        input.encrypt_secrets(&schema_type, &cipher).unwrap();
+        assert_eq!(input.as_map()["user"], Input::from("admin"));
+        assert!(input.as_map()["token"].is_encrypted());
+        input.decrypt_secrets(&schema_type, &cipher).unwrap();
+        assert_eq!(input, original);
+    }
+
+    #[test]
+    fn encrypt_insensitive_keys() {
+        let cipher = XorCipher(42);
+        for (option, key) in [
+            ("case_insensitive_keys", "PASSWORD"),
+            ("naming_insensitive_keys", "apiToken"),
+        ] {
+            let schema_type: InputSchemaType = serde_json::from_value(json!({
+                "type": "static_map",
+                option: true,
+                "items": {
+                    "password": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                    "api_token": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                }
+            }))
+            .unwrap();
+            let original = Input::from(std::collections::HashMap::from([(key, "hunter2")]));
+            let mut input = original.clone();
+            input.encrypt_secrets(&schema_type, &cipher).unwrap();
+            assert!(input.as_map()[key].is_encrypted());
+            input.decrypt_secrets(&schema_type, &cipher).unwrap();
+            assert_eq!(input, original);
+        }
+    }
+
+    #[test]
+    fn encrypt_key_value() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "key_value",
+            "value_schema": {"type": "secret", "schema": {"type": "string"}},
+        }))
+        .unwrap();
+        let cipher = XorCipher(42);
+        let original: Input = serde_json::from_value(json!({"TOKEN": "hunter2"})).unwrap();
+        let mut input = original.clone();
+        input.encrypt_secrets(&schema_type, &cipher).unwrap();
+        assert!(input.as_map()["TOKEN"].is_encrypted());
+        input.decrypt_secrets(&schema_type, &cipher).unwrap();
+        assert_eq!(input, original);
+
+        let original: Input = serde_json::from_value(json!(["TOKEN=hunter2"])).unwrap();
+        let mut input = original.clone();
+        input.encrypt_secrets(&schema_type, &cipher).unwrap();
+        let item = input.as_list()[0].as_str().clone();
+        assert!(item.starts_with(&format!("TOKEN={ENCRYPTED_PREFIX}")));
+        input.decrypt_secrets(&schema_type, &cipher).unwrap();
+        assert_eq!(input, original);
+    }
+
+    #[test]
+    fn encrypt_interval() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "interval",
+            "item_schema": {"type": "secret", "schema": {"type": "integer"}},
+        }))
+        .unwrap();
+        let cipher = XorCipher(42);
+        let original: Input = serde_json::from_value(json!({"min": 1, "max": 9})).unwrap();
+        let mut input = original.clone();
+        input.encrypt_secrets(&schema_type, &cipher).unwrap();
+        assert!(input.as_map()["min"].is_encrypted());
+        assert!(input.as_map()["max"].is_encrypted());
+        input.decrypt_secrets(&schema_type, &cipher).unwrap();
+        schema_type.validate(&mut input, None).unwrap();
+        assert_eq!(input, original);
+
+        let mut input = Input::from("1..9");
+        input.encrypt_secrets(&schema_type, &cipher).unwrap();
+        let (min, max) = input.as_str().split_once("..").unwrap();
+        assert!(Input::from(min).is_encrypted() && Input::from(max).is_encrypted());
+        input.decrypt_secrets(&schema_type, &cipher).unwrap();
+        assert_eq!(input, Input::from("1..9"));
+    }
+
+    #[test]
+    fn encrypt_weighted_list() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "weighted_list",
+            "value_schema": {"type": "secret", "schema": {"type": "string"}},
+        }))
+        .unwrap();
+        let original: Input =
+            serde_json::from_value(json!([{"value": "hunter2", "weight": 1.0}])).unwrap();
+        let cipher = XorCipher(42);
+        let mut input = original.clone();
+        input.encrypt_secrets(&schema_type, &cipher).unwrap();
+        assert!(input.as_list()[0].as_map()["value"].is_encrypted());
+        assert_eq!(input.as_list()[0].as_map()["weight"], Input::from(1.0));
+        input.decrypt_secrets(&schema_type, &cipher).unwrap();
+        assert_eq!(input, original);
+    }
+
+    #[test]
+    fn encrypt_recursive() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "recursive",
+            "name": "tree",
+            "max_depth": 2,
+            "schema": {
+                "type": "static_map",
+                "items": {
+                    "password": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                    "tree": {"schema": {"type": "ref", "name": "tree"}},
+                }
+            }
+        }))
+        .unwrap();
+        let original: Input =
+            serde_json::from_value(json!({"tree": {"password": "hunter3"}})).unwrap();
+        let cipher = XorCipher(42);
+        let mut input = original.clone();
+        input.encrypt_secrets(&schema_type, &cipher).unwrap();
+        assert!(input.as_map()["tree"].as_map()["password"].is_encrypted());
+        input.decrypt_secrets(&schema_type, &cipher).unwrap();
+        assert_eq!(input, original);
+        let mut input: Input =
+            serde_json::from_value(json!({"tree": {"tree": {"tree": {"password": "hunter4"}}}}))
+                .unwrap();
+        assert!(matches!(
+            input.encrypt_secrets(&schema_type, &cipher),
+            Err(SecretCipherError::Ref { .. })
+        ));
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{schema::InputSchemaType, Input};
+
+impl InputSchemaType {
+    // Only converts values to the types of their schemas (e.g. `"8080"` to `8080` and `"yes"` to
+    // `true`) without checking constraints like ranges, lengths, or required items, e.g. to get
+    // typed values from env or INI loaders before merging. Values that can't be converted and
+    // values of other types are kept as they are:
+    pub fn coerce_only(&self, input: &mut Input) {
+        crate::stack::maybe_grow(|| match (self, input) {
+            (Self::StaticMap(static_map), Input::Map(map)) => {
+                static_map.items().iter().for_each(|(key, schema)| {
+                    if let Some(inner_input) = map.get_mut(key.as_str()) {
+                        schema.schema_type().coerce_only(inner_input)
+                    }
+                })
+            }
+            (Self::DynamicMap(dynamic_map), Input::Map(map)) => map
+                .values_mut()
+                .for_each(|inner_input| dynamic_map.item_schema().coerce_only(inner_input)),
+            (Self::KeyValue(key_value), Input::Map(map)) => map
+                .values_mut()
+                .for_each(|inner_input| key_value.value_schema().coerce_only(inner_input)),
+            (Self::List(list), Input::List(inner_input_list)) => inner_input_list
+                .iter_mut()
+                .for_each(|inner_input| list.item_schema().coerce_only(inner_input)),
+            (Self::Secret(secret), input) => secret.inner_schema().coerce_only(input),
+            (Self::Either(either), input) => {
+                if let Some(coerced_input) = either
+                    .schema_list()
+                    .iter()
+                    .find_map(|schema_type| schema_type.coerced(input))
+                {
+                    *input = coerced_input
+                }
+            }
+            (schema_type, input) => {
+                if let Some(coerced_input) = schema_type.coerced(input) {
+                    *input = coerced_input
+                }
+            }
+        })
+    }
+
+    // The validated value, or the value validated by the unconstrained schema of the same type:
+    fn coerced(&self, input: &Input) -> Option<Input> {
+        let coerce = |schema_type: &InputSchemaType| {
+            let mut coerced_input = input.clone();
+            schema_type
+                .validate(&mut coerced_input, None)
+                .ok()
+                .map(|_| coerced_input)
+        };
+        coerce(self).or_else(|| {
+            match self {
+                Self::Boolean(_) => Some(Self::new_boolean()),
+                Self::Number(_) => Some(Self::new_number()),
+                Self::Integer(_) => Some(Self::new_integer()),
+                Self::Float(_) => Some(Self::new_float()),
+                Self::String(_) => Some(Self::new_string()),
+                Self::Port(_) => Some(Self::new_port()),
+                _ => None,
+            }
+            .and_then(|unconstrained_schema_type| coerce(&unconstrained_schema_type))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn coerce_only() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "integer", "range": {"min": 1024}}},
+                "enabled": {"schema": {"type": "boolean"}},
+                "ratio": {"schema": {"type": "float"}},
+                "name": {"schema": {"type": "string"}},
+                "workers": {"schema": {"type": "integer"}},
+                "user": {"schema": {"type": "string"}},
+            }
+        }))
+        .unwrap();
+        let mut input: Input = serde_json::from_value(json!({
+            "port": "80",
+            "enabled": "yes",
+            "ratio": "0.5",
+            "name": "app",
+            "workers": "many",
+        }))
+        .unwrap();
+        schema_type.coerce_only(&mut input);
+        assert_eq!(
+            input,
+            serde_json::from_value::<Input>(json!({
+                "port": 80,
+                "enabled": true,
+                "ratio": 0.5,
+                "name": "app",
+                "workers": "many",
+            }))
+            .unwrap()
+        );
+    }
+}
@@ -144,6 +144,26 @@ impl From<f64> for InputSchemaTypeNumberValue {
     }
 }
 
+// The text without digit separators (e.g. `"1,000.5"`, `"1_000"`, or `"1 000"` to `"1000.5"`),
+// `None` if there is none or if a separator is not between two digits:
+pub(crate) fn strip_digit_separators(text: &str) -> Option<String> {
+    let text = text.trim();
+    let char_list: Vec<char> = text.chars().collect();
+    let is_separator = |c: char| matches!(c, '_' | ',' | ' ' | '\u{a0}' | '\u{202f}' | '\'');
+    let mut stripped = String::with_capacity(text.len());
+    for (index, c) in char_list.iter().enumerate() {
+        if !is_separator(*c) {
+            stripped.push(*c);
+        } else if index == 0
+            || !char_list[index - 1].is_ascii_digit()
+            || !char_list.get(index + 1).is_some_and(char::is_ascii_digit)
+        {
+            return None;
+        }
+    }
+    Some(stripped).filter(|stripped| stripped.len() < text.len() && stripped.parse::<f64>().is_ok())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,8 +172,8 @@ mod tests {
     #[test]
     fn from() {
         assert_eq!(
-            InputSchemaTypeNumberValue::from(3.14f64),
-            InputSchemaTypeNumberValue::from_float(3.14)
+            InputSchemaTypeNumberValue::from(2.5f64),
+            InputSchemaTypeNumberValue::from_float(2.5)
         );
         assert_eq!(
             InputSchemaTypeNumberValue::from(3i8),
@@ -174,6 +194,26 @@ mod tests {
         assert_eq!(n.trunc(), 3.into());
         assert_eq!(n.integer(), None);
         n.trunc_mut();
-        assert_eq!(n.integer(), Some(3isize.into()));
+        assert_eq!(n.integer(), Some(3isize));
+    }
+
+    #[test]
+    fn strip_digit_separators() {
+        assert_eq!(
+            super::strip_digit_separators("1,000.5"),
+            Some("1000.5".to_string())
+        );
+        assert_eq!(
+            super::strip_digit_separators("-1_000_000"),
+            Some("-1000000".to_string())
+        );
+        assert_eq!(
+            super::strip_digit_separators(" 1 000 "),
+            Some("1000".to_string())
+        );
+        assert_eq!(super::strip_digit_separators("1000"), None);
+        assert_eq!(super::strip_digit_separators("1,,000"), None);
+        assert_eq!(super::strip_digit_separators("1,000,"), None);
+        assert_eq!(super::strip_digit_separators("a,b"), None);
     }
 }
@@ -158,7 +158,7 @@ mod tests {
 
     #[test]
     fn serde() {
-        let json = serde_json::to_string_pretty(&serde_json::json!(3.14)).unwrap();
+        let json = serde_json::to_string_pretty(&serde_json::json!(3.5)).unwrap();
         assert_eq!(
             serde_json::from_str::<InputSchemaTypeRange>(json.as_str())
                 .unwrap()
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter};
+use std::{
+    fmt::{Display, Formatter},
+    sync::OnceLock,
+};
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+// The pattern is compiled at its first use and the compiled regex is kept for later validations:
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct InputSchemaTypeRegex {
     pattern: String,
@@ -11,6 +15,14 @@ pub struct InputSchemaTypeRegex {
         default
     )]
     maybe_description: Option<String>,
+    #[serde(skip)]
+    compiled: OnceLock<regex::Regex>,
+}
+
+impl PartialEq for InputSchemaTypeRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern && self.maybe_description == other.maybe_description
+    }
 }
 
 impl Display for InputSchemaTypeRegex {
@@ -28,7 +40,16 @@ impl InputSchemaTypeRegex {
         Self {
             pattern: pattern.as_ref().to_string(),
             maybe_description: Default::default(),
+            compiled: Default::default(),
+        }
+    }
+
+    pub fn compile(&self) -> Result<&regex::Regex, regex::Error> {
+        if let Some(compiled) = self.compiled.get() {
+            return Ok(compiled);
         }
+        let compiled = regex::Regex::new(self.pattern.as_str())?;
+        Ok(self.compiled.get_or_init(|| compiled))
     }
 
     pub fn pattern(&self) -> &String {
@@ -36,6 +57,7 @@ impl InputSchemaTypeRegex {
     }
 
     pub fn pattern_mut(&mut self) -> &mut String {
+        self.compiled = Default::default();
         &mut self.pattern
     }
 
@@ -48,7 +70,7 @@ impl InputSchemaTypeRegex {
     }
 
     pub fn set_pattern<R: AsRef<str>>(&mut self, pattern: R) {
-        self.pattern = pattern.as_ref().to_string();
+        *self.pattern_mut() = pattern.as_ref().to_string();
     }
 
     pub fn with_pattern<R: AsRef<str>>(mut self, pattern: R) -> Self {
@@ -0,0 +1,196 @@
+use crate::{
+    migration::InputMigration,
+    position::InputPosition,
+    schema::{BatchReport, InputSchemaError, InputSchemaType},
+    Input,
+};
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+};
+
+// The results of `compat_check()`:
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompatReport {
+    batch_report: BatchReport,
+}
+
+// Why an existing input is not valid with a new schema:
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CompatFailureCause {
+    // e.g. a new required item:
+    MissingValue,
+    WrongType,
+    OutOfRange,
+    WrongSize,
+    // e.g. a stricter regex or a removed enum value:
+    InvalidValue,
+    Aborted,
+    // A bug in this crate:
+    Internal,
+}
+
+impl CompatFailureCause {
+    pub fn from_error(error: &InputSchemaError) -> Self {
+        match error.inner() {
+            InputSchemaError::NotFound { .. } => Self::MissingValue,
+            InputSchemaError::Type { .. } | InputSchemaError::Schema { .. } => Self::WrongType,
+            InputSchemaError::Range { .. } => Self::OutOfRange,
+            InputSchemaError::Size { .. } => Self::WrongSize,
+            InputSchemaError::Invalid { .. } | InputSchemaError::Context { .. } => {
+                Self::InvalidValue
+            }
+            InputSchemaError::Aborted { .. } => Self::Aborted,
+            InputSchemaError::Internal { .. } => Self::Internal,
+        }
+    }
+}
+
+// Validates copies of existing inputs (e.g. customer configurations) with a new schema, e.g. in
+// CI jobs that check a schema change doesn't break them:
+pub fn compat_check<'a, I: IntoIterator<Item = &'a Input>>(
+    new_schema_type: &InputSchemaType,
+    sample_input_list: I,
+) -> CompatReport {
+    compat_check_migrated(new_schema_type, &InputMigration::new(), sample_input_list)
+}
+
+// Like `compat_check()` but `migration` is applied to the copies first, e.g. to check that the
+// declared renames of a new schema keep existing inputs valid:
+pub fn compat_check_migrated<'a, I: IntoIterator<Item = &'a Input>>(
+    new_schema_type: &InputSchemaType,
+    migration: &InputMigration,
+    sample_input_list: I,
+) -> CompatReport {
+    let mut input_list: Vec<_> = sample_input_list
+        .into_iter()
+        .map(|input| {
+            let mut input = input.clone();
+            migration.apply(&mut input);
+            input
+        })
+        .collect();
+    CompatReport {
+        batch_report: new_schema_type.validate_batch(input_list.iter_mut()),
+    }
+}
+
+impl CompatReport {
+    pub fn batch_report(&self) -> &BatchReport {
+        &self.batch_report
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        self.batch_report.is_valid()
+    }
+
+    // The indexes of incompatible inputs with the causes and the positions of their errors:
+    pub fn failure_list(&self) -> Vec<(usize, CompatFailureCause, &InputPosition)> {
+        self.batch_report
+            .error_list()
+            .into_iter()
+            .map(|(index, error)| {
+                (
+                    index,
+                    CompatFailureCause::from_error(error),
+                    error.position(),
+                )
+            })
+            .collect()
+    }
+
+    // How many inputs failed for each cause:
+    pub fn cause_count_map(&self) -> BTreeMap<CompatFailureCause, usize> {
+        let mut cause_count_map = BTreeMap::new();
+        self.failure_list()
+            .into_iter()
+            .for_each(|(_, cause, _)| *cause_count_map.entry(cause).or_default() += 1);
+        cause_count_map
+    }
+}
+
+impl Display for CompatFailureCause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::MissingValue => "missing value",
+            Self::WrongType => "wrong type",
+            Self::OutOfRange => "out of range",
+            Self::WrongSize => "wrong size",
+            Self::InvalidValue => "invalid value",
+            Self::Aborted => "aborted",
+            Self::Internal => "internal error",
+        })
+    }
+}
+
+impl Display for CompatReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let failure_list = self.failure_list();
+        write!(
+            f,
+            "{} of {} inputs are not compatible",
+            failure_list.len(),
+            self.batch_report.len()
+        )?;
+        let cause_count_map = self.cause_count_map();
+        if !cause_count_map.is_empty() {
+            let cause_count_list: Vec<_> = cause_count_map
+                .iter()
+                .map(|(cause, count)| format!("{count} {cause}"))
+                .collect();
+            write!(f, " ({})", cause_count_list.join(", "))?;
+        }
+        self.batch_report
+            .error_list()
+            .iter()
+            .try_for_each(|(index, error)| write!(f, "\n  #{index}: {error}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn compat_check() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "integer", "range": {"min": 1024}}},
+                "name": {"schema": {"type": "string"}},
+            }
+        }))
+        .unwrap();
+        let input_list: Vec<Input> = [
+            json!({"port": 8080, "name": "a"}),
+            json!({"port": 80, "name": "b"}),
+            json!({"port": 8080}),
+            json!({"port": 8080, "name": 1}),
+        ]
+        .into_iter()
+        .map(|value| serde_json::from_value(value).unwrap())
+        .collect();
+        let report = super::compat_check(&schema_type, &input_list);
+        assert!(!report.is_compatible());
+        assert_eq!(
+            report
+                .failure_list()
+                .iter()
+                .map(|(index, cause, position)| (*index, *cause, position.to_string()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, CompatFailureCause::OutOfRange, "port".to_string()),
+                (2, CompatFailureCause::MissingValue, "name".to_string()),
+                (3, CompatFailureCause::WrongType, "name".to_string()),
+            ]
+        );
+        assert!(report.to_string().starts_with(
+            "3 of 4 inputs are not compatible (1 missing value, 1 wrong type, 1 out of range)\n"
+        ));
+        let old_input: Input = serde_json::from_value(json!({"port": 8080, "title": "c"})).unwrap();
+        let migration = InputMigration::new().with_rename_key("title", "name");
+        assert!(!super::compat_check(&schema_type, [&old_input]).is_compatible());
+        assert!(compat_check_migrated(&schema_type, &migration, [&old_input]).is_compatible());
+    }
+}
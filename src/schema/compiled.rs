@@ -0,0 +1,95 @@
+use crate::{
+    position::InputPosition,
+    schema::{InputSchemaError, InputSchemaType},
+    Input,
+};
+
+// A schema type whose regular expressions are all compiled (and checked) up front, so invalid
+// patterns fail when the schema is loaded instead of at the first value that reaches them, and
+// validations (e.g. of `validate_json_stream()`) only match them:
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledSchema {
+    schema_type: InputSchemaType,
+}
+
+impl CompiledSchema {
+    pub fn new(schema_type: InputSchemaType) -> Result<Self, InputSchemaError> {
+        compile(&schema_type)?;
+        Ok(Self { schema_type })
+    }
+
+    pub fn schema_type(&self) -> &InputSchemaType {
+        &self.schema_type
+    }
+
+    pub fn into_schema_type(self) -> InputSchemaType {
+        self.schema_type
+    }
+
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        self.schema_type.validate(input, maybe_position)
+    }
+}
+
+impl TryFrom<InputSchemaType> for CompiledSchema {
+    type Error = InputSchemaError;
+
+    fn try_from(schema_type: InputSchemaType) -> Result<Self, Self::Error> {
+        Self::new(schema_type)
+    }
+}
+
+fn compile(schema_type: &InputSchemaType) -> Result<(), InputSchemaError> {
+    crate::stack::maybe_grow(|| {
+        if let InputSchemaType::String(string) = schema_type {
+            if let Some(regex) = string.maybe_regex() {
+                regex
+                    .compile()
+                    .map_err(|error| InputSchemaError::Internal {
+                        position: InputPosition::new(),
+                        description: format!(
+                            "Invalid regular expression `{}`: {error}",
+                            regex.pattern()
+                        ),
+                    })?;
+            }
+        }
+        schema_type.child_list().into_iter().try_for_each(compile)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn new() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "list",
+            "item_schema": {"type": "string", "regex": {"pattern": "^[a-z]+$"}},
+        }))
+        .unwrap();
+        let schema = CompiledSchema::new(schema_type).unwrap();
+        schema
+            .validate(&mut Input::from(vec![Input::from("foo")]), None)
+            .unwrap();
+        assert!(schema
+            .validate(&mut Input::from(vec![Input::from("Foo")]), None)
+            .is_err());
+
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "either",
+            "schema_list": [{"type": "boolean"}, {"type": "string", "regex": {"pattern": "("}}],
+        }))
+        .unwrap();
+        assert!(matches!(
+            CompiledSchema::new(schema_type),
+            Err(InputSchemaError::Internal { .. })
+        ));
+    }
+}
@@ -0,0 +1,547 @@
+use crate::{
+    position::InputPosition,
+    schema::{InputSchema, InputSchemaType, InputSchemaTypeRange, InputSchemaTypeSize},
+    Input,
+};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputSchemaMergePolicy {
+    // Any conflict is reported as an error:
+    #[default]
+    Strict,
+    // Conflicts are resolved in favor of `self`:
+    PreferSelf,
+    // Conflicts are resolved in favor of `other`:
+    PreferOther,
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InputSchemaMergeError {
+    #[error("{position} could not merge {schema_type} with {other_schema_type}")]
+    Type {
+        position: InputPosition,
+        schema_type: InputSchemaType,
+        other_schema_type: InputSchemaType,
+    },
+    #[error("{position} has conflicting default values `{default}` and `{other_default}`")]
+    Default {
+        position: InputPosition,
+        default: Input,
+        other_default: Input,
+    },
+    #[error("{position} has disjoint {description} `{range}` and `{other_range}`")]
+    Range {
+        position: InputPosition,
+        description: String,
+        range: String,
+        other_range: String,
+    },
+}
+
+impl InputSchemaType {
+    pub fn merge(
+        &self,
+        other: &InputSchemaType,
+        policy: InputSchemaMergePolicy,
+    ) -> Result<InputSchemaType, InputSchemaMergeError> {
+        self.merge_with_position(other, policy, InputPosition::new())
+    }
+
+    pub fn merge_with_position(
+        &self,
+        other: &InputSchemaType,
+        policy: InputSchemaMergePolicy,
+        position: InputPosition,
+    ) -> Result<InputSchemaType, InputSchemaMergeError> {
+        if self == other {
+            return Ok(self.clone());
+        }
+        let merged = match (self, other) {
+            (Self::StaticMap(static_map), Self::StaticMap(other_static_map)) => {
+                let mut other_options = other_static_map.clone();
+                *other_options.items_mut() = static_map.items().clone();
+                *other_options.maybe_additional_items_mut() =
+                    static_map.maybe_additional_items().cloned().map(Box::new);
+                *other_options.order_mut() = static_map.order().clone();
+                *other_options.group_list_mut() = static_map.group_list().clone();
+                let mut merged =
+                    resolve_options(static_map, other_static_map, &other_options, policy, || {
+                        type_error(self, other, &position)
+                    })?
+                    .clone();
+                *merged.items_mut() = static_map.items().clone();
+                *merged.order_mut() = static_map.order().clone();
+                *merged.group_list_mut() = static_map.group_list().clone();
+                for (key, other_schema) in other_static_map.items() {
+                    let schema = if let Some(schema) = static_map.items().get(key) {
+                        schema.merge_with_position(
+                            other_schema,
+                            policy,
+                            position.new_with_key(key),
+                        )?
+                    } else {
+                        other_schema.clone()
+                    };
+                    merged.set_item(key, schema);
+                }
+                *merged.maybe_additional_items_mut() = match (
+                    static_map.maybe_additional_items(),
+                    other_static_map.maybe_additional_items(),
+                ) {
+                    (Some(schema), Some(other_schema)) => Some(Box::new(
+                        schema.merge_with_position(other_schema, policy, position.clone())?,
+                    )),
+                    (maybe_schema, maybe_other_schema) => {
+                        maybe_schema.or(maybe_other_schema).cloned().map(Box::new)
+                    }
+                };
+                other_static_map.order().iter().for_each(|key| {
+                    if !merged.order().contains(key) {
+                        merged.order_mut().push(key.clone())
+                    }
+                });
+                // Both sides' constraints apply:
+                other_static_map.group_list().iter().for_each(|group| {
+                    if !merged.group_list().contains(group) {
+                        merged.group_list_mut().push(group.clone())
+                    }
+                });
+                Self::StaticMap(merged)
+            }
+            (Self::DynamicMap(dynamic_map), Self::DynamicMap(other_dynamic_map)) => {
+                let mut other_options = other_dynamic_map.clone();
+                other_options.set_item_schema(dynamic_map.item_schema().clone());
+                *other_options.maybe_size_mut() = dynamic_map.maybe_size().cloned();
+                let mut merged = resolve_options(
+                    dynamic_map,
+                    other_dynamic_map,
+                    &other_options,
+                    policy,
+                    || type_error(self, other, &position),
+                )?
+                .clone();
+                merged.set_item_schema(dynamic_map.item_schema().merge_with_position(
+                    other_dynamic_map.item_schema(),
+                    policy,
+                    position.clone(),
+                )?);
+                *merged.maybe_size_mut() = merge_size(
+                    dynamic_map.maybe_size(),
+                    other_dynamic_map.maybe_size(),
+                    policy,
+                    &position,
+                )?;
+                Self::DynamicMap(merged)
+            }
+            (Self::List(list), Self::List(other_list)) => {
+                let mut other_options = other_list.clone();
+                other_options.set_item_schema(list.item_schema().clone());
+                *other_options.maybe_size_mut() = list.maybe_size().cloned();
+                let mut merged = resolve_options(list, other_list, &other_options, policy, || {
+                    type_error(self, other, &position)
+                })?
+                .clone();
+                merged.set_item_schema(list.item_schema().merge_with_position(
+                    other_list.item_schema(),
+                    policy,
+                    position.clone(),
+                )?);
+                *merged.maybe_size_mut() = merge_size(
+                    list.maybe_size(),
+                    other_list.maybe_size(),
+                    policy,
+                    &position,
+                )?;
+                Self::List(merged)
+            }
+            (Self::Enum(r#enum), Self::Enum(other_enum)) => {
+                let mut other_options = other_enum.clone();
+                *other_options.value_list_mut() = r#enum.value_list().clone();
+                let mut merged =
+                    resolve_options(r#enum, other_enum, &other_options, policy, || {
+                        type_error(self, other, &position)
+                    })?
+                    .clone();
+                *merged.value_list_mut() = r#enum.value_list().clone();
+                other_enum.value_list().iter().for_each(|value| {
+                    if !merged.value_list().contains(value) {
+                        merged.value_list_mut().push(value.clone())
+                    }
+                });
+                Self::Enum(merged)
+            }
+            (Self::Integer(integer), Self::Integer(other_integer)) => {
+                let mut other_options = other_integer.clone();
+                *other_options.maybe_range_mut() = integer.maybe_range().cloned();
+                let mut merged =
+                    resolve_options(integer, other_integer, &other_options, policy, || {
+                        type_error(self, other, &position)
+                    })?
+                    .clone();
+                *merged.maybe_range_mut() = merge_range(
+                    integer.maybe_range(),
+                    other_integer.maybe_range(),
+                    policy,
+                    &position,
+                )?;
+                Self::Integer(merged)
+            }
+            (Self::Float(float), Self::Float(other_float)) => {
+                let mut other_options = other_float.clone();
+                *other_options.maybe_range_mut() = float.maybe_range().cloned();
+                let mut merged =
+                    resolve_options(float, other_float, &other_options, policy, || {
+                        type_error(self, other, &position)
+                    })?
+                    .clone();
+                *merged.maybe_range_mut() = merge_range(
+                    float.maybe_range(),
+                    other_float.maybe_range(),
+                    policy,
+                    &position,
+                )?;
+                Self::Float(merged)
+            }
+            (Self::Number(number), Self::Number(other_number)) => {
+                let mut other_options = other_number.clone();
+                *other_options.maybe_range_mut() = number.maybe_range().cloned();
+                let mut merged =
+                    resolve_options(number, other_number, &other_options, policy, || {
+                        type_error(self, other, &position)
+                    })?
+                    .clone();
+                *merged.maybe_range_mut() = merge_range(
+                    number.maybe_range(),
+                    other_number.maybe_range(),
+                    policy,
+                    &position,
+                )?;
+                Self::Number(merged)
+            }
+            (Self::Secret(secret), Self::Secret(other_secret)) => {
+                Self::Secret(secret.clone().with_inner_schema(
+                    secret.inner_schema().merge_with_position(
+                        other_secret.inner_schema(),
+                        policy,
+                        position,
+                    )?,
+                ))
+            }
+            _ => resolve(self, other, policy, || type_error(self, other, &position))?.clone(),
+        };
+        Ok(merged)
+    }
+}
+
+impl InputSchema {
+    pub fn merge(
+        &self,
+        other: &InputSchema,
+        policy: InputSchemaMergePolicy,
+    ) -> Result<InputSchema, InputSchemaMergeError> {
+        self.merge_with_position(other, policy, InputPosition::new())
+    }
+
+    pub fn merge_with_position(
+        &self,
+        other: &InputSchema,
+        policy: InputSchemaMergePolicy,
+        position: InputPosition,
+    ) -> Result<InputSchema, InputSchemaMergeError> {
+        let schema_type = self.schema_type().merge_with_position(
+            other.schema_type(),
+            policy,
+            position.clone(),
+        )?;
+        let maybe_default = match (self.maybe_default(), other.maybe_default()) {
+            (Some(default), Some(other_default)) if default != other_default => Some(
+                resolve(default, other_default, policy, || {
+                    InputSchemaMergeError::Default {
+                        position,
+                        default: default.clone(),
+                        other_default: other_default.clone(),
+                    }
+                })?
+                .clone(),
+            ),
+            (maybe_default, maybe_other_default) => maybe_default.or(maybe_other_default).cloned(),
+        };
+        Ok(InputSchema {
+            schema_type: Box::new(schema_type),
+            maybe_default,
+            maybe_title: self.maybe_title.clone().or(other.maybe_title.clone()),
+            maybe_description: self
+                .maybe_description
+                .clone()
+                .or(other.maybe_description.clone()),
+            immutable: self.immutable || other.immutable,
+            volatile: self.volatile || other.volatile,
+            maybe_reload: self.maybe_reload.max(other.maybe_reload),
+            maybe_visibility: self.maybe_visibility.max(other.maybe_visibility),
+        })
+    }
+}
+
+fn type_error(
+    schema_type: &InputSchemaType,
+    other_schema_type: &InputSchemaType,
+    position: &InputPosition,
+) -> InputSchemaMergeError {
+    InputSchemaMergeError::Type {
+        position: position.clone(),
+        schema_type: schema_type.clone(),
+        other_schema_type: other_schema_type.clone(),
+    }
+}
+
+// The options (e.g. `digit_separators` of integers) of `value` if they are the same in
+// `other_options`, which is `other_value` with the merged fields (e.g. the range) of `value`.
+// Otherwise the options conflict:
+fn resolve_options<'a, T, F>(
+    value: &'a T,
+    other_value: &'a T,
+    other_options: &T,
+    policy: InputSchemaMergePolicy,
+    error: F,
+) -> Result<&'a T, InputSchemaMergeError>
+where
+    T: PartialEq,
+    F: FnOnce() -> InputSchemaMergeError,
+{
+    if value == other_options {
+        Ok(value)
+    } else {
+        resolve(value, other_value, policy, error)
+    }
+}
+
+fn resolve<'a, T, F>(
+    value: &'a T,
+    other_value: &'a T,
+    policy: InputSchemaMergePolicy,
+    error: F,
+) -> Result<&'a T, InputSchemaMergeError>
+where
+    F: FnOnce() -> InputSchemaMergeError,
+{
+    match policy {
+        InputSchemaMergePolicy::Strict => Err(error()),
+        InputSchemaMergePolicy::PreferSelf => Ok(value),
+        InputSchemaMergePolicy::PreferOther => Ok(other_value),
+    }
+}
+
+// Returns `None` if the intersection of two `min`/`max` pairs is empty:
+fn intersect<T: PartialOrd + Copy>(
+    (maybe_min, maybe_max): (Option<T>, Option<T>),
+    (maybe_other_min, maybe_other_max): (Option<T>, Option<T>),
+) -> Option<(Option<T>, Option<T>)> {
+    let pick =
+        |value: Option<T>, other_value: Option<T>, prefer_greater: bool| match (value, other_value)
+        {
+            (Some(value), Some(other_value)) => Some(if (value > other_value) == prefer_greater {
+                value
+            } else {
+                other_value
+            }),
+            (maybe_value, maybe_other_value) => maybe_value.or(maybe_other_value),
+        };
+    let maybe_min = pick(maybe_min, maybe_other_min, true);
+    let maybe_max = pick(maybe_max, maybe_other_max, false);
+    match (maybe_min, maybe_max) {
+        (Some(min), Some(max)) if min > max => None,
+        _ => Some((maybe_min, maybe_max)),
+    }
+}
+
+fn merge_range(
+    maybe_range: Option<&InputSchemaTypeRange>,
+    maybe_other_range: Option<&InputSchemaTypeRange>,
+    policy: InputSchemaMergePolicy,
+    position: &InputPosition,
+) -> Result<Option<InputSchemaTypeRange>, InputSchemaMergeError> {
+    let (range, other_range) = match (maybe_range, maybe_other_range) {
+        (Some(range), Some(other_range)) => (range, other_range),
+        (maybe_range, maybe_other_range) => return Ok(maybe_range.or(maybe_other_range).cloned()),
+    };
+    if let Some((maybe_min, maybe_max)) = intersect(
+        (range.maybe_min(), range.maybe_max()),
+        (other_range.maybe_min(), other_range.maybe_max()),
+    ) {
+        Ok(Some(InputSchemaTypeRange::MinMax {
+            maybe_max,
+            maybe_min,
+        }))
+    } else {
+        resolve(range, other_range, policy, || {
+            InputSchemaMergeError::Range {
+                position: position.clone(),
+                description: "ranges".into(),
+                range: range.to_string(),
+                other_range: other_range.to_string(),
+            }
+        })
+        .map(|range| Some(*range))
+    }
+}
+
+fn merge_size(
+    maybe_size: Option<&InputSchemaTypeSize>,
+    maybe_other_size: Option<&InputSchemaTypeSize>,
+    policy: InputSchemaMergePolicy,
+    position: &InputPosition,
+) -> Result<Option<InputSchemaTypeSize>, InputSchemaMergeError> {
+    let (size, other_size) = match (maybe_size, maybe_other_size) {
+        (Some(size), Some(other_size)) => (size, other_size),
+        (maybe_size, maybe_other_size) => return Ok(maybe_size.or(maybe_other_size).cloned()),
+    };
+    if let Some((maybe_min, maybe_max)) = intersect(
+        (size.maybe_min(), size.maybe_max()),
+        (other_size.maybe_min(), other_size.maybe_max()),
+    ) {
+        Ok(Some(InputSchemaTypeSize::MinMax {
+            maybe_max,
+            maybe_min,
+        }))
+    } else {
+        resolve(size, other_size, policy, || InputSchemaMergeError::Range {
+            position: position.clone(),
+            description: "sizes".into(),
+            range: size.to_string(),
+            other_range: other_size.to_string(),
+        })
+        .map(|size| Some(*size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema_type(json: serde_json::Value) -> InputSchemaType {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn merge() {
+        let base = schema_type(json!({
+            "type": "static_map",
+            "items": {
+                "level": {"schema": {"type": "enum", "items": ["info", "debug"]}},
+                "workers": {"schema": {"type": "integer", "range": {"min": 1, "max": 64}}},
+            }
+        }));
+        let fragment = schema_type(json!({
+            "type": "static_map",
+            "items": {
+                "level": {"schema": {"type": "enum", "items": ["debug", "trace"]}, "default": "info"},
+                "workers": {"schema": {"type": "integer", "range": 16}},
+                "name": {"schema": {"type": "string"}},
+            }
+        }));
+        let merged = base
+            .merge(&fragment, InputSchemaMergePolicy::Strict)
+            .unwrap();
+        let items = merged.as_static_map().items();
+        assert_eq!(items.len(), 3);
+        assert_eq!(
+            items["level"].schema_type().as_enum().value_list(),
+            &vec![Input::from("info"), "debug".into(), "trace".into()]
+        );
+        assert_eq!(items["level"].maybe_default(), Some(&Input::from("info")));
+        assert_eq!(
+            items["workers"].schema_type().as_integer().maybe_range(),
+            Some(&InputSchemaTypeRange::from((1, 16)))
+        );
+        assert!(items["name"].schema_type().is_string());
+    }
+
+    #[test]
+    fn conflict() {
+        let integer = schema_type(json!({"type": "integer", "range": {"min": 10}}));
+        let other_integer = schema_type(json!({"type": "integer", "range": 5}));
+        let error = integer
+            .merge(&other_integer, InputSchemaMergePolicy::Strict)
+            .unwrap_err();
+        assert!(matches!(error, InputSchemaMergeError::Range { .. }));
+        assert_eq!(
+            integer
+                .merge(&other_integer, InputSchemaMergePolicy::PreferOther)
+                .unwrap(),
+            other_integer
+        );
+
+        let string = schema_type(json!({"type": "string"}));
+        let error = schema_type(
+            json!({"type": "static_map", "items": {"foo": {"schema": {"type": "boolean"}}}}),
+        )
+        .merge(
+            &schema_type(json!({"type": "static_map", "items": {"foo": {"schema": string}}})),
+            InputSchemaMergePolicy::Strict,
+        )
+        .unwrap_err();
+        assert!(matches!(error, InputSchemaMergeError::Type { .. }));
+        assert!(error.to_string().starts_with("foo "));
+    }
+
+    #[test]
+    fn merge_options() {
+        let integer = schema_type(json!({
+            "type": "integer",
+            "range": {"min": 1},
+            "digit_separators": true,
+            "deny_floats": true,
+        }));
+        let merged = integer
+            .merge(
+                &schema_type(json!({
+                    "type": "integer",
+                    "range": {"max": 9},
+                    "digit_separators": true,
+                    "deny_floats": true,
+                })),
+                InputSchemaMergePolicy::Strict,
+            )
+            .unwrap();
+        assert_eq!(
+            merged,
+            schema_type(json!({
+                "type": "integer",
+                "range": {"min": 1, "max": 9},
+                "digit_separators": true,
+                "deny_floats": true,
+            }))
+        );
+        let other_integer = schema_type(json!({"type": "integer", "range": {"max": 9}}));
+        assert!(matches!(
+            integer.merge(&other_integer, InputSchemaMergePolicy::Strict),
+            Err(InputSchemaMergeError::Type { .. })
+        ));
+        assert_eq!(
+            integer
+                .merge(&other_integer, InputSchemaMergePolicy::PreferOther)
+                .unwrap(),
+            schema_type(json!({"type": "integer", "range": {"min": 1, "max": 9}}))
+        );
+
+        let static_map = schema_type(json!({
+            "type": "static_map",
+            "case_insensitive_keys": true,
+            "items": {"a": {"schema": {"type": "string"}}},
+        }));
+        let merged = static_map
+            .merge(
+                &schema_type(json!({
+                    "type": "static_map",
+                    "case_insensitive_keys": true,
+                    "items": {"b": {"schema": {"type": "string"}}},
+                })),
+                InputSchemaMergePolicy::Strict,
+            )
+            .unwrap();
+        assert!(merged.as_static_map().case_insensitive_keys());
+        assert_eq!(merged.as_static_map().items().len(), 2);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{
+    position::InputPosition,
+    schema::{
+        InputSchemaError, InputSchemaType, InputSchemaTypeRange, InputSchemaTypeStaticMapGroup,
+    },
+    Input,
+};
+
+// Where feasible, the position of `error` with a small value that makes that error go away (e.g.
+// for CLI hints like "set `port` to 1024" or for tests). It's the suggestion of the error (see
+// `InputSchemaError::suggestion()`) or a minimal valid value of the schema at the position (e.g.
+// the first enum value, the minimum of a range, or a map of only required items). The value is
+// checked with that schema, but other errors of the input may still remain:
+pub fn counterexample(
+    schema_type: &InputSchemaType,
+    error: &InputSchemaError,
+) -> Option<(InputPosition, Input)> {
+    let error = error.inner();
+    let position = error.position().clone();
+    let schema_type = match error {
+        InputSchemaError::NotFound { schema_type, .. } => schema_type.clone(),
+        _ => schema_type.subschema(&position)?,
+    };
+    error
+        .suggestion()
+        .filter(|suggestion| is_valid(&schema_type, suggestion))
+        .or_else(|| minimal_input(&schema_type))
+        .map(|input| (position, input))
+}
+
+fn is_valid(schema_type: &InputSchemaType, input: &Input) -> bool {
+    schema_type.validate(&mut input.clone(), None).is_ok()
+}
+
+// The first valid candidate:
+fn minimal_input(schema_type: &InputSchemaType) -> Option<Input> {
+    crate::stack::maybe_grow(|| {
+        let candidate_list = match schema_type {
+            InputSchemaType::Enum(r#enum) => r#enum.value_list().clone(),
+            InputSchemaType::Either(either) => {
+                return either.schema_list().iter().find_map(minimal_input)
+            }
+            InputSchemaType::Secret(secret) => return minimal_input(secret.inner_schema()),
+            InputSchemaType::Integer(integer) => range_candidate_list(integer.maybe_range()),
+            InputSchemaType::Float(float) => range_candidate_list(float.maybe_range()),
+            InputSchemaType::Number(number) => range_candidate_list(number.maybe_range()),
+            InputSchemaType::String(string) => {
+                let min = string.maybe_size().and_then(|size| size.maybe_min());
+                vec![Input::from("a".repeat(min.unwrap_or_default()))]
+            }
+            InputSchemaType::List(list) => {
+                let min = list.maybe_size().and_then(|size| size.maybe_min());
+                match min.unwrap_or_default() {
+                    0 => vec![Input::new_list()],
+                    min => {
+                        let item = minimal_input(list.item_schema())?;
+                        vec![Input::from(vec![item; min])]
+                    }
+                }
+            }
+            InputSchemaType::StaticMap(static_map) => {
+                let mut map = std::collections::HashMap::new();
+                for (key, schema) in static_map.items() {
+                    let is_in_group = static_map
+                        .group_list()
+                        .iter()
+                        .any(|group| group.key_list().contains(key));
+                    if schema.maybe_default().is_none() && !is_in_group {
+                        map.insert(key.clone(), minimal_input(schema.schema_type())?);
+                    }
+                }
+                for group in static_map.group_list() {
+                    if let InputSchemaTypeStaticMapGroup::ExactlyOneOf(key_list) = group {
+                        let key = key_list.first()?;
+                        let schema = static_map.items().get(key)?;
+                        let value = schema
+                            .maybe_default()
+                            .cloned()
+                            .or_else(|| minimal_input(schema.schema_type()))?;
+                        map.insert(key.clone(), value);
+                    }
+                }
+                vec![Input::from(map)]
+            }
+            _ => Vec::new(),
+        };
+        candidate_list
+            .into_iter()
+            .chain([
+                Input::Null,
+                Input::from(false),
+                Input::from(0),
+                Input::from(1),
+                Input::new_str(),
+                Input::new_list(),
+                Input::new_map(),
+            ])
+            .find(|candidate| is_valid(schema_type, candidate))
+    })
+}
+
+fn range_candidate_list(maybe_range: Option<&InputSchemaTypeRange>) -> Vec<Input> {
+    let Some(range) = maybe_range else {
+        return Vec::new();
+    };
+    [range.maybe_min(), range.maybe_max()]
+        .into_iter()
+        .flatten()
+        .flat_map(|number| {
+            [
+                Input::from(number.float().ceil() as isize),
+                Input::from(number.float().floor() as isize),
+                Input::from(number.float()),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn counterexample() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "integer", "range": {"min": 1024}}},
+                "level": {"schema": {"type": "enum", "items": ["low", "high"]}},
+                "tags": {"schema": {"type": "list", "size": {"min": 2}, "item_schema": {
+                    "type": "string", "size": {"min": 3}
+                }}},
+            }
+        }))
+        .unwrap();
+        let counterexample = |input: serde_json::Value| {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            let error = schema_type.validate(&mut input, None).unwrap_err();
+            super::counterexample(&schema_type, &error)
+                .map(|(position, value)| (position.to_string(), value))
+        };
+        assert_eq!(
+            counterexample(json!({"port": 80, "level": "low", "tags": ["abc", "def"]})),
+            Some(("port".to_string(), Input::from(1024)))
+        );
+        assert_eq!(
+            counterexample(json!({"port": 8080, "level": "low"})),
+            Some((
+                "tags".to_string(),
+                Input::from([Input::from("aaa"), Input::from("aaa")])
+            ))
+        );
+        assert_eq!(
+            counterexample(json!({"port": 8080, "level": "x", "tags": ["abc", "def"]}))
+                .map(|(position, _)| position),
+            Some("level".to_string())
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{schema::InputSchemaType, Input};
+
+impl InputSchemaType {
+    // Inserts the defaults of missing static map items (recursively, also inside lists and
+    // dynamic maps) without validating anything, e.g. to show a complete document for editing.
+    // Values of other types are kept as they are and `either` branches are not tried:
+    pub fn apply_defaults(&self, input: &mut Input) {
+        crate::stack::maybe_grow(|| match (self, input) {
+            (Self::StaticMap(static_map), Input::Map(map)) => {
+                static_map.items().iter().for_each(|(key, schema)| {
+                    if !map.contains_key(key.as_str()) {
+                        if let Some(default) = schema.maybe_default() {
+                            map.insert(crate::intern_key(key), default.clone());
+                        }
+                    }
+                    if let Some(inner_input) = map.get_mut(key.as_str()) {
+                        schema.schema_type().apply_defaults(inner_input)
+                    }
+                })
+            }
+            (Self::DynamicMap(dynamic_map), Input::Map(map)) => map
+                .values_mut()
+                .for_each(|inner_input| dynamic_map.item_schema().apply_defaults(inner_input)),
+            (Self::KeyValue(key_value), Input::Map(map)) => map
+                .values_mut()
+                .for_each(|inner_input| key_value.value_schema().apply_defaults(inner_input)),
+            (Self::List(list), Input::List(inner_input_list)) => inner_input_list
+                .iter_mut()
+                .for_each(|inner_input| list.item_schema().apply_defaults(inner_input)),
+            (Self::Secret(secret), input) => secret.inner_schema().apply_defaults(input),
+            _ => (),
+        })
+    }
+
+    // The inverse of `apply_defaults()`: removes static map items that are equal to their
+    // defaults (also after removing their own inner defaults), e.g. to store minimal overrides:
+    pub fn strip_defaults(&self, input: &mut Input) {
+        crate::stack::maybe_grow(|| match (self, input) {
+            (Self::StaticMap(static_map), Input::Map(map)) => {
+                static_map.items().iter().for_each(|(key, schema)| {
+                    let Some(inner_input) = map.get_mut(key.as_str()) else {
+                        return;
+                    };
+                    if schema.maybe_default() == Some(inner_input) {
+                        map.remove(key.as_str());
+                        return;
+                    }
+                    schema.schema_type().strip_defaults(inner_input);
+                    if let Some(default) = schema.maybe_default() {
+                        let mut stripped_default = default.clone();
+                        schema.schema_type().strip_defaults(&mut stripped_default);
+                        if &stripped_default == inner_input {
+                            map.remove(key.as_str());
+                        }
+                    }
+                })
+            }
+            (Self::DynamicMap(dynamic_map), Input::Map(map)) => map
+                .values_mut()
+                .for_each(|inner_input| dynamic_map.item_schema().strip_defaults(inner_input)),
+            (Self::KeyValue(key_value), Input::Map(map)) => map
+                .values_mut()
+                .for_each(|inner_input| key_value.value_schema().strip_defaults(inner_input)),
+            (Self::List(list), Input::List(inner_input_list)) => inner_input_list
+                .iter_mut()
+                .for_each(|inner_input| list.item_schema().strip_defaults(inner_input)),
+            (Self::Secret(secret), input) => secret.inner_schema().strip_defaults(input),
+            _ => (),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema_type() -> InputSchemaType {
+        serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "server": {"schema": {"type": "static_map", "items": {
+                    "port": {"schema": {"type": "port"}, "default": 80},
+                    "host": {"schema": {"type": "string"}},
+                }}, "default": {}},
+                "plugins": {"schema": {"type": "list", "item_schema": {"type": "static_map", "items": {
+                    "enabled": {"schema": {"type": "boolean"}, "default": true},
+                }}}},
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn apply_defaults() {
+        let mut input: Input =
+            serde_json::from_value(json!({"plugins": [{"name": "a"}, {"enabled": "no"}]})).unwrap();
+        schema_type().apply_defaults(&mut input);
+        let expected: Input = serde_json::from_value(json!({
+            "server": {"port": 80},
+            "plugins": [{"name": "a", "enabled": true}, {"enabled": "no"}],
+        }))
+        .unwrap();
+        assert_eq!(input, expected);
+    }
+
+    #[test]
+    fn strip_defaults() {
+        let mut input: Input = serde_json::from_value(json!({
+            "server": {"port": 80, "host": "localhost"},
+            "plugins": [{"enabled": true}, {"enabled": false}],
+        }))
+        .unwrap();
+        let original_input = input.clone();
+        schema_type().strip_defaults(&mut input);
+        let expected: Input = serde_json::from_value(json!({
+            "server": {"host": "localhost"},
+            "plugins": [{}, {"enabled": false}],
+        }))
+        .unwrap();
+        assert_eq!(input, expected);
+        schema_type().apply_defaults(&mut input);
+        assert_eq!(input, original_input);
+
+        let mut input: Input = serde_json::from_value(json!({"server": {"port": 80}})).unwrap();
+        schema_type().strip_defaults(&mut input);
+        assert_eq!(input, Input::new_map());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::schema::InputSchemaTypeStaticMap;
+
+// Defines a struct like it is written and adds `field_descriptions()` to it with the `///` doc
+// comments of its fields, so the descriptions of schema items (and their Markdown and JSON Schema
+// exports) come from the code:
+//
+//     plugx_input::described_struct! {
+//         #[derive(Deserialize)]
+//         pub struct Config {
+//             /// Where to listen.
+//             pub port: u16,
+//         }
+//     }
+//
+//     let static_map = static_map.with_item_descriptions(Config::field_descriptions());
+//
+// Keys are the field names as they are written (`serde` renames are not followed) and lines of a
+// doc comment are joined with spaces. Structs with generics are not supported:
+#[macro_export]
+macro_rules! described_struct {
+    (
+        $(#[$($attribute:tt)*])*
+        $visibility:vis struct $name:ident {
+            $(
+                $(#[$($field_attribute:tt)*])*
+                $field_visibility:vis $field:ident: $field_type:ty
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$($attribute)*])*
+        $visibility struct $name {
+            $(
+                $(#[$($field_attribute)*])*
+                $field_visibility $field: $field_type,
+            )*
+        }
+
+        impl $name {
+            pub fn field_descriptions() -> Vec<(&'static str, String)> {
+                vec![$({
+                    let line_list: Vec<Option<&'static str>> =
+                        vec![$($crate::__doc_line!($($field_attribute)*)),*];
+                    let description = line_list
+                        .into_iter()
+                        .flatten()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    (stringify!($field), description)
+                }),*]
+            }
+        }
+    };
+}
+
+// The text of a `#[doc = "..."]` attribute (which is what `///` comments are) for
+// `described_struct!()`:
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __doc_line {
+    (doc = $line:literal) => {
+        Some($line)
+    };
+    ($($attribute:tt)*) => {
+        None
+    };
+}
+
+impl InputSchemaTypeStaticMap {
+    // Sets the descriptions of items that don't have one (e.g. from `field_descriptions()` of a
+    // `described_struct!()`). Empty descriptions and keys that are not items are skipped:
+    pub fn set_item_descriptions<K: AsRef<str>, D: ToString, I: IntoIterator<Item = (K, D)>>(
+        &mut self,
+        description_list: I,
+    ) {
+        description_list.into_iter().for_each(|(key, description)| {
+            let description = description.to_string();
+            match self.items_mut().get_mut(key.as_ref()) {
+                Some(schema) if schema.maybe_description().is_none() && !description.is_empty() => {
+                    schema.set_description(description)
+                }
+                _ => (),
+            }
+        })
+    }
+
+    pub fn with_item_descriptions<K: AsRef<str>, D: ToString, I: IntoIterator<Item = (K, D)>>(
+        mut self,
+        description_list: I,
+    ) -> Self {
+        self.set_item_descriptions(description_list);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::InputSchemaType;
+    use serde_json::json;
+
+    crate::described_struct! {
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Config {
+            /// Where to listen.
+            #[allow(unused_qualifications)]
+            pub port: u16,
+            /// Names of the
+            /// allowed users.
+            ///
+            user_list: Vec<String>,
+            workers: usize,
+        }
+    }
+
+    #[test]
+    fn described_struct() {
+        assert_eq!(
+            Config::field_descriptions(),
+            vec![
+                ("port", "Where to listen.".to_string()),
+                ("user_list", "Names of the allowed users.".to_string()),
+                ("workers", String::new()),
+            ]
+        );
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "port"}},
+                "user_list": {"schema": {"type": "string"}, "description": "Users"},
+                "workers": {"schema": {"type": "integer"}},
+            }
+        }))
+        .unwrap();
+        let static_map = schema_type
+            .as_static_map()
+            .clone()
+            .with_item_descriptions(Config::field_descriptions());
+        let description = |key: &str| static_map.items()[key].maybe_description().cloned();
+        assert_eq!(description("port"), Some("Where to listen.".to_string()));
+        assert_eq!(description("user_list"), Some("Users".to_string()));
+        assert_eq!(description("workers"), None);
+    }
+}
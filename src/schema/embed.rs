@@ -0,0 +1,164 @@
+use crate::{
+    position::InputPosition,
+    schema::{
+        InputSchema, InputSchemaError, InputSchemaParseError, InputSchemaRefError, InputSchemaType,
+    },
+};
+use std::{io, path::Path, path::PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputSchemaCheckError {
+    #[error("Could not read schema file {path:?}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error(transparent)]
+    Parse(#[from] InputSchemaParseError),
+    #[error(transparent)]
+    Ref(#[from] InputSchemaRefError),
+    #[error("Invalid default: {0}")]
+    Default(#[source] InputSchemaError),
+}
+
+// Embeds a JSON schema file (relative to the current file like `include_str!()`) as an
+// `InputSchema`. The macro alone does not check the schema at build time: only a missing file fails
+// the build, and a malformed or invalid one panics at the first use. Check the file with
+// `check_schema_file()` in `build.rs` so that a bad schema fails the build:
+#[macro_export]
+macro_rules! include_schema {
+    ($path:literal) => {
+        $crate::schema::InputSchema::from_json_str_checked(include_str!($path))
+            .unwrap_or_else(|error| panic!("Invalid schema file `{}`: {error}", $path))
+    };
+}
+
+// The build-time check of schema files, for `build.rs` (with `plugx-input` and its `json` feature
+// in `[build-dependencies]`):
+//
+//     fn main() {
+//         plugx_input::schema::check_schema_file("src/schema.json").unwrap();
+//     }
+//
+// The path is relative to the package root (where `build.rs` runs). Also asks Cargo to run the
+// build script again when the file changes:
+pub fn check_schema_file<P: AsRef<Path>>(path: P) -> Result<InputSchema, InputSchemaCheckError> {
+    let path = path.as_ref();
+    println!("cargo:rerun-if-changed={}", path.display());
+    let text = std::fs::read_to_string(path).map_err(|source| InputSchemaCheckError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    InputSchema::from_json_str_checked(&text)
+}
+
+impl InputSchema {
+    pub fn from_json_str_checked(text: &str) -> Result<Self, InputSchemaCheckError> {
+        let schema = Self::from_json_str(text)?;
+        schema.check()?;
+        Ok(schema)
+    }
+
+    // Every `ref` should resolve (see `InputSchemaType::check_refs()`) and defaults of the schema
+    // and its static map items should be valid. Defaults inside recursive schemas are not checked:
+    pub fn check(&self) -> Result<(), InputSchemaCheckError> {
+        self.schema_type().check_refs()?;
+        if let Some(default) = self.maybe_default() {
+            self.schema_type()
+                .validate(&mut default.clone(), None)
+                .map_err(InputSchemaCheckError::Default)?;
+        }
+        check_defaults(self.schema_type(), InputPosition::new())
+            .map_err(InputSchemaCheckError::Default)
+    }
+}
+
+fn check_defaults(
+    schema_type: &InputSchemaType,
+    position: InputPosition,
+) -> Result<(), InputSchemaError> {
+    crate::stack::maybe_grow(|| match schema_type {
+        InputSchemaType::StaticMap(static_map) => static_map
+            .ordered_items()
+            .into_iter()
+            .try_for_each(|(key, schema)| {
+                let item_position = position.new_with_key(key);
+                if let Some(default) = schema.maybe_default() {
+                    schema
+                        .schema_type()
+                        .validate(&mut default.clone(), Some(item_position.clone()))?;
+                }
+                check_defaults(schema.schema_type(), item_position)
+            }),
+        InputSchemaType::List(list) => {
+            check_defaults(list.item_schema(), position.new_with_index(0))
+        }
+        InputSchemaType::DynamicMap(dynamic_map) => {
+            check_defaults(dynamic_map.item_schema(), position.new_with_key("*"))
+        }
+        InputSchemaType::KeyValue(key_value) => {
+            check_defaults(key_value.value_schema(), position.new_with_key("*"))
+        }
+        InputSchemaType::Secret(secret) => check_defaults(secret.inner_schema(), position),
+        InputSchemaType::Either(either) => either
+            .schema_list()
+            .iter()
+            .try_for_each(|schema_type| check_defaults(schema_type, position.clone())),
+        _ => Ok(()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check() {
+        let schema = InputSchema::from_json_str_checked(
+            r#"{"schema": {"type": "static_map", "items": {"port": {"schema": {"type": "port"}, "default": 80}}}}"#,
+        )
+        .unwrap();
+        assert!(schema.schema_type().is_static_map());
+        for (text, position) in [
+            (
+                r#"{"schema": {"type": "static_map", "items": {"port": {"schema": {"type": "port"}, "default": "http"}}}}"#,
+                "port",
+            ),
+            (r#"{"schema": {"type": "port"}, "default": -1}"#, ""),
+        ] {
+            match InputSchema::from_json_str_checked(text) {
+                Err(InputSchemaCheckError::Default(error)) => {
+                    assert_eq!(error.position().to_string(), position)
+                }
+                result => panic!("{result:?}"),
+            }
+        }
+        assert!(matches!(
+            InputSchema::from_json_str_checked(r#"{"schema": {"type": "ref", "name": "node"}}"#),
+            Err(InputSchemaCheckError::Ref(_))
+        ));
+    }
+
+    #[test]
+    fn check_schema_file() {
+        let directory = std::env::temp_dir().join(format!("plugx-input-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("schema.json");
+        std::fs::write(&path, r#"{"schema": {"type": "port"}, "default": 80}"#).unwrap();
+        let schema = super::check_schema_file(&path).unwrap();
+        assert!(schema.schema_type().is_port());
+        std::fs::write(&path, r#"{"schema": {"type": "port"}, "default": -1}"#).unwrap();
+        assert!(matches!(
+            super::check_schema_file(&path),
+            Err(InputSchemaCheckError::Default(_))
+        ));
+        std::fs::write(&path, r#"{"schema": {"type": "port""#).unwrap();
+        assert!(matches!(
+            super::check_schema_file(&path),
+            Err(InputSchemaCheckError::Parse(_))
+        ));
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert!(matches!(
+            super::check_schema_file(&path),
+            Err(InputSchemaCheckError::Io { .. })
+        ));
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{schema::InputSchemaType, Input};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+impl InputSchemaType {
+    // 16 hex digits of the 64-bit FNV-1a hash of the canonical JSON (see
+    // `Input::to_canonical_json()`) of the schema, e.g. for hosts that store it next to validated
+    // configurations and only re-validate or migrate them once a plugin ships a changed schema.
+    // Unlike `Input::fingerprint()`, it's the same for every build of this crate:
+    pub fn schema_hash(&self) -> String {
+        // Schemas only have string keys and serializable values:
+        let input = serde_json::to_value(self)
+            .ok()
+            .and_then(|value| serde_json::from_value::<Input>(value).ok())
+            .unwrap_or_default();
+        let hash = input
+            .to_canonical_json()
+            .bytes()
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+            });
+        format!("{hash:016x}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn schema_hash() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "port"}},
+                "host": {"schema": {"type": "string"}},
+            }
+        }))
+        .unwrap();
+        let same_schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "host": {"schema": {"type": "string"}},
+                "port": {"schema": {"type": "port"}},
+            }
+        }))
+        .unwrap();
+        let hash = schema_type.schema_hash();
+        assert_eq!(hash.len(), 16);
+        assert_eq!(hash, same_schema_type.schema_hash());
+        assert_ne!(hash, InputSchemaType::new_port().schema_hash());
+        // The same for every build:
+        assert_eq!(
+            InputSchemaType::new_port().schema_hash(),
+            "538ffb00898c61e6"
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{
+    naming::NamingConvention,
+    schema::{InputSchema, InputSchemaType, InputSchemaTypeStaticMap},
+};
+use std::collections::HashSet;
+
+impl InputSchemaType {
+    // A simple struct-like IDL of the schema, e.g. for generating typed clients of a plugin
+    // configuration in other languages:
+    //
+    //     message Config {
+    //       // port number which should be at least 1024 (default: 8080)
+    //       optional int port;
+    //       list<Routes> routes;
+    //     }
+    //
+    // Each (nested) static map or recursive schema is a `message` named after its key (or its
+    // recursive name) in Pascal case. Types are `bool`, `int`, `float`, `number`, `string`, `any`,
+    // `list<T>`, `map<string, T>`, `oneof<A, B>`, and message names, items with defaults are
+    // `optional`, and constraints (and titles and descriptions) are comments:
+    pub fn to_idl<N: AsRef<str>>(&self, name: N) -> String {
+        let mut exporter = IdlExporter::default();
+        let type_name = exporter.type_name(self, name.as_ref());
+        if exporter.message_list.is_empty() {
+            let alias_name = NamingConvention::Pascal.convert(name.as_ref());
+            exporter
+                .message_list
+                .push(format!("type {alias_name} = {type_name};\n"));
+        }
+        exporter.message_list.join("\n")
+    }
+}
+
+#[derive(Default)]
+struct IdlExporter {
+    message_list: Vec<String>,
+    message_name_set: HashSet<String>,
+    recursive_name_set: HashSet<String>,
+}
+
+impl IdlExporter {
+    fn type_name(&mut self, schema_type: &InputSchemaType, name: &str) -> String {
+        crate::stack::maybe_grow(|| match schema_type {
+            InputSchemaType::Any(_) | InputSchemaType::Unknown(_) => "any".to_string(),
+            InputSchemaType::Boolean(_) => "bool".to_string(),
+            InputSchemaType::Integer(_) | InputSchemaType::Port(_) => "int".to_string(),
+            InputSchemaType::Float(_) => "float".to_string(),
+            InputSchemaType::Number(_) => "number".to_string(),
+            InputSchemaType::List(list) => {
+                format!("list<{}>", self.type_name(list.item_schema(), name))
+            }
+            InputSchemaType::DynamicMap(dynamic_map) => format!(
+                "map<string, {}>",
+                self.type_name(dynamic_map.item_schema(), name)
+            ),
+            InputSchemaType::KeyValue(key_value) => format!(
+                "map<string, {}>",
+                self.type_name(key_value.value_schema(), name)
+            ),
+            InputSchemaType::Either(either) => format!(
+                "oneof<{}>",
+                either
+                    .schema_list()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, schema_type)| {
+                        self.type_name(schema_type, format!("{name}_{}", index + 1).as_str())
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            InputSchemaType::Secret(secret) => self.type_name(secret.inner_schema(), name),
+            InputSchemaType::StaticMap(static_map) => self.message(static_map, name),
+            InputSchemaType::Recursive(recursive) => {
+                let message_name = NamingConvention::Pascal.convert(recursive.name());
+                if !self.recursive_name_set.insert(message_name.clone()) {
+                    return message_name;
+                }
+                match recursive.inner_schema() {
+                    InputSchemaType::StaticMap(static_map) => {
+                        self.message(static_map, recursive.name())
+                    }
+                    inner_schema => self.type_name(inner_schema, name),
+                }
+            }
+            InputSchemaType::Ref(reference) => NamingConvention::Pascal.convert(reference.name()),
+            // Everything else is checked as JSON schema types:
+            _ => match schema_type.to_json_schema().as_map().get("type") {
+                Some(json_type) if json_type.is_str() => match json_type.as_str().as_str() {
+                    "boolean" => "bool",
+                    "integer" => "int",
+                    "number" => "number",
+                    "array" => "list<any>",
+                    "object" => "map<string, any>",
+                    _ => "string",
+                }
+                .to_string(),
+                _ => "any".to_string(),
+            },
+        })
+    }
+
+    // Nested messages are added after this one:
+    fn message(&mut self, static_map: &InputSchemaTypeStaticMap, name: &str) -> String {
+        let base_message_name = NamingConvention::Pascal.convert(name);
+        let mut message_name = base_message_name.clone();
+        let mut index = 1;
+        while !self.message_name_set.insert(message_name.clone()) {
+            index += 1;
+            message_name = format!("{base_message_name}{index}");
+        }
+        let message_index = self.message_list.len();
+        self.message_list.push(String::new());
+        let mut text = format!("message {message_name} {{\n");
+        for group in static_map.group_list() {
+            text.push_str(format!("  // {group}\n").as_str());
+        }
+        for (key, schema) in static_map.ordered_items() {
+            let type_name = self.type_name(schema.schema_type(), key);
+            for comment in comment_list(schema) {
+                text.push_str(format!("  // {comment}\n").as_str());
+            }
+            let optional = if schema.maybe_default().is_some() {
+                "optional "
+            } else {
+                ""
+            };
+            text.push_str(format!("  {optional}{type_name} {key};\n").as_str());
+        }
+        if let Some(additional_items) = static_map.maybe_additional_items() {
+            let type_name = self.type_name(additional_items.schema_type(), name);
+            text.push_str(format!("  // Other keys: {type_name}\n").as_str());
+        }
+        text.push_str("}\n");
+        self.message_list[message_index] = text;
+        message_name
+    }
+}
+
+fn comment_list(schema: &InputSchema) -> Vec<String> {
+    let mut comment_list: Vec<_> = schema
+        .maybe_title()
+        .into_iter()
+        .chain(schema.maybe_description())
+        .flat_map(|text| text.lines().map(str::to_string))
+        .collect();
+    let mut constraint_list = Vec::new();
+    if !is_plain(schema.schema_type()) {
+        constraint_list.push(schema.schema_type().to_string())
+    }
+    if let Some(default) = schema.maybe_default() {
+        constraint_list.push(format!("(default: {default})"))
+    }
+    if !constraint_list.is_empty() {
+        comment_list.push(constraint_list.join(" "))
+    }
+    comment_list
+}
+
+// Types that are fully described by their IDL type:
+fn is_plain(schema_type: &InputSchemaType) -> bool {
+    match schema_type {
+        InputSchemaType::Any(_) | InputSchemaType::Boolean(_) => true,
+        InputSchemaType::String(string) => string == &Default::default(),
+        InputSchemaType::Integer(integer) => integer == &Default::default(),
+        InputSchemaType::Float(float) => float == &Default::default(),
+        InputSchemaType::Number(number) => number == &Default::default(),
+        InputSchemaType::StaticMap(_) | InputSchemaType::Recursive(_) | InputSchemaType::Ref(_) => {
+            true
+        }
+        InputSchemaType::List(list) => {
+            list.maybe_size().is_none()
+                && list.maybe_item_map().is_none()
+                && is_plain(list.item_schema())
+        }
+        InputSchemaType::DynamicMap(dynamic_map) => {
+            dynamic_map.maybe_size().is_none()
+                && dynamic_map.maybe_format().is_none()
+                && is_plain(dynamic_map.item_schema())
+        }
+        InputSchemaType::Either(either) => either.schema_list().iter().all(is_plain),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn to_idl() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "port", "start": 1024}, "default": 8080},
+                "name": {"schema": {"type": "string"}, "title": "Name"},
+                "routes": {"schema": {"type": "list", "item_schema": {
+                    "type": "static_map",
+                    "items": {
+                        "path": {"schema": {"type": "string"}},
+                        "host": {"schema": {"type": "ip"}},
+                    }
+                }}},
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            schema_type.to_idl("config"),
+            "message Config {
+  // Name
+  string name;
+  // port number which should be at least 1024 (default: 8080)
+  optional int port;
+  list<Routes> routes;
+}
+
+message Routes {
+  // IP address
+  string host;
+  string path;
+}
+"
+        );
+        assert_eq!(
+            InputSchemaType::new_boolean().to_idl("enabled"),
+            "type Enabled = bool;\n"
+        );
+    }
+}
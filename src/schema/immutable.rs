@@ -0,0 +1,260 @@
+use crate::{
+    eq::is_unchanged,
+    merge::merge,
+    position::InputPosition,
+    schema::{InputSchemaError, InputSchemaType},
+    Input,
+};
+
+impl InputSchemaType {
+    // Fails if a value of an `immutable` item that is set in `old_input` is changed or removed in
+    // `new_input`. Both inputs should be validated so that coercions are not seen as changes:
+    pub fn check_immutable(
+        &self,
+        old_input: &Input,
+        new_input: &Input,
+    ) -> Result<(), InputSchemaError> {
+        check_immutable(self, old_input, new_input, InputPosition::new())
+    }
+}
+
+// Merges `to_be_merged_input` into a copy of `input` which is validated and checked for changes
+// of immutable values before it replaces `input`. Items of lists with a `merge_key` are merged
+// with the existing item that has the same value of that key:
+pub fn merge_with_schema(
+    input: &mut Input,
+    to_be_merged_input: &Input,
+    schema_type: &InputSchemaType,
+) -> Result<(), InputSchemaError> {
+    let mut merged_input = input.clone();
+    merge_by_schema(&mut merged_input, to_be_merged_input, schema_type);
+    schema_type.validate(&mut merged_input, None)?;
+    schema_type.check_immutable(input, &merged_input)?;
+    *input = merged_input;
+    Ok(())
+}
+
+// Like `merge()` until there's a list with a `merge_key`:
+fn merge_by_schema(input: &mut Input, to_be_merged_input: &Input, schema_type: &InputSchemaType) {
+    crate::stack::maybe_grow(|| match (schema_type, &mut *input, to_be_merged_input) {
+        (InputSchemaType::StaticMap(static_map), Input::Map(map), Input::Map(to_be_merged_map)) => {
+            to_be_merged_map
+                .iter()
+                .for_each(|(key, inner_to_be_merged_input)| {
+                    match (map.get_mut(key), static_map.items().get(&**key)) {
+                        (Some(inner_input), Some(schema)) => merge_by_schema(
+                            inner_input,
+                            inner_to_be_merged_input,
+                            schema.schema_type(),
+                        ),
+                        (Some(inner_input), None) => merge(inner_input, inner_to_be_merged_input),
+                        (None, _) => {
+                            map.insert(key.clone(), inner_to_be_merged_input.clone());
+                        }
+                    }
+                })
+        }
+        (
+            InputSchemaType::DynamicMap(dynamic_map),
+            Input::Map(map),
+            Input::Map(to_be_merged_map),
+        ) => to_be_merged_map
+            .iter()
+            .for_each(|(key, inner_to_be_merged_input)| match map.get_mut(key) {
+                Some(inner_input) => merge_by_schema(
+                    inner_input,
+                    inner_to_be_merged_input,
+                    dynamic_map.item_schema(),
+                ),
+                None => {
+                    map.insert(key.clone(), inner_to_be_merged_input.clone());
+                }
+            }),
+        (InputSchemaType::List(list_schema), Input::List(list), Input::List(to_be_merged_list)) => {
+            let Some(merge_key) = list_schema.maybe_merge_key() else {
+                return merge(input, to_be_merged_input);
+            };
+            let key_value = |input: &Input| {
+                input
+                    .is_map()
+                    .then(|| input.as_map().get(merge_key.as_str()).cloned())
+                    .flatten()
+            };
+            to_be_merged_list
+                .iter()
+                .for_each(|inner_to_be_merged_input| {
+                    let maybe_index = key_value(inner_to_be_merged_input).and_then(|value| {
+                        list.iter()
+                            .position(|inner_input| key_value(inner_input).as_ref() == Some(&value))
+                    });
+                    match maybe_index {
+                        Some(index) => merge_by_schema(
+                            &mut list[index],
+                            inner_to_be_merged_input,
+                            list_schema.item_schema(),
+                        ),
+                        None if !list.contains(inner_to_be_merged_input) => {
+                            list.push(inner_to_be_merged_input.clone())
+                        }
+                        None => (),
+                    }
+                })
+        }
+        (InputSchemaType::Secret(secret), _, _) => {
+            merge_by_schema(input, to_be_merged_input, secret.inner_schema())
+        }
+        _ => merge(input, to_be_merged_input),
+    })
+}
+
+fn check_immutable(
+    schema_type: &InputSchemaType,
+    old_input: &Input,
+    new_input: &Input,
+    position: InputPosition,
+) -> Result<(), InputSchemaError> {
+    crate::stack::maybe_grow(|| match (schema_type, old_input, new_input) {
+        (InputSchemaType::StaticMap(static_map), Input::Map(old_map), Input::Map(new_map)) => {
+            static_map.items().iter().try_for_each(|(key, schema)| {
+                let Some(old_value) = old_map.get(key.as_str()) else {
+                    return Ok(());
+                };
+                let inner_position = position.new_with_key(key);
+                match new_map.get(key.as_str()) {
+                    Some(new_value)
+                        if schema.immutable() && !is_unchanged(new_value, old_value) =>
+                    {
+                        Err(InputSchemaError::Invalid {
+                            description: format!(
+                                "Immutable value `{old_value}` can not be changed"
+                            ),
+                            position: inner_position,
+                            input: new_value.clone(),
+                        })
+                    }
+                    Some(new_value) => {
+                        check_immutable(schema.schema_type(), old_value, new_value, inner_position)
+                    }
+                    None if schema.immutable() => Err(InputSchemaError::Invalid {
+                        description: format!("Immutable value `{old_value}` can not be removed"),
+                        position: inner_position,
+                        input: Input::Null,
+                    }),
+                    None => Ok(()),
+                }
+            })
+        }
+        (InputSchemaType::DynamicMap(dynamic_map), Input::Map(old_map), Input::Map(new_map)) => {
+            old_map.iter().try_for_each(|(key, old_value)| {
+                new_map.get(key).map_or(Ok(()), |new_value| {
+                    check_immutable(
+                        dynamic_map.item_schema(),
+                        old_value,
+                        new_value,
+                        position.new_with_key(key),
+                    )
+                })
+            })
+        }
+        (InputSchemaType::List(list), Input::List(old_list), Input::List(new_list)) => old_list
+            .iter()
+            .zip(new_list.iter())
+            .enumerate()
+            .try_for_each(|(index, (old_value, new_value))| {
+                check_immutable(
+                    list.item_schema(),
+                    old_value,
+                    new_value,
+                    position.new_with_index(index),
+                )
+            }),
+        (InputSchemaType::Secret(secret), _, _) => {
+            check_immutable(secret.inner_schema(), old_input, new_input, position)
+        }
+        _ => Ok(()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_with_schema() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "id": {"schema": {"type": "string"}, "immutable": true},
+                "port": {"schema": {"type": "port"}},
+            }
+        }))
+        .unwrap();
+        let mut input: Input = serde_json::from_value(json!({"id": "node-1", "port": 80})).unwrap();
+        let update: Input = serde_json::from_value(json!({"port": "8080"})).unwrap();
+        super::merge_with_schema(&mut input, &update, &schema_type).unwrap();
+        assert_eq!(input.as_map()["port"], Input::from(8080));
+
+        let original_input = input.clone();
+        let update: Input = serde_json::from_value(json!({"id": "node-2"})).unwrap();
+        let error = super::merge_with_schema(&mut input, &update, &schema_type).unwrap_err();
+        assert_eq!(error.position().to_string(), "id");
+        assert_eq!(input, original_input);
+
+        let id = InputPosition::new().new_with_key("id");
+        let mut transaction = input.transaction(&schema_type);
+        transaction.set_path(&id, "node-2").unwrap();
+        assert!(transaction.commit().is_err());
+        let mut transaction = input.transaction(&schema_type);
+        transaction.set_path(&id, "node-1").unwrap();
+        assert!(transaction.commit().is_ok());
+    }
+
+    #[test]
+    fn check_immutable_nan() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {"ratio": {"schema": {"type": "float"}, "immutable": true}}
+        }))
+        .unwrap();
+        let input = Input::from(std::collections::HashMap::from([(
+            "ratio",
+            Input::from(f64::NAN),
+        )]));
+        schema_type.check_immutable(&input, &input.clone()).unwrap();
+        assert!(schema_type
+            .check_immutable(
+                &input,
+                &Input::from(std::collections::HashMap::from([("ratio", 0.5)]))
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn merge_key() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "list",
+            "merge_key": "name",
+            "item_schema": {"type": "static_map", "items": {
+                "name": {"schema": {"type": "string"}},
+                "port": {"schema": {"type": "port"}},
+            }}
+        }))
+        .unwrap();
+        let mut input: Input =
+            serde_json::from_value(json!([{"name": "a", "port": 80}, {"name": "b", "port": 81}]))
+                .unwrap();
+        let update: Input = serde_json::from_value(
+            json!([{"name": "b", "port": "8081"}, {"name": "c", "port": 82}]),
+        )
+        .unwrap();
+        super::merge_with_schema(&mut input, &update, &schema_type).unwrap();
+        let expected: Input = serde_json::from_value(json!([
+            {"name": "a", "port": 80},
+            {"name": "b", "port": 8081},
+            {"name": "c", "port": 82},
+        ]))
+        .unwrap();
+        assert_eq!(input, expected);
+    }
+}
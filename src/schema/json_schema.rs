@@ -0,0 +1,511 @@
+#[cfg(feature = "iso-codes")]
+use crate::schema::InputSchemaTypeCountryCodeForm;
+use crate::{
+    schema::{
+        InputSchema, InputSchemaType, InputSchemaTypeBinaryEncoding,
+        InputSchemaTypeDynamicMapFormat, InputSchemaTypeNumberValue, InputSchemaTypePercentForm,
+        InputSchemaTypeRange, InputSchemaTypeSize, InputSchemaTypeSocketAddressForm,
+        InputSchemaTypeStaticMapGroup,
+    },
+    Input, InputMap,
+};
+use std::collections::HashMap;
+
+// Describes the validated form of the input, coercions (e.g. `"8080"` for a port) are not covered:
+impl InputSchemaType {
+    pub fn to_json_schema(&self) -> Input {
+        let mut map = InputMap::new();
+        match self {
+            Self::Any(_) => (),
+            Self::Boolean(_) => set_type(&mut map, "boolean"),
+            Self::Number(number) => {
+                set_type(&mut map, "number");
+                set_range(&mut map, number.maybe_range());
+            }
+            Self::Integer(integer) => {
+                set_type(&mut map, "integer");
+                set_range(&mut map, integer.maybe_range());
+            }
+            Self::Float(float) => {
+                set_type(&mut map, "number");
+                set_range(&mut map, float.maybe_range());
+            }
+            Self::String(string) => {
+                set_type(&mut map, "string");
+                set_size(&mut map, string.maybe_size(), "Length");
+                if let Some(regex) = string.maybe_regex() {
+                    map.insert("pattern".into(), regex.pattern().as_str().into());
+                    if let Some(description) = regex.maybe_description() {
+                        map.insert("description".into(), description.as_str().into());
+                    }
+                }
+            }
+            Self::List(list) => {
+                set_type(&mut map, "array");
+                map.insert("items".into(), list.item_schema().to_json_schema());
+                set_size(&mut map, list.maybe_size(), "Items");
+            }
+            Self::StaticMap(static_map) => {
+                set_type(&mut map, "object");
+                let mut required_list: Vec<_> = static_map
+                    .items()
+                    .iter()
+                    .filter(|(key, schema)| {
+                        schema.maybe_default().is_none()
+                            && !static_map
+                                .group_list()
+                                .iter()
+                                .any(|group| group.key_list().contains(key))
+                    })
+                    .map(|(key, _)| key.clone())
+                    .collect();
+                required_list.sort();
+                map.insert(
+                    "properties".into(),
+                    static_map
+                        .items()
+                        .iter()
+                        .map(|(key, schema)| (key.clone(), schema.to_json_schema()))
+                        .collect::<HashMap<_, _>>()
+                        .into(),
+                );
+                if !required_list.is_empty() {
+                    map.insert("required".into(), required_list.into());
+                }
+                let group_list: Vec<_> = static_map.group_list().iter().map(group).collect();
+                if !group_list.is_empty() {
+                    map.insert("allOf".into(), group_list.into());
+                }
+                if let Some(additional_items) = static_map.maybe_additional_items() {
+                    map.insert(
+                        "additionalProperties".into(),
+                        additional_items.to_json_schema(),
+                    );
+                }
+            }
+            Self::DynamicMap(dynamic_map) => {
+                set_type(&mut map, "object");
+                map.insert(
+                    "additionalProperties".into(),
+                    dynamic_map.item_schema().to_json_schema(),
+                );
+                set_size(&mut map, dynamic_map.maybe_size(), "Properties");
+                if let Some(format) = dynamic_map.maybe_format() {
+                    let pattern = match format {
+                        InputSchemaTypeDynamicMapFormat::HttpHeaders => {
+                            "^[!#$%&'*+.^_`|~0-9A-Za-z-]+$"
+                        }
+                        InputSchemaTypeDynamicMapFormat::Identifiers => {
+                            "^[A-Za-z_][A-Za-z0-9_.-]*$"
+                        }
+                    };
+                    map.insert(
+                        "propertyNames".into(),
+                        Input::from(HashMap::from([(
+                            "pattern".to_string(),
+                            Input::from(pattern),
+                        )])),
+                    );
+                }
+            }
+            Self::Enum(r#enum) => {
+                map.insert("enum".into(), r#enum.value_list().clone().into());
+            }
+            Self::Either(either) => {
+                map.insert(
+                    "anyOf".into(),
+                    either
+                        .schema_list()
+                        .iter()
+                        .map(|schema_type| schema_type.to_json_schema())
+                        .collect::<Vec<_>>()
+                        .into(),
+                );
+            }
+            Self::Fs(_) => set_type(&mut map, "string"),
+            Self::LogLevel(_) => {
+                map.insert(
+                    "enum".into(),
+                    Input::from(["error", "warn", "info", "debug", "trace"]),
+                );
+            }
+            Self::LogLevelFilter(_) => {
+                map.insert(
+                    "enum".into(),
+                    Input::from(["off", "error", "warn", "info", "debug", "trace"]),
+                );
+            }
+            Self::Ip(ip) => {
+                set_type(&mut map, "string");
+                match (ip.v4(), ip.v6()) {
+                    (true, false) => {
+                        map.insert("format".into(), "ipv4".into());
+                    }
+                    (false, true) => {
+                        map.insert("format".into(), "ipv6".into());
+                    }
+                    _ => (),
+                }
+            }
+            Self::Port(port) => {
+                set_type(&mut map, "integer");
+                map.insert("minimum".into(), (port.start() as isize).into());
+                map.insert("maximum".into(), (u16::MAX as isize).into());
+            }
+            Self::SocketAddress(socket_address) => match socket_address.form() {
+                InputSchemaTypeSocketAddressForm::String => set_type(&mut map, "string"),
+                InputSchemaTypeSocketAddressForm::Map => {
+                    set_type(&mut map, "object");
+                    let mut ip_map = InputMap::new();
+                    set_type(&mut ip_map, "string");
+                    let mut port_map = InputMap::new();
+                    set_type(&mut port_map, "integer");
+                    port_map.insert("minimum".into(), 0.into());
+                    port_map.insert("maximum".into(), Input::from(u16::MAX as isize));
+                    map.insert(
+                        "properties".into(),
+                        Input::from(HashMap::from([
+                            ("ip".to_string(), Input::from(ip_map)),
+                            ("port".to_string(), Input::from(port_map)),
+                        ])),
+                    );
+                    map.insert("required".into(), Input::from(["ip", "port"]));
+                    map.insert("additionalProperties".into(), false.into());
+                }
+            },
+            Self::Secret(secret) => {
+                map = secret.inner_schema().to_json_schema().into_map();
+                map.insert("writeOnly".into(), true.into());
+            }
+            Self::Percent(percent) => match percent.form() {
+                InputSchemaTypePercentForm::Ratio => {
+                    set_type(&mut map, "number");
+                    map.insert("minimum".into(), 0.into());
+                    map.insert("maximum".into(), 1.into());
+                }
+                InputSchemaTypePercentForm::Percent => {
+                    set_type(&mut map, "number");
+                    map.insert("minimum".into(), 0.into());
+                    map.insert("maximum".into(), 100.into());
+                }
+                InputSchemaTypePercentForm::String => {
+                    set_type(&mut map, "string");
+                    map.insert("pattern".into(), "^[0-9]+(\\.[0-9]+)?%$".into());
+                }
+            },
+            Self::Binary(binary) => {
+                set_type(&mut map, "string");
+                map.insert(
+                    "contentEncoding".into(),
+                    match binary.encoding() {
+                        InputSchemaTypeBinaryEncoding::Base64 => "base64",
+                        InputSchemaTypeBinaryEncoding::Hex => "base16",
+                    }
+                    .into(),
+                );
+            }
+            Self::Dsn(_) => {
+                set_type(&mut map, "string");
+                map.insert("format".into(), "uri".into());
+            }
+            Self::Tls(_) => {
+                set_type(&mut map, "object");
+                let mut path_map = InputMap::new();
+                set_type(&mut path_map, "string");
+                map.insert(
+                    "properties".into(),
+                    Input::from(HashMap::from([
+                        ("cert".to_string(), Input::from(path_map.clone())),
+                        ("key".to_string(), Input::from(path_map.clone())),
+                        ("ca".to_string(), Input::from(path_map)),
+                    ])),
+                );
+                map.insert(
+                    "dependentRequired".into(),
+                    Input::from(HashMap::from([
+                        ("cert".to_string(), Input::from(["key"])),
+                        ("key".to_string(), Input::from(["cert"])),
+                    ])),
+                );
+                map.insert("additionalProperties".into(), false.into());
+            }
+            Self::KeyValue(key_value) => {
+                set_type(&mut map, "object");
+                map.insert(
+                    "additionalProperties".into(),
+                    key_value.value_schema().to_json_schema(),
+                );
+            }
+            Self::WeightedList(weighted_list) => {
+                set_type(&mut map, "array");
+                let mut item_map = InputMap::new();
+                set_type(&mut item_map, "object");
+                let mut weight_map = InputMap::new();
+                set_type(&mut weight_map, "number");
+                weight_map.insert("exclusiveMinimum".into(), 0.into());
+                item_map.insert(
+                    "properties".into(),
+                    Input::from(HashMap::from([
+                        (
+                            "value".to_string(),
+                            weighted_list.value_schema().to_json_schema(),
+                        ),
+                        ("weight".to_string(), Input::from(weight_map)),
+                    ])),
+                );
+                item_map.insert("required".into(), Input::from(["value", "weight"]));
+                item_map.insert("additionalProperties".into(), false.into());
+                map.insert("items".into(), Input::from(item_map));
+            }
+            Self::Interval(interval) => {
+                set_type(&mut map, "object");
+                let bound = interval.item_schema().to_json_schema();
+                map.insert(
+                    "properties".into(),
+                    Input::from(HashMap::from([
+                        ("min".to_string(), bound.clone()),
+                        ("max".to_string(), bound),
+                    ])),
+                );
+                map.insert("required".into(), Input::from(["max", "min"]));
+                map.insert("additionalProperties".into(), false.into());
+            }
+            Self::TimeWindow(_) => {
+                let mut window_map = InputMap::new();
+                set_type(&mut window_map, "string");
+                window_map.insert(
+                    "pattern".into(),
+                    "^[0-9]{1,2}:[0-9]{2}-[0-9]{1,2}:[0-9]{2}$".into(),
+                );
+                let mut list_map = InputMap::new();
+                set_type(&mut list_map, "array");
+                list_map.insert("items".into(), Input::from(window_map.clone()));
+                map.insert(
+                    "anyOf".into(),
+                    Input::from(vec![Input::from(window_map), Input::from(list_map)]),
+                );
+            }
+            Self::Rate(_) => {
+                set_type(&mut map, "object");
+                let mut count_map = InputMap::new();
+                set_type(&mut count_map, "number");
+                let mut per_seconds_map = InputMap::new();
+                set_type(&mut per_seconds_map, "integer");
+                per_seconds_map.insert("minimum".into(), 1.into());
+                map.insert(
+                    "properties".into(),
+                    Input::from(HashMap::from([
+                        ("count".to_string(), Input::from(count_map)),
+                        ("per_seconds".to_string(), Input::from(per_seconds_map)),
+                    ])),
+                );
+                map.insert("required".into(), Input::from(["count", "per_seconds"]));
+                map.insert("additionalProperties".into(), false.into());
+            }
+            Self::Mime(_) => {
+                set_type(&mut map, "string");
+                map.insert("pattern".into(), "^[a-z0-9][a-z0-9!#$&^_.+-]*/[a-z0-9][a-z0-9!#$&^_.+-]*(; *[a-z0-9][a-z0-9!#$&^_.+-]*=.+)*$".into());
+            }
+            Self::RegexValue(_) => {
+                set_type(&mut map, "string");
+                map.insert("format".into(), "regex".into());
+            }
+            Self::Switch(_) => {
+                set_type(&mut map, "object");
+                let mut enabled_map = InputMap::new();
+                set_type(&mut enabled_map, "boolean");
+                let mut reason_map = InputMap::new();
+                set_type(&mut reason_map, "string");
+                map.insert(
+                    "properties".into(),
+                    Input::from(HashMap::from([
+                        ("enabled".to_string(), Input::from(enabled_map)),
+                        ("reason".to_string(), Input::from(reason_map)),
+                    ])),
+                );
+                map.insert("required".into(), Input::from(["enabled"]));
+                map.insert("additionalProperties".into(), false.into());
+            }
+            Self::Recursive(recursive) => {
+                map = recursive.inner_schema().to_json_schema().as_map().clone();
+                map.insert("$anchor".into(), recursive.name().as_str().into());
+            }
+            Self::Ref(reference) => {
+                map.insert("$ref".into(), format!("#{}", reference.name()).into());
+            }
+            #[cfg(feature = "expr")]
+            Self::Expr(_) => set_type(&mut map, "string"),
+            // Nothing is known about its values:
+            Self::Unknown(_) => (),
+            #[cfg(feature = "iso-codes")]
+            Self::CountryCode(country_code) => {
+                set_type(&mut map, "string");
+                map.insert(
+                    "pattern".into(),
+                    match country_code.maybe_form() {
+                        Some(InputSchemaTypeCountryCodeForm::Alpha2) => "^[A-Z]{2}$",
+                        Some(InputSchemaTypeCountryCodeForm::Alpha3) => "^[A-Z]{3}$",
+                        None => "^[A-Z]{2,3}$",
+                    }
+                    .into(),
+                );
+            }
+            #[cfg(feature = "iso-codes")]
+            Self::Currency(_) => {
+                set_type(&mut map, "string");
+                map.insert("pattern".into(), "^[A-Z]{3}$".into());
+            }
+            #[cfg(feature = "rust_decimal")]
+            Self::Decimal(_) => {
+                set_type(&mut map, "string");
+                map.insert("format".into(), "decimal".into());
+            }
+        }
+        Input::from(map)
+    }
+
+    pub fn to_json_schema_document(&self) -> Input {
+        let mut input = self.to_json_schema();
+        input.map_mut().insert(
+            "$schema".into(),
+            "https://json-schema.org/draft/2020-12/schema".into(),
+        );
+        input
+    }
+}
+
+impl InputSchema {
+    pub fn to_json_schema(&self) -> Input {
+        let mut input = self.schema_type().to_json_schema();
+        if let Some(default) = self.maybe_default() {
+            input.map_mut().insert("default".into(), default.clone());
+        }
+        if let Some(title) = self.maybe_title() {
+            input
+                .map_mut()
+                .insert("title".into(), title.as_str().into());
+        }
+        if let Some(description) = self.maybe_description() {
+            input
+                .map_mut()
+                .insert("description".into(), description.as_str().into());
+        }
+        if self.immutable() {
+            input.map_mut().insert("readOnly".into(), true.into());
+        }
+        input
+    }
+}
+
+// `exactly_one_of` is one of the keys being required, and `at_most_one_of` is no pair of the
+// keys being required:
+fn group(group: &InputSchemaTypeStaticMapGroup) -> Input {
+    let required = |key_list: Vec<&String>| {
+        Input::from(HashMap::from([(
+            "required",
+            Input::from(key_list.into_iter().cloned().collect::<Vec<_>>()),
+        )]))
+    };
+    let key_list = group.key_list();
+    match group {
+        InputSchemaTypeStaticMapGroup::ExactlyOneOf(_) => Input::from(HashMap::from([(
+            "oneOf",
+            Input::from(
+                key_list
+                    .iter()
+                    .map(|key| required(vec![key]))
+                    .collect::<Vec<_>>(),
+            ),
+        )])),
+        InputSchemaTypeStaticMapGroup::AtMostOneOf(_) => {
+            let pair_list: Vec<_> = key_list
+                .iter()
+                .enumerate()
+                .flat_map(|(index, key)| {
+                    key_list[index + 1..]
+                        .iter()
+                        .map(move |other_key| required(vec![key, other_key]))
+                })
+                .collect();
+            Input::from(HashMap::from([(
+                "not",
+                Input::from(HashMap::from([("anyOf", Input::from(pair_list))])),
+            )]))
+        }
+    }
+}
+
+fn set_type(map: &mut InputMap, type_name: &str) {
+    map.insert("type".into(), type_name.into());
+}
+
+fn number(value: InputSchemaTypeNumberValue) -> Input {
+    match value {
+        InputSchemaTypeNumberValue::Integer(integer) => integer.into(),
+        InputSchemaTypeNumberValue::Float(float) => float.into(),
+    }
+}
+
+fn set_range(map: &mut InputMap, maybe_range: Option<&InputSchemaTypeRange>) {
+    if let Some(range) = maybe_range {
+        if let Some(min) = range.maybe_min() {
+            map.insert("minimum".into(), number(min));
+        }
+        if let Some(max) = range.maybe_max() {
+            map.insert("maximum".into(), number(max));
+        }
+    }
+}
+
+fn set_size(map: &mut InputMap, maybe_size: Option<&InputSchemaTypeSize>, suffix: &str) {
+    if let Some(size) = maybe_size {
+        if let Some(min) = size.maybe_min() {
+            map.insert(
+                crate::intern_key(format!("min{suffix}")),
+                (min as isize).into(),
+            );
+        }
+        if let Some(max) = size.maybe_max() {
+            map.insert(
+                crate::intern_key(format!("max{suffix}")),
+                (max as isize).into(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn to_json_schema() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "port", "start": 1024}},
+                "level": {"schema": {"type": "log_level"}, "default": "info"},
+                "tags": {"schema": {"type": "list", "item_schema": {"type": "string"}, "size": 3}},
+                "password": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+            }
+        }))
+        .unwrap();
+        let expected: Input = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "port": {"type": "integer", "minimum": 1024, "maximum": 65535},
+                "level": {"enum": ["error", "warn", "info", "debug", "trace"], "default": "info"},
+                "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 3},
+                "password": {"type": "string", "writeOnly": true},
+            },
+            "required": ["password", "port", "tags"]
+        }))
+        .unwrap();
+        assert_eq!(schema_type.to_json_schema(), expected);
+        assert!(schema_type
+            .to_json_schema_document()
+            .as_map()
+            .contains_key("$schema"));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    position::InputPositionPatternType,
+    schema::{InputSchema, InputSchemaReload, InputSchemaType},
+};
+
+impl InputSchemaType {
+    // A Markdown table of all keys of the schema with their types, defaults, titles, and
+    // descriptions, e.g. for the documentation of a plugin configuration:
+    //
+    //     | Key | Type | Default | Description |
+    //     | --- | --- | --- | --- |
+    //     | `server` | map |  |  |
+    //     | `server.port` | port number | `8080` | **Port**<br>Where to listen |
+    //
+    // Keys of nested static maps are joined with `.` like `InputPositionPattern`s, where `*`
+    // stands for keys of dynamic maps, items of lists, and additional items. Secrets, immutable
+    // items, and items that require a restart are marked in the description:
+    pub fn to_markdown(&self) -> String {
+        let mut row_list = Vec::new();
+        match self {
+            InputSchemaType::StaticMap(_)
+            | InputSchemaType::DynamicMap(_)
+            | InputSchemaType::List(_) => collect_row_list(self, "", &mut row_list),
+            _ => row_list.push(row("", self, None)),
+        }
+        let mut text =
+            "| Key | Type | Default | Description |\n| --- | --- | --- | --- |\n".to_string();
+        row_list.into_iter().for_each(|row| {
+            text.push_str(format!("| {} |\n", row.join(" | ")).as_str());
+        });
+        text
+    }
+}
+
+fn collect_row_list(schema_type: &InputSchemaType, prefix: &str, row_list: &mut Vec<[String; 4]>) {
+    crate::stack::maybe_grow(|| match schema_type {
+        InputSchemaType::Secret(secret) => {
+            collect_row_list(secret.inner_schema(), prefix, row_list)
+        }
+        InputSchemaType::StaticMap(static_map) => {
+            for (key, schema) in static_map.ordered_items() {
+                let key = join(prefix, InputPositionPatternType::Key(key.clone()));
+                row_list.push(row(&key, schema.schema_type(), Some(schema)));
+                collect_row_list(schema.schema_type(), &key, row_list);
+            }
+            if let Some(schema) = static_map.maybe_additional_items() {
+                let key = join(prefix, InputPositionPatternType::Any);
+                row_list.push(row(&key, schema.schema_type(), Some(schema)));
+                collect_row_list(schema.schema_type(), &key, row_list);
+            }
+        }
+        InputSchemaType::DynamicMap(dynamic_map) => {
+            nested_row_list(dynamic_map.item_schema(), prefix, row_list)
+        }
+        InputSchemaType::List(list) => nested_row_list(list.item_schema(), prefix, row_list),
+        _ => (),
+    })
+}
+
+// Items of dynamic maps and lists only have their own rows if they are maps or lists themselves:
+fn nested_row_list(item_schema: &InputSchemaType, prefix: &str, row_list: &mut Vec<[String; 4]>) {
+    if has_row_list(item_schema) {
+        let key = join(prefix, InputPositionPatternType::Any);
+        row_list.push(row(&key, item_schema, None));
+        collect_row_list(item_schema, &key, row_list);
+    }
+}
+
+fn has_row_list(schema_type: &InputSchemaType) -> bool {
+    match schema_type {
+        InputSchemaType::Secret(secret) => has_row_list(secret.inner_schema()),
+        InputSchemaType::StaticMap(_) => true,
+        InputSchemaType::DynamicMap(dynamic_map) => has_row_list(dynamic_map.item_schema()),
+        InputSchemaType::List(list) => has_row_list(list.item_schema()),
+        _ => false,
+    }
+}
+
+fn join(prefix: &str, pattern_type: InputPositionPatternType) -> String {
+    if prefix.is_empty() {
+        pattern_type.to_string()
+    } else {
+        format!("{prefix}.{pattern_type}")
+    }
+}
+
+fn row(
+    key: &str,
+    schema_type: &InputSchemaType,
+    maybe_schema: Option<&InputSchema>,
+) -> [String; 4] {
+    let key = if key.is_empty() {
+        String::new()
+    } else {
+        format!("`{key}`")
+    };
+    let Some(schema) = maybe_schema else {
+        return [key, type_text(schema_type), String::new(), String::new()];
+    };
+    let default = schema
+        .maybe_default()
+        .map(|default| format!("`{}`", escape(&default.to_string())))
+        .unwrap_or_default();
+    let mut description_list = Vec::new();
+    if let Some(title) = schema.maybe_title() {
+        description_list.push(format!("**{}**", escape(title)));
+    }
+    if let Some(description) = schema.maybe_description() {
+        description_list.push(escape(description));
+    }
+    let mut note_list = Vec::new();
+    if schema_type.is_secret() {
+        note_list.push("Secret.");
+    }
+    if schema.immutable() {
+        note_list.push("Immutable.");
+    } else if schema.reload() == InputSchemaReload::Restart {
+        note_list.push("Requires a restart.");
+    }
+    if !note_list.is_empty() {
+        description_list.push(format!("_{}_", note_list.join(" ")));
+    }
+    [
+        key,
+        type_text(schema_type),
+        default,
+        description_list.join("<br>"),
+    ]
+}
+
+// Maps are described by their own rows:
+fn type_text(schema_type: &InputSchemaType) -> String {
+    match schema_type {
+        InputSchemaType::Secret(secret) if has_row_list(secret.inner_schema()) => {
+            format!("secret {}", type_text(secret.inner_schema()))
+        }
+        InputSchemaType::StaticMap(_) => "map".to_string(),
+        InputSchemaType::DynamicMap(dynamic_map) if has_row_list(dynamic_map.item_schema()) => {
+            "map".to_string()
+        }
+        InputSchemaType::List(list) if has_row_list(list.item_schema()) => "list".to_string(),
+        _ => escape(&schema_type.to_string()),
+    }
+}
+
+// Table cells can't contain `|` or line breaks:
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn to_markdown() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "order": ["server"],
+            "items": {
+                "server": {"schema": {"type": "static_map", "items": {
+                    "port": {
+                        "schema": {"type": "port"},
+                        "default": 8080,
+                        "title": "Port",
+                        "description": "Where to listen",
+                        "reload": "restart",
+                    },
+                }}},
+                "token": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                "routes": {"schema": {"type": "list", "item_schema": {
+                    "type": "static_map",
+                    "items": {"path": {"schema": {"type": "string"}, "immutable": true}},
+                }}},
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            schema_type.to_markdown(),
+            "| Key | Type | Default | Description |
+| --- | --- | --- | --- |
+| `server` | map |  |  |
+| `server.port` | port number | `8080` | **Port**<br>Where to listen<br>_Requires a restart._ |
+| `routes` | list |  |  |
+| `routes.*` | map |  |  |
+| `routes.*.path` | string |  | _Immutable._ |
+| `token` | secret string |  | _Secret._ |
+"
+        );
+        assert_eq!(
+            InputSchemaType::new_boolean().to_markdown(),
+            "| Key | Type | Default | Description |\n| --- | --- | --- | --- |\n|  | boolean |  |  |\n"
+        );
+    }
+}
@@ -1,24 +1,133 @@
+#[cfg(feature = "ansi")]
+mod ansi;
+mod batch;
+mod budget;
+mod cache;
+mod cipher;
+mod coerce;
 pub mod common;
+mod compat;
+mod compiled;
+mod compose;
+mod counterexample;
+mod defaults;
+mod describe;
+#[cfg(feature = "json")]
+mod embed;
+#[cfg(feature = "json")]
+mod hash;
+mod idl;
+mod immutable;
+mod json_schema;
+mod markdown;
+mod obfuscate;
+mod openapi;
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+mod parse;
+mod paths;
+pub mod presets;
+mod prompt;
+#[cfg(feature = "registry")]
+pub mod registry;
+mod reload;
+#[cfg(feature = "json")]
+mod roundtrip;
+mod set;
+mod simplify;
+mod stats;
+#[cfg(feature = "json")]
+mod stream;
+mod suggest;
+mod transaction;
 mod types;
+mod ui_model;
+mod visibility;
 
+pub use batch::BatchReport;
+pub use budget::ValidationOptions;
+pub use cache::ValidationCache;
+pub use cipher::{SecretCipher, SecretCipherError, ENCRYPTED_PREFIX};
 pub use common::{
     number::InputSchemaTypeNumberValue, range::InputSchemaTypeRange, regex::InputSchemaTypeRegex,
     size::InputSchemaTypeSize,
 };
+pub use compat::{compat_check, compat_check_migrated, CompatFailureCause, CompatReport};
+pub use compiled::CompiledSchema;
+pub use compose::{InputSchemaMergeError, InputSchemaMergePolicy};
+pub use counterexample::counterexample;
+#[cfg(feature = "json")]
+pub use embed::{check_schema_file, InputSchemaCheckError};
+pub use immutable::merge_with_schema;
+pub use openapi::to_openapi_component;
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+pub use parse::InputSchemaParseError;
+pub use prompt::{apply_answers, prompts, InputPrompt};
+#[cfg(feature = "registry")]
+pub use registry::{SchemaRegistry, SchemaRegistryError};
+pub use reload::{classify_diffs, InputSchemaReload, ReloadPlan};
+#[cfg(feature = "json")]
+pub use roundtrip::{roundtrip_check, InputSchemaRoundtripError};
+pub use set::SchemaSet;
+pub use stats::{InputSchemaCostClass, InputSchemaStats};
+#[cfg(feature = "json")]
+pub use stream::{validate_json_stream, InputSchemaStreamError};
+pub use suggest::ValidationReport;
+pub use transaction::{InputTransaction, InputTransactionError};
+#[cfg(feature = "rust_decimal")]
+pub use types::decimal::InputSchemaTypeDecimal;
+#[cfg(feature = "expr")]
+pub use types::expr::InputSchemaTypeExpr;
 pub use types::{
-    any::InputSchemaTypeAny, boolean::InputSchemaTypeBoolean,
-    dynamic_map::InputSchemaTypeDynamicMap, either::InputSchemaTypeEither,
-    float::InputSchemaTypeFloat, fs::InputSchemaTypeFs, integer::InputSchemaTypeInteger,
-    ip::InputSchemaTypeIp, list::InputSchemaTypeList, log_level::InputSchemaTypeLogLevel,
-    log_level_filter::InputSchemaTypeLogLevelFilter, number::InputSchemaTypeNumber,
-    port::InputSchemaTypePort, r#enum::InputSchemaTypeEnum,
-    socket_address::InputSchemaTypeSocketAddress, static_map::InputSchemaTypeStaticMap,
+    any::InputSchemaTypeAny,
+    binary::{InputSchemaTypeBinary, InputSchemaTypeBinaryEncoding},
+    boolean::InputSchemaTypeBoolean,
+    dsn::{InputSchemaTypeDsn, InputSchemaTypeDsnComponent},
+    dynamic_map::{InputSchemaTypeDynamicMap, InputSchemaTypeDynamicMapFormat},
+    either::InputSchemaTypeEither,
+    float::InputSchemaTypeFloat,
+    fs::InputSchemaTypeFs,
+    integer::{InputSchemaTypeInteger, InputSchemaTypeIntegerSuffix},
+    interval::InputSchemaTypeInterval,
+    ip::InputSchemaTypeIp,
+    key_value::InputSchemaTypeKeyValue,
+    list::{InputSchemaTypeList, InputSchemaTypeListItemMap},
+    log_level::InputSchemaTypeLogLevel,
+    log_level_filter::InputSchemaTypeLogLevelFilter,
+    mime::InputSchemaTypeMime,
+    number::InputSchemaTypeNumber,
+    percent::{InputSchemaTypePercent, InputSchemaTypePercentForm},
+    port::InputSchemaTypePort,
+    r#enum::InputSchemaTypeEnum,
+    rate::InputSchemaTypeRate,
+    recursive::{InputSchemaRefError, InputSchemaTypeRecursive},
+    reference::InputSchemaTypeRef,
+    regex_value::InputSchemaTypeRegexValue,
+    secret::InputSchemaTypeSecret,
+    socket_address::{InputSchemaTypeSocketAddress, InputSchemaTypeSocketAddressForm},
+    static_map::{
+        InputSchemaTypeStaticMap, InputSchemaTypeStaticMapGroup, InputSchemaTypeStaticMapNullPolicy,
+    },
     string::InputSchemaTypeString,
+    switch::InputSchemaTypeSwitch,
+    time_window::InputSchemaTypeTimeWindow,
+    tls::InputSchemaTypeTls,
+    unknown::{with_unknown_types, InputSchemaTypeUnknown},
+    weighted_list::InputSchemaTypeWeightedList,
+};
+#[cfg(feature = "iso-codes")]
+pub use types::{
+    country_code::{InputSchemaTypeCountryCode, InputSchemaTypeCountryCodeForm},
+    currency::InputSchemaTypeCurrency,
 };
+pub use ui_model::{ui_model, UiField, UiModel, UiWidget};
+pub use visibility::InputSchemaVisibility;
 
 use crate::{position::InputPosition, Input};
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter};
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter},
+};
 use thiserror::Error;
 
 #[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
@@ -32,6 +141,29 @@ pub struct InputSchema {
     pub(crate) schema_type: Box<InputSchemaType>,
     #[serde(rename = "default", skip_serializing_if = "Option::is_none")]
     pub(crate) maybe_default: Option<Input>,
+    #[serde(rename = "title", skip_serializing_if = "Option::is_none", default)]
+    pub(crate) maybe_title: Option<String>,
+    #[serde(
+        rename = "description",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub(crate) maybe_description: Option<String>,
+    // Once set, the value can not be changed by `merge_with_schema()` or transactions:
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub(crate) immutable: bool,
+    // Changes (e.g. of timestamps or cache hints) are not reported by `diff::diff_with_schema()`
+    // and are ignored by `classify_diffs()`:
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub(crate) volatile: bool,
+    #[serde(rename = "reload", skip_serializing_if = "Option::is_none", default)]
+    pub(crate) maybe_reload: Option<InputSchemaReload>,
+    #[serde(
+        rename = "visibility",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub(crate) maybe_visibility: Option<InputSchemaVisibility>,
 }
 
 impl Display for InputSchema {
@@ -44,8 +176,14 @@ impl Display for InputSchema {
     }
 }
 
+// `Deserialize` and `Serialize` are implemented in `types/unknown.rs` on top of these derives:
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
-#[serde(rename_all = "snake_case", tag = "type", deny_unknown_fields)]
+#[serde(
+    remote = "Self",
+    rename_all = "snake_case",
+    tag = "type",
+    deny_unknown_fields
+)]
 pub enum InputSchemaType {
     Any(InputSchemaTypeAny),
     Boolean(InputSchemaTypeBoolean),
@@ -64,6 +202,31 @@ pub enum InputSchemaType {
     Ip(InputSchemaTypeIp),
     Port(InputSchemaTypePort),
     SocketAddress(InputSchemaTypeSocketAddress),
+    Secret(InputSchemaTypeSecret),
+    #[cfg(feature = "rust_decimal")]
+    Decimal(InputSchemaTypeDecimal),
+    Percent(InputSchemaTypePercent),
+    WeightedList(InputSchemaTypeWeightedList),
+    KeyValue(InputSchemaTypeKeyValue),
+    Binary(InputSchemaTypeBinary),
+    Tls(InputSchemaTypeTls),
+    Dsn(InputSchemaTypeDsn),
+    Interval(InputSchemaTypeInterval),
+    TimeWindow(InputSchemaTypeTimeWindow),
+    Rate(InputSchemaTypeRate),
+    #[cfg(feature = "iso-codes")]
+    CountryCode(InputSchemaTypeCountryCode),
+    #[cfg(feature = "iso-codes")]
+    Currency(InputSchemaTypeCurrency),
+    Mime(InputSchemaTypeMime),
+    RegexValue(InputSchemaTypeRegexValue),
+    Recursive(InputSchemaTypeRecursive),
+    Ref(InputSchemaTypeRef),
+    #[cfg(feature = "expr")]
+    Expr(InputSchemaTypeExpr),
+    Switch(InputSchemaTypeSwitch),
+    #[serde(skip)]
+    Unknown(InputSchemaTypeUnknown),
 }
 
 impl Display for InputSchemaType {
@@ -86,6 +249,30 @@ impl Display for InputSchemaType {
             Self::Ip(ip) => format!("{ip}"),
             Self::Port(port) => format!("{port}"),
             Self::SocketAddress(socket_address) => format!("{socket_address}"),
+            Self::Secret(secret) => format!("{secret}"),
+            #[cfg(feature = "rust_decimal")]
+            Self::Decimal(decimal) => format!("{decimal}"),
+            Self::Percent(percent) => format!("{percent}"),
+            Self::WeightedList(weighted_list) => format!("{weighted_list}"),
+            Self::KeyValue(key_value) => format!("{key_value}"),
+            Self::Binary(binary) => format!("{binary}"),
+            Self::Tls(tls) => format!("{tls}"),
+            Self::Dsn(dsn) => format!("{dsn}"),
+            Self::Interval(interval) => format!("{interval}"),
+            Self::TimeWindow(time_window) => format!("{time_window}"),
+            Self::Rate(rate) => format!("{rate}"),
+            #[cfg(feature = "iso-codes")]
+            Self::CountryCode(country_code) => format!("{country_code}"),
+            #[cfg(feature = "iso-codes")]
+            Self::Currency(currency) => format!("{currency}"),
+            Self::Mime(mime) => format!("{mime}"),
+            Self::RegexValue(regex_value) => format!("{regex_value}"),
+            Self::Recursive(recursive) => format!("{recursive}"),
+            Self::Ref(reference) => format!("{reference}"),
+            #[cfg(feature = "expr")]
+            Self::Expr(expr) => format!("{expr}"),
+            Self::Switch(switch) => format!("{switch}"),
+            Self::Unknown(unknown) => format!("{unknown}"),
         };
         f.write_str(text.as_str())
     }
@@ -96,6 +283,59 @@ impl InputSchemaType {
         &self,
         input: &mut Input,
         maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        budget::check(maybe_position.as_ref())?;
+        crate::stack::maybe_grow(|| {
+            cache::validate(self, input, maybe_position, |input, maybe_position| {
+                self.validate_level(input, maybe_position)
+            })
+        })
+    }
+
+    // Like `validate()`, but `input` is only copied if validation changes it (coerced values or
+    // defaults), so an already valid input is returned as `Cow::Borrowed`. Lists, maps, `either`,
+    // and `secret` are validated this way and other types are validated on a copy of the value:
+    pub fn validate_cow<'a>(
+        &self,
+        input: &'a Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<Cow<'a, Input>, InputSchemaError> {
+        budget::check(maybe_position.as_ref())?;
+        crate::stack::maybe_grow(|| {
+            cache::validate_cow(self, input, |input| {
+                self.validate_cow_level(input, maybe_position)
+            })
+        })
+    }
+
+    fn validate_cow_level<'a>(
+        &self,
+        input: &'a Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<Cow<'a, Input>, InputSchemaError> {
+        match self {
+            Self::Any(_) => Ok(Cow::Borrowed(input)),
+            Self::List(list) => list.validate_cow(input, maybe_position),
+            Self::StaticMap(static_map) => static_map.validate_cow(input, maybe_position),
+            Self::DynamicMap(dynamic_map) => dynamic_map.validate_cow(input, maybe_position),
+            Self::Either(either) => either.validate_cow(input, maybe_position),
+            Self::Secret(secret) => secret.validate_cow(input, maybe_position),
+            _ => {
+                let mut new_input = input.clone();
+                self.validate_level(&mut new_input, maybe_position)?;
+                Ok(if &new_input == input {
+                    Cow::Borrowed(input)
+                } else {
+                    Cow::Owned(new_input)
+                })
+            }
+        }
+    }
+
+    fn validate_level(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
     ) -> Result<(), InputSchemaError> {
         match self {
             Self::Any(any) => any.validate(input, maybe_position),
@@ -117,6 +357,30 @@ impl InputSchemaType {
             Self::Ip(ip) => ip.validate(input, maybe_position),
             Self::Port(port) => port.validate(input, maybe_position),
             Self::SocketAddress(socket_address) => socket_address.validate(input, maybe_position),
+            Self::Secret(secret) => secret.validate(input, maybe_position),
+            #[cfg(feature = "rust_decimal")]
+            Self::Decimal(decimal) => decimal.validate(input, maybe_position),
+            Self::Percent(percent) => percent.validate(input, maybe_position),
+            Self::WeightedList(weighted_list) => weighted_list.validate(input, maybe_position),
+            Self::KeyValue(key_value) => key_value.validate(input, maybe_position),
+            Self::Binary(binary) => binary.validate(input, maybe_position),
+            Self::Tls(tls) => tls.validate(input, maybe_position),
+            Self::Dsn(dsn) => dsn.validate(input, maybe_position),
+            Self::Interval(interval) => interval.validate(input, maybe_position),
+            Self::TimeWindow(time_window) => time_window.validate(input, maybe_position),
+            Self::Rate(rate) => rate.validate(input, maybe_position),
+            #[cfg(feature = "iso-codes")]
+            Self::CountryCode(country_code) => country_code.validate(input, maybe_position),
+            #[cfg(feature = "iso-codes")]
+            Self::Currency(currency) => currency.validate(input, maybe_position),
+            Self::Mime(mime) => mime.validate(input, maybe_position),
+            Self::RegexValue(regex_value) => regex_value.validate(input, maybe_position),
+            Self::Recursive(recursive) => recursive.validate(input, maybe_position),
+            Self::Ref(reference) => reference.validate(input, maybe_position),
+            #[cfg(feature = "expr")]
+            Self::Expr(expr) => expr.validate(input, maybe_position),
+            Self::Switch(switch) => switch.validate(input, maybe_position),
+            Self::Unknown(unknown) => unknown.validate(input, maybe_position),
         }
     }
 }
@@ -189,6 +453,90 @@ impl InputSchemaType {
     pub fn new_socket_address() -> Self {
         Self::SocketAddress(Default::default())
     }
+
+    pub fn new_secret() -> Self {
+        Self::Secret(Default::default())
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    pub fn new_decimal() -> Self {
+        Self::Decimal(Default::default())
+    }
+
+    pub fn new_percent() -> Self {
+        Self::Percent(Default::default())
+    }
+
+    pub fn new_weighted_list() -> Self {
+        Self::WeightedList(Default::default())
+    }
+
+    pub fn new_key_value() -> Self {
+        Self::KeyValue(Default::default())
+    }
+
+    pub fn new_binary() -> Self {
+        Self::Binary(Default::default())
+    }
+
+    pub fn new_tls() -> Self {
+        Self::Tls(Default::default())
+    }
+
+    pub fn new_dsn() -> Self {
+        Self::Dsn(Default::default())
+    }
+
+    pub fn new_interval() -> Self {
+        Self::Interval(Default::default())
+    }
+
+    pub fn new_time_window() -> Self {
+        Self::TimeWindow(Default::default())
+    }
+
+    pub fn new_rate() -> Self {
+        Self::Rate(Default::default())
+    }
+
+    #[cfg(feature = "iso-codes")]
+    pub fn new_country_code() -> Self {
+        Self::CountryCode(Default::default())
+    }
+
+    #[cfg(feature = "iso-codes")]
+    pub fn new_currency() -> Self {
+        Self::Currency(Default::default())
+    }
+
+    pub fn new_mime() -> Self {
+        Self::Mime(Default::default())
+    }
+
+    pub fn new_regex_value() -> Self {
+        Self::RegexValue(Default::default())
+    }
+
+    pub fn new_recursive() -> Self {
+        Self::Recursive(Default::default())
+    }
+
+    pub fn new_reference() -> Self {
+        Self::Ref(Default::default())
+    }
+
+    #[cfg(feature = "expr")]
+    pub fn new_expr() -> Self {
+        Self::Expr(Default::default())
+    }
+
+    pub fn new_switch() -> Self {
+        Self::Switch(Default::default())
+    }
+
+    pub fn new_unknown() -> Self {
+        Self::Unknown(Default::default())
+    }
 }
 
 impl InputSchemaType {
@@ -259,6 +607,90 @@ impl InputSchemaType {
     pub fn is_socket_address(&self) -> bool {
         matches!(self, Self::SocketAddress(_))
     }
+
+    pub fn is_secret(&self) -> bool {
+        matches!(self, Self::Secret(_))
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, Self::Decimal(_))
+    }
+
+    pub fn is_percent(&self) -> bool {
+        matches!(self, Self::Percent(_))
+    }
+
+    pub fn is_weighted_list(&self) -> bool {
+        matches!(self, Self::WeightedList(_))
+    }
+
+    pub fn is_key_value(&self) -> bool {
+        matches!(self, Self::KeyValue(_))
+    }
+
+    pub fn is_binary(&self) -> bool {
+        matches!(self, Self::Binary(_))
+    }
+
+    pub fn is_tls(&self) -> bool {
+        matches!(self, Self::Tls(_))
+    }
+
+    pub fn is_dsn(&self) -> bool {
+        matches!(self, Self::Dsn(_))
+    }
+
+    pub fn is_interval(&self) -> bool {
+        matches!(self, Self::Interval(_))
+    }
+
+    pub fn is_time_window(&self) -> bool {
+        matches!(self, Self::TimeWindow(_))
+    }
+
+    pub fn is_rate(&self) -> bool {
+        matches!(self, Self::Rate(_))
+    }
+
+    #[cfg(feature = "iso-codes")]
+    pub fn is_country_code(&self) -> bool {
+        matches!(self, Self::CountryCode(_))
+    }
+
+    #[cfg(feature = "iso-codes")]
+    pub fn is_currency(&self) -> bool {
+        matches!(self, Self::Currency(_))
+    }
+
+    pub fn is_mime(&self) -> bool {
+        matches!(self, Self::Mime(_))
+    }
+
+    pub fn is_regex_value(&self) -> bool {
+        matches!(self, Self::RegexValue(_))
+    }
+
+    pub fn is_recursive(&self) -> bool {
+        matches!(self, Self::Recursive(_))
+    }
+
+    pub fn is_reference(&self) -> bool {
+        matches!(self, Self::Ref(_))
+    }
+
+    #[cfg(feature = "expr")]
+    pub fn is_expr(&self) -> bool {
+        matches!(self, Self::Expr(_))
+    }
+
+    pub fn is_switch(&self) -> bool {
+        matches!(self, Self::Switch(_))
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown(_))
+    }
 }
 
 impl InputSchemaType {
@@ -431,6 +863,210 @@ impl InputSchemaType {
             )
         }
     }
+
+    pub fn as_secret(&self) -> &InputSchemaTypeSecret {
+        if let Self::Secret(secret) = self {
+            secret
+        } else {
+            panic!(
+                "`&self` is not `Secret`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    pub fn as_decimal(&self) -> &InputSchemaTypeDecimal {
+        if let Self::Decimal(decimal) = self {
+            decimal
+        } else {
+            panic!(
+                "`&self` is not `Decimal`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_percent(&self) -> &InputSchemaTypePercent {
+        if let Self::Percent(percent) = self {
+            percent
+        } else {
+            panic!(
+                "`&self` is not `Percent`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_weighted_list(&self) -> &InputSchemaTypeWeightedList {
+        if let Self::WeightedList(weighted_list) = self {
+            weighted_list
+        } else {
+            panic!(
+                "`&self` is not `WeightedList`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_key_value(&self) -> &InputSchemaTypeKeyValue {
+        if let Self::KeyValue(key_value) = self {
+            key_value
+        } else {
+            panic!(
+                "`&self` is not `KeyValue`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_binary(&self) -> &InputSchemaTypeBinary {
+        if let Self::Binary(binary) = self {
+            binary
+        } else {
+            panic!(
+                "`&self` is not `Binary`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_tls(&self) -> &InputSchemaTypeTls {
+        if let Self::Tls(tls) = self {
+            tls
+        } else {
+            panic!(
+                "`&self` is not `Tls`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_dsn(&self) -> &InputSchemaTypeDsn {
+        if let Self::Dsn(dsn) = self {
+            dsn
+        } else {
+            panic!(
+                "`&self` is not `Dsn`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_interval(&self) -> &InputSchemaTypeInterval {
+        if let Self::Interval(interval) = self {
+            interval
+        } else {
+            panic!(
+                "`&self` is not `Interval`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_time_window(&self) -> &InputSchemaTypeTimeWindow {
+        if let Self::TimeWindow(time_window) = self {
+            time_window
+        } else {
+            panic!(
+                "`&self` is not `TimeWindow`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_rate(&self) -> &InputSchemaTypeRate {
+        if let Self::Rate(rate) = self {
+            rate
+        } else {
+            panic!(
+                "`&self` is not `Rate`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    #[cfg(feature = "iso-codes")]
+    pub fn as_country_code(&self) -> &InputSchemaTypeCountryCode {
+        if let Self::CountryCode(country_code) = self {
+            country_code
+        } else {
+            panic!(
+                "`&self` is not `CountryCode`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    #[cfg(feature = "iso-codes")]
+    pub fn as_currency(&self) -> &InputSchemaTypeCurrency {
+        if let Self::Currency(currency) = self {
+            currency
+        } else {
+            panic!(
+                "`&self` is not `Currency`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_mime(&self) -> &InputSchemaTypeMime {
+        if let Self::Mime(mime) = self {
+            mime
+        } else {
+            panic!(
+                "`&self` is not `Mime`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_regex_value(&self) -> &InputSchemaTypeRegexValue {
+        if let Self::RegexValue(regex_value) = self {
+            regex_value
+        } else {
+            panic!(
+                "`&self` is not `RegexValue`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_recursive(&self) -> &InputSchemaTypeRecursive {
+        if let Self::Recursive(recursive) = self {
+            recursive
+        } else {
+            panic!(
+                "`&self` is not `Recursive`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_reference(&self) -> &InputSchemaTypeRef {
+        if let Self::Ref(reference) = self {
+            reference
+        } else {
+            panic!(
+                "`&self` is not `Ref`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    #[cfg(feature = "expr")]
+    pub fn as_expr(&self) -> &InputSchemaTypeExpr {
+        if let Self::Expr(expr) = self {
+            expr
+        } else {
+            panic!(
+                "`&self` is not `Expr`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_switch(&self) -> &InputSchemaTypeSwitch {
+        if let Self::Switch(switch) = self {
+            switch
+        } else {
+            panic!(
+                "`&self` is not `Switch`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn as_unknown(&self) -> &InputSchemaTypeUnknown {
+        if let Self::Unknown(unknown) = self {
+            unknown
+        } else {
+            panic!(
+                "`&self` is not `Unknown`. You should call `is_<TYPE>()` method before using any `as_<TYPE>()` method."
+            )
+        }
+    }
 }
 
 impl InputSchemaType {
@@ -603,6 +1239,210 @@ impl InputSchemaType {
             )
         }
     }
+
+    pub fn mut_secret(&mut self) -> &mut InputSchemaTypeSecret {
+        if let Self::Secret(secret) = self {
+            secret
+        } else {
+            panic!(
+                "`&self` is not `Secret`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    pub fn mut_decimal(&mut self) -> &mut InputSchemaTypeDecimal {
+        if let Self::Decimal(decimal) = self {
+            decimal
+        } else {
+            panic!(
+                "`&self` is not `Decimal`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_percent(&mut self) -> &mut InputSchemaTypePercent {
+        if let Self::Percent(percent) = self {
+            percent
+        } else {
+            panic!(
+                "`&self` is not `Percent`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_weighted_list(&mut self) -> &mut InputSchemaTypeWeightedList {
+        if let Self::WeightedList(weighted_list) = self {
+            weighted_list
+        } else {
+            panic!(
+                "`&self` is not `WeightedList`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_key_value(&mut self) -> &mut InputSchemaTypeKeyValue {
+        if let Self::KeyValue(key_value) = self {
+            key_value
+        } else {
+            panic!(
+                "`&self` is not `KeyValue`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_binary(&mut self) -> &mut InputSchemaTypeBinary {
+        if let Self::Binary(binary) = self {
+            binary
+        } else {
+            panic!(
+                "`&self` is not `Binary`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_tls(&mut self) -> &mut InputSchemaTypeTls {
+        if let Self::Tls(tls) = self {
+            tls
+        } else {
+            panic!(
+                "`&self` is not `Tls`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_dsn(&mut self) -> &mut InputSchemaTypeDsn {
+        if let Self::Dsn(dsn) = self {
+            dsn
+        } else {
+            panic!(
+                "`&self` is not `Dsn`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_interval(&mut self) -> &mut InputSchemaTypeInterval {
+        if let Self::Interval(interval) = self {
+            interval
+        } else {
+            panic!(
+                "`&self` is not `Interval`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_time_window(&mut self) -> &mut InputSchemaTypeTimeWindow {
+        if let Self::TimeWindow(time_window) = self {
+            time_window
+        } else {
+            panic!(
+                "`&self` is not `TimeWindow`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_rate(&mut self) -> &mut InputSchemaTypeRate {
+        if let Self::Rate(rate) = self {
+            rate
+        } else {
+            panic!(
+                "`&self` is not `Rate`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    #[cfg(feature = "iso-codes")]
+    pub fn mut_country_code(&mut self) -> &mut InputSchemaTypeCountryCode {
+        if let Self::CountryCode(country_code) = self {
+            country_code
+        } else {
+            panic!(
+                "`&self` is not `CountryCode`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    #[cfg(feature = "iso-codes")]
+    pub fn mut_currency(&mut self) -> &mut InputSchemaTypeCurrency {
+        if let Self::Currency(currency) = self {
+            currency
+        } else {
+            panic!(
+                "`&self` is not `Currency`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_mime(&mut self) -> &mut InputSchemaTypeMime {
+        if let Self::Mime(mime) = self {
+            mime
+        } else {
+            panic!(
+                "`&self` is not `Mime`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_regex_value(&mut self) -> &mut InputSchemaTypeRegexValue {
+        if let Self::RegexValue(regex_value) = self {
+            regex_value
+        } else {
+            panic!(
+                "`&self` is not `RegexValue`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_recursive(&mut self) -> &mut InputSchemaTypeRecursive {
+        if let Self::Recursive(recursive) = self {
+            recursive
+        } else {
+            panic!(
+                "`&self` is not `Recursive`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_reference(&mut self) -> &mut InputSchemaTypeRef {
+        if let Self::Ref(reference) = self {
+            reference
+        } else {
+            panic!(
+                "`&self` is not `Ref`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    #[cfg(feature = "expr")]
+    pub fn mut_expr(&mut self) -> &mut InputSchemaTypeExpr {
+        if let Self::Expr(expr) = self {
+            expr
+        } else {
+            panic!(
+                "`&self` is not `Expr`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_switch(&mut self) -> &mut InputSchemaTypeSwitch {
+        if let Self::Switch(switch) = self {
+            switch
+        } else {
+            panic!(
+                "`&self` is not `Switch`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
+
+    pub fn mut_unknown(&mut self) -> &mut InputSchemaTypeUnknown {
+        if let Self::Unknown(unknown) = self {
+            unknown
+        } else {
+            panic!(
+                "`&self` is not `Unknown`. You should call `is_<TYPE>()` method before using any `mut_<TYPE>()` method."
+            )
+        }
+    }
 }
 
 impl Default for InputSchemaType {
@@ -615,7 +1455,7 @@ impl From<InputSchemaType> for InputSchema {
     fn from(schema_type: InputSchemaType) -> Self {
         Self {
             schema_type: Box::new(schema_type),
-            maybe_default: None,
+            ..Self::new()
         }
     }
 }
@@ -662,6 +1502,130 @@ impl From<InputSchemaTypeEnum> for InputSchemaType {
     }
 }
 
+impl From<InputSchemaTypeSecret> for InputSchemaType {
+    fn from(secret: InputSchemaTypeSecret) -> Self {
+        Self::Secret(secret)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<InputSchemaTypeDecimal> for InputSchemaType {
+    fn from(decimal: InputSchemaTypeDecimal) -> Self {
+        Self::Decimal(decimal)
+    }
+}
+
+impl From<InputSchemaTypePercent> for InputSchemaType {
+    fn from(percent: InputSchemaTypePercent) -> Self {
+        Self::Percent(percent)
+    }
+}
+
+impl From<InputSchemaTypeWeightedList> for InputSchemaType {
+    fn from(weighted_list: InputSchemaTypeWeightedList) -> Self {
+        Self::WeightedList(weighted_list)
+    }
+}
+
+impl From<InputSchemaTypeKeyValue> for InputSchemaType {
+    fn from(key_value: InputSchemaTypeKeyValue) -> Self {
+        Self::KeyValue(key_value)
+    }
+}
+
+impl From<InputSchemaTypeBinary> for InputSchemaType {
+    fn from(binary: InputSchemaTypeBinary) -> Self {
+        Self::Binary(binary)
+    }
+}
+
+impl From<InputSchemaTypeTls> for InputSchemaType {
+    fn from(tls: InputSchemaTypeTls) -> Self {
+        Self::Tls(tls)
+    }
+}
+
+impl From<InputSchemaTypeDsn> for InputSchemaType {
+    fn from(dsn: InputSchemaTypeDsn) -> Self {
+        Self::Dsn(dsn)
+    }
+}
+
+impl From<InputSchemaTypeInterval> for InputSchemaType {
+    fn from(interval: InputSchemaTypeInterval) -> Self {
+        Self::Interval(interval)
+    }
+}
+
+impl From<InputSchemaTypeTimeWindow> for InputSchemaType {
+    fn from(time_window: InputSchemaTypeTimeWindow) -> Self {
+        Self::TimeWindow(time_window)
+    }
+}
+
+impl From<InputSchemaTypeRate> for InputSchemaType {
+    fn from(rate: InputSchemaTypeRate) -> Self {
+        Self::Rate(rate)
+    }
+}
+
+#[cfg(feature = "iso-codes")]
+impl From<InputSchemaTypeCountryCode> for InputSchemaType {
+    fn from(country_code: InputSchemaTypeCountryCode) -> Self {
+        Self::CountryCode(country_code)
+    }
+}
+
+#[cfg(feature = "iso-codes")]
+impl From<InputSchemaTypeCurrency> for InputSchemaType {
+    fn from(currency: InputSchemaTypeCurrency) -> Self {
+        Self::Currency(currency)
+    }
+}
+
+impl From<InputSchemaTypeMime> for InputSchemaType {
+    fn from(mime: InputSchemaTypeMime) -> Self {
+        Self::Mime(mime)
+    }
+}
+
+impl From<InputSchemaTypeRegexValue> for InputSchemaType {
+    fn from(regex_value: InputSchemaTypeRegexValue) -> Self {
+        Self::RegexValue(regex_value)
+    }
+}
+
+impl From<InputSchemaTypeRecursive> for InputSchemaType {
+    fn from(recursive: InputSchemaTypeRecursive) -> Self {
+        Self::Recursive(recursive)
+    }
+}
+
+impl From<InputSchemaTypeRef> for InputSchemaType {
+    fn from(reference: InputSchemaTypeRef) -> Self {
+        Self::Ref(reference)
+    }
+}
+
+#[cfg(feature = "expr")]
+impl From<InputSchemaTypeExpr> for InputSchemaType {
+    fn from(expr: InputSchemaTypeExpr) -> Self {
+        Self::Expr(expr)
+    }
+}
+
+impl From<InputSchemaTypeSwitch> for InputSchemaType {
+    fn from(switch: InputSchemaTypeSwitch) -> Self {
+        Self::Switch(switch)
+    }
+}
+
+impl From<InputSchemaTypeUnknown> for InputSchemaType {
+    fn from(unknown: InputSchemaTypeUnknown) -> Self {
+        Self::Unknown(unknown)
+    }
+}
+
 pub(crate) mod default {
     #[inline(always)]
     pub fn default_true() -> bool {
@@ -672,6 +1636,11 @@ pub(crate) mod default {
     pub fn default_port_zero() -> u16 {
         0
     }
+
+    #[inline(always)]
+    pub fn default_colon() -> String {
+        ":".to_string()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Error)]
@@ -682,7 +1651,7 @@ pub enum InputSchemaError {
         expected_type: String,
         input_type: String,
     },
-    #[error("Expected {schema_type:?}, got `{input}`")]
+    #[error("Expected {schema_type:?}, got `{input}`{}", suggest::enum_hint(.schema_type, .input))]
     Schema {
         position: InputPosition,
         schema_type: InputSchemaType,
@@ -715,6 +1684,73 @@ pub enum InputSchemaError {
         position: InputPosition,
         input: Input,
     },
+    // See `InputSchemaType::validate_with_options()`:
+    #[error("{position} validation aborted: {description}")]
+    Aborted {
+        position: InputPosition,
+        description: String,
+    },
+    // A broken invariant (i.e. a bug in this crate) that is reported instead of panicking, so
+    // that hosts validating untrusted inputs don't abort:
+    #[error("{position} internal error: {description}")]
+    Internal {
+        position: InputPosition,
+        description: String,
+    },
+    // The `title` and `description` of the nearest static map item that failed:
+    #[error("{context}: {source}")]
+    Context {
+        context: String,
+        source: Box<InputSchemaError>,
+    },
+}
+
+impl InputSchemaError {
+    pub fn position(&self) -> &InputPosition {
+        match self {
+            Self::Type { position, .. }
+            | Self::Schema { position, .. }
+            | Self::Size { position, .. }
+            | Self::Range { position, .. }
+            | Self::NotFound { position, .. }
+            | Self::Invalid { position, .. }
+            | Self::Aborted { position, .. }
+            | Self::Internal { position, .. } => position,
+            Self::Context { source, .. } => source.position(),
+        }
+    }
+
+    pub fn position_mut(&mut self) -> &mut InputPosition {
+        match self {
+            Self::Type { position, .. }
+            | Self::Schema { position, .. }
+            | Self::Size { position, .. }
+            | Self::Range { position, .. }
+            | Self::NotFound { position, .. }
+            | Self::Invalid { position, .. }
+            | Self::Aborted { position, .. }
+            | Self::Internal { position, .. } => position,
+            Self::Context { source, .. } => source.position_mut(),
+        }
+    }
+
+    // Without the `title` and `description` context:
+    pub fn inner(&self) -> &InputSchemaError {
+        match self {
+            Self::Context { source, .. } => source.inner(),
+            _ => self,
+        }
+    }
+
+    pub(crate) fn with_maybe_context(self, maybe_context: Option<String>) -> Self {
+        match (maybe_context, self) {
+            (Some(context), error) if !matches!(error, Self::Context { .. }) => Self::Context {
+                context,
+                source: Box::new(error),
+            },
+            (_, error) => error,
+        }
+    }
 }
 
 impl InputSchema {
@@ -722,6 +1758,12 @@ impl InputSchema {
         Self {
             schema_type: Default::default(),
             maybe_default: Default::default(),
+            maybe_title: Default::default(),
+            maybe_description: Default::default(),
+            immutable: Default::default(),
+            volatile: Default::default(),
+            maybe_reload: Default::default(),
+            maybe_visibility: Default::default(),
         }
     }
 
@@ -734,7 +1776,7 @@ impl InputSchema {
     }
 
     pub fn set_schema_type<T: Into<InputSchemaType>>(&mut self, schema_type: T) {
-        self.schema_type = Box::new(schema_type.into());
+        *self.schema_type = schema_type.into();
     }
 
     pub fn with_schema_type<T: Into<InputSchemaType>>(mut self, schema_type: T) -> Self {
@@ -767,6 +1809,145 @@ impl InputSchema {
         self.set_maybe_default(default);
         self
     }
+
+    pub fn maybe_title(&self) -> Option<&String> {
+        self.maybe_title.as_ref()
+    }
+
+    pub fn maybe_title_mut(&mut self) -> &mut Option<String> {
+        &mut self.maybe_title
+    }
+
+    pub fn set_title<T: ToString>(&mut self, title: T) {
+        self.maybe_title = Some(title.to_string())
+    }
+
+    pub fn with_title<T: ToString>(mut self, title: T) -> Self {
+        self.set_title(title);
+        self
+    }
+
+    pub fn maybe_description(&self) -> Option<&String> {
+        self.maybe_description.as_ref()
+    }
+
+    pub fn maybe_description_mut(&mut self) -> &mut Option<String> {
+        &mut self.maybe_description
+    }
+
+    pub fn set_description<T: ToString>(&mut self, description: T) {
+        self.maybe_description = Some(description.to_string())
+    }
+
+    pub fn with_description<T: ToString>(mut self, description: T) -> Self {
+        self.set_description(description);
+        self
+    }
+
+    pub fn immutable(&self) -> bool {
+        self.immutable
+    }
+
+    pub fn set_immutable(&mut self, immutable: bool) {
+        self.immutable = immutable
+    }
+
+    pub fn with_immutable(mut self, immutable: bool) -> Self {
+        self.set_immutable(immutable);
+        self
+    }
+
+    pub fn volatile(&self) -> bool {
+        self.volatile
+    }
+
+    pub fn set_volatile(&mut self, volatile: bool) {
+        self.volatile = volatile
+    }
+
+    pub fn with_volatile(mut self, volatile: bool) -> Self {
+        self.set_volatile(volatile);
+        self
+    }
+
+    pub fn maybe_reload(&self) -> Option<InputSchemaReload> {
+        self.maybe_reload
+    }
+
+    pub fn maybe_reload_mut(&mut self) -> &mut Option<InputSchemaReload> {
+        &mut self.maybe_reload
+    }
+
+    pub fn set_reload(&mut self, reload: InputSchemaReload) {
+        self.maybe_reload = Some(reload)
+    }
+
+    pub fn with_reload(mut self, reload: InputSchemaReload) -> Self {
+        self.set_reload(reload);
+        self
+    }
+
+    // `immutable` items are `Immutable` and items without a reload are `Hot`:
+    pub fn reload(&self) -> InputSchemaReload {
+        if self.immutable {
+            InputSchemaReload::Immutable
+        } else {
+            self.maybe_reload.unwrap_or_default()
+        }
+    }
+
+    pub fn maybe_visibility(&self) -> Option<InputSchemaVisibility> {
+        self.maybe_visibility
+    }
+
+    pub fn maybe_visibility_mut(&mut self) -> &mut Option<InputSchemaVisibility> {
+        &mut self.maybe_visibility
+    }
+
+    pub fn set_visibility(&mut self, visibility: InputSchemaVisibility) {
+        self.maybe_visibility = Some(visibility)
+    }
+
+    pub fn with_visibility(mut self, visibility: InputSchemaVisibility) -> Self {
+        self.set_visibility(visibility);
+        self
+    }
+
+    // Items without a visibility are `Public` and `secret` schemas are `Secret`:
+    pub fn visibility(&self) -> InputSchemaVisibility {
+        if matches!(self.schema_type(), InputSchemaType::Secret(_)) {
+            InputSchemaVisibility::Secret
+        } else {
+            self.maybe_visibility.unwrap_or_default()
+        }
+    }
+
+    // `title (description)` for error messages:
+    pub(crate) fn maybe_context(&self) -> Option<String> {
+        match (self.maybe_title.as_ref(), self.maybe_description.as_ref()) {
+            (Some(title), Some(description)) => Some(format!("{title} ({description})")),
+            (Some(text), None) | (None, Some(text)) => Some(text.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+// `validate()` of the types that implement `validate_cow()`:
+pub(crate) fn validate_with_cow<F>(
+    input: &mut Input,
+    validate_cow: F,
+) -> Result<(), InputSchemaError>
+where
+    F: for<'a> FnOnce(&'a Input) -> Result<Cow<'a, Input>, InputSchemaError>,
+{
+    let maybe_validated_input = match validate_cow(input)? {
+        Cow::Owned(validated_input) => Some(validated_input),
+        Cow::Borrowed(_) => None,
+    };
+    if let Some(validated_input) = maybe_validated_input {
+        *input = validated_input;
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -774,6 +1955,37 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn validate_cow() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "either", "schema_list": [{"type": "boolean"}, {"type": "port"}]}},
+                "hosts": {"schema": {"type": "list", "item_schema": {"type": "string"}}},
+                "debug": {"schema": {"type": "boolean"}, "default": false},
+            }
+        }))
+        .unwrap();
+        let valid: Input =
+            serde_json::from_value(json!({"port": 80, "hosts": ["a", "b"], "debug": true}))
+                .unwrap();
+        assert!(matches!(
+            schema_type.validate_cow(&valid, None),
+            Ok(Cow::Borrowed(_))
+        ));
+        let input: Input = serde_json::from_value(json!({"port": "80", "hosts": ["a"]})).unwrap();
+        let expected: Input =
+            serde_json::from_value(json!({"port": 80, "hosts": ["a"], "debug": false})).unwrap();
+        assert_eq!(
+            schema_type.validate_cow(&input, None).unwrap(),
+            Cow::<Input>::Owned(expected)
+        );
+        let invalid: Input = serde_json::from_value(json!({"port": "80", "hosts": [1]})).unwrap();
+        let mut invalid_copy = invalid.clone();
+        assert!(schema_type.validate(&mut invalid_copy, None).is_err());
+        assert_eq!(invalid_copy, invalid);
+    }
+
     #[test]
     fn serde() {
         let json = json!({
@@ -0,0 +1,115 @@
+use crate::{schema::InputSchemaType, Input};
+use std::collections::HashMap;
+
+// OpenAPI 3.1 Schema Objects for `components/schemas`, e.g. to publish the configuration of a
+// plugin over REST. The result maps component names to schemas: `name` is the component of the
+// whole schema and each recursive schema is another component (`<name>.<recursive name>`) since
+// `$anchor`s are not supported by most OpenAPI tools. `name` should only have letters, digits,
+// `.`, `-`, and `_`:
+pub fn to_openapi_component<N: AsRef<str>>(name: N, schema_type: &InputSchemaType) -> Input {
+    let name = name.as_ref();
+    let mut component_map = HashMap::new();
+    let mut input = schema_type.to_json_schema();
+    hoist_anchors(&mut input, name, &mut component_map);
+    component_map.insert(name.to_string(), input);
+    Input::from(component_map)
+}
+
+// Values of these keywords are not schemas:
+const VALUE_KEYWORD_LIST: [&str; 4] = ["enum", "const", "default", "examples"];
+
+fn hoist_anchors(input: &mut Input, name: &str, component_map: &mut HashMap<String, Input>) {
+    crate::stack::maybe_grow(|| match input {
+        Input::Map(map) => {
+            for (key, inner_input) in map.iter_mut() {
+                match (&**key, inner_input) {
+                    (key, _) if VALUE_KEYWORD_LIST.contains(&key) => (),
+                    // Keys of `properties` are names of properties:
+                    ("properties", Input::Map(property_map)) => property_map
+                        .values_mut()
+                        .for_each(|schema| hoist_anchors(schema, name, component_map)),
+                    (_, inner_input) => hoist_anchors(inner_input, name, component_map),
+                }
+            }
+            if let Some(Input::Str(reference)) = map.get_mut("$ref") {
+                if let Some(anchor) = reference
+                    .strip_prefix('#')
+                    .filter(|anchor| !anchor.starts_with('/'))
+                {
+                    *reference = component_reference(name, anchor);
+                }
+            }
+            if let Some(anchor) = map.remove("$anchor").filter(Input::is_str) {
+                let anchor = anchor.into_str();
+                let reference = component_reference(name, &anchor);
+                let component = std::mem::replace(
+                    input,
+                    Input::from(HashMap::from([(
+                        "$ref".to_string(),
+                        Input::from(reference),
+                    )])),
+                );
+                component_map.insert(format!("{name}.{anchor}"), component);
+            }
+        }
+        Input::List(list) => list
+            .iter_mut()
+            .for_each(|inner_input| hoist_anchors(inner_input, name, component_map)),
+        _ => (),
+    })
+}
+
+fn component_reference(name: &str, anchor: &str) -> String {
+    format!("#/components/schemas/{name}.{anchor}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn to_openapi_component() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "port"}, "default": 80},
+                "routes": {"schema": {"type": "recursive", "name": "route", "schema": {
+                    "type": "static_map",
+                    "items": {
+                        "path": {"schema": {"type": "string"}},
+                        "children": {"schema": {"type": "list", "item_schema": {"type": "ref", "name": "route"}}, "default": []},
+                    }
+                }}},
+            }
+        }))
+        .unwrap();
+        let expected: Input = serde_json::from_value(json!({
+            "config": {
+                "type": "object",
+                "properties": {
+                    "port": {"type": "integer", "minimum": 0, "maximum": 65535, "default": 80},
+                    "routes": {"$ref": "#/components/schemas/config.route"},
+                },
+                "required": ["routes"]
+            },
+            "config.route": {
+                "type": "object",
+                "properties": {
+                    "path": {"type": "string"},
+                    "children": {
+                        "type": "array",
+                        "items": {"$ref": "#/components/schemas/config.route"},
+                        "default": []
+                    },
+                },
+                "required": ["path"]
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            super::to_openapi_component("config", &schema_type),
+            expected
+        );
+    }
+}
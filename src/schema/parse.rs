@@ -0,0 +1,120 @@
+use crate::schema::{InputSchema, InputSchemaType};
+#[cfg(feature = "json")]
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputSchemaParseError {
+    #[cfg(feature = "json")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[cfg(feature = "toml")]
+    #[error(transparent)]
+    Toml(#[from] ::toml::de::Error),
+    #[cfg(feature = "yaml")]
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+}
+
+// Schemas embedded as string literals in plugin code (e.g. `r#"{"schema": {"type": "port"}}"#`).
+// `FromStr` and `TryFrom<&str>` expect JSON:
+impl InputSchema {
+    #[cfg(feature = "json")]
+    pub fn from_json_str(text: &str) -> Result<Self, InputSchemaParseError> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(text: &str) -> Result<Self, InputSchemaParseError> {
+        Ok(::toml::from_str(text)?)
+    }
+
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_str(text: &str) -> Result<Self, InputSchemaParseError> {
+        Ok(serde_yaml::from_str(text)?)
+    }
+}
+
+impl InputSchemaType {
+    #[cfg(feature = "json")]
+    pub fn from_json_str(text: &str) -> Result<Self, InputSchemaParseError> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(text: &str) -> Result<Self, InputSchemaParseError> {
+        Ok(::toml::from_str(text)?)
+    }
+
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_str(text: &str) -> Result<Self, InputSchemaParseError> {
+        Ok(serde_yaml::from_str(text)?)
+    }
+}
+
+#[cfg(feature = "json")]
+impl FromStr for InputSchema {
+    type Err = InputSchemaParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::from_json_str(text)
+    }
+}
+
+#[cfg(feature = "json")]
+impl TryFrom<&str> for InputSchema {
+    type Error = InputSchemaParseError;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        Self::from_json_str(text)
+    }
+}
+
+#[cfg(feature = "json")]
+impl FromStr for InputSchemaType {
+    type Err = InputSchemaParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::from_json_str(text)
+    }
+}
+
+#[cfg(feature = "json")]
+impl TryFrom<&str> for InputSchemaType {
+    type Error = InputSchemaParseError;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        Self::from_json_str(text)
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let schema: InputSchema =
+            r#"{"schema": {"type": "port"}, "default": 8080}"#.parse().unwrap();
+        assert!(schema.schema_type().is_port());
+        assert!(InputSchemaType::try_from(r#"{"type": "port"}"#)
+            .unwrap()
+            .is_port());
+        assert!(matches!(
+            "{\"type\": \"prot\"}".parse::<InputSchemaType>(),
+            Err(InputSchemaParseError::Json(_))
+        ));
+    }
+
+    #[cfg(all(feature = "toml", feature = "yaml"))]
+    #[test]
+    fn from_toml_and_yaml_str() {
+        let schema_type = InputSchemaType::from_toml_str("type = \"port\"").unwrap();
+        assert_eq!(
+            InputSchemaType::from_yaml_str("type: port").unwrap(),
+            schema_type
+        );
+        let schema = InputSchema::from_yaml_str("schema:\n  type: port\ndefault: 80\n").unwrap();
+        assert_eq!(schema.schema_type(), &schema_type);
+    }
+}
@@ -0,0 +1,386 @@
+use crate::{
+    position::{InputPosition, InputPositionType},
+    schema::{
+        types::recursive::RefScope, InputSchemaType, InputSchemaTypeRecursive,
+        InputSchemaTypeSecret,
+    },
+};
+
+impl InputSchemaType {
+    // Positions of all values declared by (nested) static maps, in the order of their
+    // `ordered_items()`. Dynamic maps, lists, and other types are leaves since their keys or
+    // indexes are not known in advance:
+    pub fn path_list(&self) -> Vec<InputPosition> {
+        let mut path_list = Vec::new();
+        collect_path_list(self, InputPosition::new(), &mut path_list);
+        path_list
+    }
+
+    // A standalone schema of the values at the position (e.g. to hand a plugin only the part of
+    // the configuration it owns), or `None` if the schema doesn't declare the position. The first
+    // `either` branch that declares it is used, and the result stays a secret if the position is
+    // inside a secret. Refs are followed up to the `max_depth` of their recursive schema, and refs
+    // in the result to recursive schemas outside of it are replaced with those schemas:
+    pub fn subschema(&self, position: &InputPosition) -> Option<InputSchemaType> {
+        let position_type_list: Vec<_> = position.iter().collect();
+        subschema(self, &position_type_list, false, &mut RefScope::default())
+    }
+
+    // True only if the schema declares the position and no value at or inside it can be a secret
+    // in any `either` branch that declares it (e.g. to decide what may be shown or passed as a
+    // flag). Refs beyond the `max_depth` of their recursive schema and unknown types are not
+    // public:
+    pub fn is_public_at(&self, position: &InputPosition) -> bool {
+        let position_type_list: Vec<_> = position.iter().collect();
+        is_public_at(self, &position_type_list, &mut RefScope::default()).unwrap_or(false)
+    }
+
+    // True if the position is (inside) a `volatile` static map item:
+    pub fn is_volatile_at(&self, position: &InputPosition) -> bool {
+        let position_type_list: Vec<_> = position.iter().collect();
+        is_volatile_at(self, &position_type_list)
+    }
+}
+
+fn subschema<'a>(
+    schema_type: &'a InputSchemaType,
+    position_type_list: &[&InputPositionType],
+    is_secret: bool,
+    scope: &mut RefScope<'a>,
+) -> Option<InputSchemaType> {
+    crate::stack::maybe_grow(|| {
+        let Some((position_type, rest)) = position_type_list.split_first() else {
+            let mut schema_type = schema_type.clone();
+            close_refs(&mut schema_type, scope, &mut Vec::new());
+            return Some(match schema_type {
+                InputSchemaType::Secret(_) => schema_type,
+                _ if is_secret => InputSchemaTypeSecret::default()
+                    .with_inner_schema(schema_type)
+                    .into(),
+                _ => schema_type,
+            });
+        };
+        match (schema_type, position_type) {
+            (InputSchemaType::Secret(secret), _) => {
+                subschema(secret.inner_schema(), position_type_list, true, scope)
+            }
+            (InputSchemaType::Either(either), _) => {
+                either.schema_list().iter().find_map(|schema_type| {
+                    subschema(schema_type, position_type_list, is_secret, scope)
+                })
+            }
+            (InputSchemaType::StaticMap(static_map), InputPositionType::Key(key)) => {
+                let schema = static_map.item_schema(key)?;
+                subschema(schema.schema_type(), rest, is_secret, scope)
+            }
+            (InputSchemaType::DynamicMap(dynamic_map), InputPositionType::Key(_)) => {
+                subschema(dynamic_map.item_schema(), rest, is_secret, scope)
+            }
+            (InputSchemaType::KeyValue(key_value), InputPositionType::Key(_)) => {
+                subschema(key_value.value_schema(), rest, is_secret, scope)
+            }
+            (InputSchemaType::List(list), InputPositionType::Index(_)) => {
+                subschema(list.item_schema(), rest, is_secret, scope)
+            }
+            (InputSchemaType::Recursive(recursive), _) => {
+                scope.with_recursive(recursive, |scope| {
+                    subschema(
+                        recursive.inner_schema(),
+                        position_type_list,
+                        is_secret,
+                        scope,
+                    )
+                })
+            }
+            (InputSchemaType::Ref(reference), _) => scope
+                .with_ref(reference.name(), |scope, schema_type| {
+                    subschema(schema_type, position_type_list, is_secret, scope)
+                })
+                .ok()
+                .flatten(),
+            _ => None,
+        }
+    })
+}
+
+// `None` if the position is not declared:
+fn is_public_at<'a>(
+    schema_type: &'a InputSchemaType,
+    position_type_list: &[&InputPositionType],
+    scope: &mut RefScope<'a>,
+) -> Option<bool> {
+    crate::stack::maybe_grow(|| {
+        let Some((position_type, rest)) = position_type_list.split_first() else {
+            return Some(!may_be_secret(schema_type, scope, &mut Vec::new()));
+        };
+        match (schema_type, position_type) {
+            (InputSchemaType::Secret(_), _) => Some(false),
+            (InputSchemaType::Either(either), _) => {
+                let declared_list: Vec<_> = either
+                    .schema_list()
+                    .iter()
+                    .filter_map(|schema_type| is_public_at(schema_type, position_type_list, scope))
+                    .collect();
+                if declared_list.is_empty() {
+                    None
+                } else {
+                    Some(declared_list.into_iter().all(|is_public| is_public))
+                }
+            }
+            (InputSchemaType::StaticMap(static_map), InputPositionType::Key(key)) => {
+                let schema = static_map.item_schema(key)?;
+                is_public_at(schema.schema_type(), rest, scope)
+            }
+            (InputSchemaType::DynamicMap(dynamic_map), InputPositionType::Key(_)) => {
+                is_public_at(dynamic_map.item_schema(), rest, scope)
+            }
+            (InputSchemaType::KeyValue(key_value), InputPositionType::Key(_)) => {
+                is_public_at(key_value.value_schema(), rest, scope)
+            }
+            (InputSchemaType::List(list), InputPositionType::Index(_)) => {
+                is_public_at(list.item_schema(), rest, scope)
+            }
+            (InputSchemaType::Recursive(recursive), _) => scope
+                .with_recursive(recursive, |scope| {
+                    is_public_at(recursive.inner_schema(), position_type_list, scope)
+                }),
+            (InputSchemaType::Ref(reference), _) => scope
+                .with_ref(reference.name(), |scope, schema_type| {
+                    is_public_at(schema_type, position_type_list, scope)
+                })
+                .unwrap_or(Some(false)),
+            (InputSchemaType::Unknown(_), _) => Some(false),
+            _ => None,
+        }
+    })
+}
+
+// Each recursive schema is walked once, since walking it again through a ref finds nothing new:
+fn may_be_secret<'a>(
+    schema_type: &'a InputSchemaType,
+    scope: &RefScope<'a>,
+    walked_list: &mut Vec<&'a String>,
+) -> bool {
+    crate::stack::maybe_grow(|| match schema_type {
+        InputSchemaType::Secret(_) | InputSchemaType::Unknown(_) => true,
+        InputSchemaType::Recursive(recursive) => {
+            recursive_may_be_secret(recursive, scope, walked_list)
+        }
+        InputSchemaType::Ref(reference) if walked_list.contains(&reference.name()) => false,
+        InputSchemaType::Ref(reference) => scope
+            .find(reference.name())
+            .is_none_or(|recursive| recursive_may_be_secret(recursive, scope, walked_list)),
+        _ => schema_type
+            .child_list()
+            .into_iter()
+            .any(|schema_type| may_be_secret(schema_type, scope, walked_list)),
+    })
+}
+
+fn recursive_may_be_secret<'a>(
+    recursive: &'a InputSchemaTypeRecursive,
+    scope: &RefScope<'a>,
+    walked_list: &mut Vec<&'a String>,
+) -> bool {
+    walked_list.push(recursive.name());
+    let may_be_secret = may_be_secret(recursive.inner_schema(), scope, walked_list);
+    walked_list.pop();
+    may_be_secret
+}
+
+// Replaces refs that are not inside a recursive schema with the same name with the (innermost)
+// enclosing recursive schema of the scope, so the subschema can be validated on its own:
+fn close_refs(schema_type: &mut InputSchemaType, scope: &RefScope, bound_list: &mut Vec<String>) {
+    crate::stack::maybe_grow(|| match schema_type {
+        InputSchemaType::Recursive(recursive) => {
+            bound_list.push(recursive.name().clone());
+            close_refs(recursive.inner_schema_mut(), scope, bound_list);
+            bound_list.pop();
+        }
+        InputSchemaType::Ref(reference) if !bound_list.contains(reference.name()) => {
+            if let Some(recursive) = scope.find(reference.name()) {
+                *schema_type = InputSchemaType::Recursive(recursive.clone());
+                close_refs(schema_type, scope, bound_list)
+            }
+        }
+        _ => schema_type
+            .child_list_mut()
+            .into_iter()
+            .for_each(|schema_type| close_refs(schema_type, scope, bound_list)),
+    })
+}
+
+fn collect_path_list(
+    schema_type: &InputSchemaType,
+    position: InputPosition,
+    path_list: &mut Vec<InputPosition>,
+) {
+    crate::stack::maybe_grow(|| match schema_type {
+        InputSchemaType::StaticMap(static_map) => {
+            static_map
+                .ordered_items()
+                .into_iter()
+                .for_each(|(key, schema)| {
+                    collect_path_list(schema.schema_type(), position.new_with_key(key), path_list)
+                })
+        }
+        InputSchemaType::Secret(secret) => {
+            collect_path_list(secret.inner_schema(), position, path_list)
+        }
+        InputSchemaType::Either(either) => either
+            .schema_list()
+            .iter()
+            .for_each(|schema_type| collect_path_list(schema_type, position.clone(), path_list)),
+        _ => {
+            if !path_list.contains(&position) {
+                path_list.push(position)
+            }
+        }
+    })
+}
+
+fn is_volatile_at(
+    schema_type: &InputSchemaType,
+    position_type_list: &[&InputPositionType],
+) -> bool {
+    crate::stack::maybe_grow(|| {
+        let Some((position_type, rest)) = position_type_list.split_first() else {
+            return false;
+        };
+        match (schema_type, position_type) {
+            (InputSchemaType::Secret(secret), _) => {
+                is_volatile_at(secret.inner_schema(), position_type_list)
+            }
+            (InputSchemaType::Either(either), _) => either
+                .schema_list()
+                .iter()
+                .any(|schema_type| is_volatile_at(schema_type, position_type_list)),
+            (InputSchemaType::StaticMap(static_map), InputPositionType::Key(key)) => {
+                static_map.items().get(&**key).is_some_and(|schema| {
+                    schema.volatile() || is_volatile_at(schema.schema_type(), rest)
+                })
+            }
+            (InputSchemaType::DynamicMap(dynamic_map), InputPositionType::Key(_)) => {
+                is_volatile_at(dynamic_map.item_schema(), rest)
+            }
+            (InputSchemaType::KeyValue(key_value), InputPositionType::Key(_)) => {
+                is_volatile_at(key_value.value_schema(), rest)
+            }
+            (InputSchemaType::List(list), InputPositionType::Index(_)) => {
+                is_volatile_at(list.item_schema(), rest)
+            }
+            _ => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn path_list() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "server": {"schema": {"type": "static_map", "items": {
+                    "port": {"schema": {"type": "port"}},
+                    "host": {"schema": {"type": "either", "schema_list": [{"type": "ip"}, {"type": "string"}]}},
+                }}},
+                "headers": {"schema": {"type": "dynamic_map"}},
+            }
+        }))
+        .unwrap();
+        let path_list: Vec<_> = schema_type
+            .path_list()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(path_list, ["headers", "[server][host]", "[server][port]"]);
+        assert_eq!(
+            InputSchemaType::new_integer().path_list(),
+            [InputPosition::new()]
+        );
+    }
+
+    #[test]
+    fn subschema() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "plugins": {"schema": {"type": "dynamic_map", "item_schema": {
+                    "type": "static_map",
+                    "items": {"port": {"schema": {"type": "port"}}},
+                }}},
+                "token": {"schema": {"type": "secret", "schema": {"type": "list", "item_schema": {"type": "string"}}}},
+            }
+        }))
+        .unwrap();
+        let position = InputPosition::new()
+            .new_with_key("plugins")
+            .new_with_key("web");
+        let subschema = schema_type.subschema(&position).unwrap();
+        assert!(matches!(subschema, InputSchemaType::StaticMap(_)));
+        assert_eq!(
+            subschema.subschema(&InputPosition::new().new_with_key("port")),
+            Some(InputSchemaType::new_port())
+        );
+        assert!(matches!(
+            schema_type.subschema(&InputPosition::new().new_with_key("token").new_with_index(0)),
+            Some(InputSchemaType::Secret(_))
+        ));
+        assert_eq!(
+            schema_type.subschema(&InputPosition::new().new_with_key("user")),
+            None
+        );
+    }
+
+    #[test]
+    fn subschema_recursive() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "recursive",
+            "name": "tree",
+            "max_depth": 2,
+            "schema": {
+                "type": "static_map",
+                "items": {
+                    "password": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                    "children": {
+                        "schema": {"type": "list", "item_schema": {"type": "ref", "name": "tree"}},
+                        "default": [],
+                    },
+                }
+            }
+        }))
+        .unwrap();
+        let position = InputPosition::new()
+            .new_with_key("children")
+            .new_with_index(0)
+            .new_with_key("password");
+        assert!(schema_type.subschema(&position).unwrap().is_secret());
+        // Refs to the enclosing tree are replaced, so the subschema validates on its own:
+        let subschema = schema_type
+            .subschema(
+                &InputPosition::new()
+                    .new_with_key("children")
+                    .new_with_index(0),
+            )
+            .unwrap();
+        let mut input: crate::Input = serde_json::from_value(json!({
+            "password": "hunter3",
+            "children": [{"password": "hunter4"}],
+        }))
+        .unwrap();
+        subschema.validate(&mut input, None).unwrap();
+        let position = InputPosition::new()
+            .new_with_key("children")
+            .new_with_index(0)
+            .new_with_key("children")
+            .new_with_index(0)
+            .new_with_key("children")
+            .new_with_index(0)
+            .new_with_key("password");
+        assert_eq!(schema_type.subschema(&position), None);
+    }
+}
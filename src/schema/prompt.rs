@@ -0,0 +1,178 @@
+use crate::{
+    position::InputPosition,
+    schema::{InputSchema, InputSchemaError, InputSchemaType},
+    Input,
+};
+
+// A question for each value declared by (nested) static maps, e.g. for interactive `init`
+// commands. Answers are usually strings that validation coerces (see `apply_answers()`):
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputPrompt {
+    position: InputPosition,
+    schema_type: InputSchemaType,
+    maybe_title: Option<String>,
+    maybe_description: Option<String>,
+    maybe_default: Option<Input>,
+    option_list: Vec<Input>,
+    is_secret: bool,
+}
+
+impl InputPrompt {
+    pub fn position(&self) -> &InputPosition {
+        &self.position
+    }
+
+    pub fn schema_type(&self) -> &InputSchemaType {
+        &self.schema_type
+    }
+
+    pub fn maybe_title(&self) -> Option<&String> {
+        self.maybe_title.as_ref()
+    }
+
+    pub fn maybe_description(&self) -> Option<&String> {
+        self.maybe_description.as_ref()
+    }
+
+    pub fn maybe_default(&self) -> Option<&Input> {
+        self.maybe_default.as_ref()
+    }
+
+    // The values of `enum` types:
+    pub fn option_list(&self) -> &Vec<Input> {
+        &self.option_list
+    }
+
+    // Answers should not be echoed:
+    pub fn is_secret(&self) -> bool {
+        self.is_secret
+    }
+
+    // The title, or the position if there is no title, followed by the expected value (e.g.
+    // `port (integer in range 1 to 65535)`):
+    pub fn question(&self) -> String {
+        let name = self
+            .maybe_title
+            .clone()
+            .unwrap_or_else(|| self.position.to_string());
+        format!("{name} ({})", self.schema_type)
+    }
+}
+
+// Prompts follow `InputSchemaTypeStaticMap::ordered_items()`. A schema that is not a static map has one prompt for the root:
+pub fn prompts(schema_type: &InputSchemaType) -> Vec<InputPrompt> {
+    let mut prompt_list = Vec::new();
+    collect_prompt_list(schema_type, None, InputPosition::new(), &mut prompt_list);
+    prompt_list
+}
+
+// Missing answers are left to the defaults of the schema:
+pub fn apply_answers<I: IntoIterator<Item = (InputPosition, Input)>>(
+    schema_type: &InputSchemaType,
+    answer_list: I,
+) -> Result<Input, InputSchemaError> {
+    let mut input = Input::new_map();
+    for (position, answer) in answer_list {
+        if position.is_empty() {
+            input = answer;
+        } else if !input.insert_at(&position, answer.clone()) {
+            return Err(InputSchemaError::Invalid {
+                description: "Could not set answer".to_string(),
+                position,
+                input: answer,
+            });
+        }
+    }
+    schema_type.validate(&mut input, None)?;
+    Ok(input)
+}
+
+fn collect_prompt_list(
+    schema_type: &InputSchemaType,
+    maybe_schema: Option<&InputSchema>,
+    position: InputPosition,
+    prompt_list: &mut Vec<InputPrompt>,
+) {
+    crate::stack::maybe_grow(|| {
+        if let InputSchemaType::StaticMap(static_map) = schema_type {
+            static_map
+                .ordered_items()
+                .into_iter()
+                .for_each(|(key, schema)| {
+                    collect_prompt_list(
+                        schema.schema_type(),
+                        Some(schema),
+                        position.new_with_key(key),
+                        prompt_list,
+                    )
+                });
+            return;
+        }
+        let (is_secret, prompt_schema_type) = match schema_type {
+            InputSchemaType::Secret(secret) => (true, secret.inner_schema()),
+            _ => (false, schema_type),
+        };
+        let option_list = match prompt_schema_type {
+            InputSchemaType::Enum(r#enum) => r#enum.value_list().clone(),
+            _ => Vec::new(),
+        };
+        prompt_list.push(InputPrompt {
+            position,
+            schema_type: schema_type.clone(),
+            maybe_title: maybe_schema.and_then(|schema| schema.maybe_title().cloned()),
+            maybe_description: maybe_schema.and_then(|schema| schema.maybe_description().cloned()),
+            maybe_default: maybe_schema.and_then(|schema| schema.maybe_default().cloned()),
+            option_list,
+            is_secret,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn prompts() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "server": {"schema": {"type": "static_map", "items": {
+                    "port": {"schema": {"type": "integer"}, "default": 80, "title": "Port"},
+                    "password": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                }}},
+                "level": {"schema": {"type": "enum", "items": ["info", "debug"]}},
+            }
+        }))
+        .unwrap();
+        let prompt_list = super::prompts(&schema_type);
+        let position_list: Vec<_> = prompt_list
+            .iter()
+            .map(|prompt| prompt.position().to_string())
+            .collect();
+        assert_eq!(
+            position_list,
+            ["level", "[server][password]", "[server][port]"]
+        );
+        assert_eq!(prompt_list[0].option_list().len(), 2);
+        assert!(prompt_list[1].is_secret());
+        assert_eq!(prompt_list[2].maybe_default(), Some(&Input::from(80)));
+        assert!(prompt_list[2].question().starts_with("Port ("));
+
+        let input = apply_answers(
+            &schema_type,
+            [
+                (prompt_list[0].position().clone(), Input::from("debug")),
+                (prompt_list[1].position().clone(), Input::from("hunter2")),
+            ],
+        )
+        .unwrap();
+        let expected: Input = serde_json::from_value(
+            json!({"level": "debug", "server": {"password": "hunter2", "port": 80}}),
+        )
+        .unwrap();
+        assert_eq!(input, expected);
+        assert!(apply_answers(&schema_type, []).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    schema::{InputSchema, InputSchemaError},
+    Input,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, RwLock},
+};
+use thiserror::Error;
+
+// Schemas by name, e.g. for plugins (compiled as separate crates) that register their schemas
+// when they're loaded so that hosts can validate by name. See `SchemaRegistry::global()`:
+#[derive(Debug, Default)]
+pub struct SchemaRegistry {
+    schema_map: RwLock<HashMap<String, Arc<InputSchema>>>,
+}
+
+#[derive(Debug, Error)]
+pub enum SchemaRegistryError {
+    #[error("Schema `{name}` is not registered")]
+    NotFound { name: String },
+    #[error(transparent)]
+    Schema(Box<InputSchemaError>),
+}
+
+static GLOBAL: OnceLock<SchemaRegistry> = OnceLock::new();
+
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // Created at first use:
+    pub fn global() -> &'static SchemaRegistry {
+        GLOBAL.get_or_init(SchemaRegistry::new)
+    }
+
+    // Returns the previously registered schema:
+    pub fn register<N: ToString, S: Into<InputSchema>>(
+        &self,
+        name: N,
+        schema: S,
+    ) -> Option<Arc<InputSchema>> {
+        self.schema_map
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(name.to_string(), Arc::new(schema.into()))
+    }
+
+    pub fn unregister(&self, name: &str) -> Option<Arc<InputSchema>> {
+        self.schema_map
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<InputSchema>> {
+        self.schema_map
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(name)
+            .cloned()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn name_list(&self) -> Vec<String> {
+        let mut name_list: Vec<_> = self
+            .schema_map
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .keys()
+            .cloned()
+            .collect();
+        name_list.sort();
+        name_list
+    }
+
+    // The lock is not held while validating, so schemas may be (re)registered meanwhile:
+    pub fn validate(&self, name: &str, input: &mut Input) -> Result<(), SchemaRegistryError> {
+        let schema = self
+            .get(name)
+            .ok_or_else(|| SchemaRegistryError::NotFound {
+                name: name.to_string(),
+            })?;
+        schema
+            .schema_type()
+            .validate(input, None)
+            .map_err(|error| SchemaRegistryError::Schema(Box::new(error)))
+    }
+}
+
+// Registers in `SchemaRegistry::global()`:
+pub fn register<N: ToString, S: Into<InputSchema>>(name: N, schema: S) -> Option<Arc<InputSchema>> {
+    SchemaRegistry::global().register(name, schema)
+}
+
+// Validates with a schema of `SchemaRegistry::global()`:
+pub fn validate(name: &str, input: &mut Input) -> Result<(), SchemaRegistryError> {
+    SchemaRegistry::global().validate(name, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::InputSchemaType;
+
+    #[test]
+    fn global() {
+        let schema = InputSchema::new().with_schema_type(InputSchemaType::new_port());
+        assert!(super::register("registry-test", schema).is_none());
+        let mut input = Input::from("8080");
+        super::validate("registry-test", &mut input).unwrap();
+        assert_eq!(input, Input::from(8080));
+        assert!(matches!(
+            super::validate("registry-test", &mut Input::from("http")),
+            Err(SchemaRegistryError::Schema(_))
+        ));
+        assert!(matches!(
+            super::validate("registry-missing", &mut input),
+            Err(SchemaRegistryError::NotFound { .. })
+        ));
+        assert!(SchemaRegistry::global()
+            .unregister("registry-test")
+            .is_some());
+        assert!(!SchemaRegistry::global().contains("registry-test"));
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{
+    diff::InputDiff,
+    position::{InputPosition, InputPositionType},
+    schema::InputSchemaType,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// How a changed value can be applied. Items inherit the reload of their parent item:
+#[derive(Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Deserialize, Serialize)]
+#[serde(
+    rename_all = "snake_case",
+    deny_unknown_fields,
+    expecting = "Expecting reload `hot`, `restart`, or `immutable`"
+)]
+pub enum InputSchemaReload {
+    // Applied live:
+    #[default]
+    Hot,
+    // Applied after a restart of the plugin:
+    Restart,
+    // Not applied at all (see `InputSchema::immutable()`):
+    Immutable,
+}
+
+// Diffs grouped by how they can be applied (see `classify_diffs()`):
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReloadPlan {
+    hot_list: Vec<InputDiff>,
+    restart_list: Vec<InputDiff>,
+    immutable_list: Vec<InputDiff>,
+    volatile_list: Vec<InputDiff>,
+}
+
+impl ReloadPlan {
+    pub fn hot_list(&self) -> &Vec<InputDiff> {
+        &self.hot_list
+    }
+
+    pub fn restart_list(&self) -> &Vec<InputDiff> {
+        &self.restart_list
+    }
+
+    pub fn immutable_list(&self) -> &Vec<InputDiff> {
+        &self.immutable_list
+    }
+
+    // Changes of `volatile` items which don't count as changes:
+    pub fn volatile_list(&self) -> &Vec<InputDiff> {
+        &self.volatile_list
+    }
+
+    pub fn has_changes(&self) -> bool {
+        !(self.hot_list.is_empty()
+            && self.restart_list.is_empty()
+            && self.immutable_list.is_empty())
+    }
+
+    // The strictest reload of all diffs (`Hot` if there is no diff):
+    pub fn reload(&self) -> InputSchemaReload {
+        if !self.immutable_list.is_empty() {
+            InputSchemaReload::Immutable
+        } else if !self.restart_list.is_empty() {
+            InputSchemaReload::Restart
+        } else {
+            InputSchemaReload::Hot
+        }
+    }
+
+    pub fn requires_restart(&self) -> bool {
+        self.reload() == InputSchemaReload::Restart
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.reload() == InputSchemaReload::Immutable
+    }
+}
+
+// The reload of a diff is the strictest reload of its item and of the items inside it (e.g.
+// replacing a whole map that contains a `restart` item requires a restart):
+pub fn classify_diffs(schema_type: &InputSchemaType, diff_list: &[InputDiff]) -> ReloadPlan {
+    let mut plan = ReloadPlan::default();
+    diff_list.iter().for_each(|diff| {
+        if schema_type.is_volatile_at(diff.position()) {
+            return plan.volatile_list.push(diff.clone());
+        }
+        match reload_at(schema_type, diff.position()) {
+            InputSchemaReload::Hot => &mut plan.hot_list,
+            InputSchemaReload::Restart => &mut plan.restart_list,
+            InputSchemaReload::Immutable => &mut plan.immutable_list,
+        }
+        .push(diff.clone())
+    });
+    plan
+}
+
+fn reload_at(schema_type: &InputSchemaType, position: &InputPosition) -> InputSchemaReload {
+    let mut schema_type = schema_type;
+    let mut reload = InputSchemaReload::Hot;
+    for position_type in position.iter() {
+        while let InputSchemaType::Secret(secret) = schema_type {
+            schema_type = secret.inner_schema();
+        }
+        match (schema_type, position_type) {
+            (InputSchemaType::StaticMap(static_map), InputPositionType::Key(key)) => {
+                let Some(schema) = static_map.items().get(&**key) else {
+                    return reload;
+                };
+                reload = schema.reload().max(reload);
+                schema_type = schema.schema_type();
+            }
+            (InputSchemaType::DynamicMap(dynamic_map), InputPositionType::Key(_)) => {
+                schema_type = dynamic_map.item_schema()
+            }
+            (InputSchemaType::KeyValue(key_value), InputPositionType::Key(_)) => {
+                schema_type = key_value.value_schema()
+            }
+            (InputSchemaType::List(list), InputPositionType::Index(_)) => {
+                schema_type = list.item_schema()
+            }
+            _ => return reload,
+        }
+    }
+    max_inner_reload(schema_type).max(reload)
+}
+
+fn max_inner_reload(schema_type: &InputSchemaType) -> InputSchemaReload {
+    crate::stack::maybe_grow(|| match schema_type {
+        InputSchemaType::StaticMap(static_map) => static_map
+            .items()
+            .values()
+            .map(|schema| schema.reload().max(max_inner_reload(schema.schema_type())))
+            .max()
+            .unwrap_or_default(),
+        InputSchemaType::DynamicMap(dynamic_map) => max_inner_reload(dynamic_map.item_schema()),
+        InputSchemaType::KeyValue(key_value) => max_inner_reload(key_value.value_schema()),
+        InputSchemaType::List(list) => max_inner_reload(list.item_schema()),
+        InputSchemaType::Secret(secret) => max_inner_reload(secret.inner_schema()),
+        InputSchemaType::Either(either) => either
+            .schema_list()
+            .iter()
+            .map(max_inner_reload)
+            .max()
+            .unwrap_or_default(),
+        _ => InputSchemaReload::Hot,
+    })
+}
+
+impl Display for InputSchemaReload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Hot => "hot",
+            Self::Restart => "restart",
+            Self::Immutable => "immutable",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diff::diff, Input};
+    use serde_json::json;
+
+    #[test]
+    fn classify_diffs() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "id": {"schema": {"type": "string"}, "immutable": true},
+                "log_level": {"schema": {"type": "log_level"}},
+                "loaded_at": {"schema": {"type": "integer"}, "volatile": true},
+                "server": {"schema": {"type": "static_map", "items": {
+                    "port": {"schema": {"type": "port"}, "reload": "restart"},
+                    "timeout": {"schema": {"type": "integer"}},
+                }}},
+            }
+        }))
+        .unwrap();
+        let old_input: Input = serde_json::from_value(json!({
+            "id": "a", "log_level": "info", "server": {"port": 80, "timeout": 1}
+        }))
+        .unwrap();
+        let diff_list_of = |new_input: serde_json::Value| {
+            let mut diff_list = Vec::new();
+            diff(
+                &old_input,
+                &serde_json::from_value(new_input).unwrap(),
+                &mut |diff| diff_list.push(diff),
+            );
+            diff_list
+        };
+
+        let plan = super::classify_diffs(
+            &schema_type,
+            &diff_list_of(json!({
+                "id": "a", "log_level": "debug", "server": {"port": 80, "timeout": 2}
+            })),
+        );
+        assert_eq!(plan.reload(), InputSchemaReload::Hot);
+        assert_eq!(plan.hot_list().len(), 2);
+
+        let plan = super::classify_diffs(
+            &schema_type,
+            &diff_list_of(json!({
+                "id": "a", "log_level": "debug", "server": {"port": 8080, "timeout": 1}
+            })),
+        );
+        assert!(plan.requires_restart());
+        assert_eq!(
+            plan.restart_list()[0].position().to_string(),
+            "[server][port]"
+        );
+
+        let plan = super::classify_diffs(
+            &schema_type,
+            &diff_list_of(json!({"id": "b", "log_level": "info", "server": []})),
+        );
+        assert!(plan.is_rejected());
+        assert_eq!(plan.restart_list().len(), 1);
+
+        let plan = super::classify_diffs(
+            &schema_type,
+            &diff_list_of(json!({
+                "id": "a", "log_level": "info", "server": {"port": 80, "timeout": 1}, "loaded_at": 1
+            })),
+        );
+        assert!(!plan.has_changes());
+        assert_eq!(plan.volatile_list().len(), 1);
+    }
+}
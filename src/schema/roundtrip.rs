@@ -0,0 +1,149 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputSchemaRoundtripError {
+    #[error("Could not serialize: {0}")]
+    Serialize(#[source] serde_json::Error),
+    #[error("Could not deserialize {serialized}: {source}")]
+    Deserialize {
+        serialized: String,
+        source: serde_json::Error,
+    },
+    #[error("Deserialized value is not equal to the original one ({original} is {deserialized})")]
+    NotEqual {
+        original: String,
+        deserialized: String,
+    },
+    #[error("Serialization is not stable ({serialized} is serialized again as {reserialized})")]
+    Unstable {
+        serialized: String,
+        reserialized: String,
+    },
+}
+
+// Checks that serialize → deserialize gives an equal value and serializing it again gives the same
+// output, e.g. that `skip_serializing_if` fields have a matching `default`, renamed fields are
+// read back under their new name, and defaults are not serialized in a different form. Works for
+// any serde type so downstream crates can check their own schema extensions in their tests:
+pub fn roundtrip_check<T: Serialize + DeserializeOwned + PartialEq + Debug>(
+    value: &T,
+) -> Result<(), InputSchemaRoundtripError> {
+    let serialized = serde_json::to_value(value).map_err(InputSchemaRoundtripError::Serialize)?;
+    let deserialized: T = serde_json::from_value(serialized.clone()).map_err(|source| {
+        InputSchemaRoundtripError::Deserialize {
+            serialized: serialized.to_string(),
+            source,
+        }
+    })?;
+    if &deserialized != value {
+        return Err(InputSchemaRoundtripError::NotEqual {
+            original: format!("{value:?}"),
+            deserialized: format!("{deserialized:?}"),
+        });
+    }
+    let reserialized =
+        serde_json::to_value(&deserialized).map_err(InputSchemaRoundtripError::Serialize)?;
+    if reserialized != serialized {
+        return Err(InputSchemaRoundtripError::Unstable {
+            serialized: serialized.to_string(),
+            reserialized: reserialized.to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{
+        InputSchema, InputSchemaType, InputSchemaTypeIntegerSuffix,
+        InputSchemaTypeSocketAddressForm,
+    };
+    use serde::Deserialize;
+
+    #[test]
+    fn roundtrip_check() {
+        #[allow(unused_mut)]
+        let mut schema_type_list = vec![
+            InputSchemaType::new_any(),
+            InputSchemaType::new_boolean(),
+            InputSchemaType::new_number(),
+            InputSchemaType::new_integer(),
+            InputSchemaType::new_float(),
+            InputSchemaType::new_string(),
+            InputSchemaType::new_list(),
+            InputSchemaType::new_static_map(),
+            InputSchemaType::new_dynamic_map(),
+            InputSchemaType::new_enum(),
+            InputSchemaType::new_either(),
+            InputSchemaType::new_fs(),
+            InputSchemaType::new_log_level(),
+            InputSchemaType::new_log_level_filter(),
+            InputSchemaType::new_ip(),
+            InputSchemaType::new_port(),
+            InputSchemaType::new_socket_address(),
+            InputSchemaType::new_secret(),
+            InputSchemaType::new_percent(),
+            InputSchemaType::new_weighted_list(),
+            InputSchemaType::new_key_value(),
+            InputSchemaType::new_binary(),
+            InputSchemaType::new_tls(),
+            InputSchemaType::new_dsn(),
+            InputSchemaType::new_interval(),
+            InputSchemaType::new_time_window(),
+            InputSchemaType::new_rate(),
+            InputSchemaType::new_mime(),
+            InputSchemaType::new_regex_value(),
+            InputSchemaType::new_recursive(),
+            InputSchemaType::new_reference(),
+            InputSchemaType::new_switch(),
+            InputSchemaType::Integer(
+                InputSchemaType::new_integer()
+                    .as_integer()
+                    .clone()
+                    .with_suffix(InputSchemaTypeIntegerSuffix::Binary),
+            ),
+            InputSchemaType::SocketAddress(
+                InputSchemaType::new_socket_address()
+                    .as_socket_address()
+                    .clone()
+                    .with_form(InputSchemaTypeSocketAddressForm::Map),
+            ),
+        ];
+        #[cfg(feature = "rust_decimal")]
+        schema_type_list.push(InputSchemaType::new_decimal());
+        #[cfg(feature = "iso-codes")]
+        schema_type_list.extend([
+            InputSchemaType::new_country_code(),
+            InputSchemaType::new_currency(),
+        ]);
+        #[cfg(feature = "expr")]
+        schema_type_list.push(InputSchemaType::new_expr());
+        for schema_type in schema_type_list {
+            super::roundtrip_check(&schema_type).unwrap();
+            super::roundtrip_check(&InputSchema::new().with_schema_type(schema_type)).unwrap();
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Extension {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            maybe_limit: Option<usize>,
+            #[serde(skip)]
+            cache: usize,
+        }
+        super::roundtrip_check(&Extension {
+            maybe_limit: Some(1),
+            cache: 0,
+        })
+        .unwrap();
+        assert!(matches!(
+            super::roundtrip_check(&Extension {
+                maybe_limit: None,
+                cache: 1,
+            }),
+            Err(InputSchemaRoundtripError::NotEqual { .. })
+        ));
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    position::InputPosition,
+    schema::{InputSchema, InputSchemaError, InputSchemaType, InputSchemaTypeStaticMap},
+    Input,
+};
+use std::collections::HashMap;
+
+// Every plugin owns one top-level key of the combined document:
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SchemaSet {
+    schema_map: HashMap<String, InputSchema>,
+}
+
+impl SchemaSet {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn schema_map(&self) -> &HashMap<String, InputSchema> {
+        &self.schema_map
+    }
+
+    pub fn schema_map_mut(&mut self) -> &mut HashMap<String, InputSchema> {
+        &mut self.schema_map
+    }
+
+    pub fn get(&self, plugin_name: &str) -> Option<&InputSchema> {
+        self.schema_map.get(plugin_name)
+    }
+
+    pub fn contains(&self, plugin_name: &str) -> bool {
+        self.schema_map.contains_key(plugin_name)
+    }
+
+    pub fn insert<K: ToString, S: Into<InputSchema>>(
+        &mut self,
+        plugin_name: K,
+        schema: S,
+    ) -> Option<InputSchema> {
+        self.schema_map
+            .insert(plugin_name.to_string(), schema.into())
+    }
+
+    pub fn with_schema<K: ToString, S: Into<InputSchema>>(
+        mut self,
+        plugin_name: K,
+        schema: S,
+    ) -> Self {
+        self.insert(plugin_name, schema);
+        self
+    }
+
+    pub fn remove(&mut self, plugin_name: &str) -> Option<InputSchema> {
+        self.schema_map.remove(plugin_name)
+    }
+
+    pub fn plugin_name_list(&self) -> Vec<&String> {
+        let mut plugin_name_list: Vec<_> = self.schema_map.keys().collect();
+        plugin_name_list.sort();
+        plugin_name_list
+    }
+
+    pub fn len(&self) -> usize {
+        self.schema_map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schema_map.is_empty()
+    }
+
+    pub fn schema_type(&self) -> InputSchemaType {
+        InputSchemaType::StaticMap(self.schema_map.iter().fold(
+            InputSchemaTypeStaticMap::default(),
+            |static_map, (plugin_name, schema)| static_map.with_item(plugin_name, schema.clone()),
+        ))
+    }
+
+    pub fn validate(&self, input: &mut Input) -> Result<(), InputSchemaError> {
+        self.schema_type().validate(input, None)
+    }
+
+    // Validates the section of every plugin on its own, so one invalid section doesn't keep the
+    // others from being validated (and loaded). Valid sections (or defaults of missing ones) are
+    // updated in `input` while invalid ones are kept as they are. Validation stops at the first
+    // error of each section, so the lists have one error for now:
+    pub fn validate_isolated(
+        &self,
+        input: &mut Input,
+    ) -> HashMap<String, Result<(), Vec<InputSchemaError>>> {
+        let Input::Map(map) = input else {
+            let error = InputSchemaError::Type {
+                position: Default::default(),
+                expected_type: Input::map_type_name(),
+                input_type: input.type_name(),
+            };
+            return self
+                .schema_map
+                .keys()
+                .map(|plugin_name| (plugin_name.clone(), Err(vec![error.clone()])))
+                .collect();
+        };
+        self.schema_map
+            .iter()
+            .map(|(plugin_name, schema)| {
+                let position = InputPosition::new().new_with_key(plugin_name);
+                let result = match (map.get(plugin_name.as_str()), schema.maybe_default()) {
+                    (Some(inner_input), _) => {
+                        let mut validated_inner_input = inner_input.clone();
+                        schema
+                            .schema_type()
+                            .validate(&mut validated_inner_input, Some(position))
+                            .map_err(|error| error.with_maybe_context(schema.maybe_context()))
+                            .map(|_| {
+                                map.insert(crate::intern_key(plugin_name), validated_inner_input);
+                            })
+                    }
+                    (None, Some(default)) => {
+                        map.insert(crate::intern_key(plugin_name), default.clone());
+                        Ok(())
+                    }
+                    (None, None) => Err(InputSchemaError::NotFound {
+                        position,
+                        schema_type: schema.schema_type().clone(),
+                    }
+                    .with_maybe_context(schema.maybe_context())),
+                };
+                (plugin_name.clone(), result.map_err(|error| vec![error]))
+            })
+            .collect()
+    }
+
+    // Keys that do not belong to any registered plugin are dropped:
+    pub fn split(&self, input: Input) -> HashMap<String, Input> {
+        if input.is_map() {
+            input
+                .into_map()
+                .into_iter()
+                .filter(|(plugin_name, _)| self.contains(plugin_name))
+                .map(|(plugin_name, inner_input)| (plugin_name.to_string(), inner_input))
+                .collect()
+        } else {
+            HashMap::new()
+        }
+    }
+
+    pub fn validate_and_split(
+        &self,
+        mut input: Input,
+    ) -> Result<HashMap<String, Input>, InputSchemaError> {
+        self.validate(&mut input)?;
+        Ok(self.split(input))
+    }
+}
+
+impl<K: ToString, S: Into<InputSchema>> FromIterator<(K, S)> for SchemaSet {
+    fn from_iter<T: IntoIterator<Item = (K, S)>>(iter: T) -> Self {
+        Self {
+            schema_map: iter
+                .into_iter()
+                .map(|(plugin_name, schema)| (plugin_name.to_string(), schema.into()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(json: serde_json::Value) -> InputSchema {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn validate_and_split() {
+        let schema_set = SchemaSet::new()
+            .with_schema("http", schema(json!({"schema": {"type": "static_map", "items": {"port": {"schema": {"type": "port"}}}}})))
+            .with_schema("log", schema(json!({"schema": {"type": "log_level"}, "default": "info"})));
+        assert_eq!(schema_set.plugin_name_list(), vec!["http", "log"]);
+        assert!(schema_set
+            .schema_type()
+            .as_static_map()
+            .items()
+            .contains_key("log"));
+
+        let input: Input =
+            serde_json::from_value(json!({"http": {"port": "8080"}, "other": true})).unwrap();
+        let mut plugin_map = schema_set.validate_and_split(input).unwrap();
+        assert_eq!(plugin_map.len(), 2);
+        assert_eq!(plugin_map.remove("log"), Some(Input::from("info")));
+        assert_eq!(
+            plugin_map.remove("http").unwrap().as_map().get("port"),
+            Some(&Input::from(8080))
+        );
+
+        let input: Input = serde_json::from_value(json!({"http": {"port": false}})).unwrap();
+        assert!(schema_set.validate_and_split(input).is_err());
+    }
+
+    #[test]
+    fn validate_isolated() {
+        let schema_set = SchemaSet::new()
+            .with_schema("http", schema(json!({"schema": {"type": "port"}})))
+            .with_schema(
+                "log",
+                schema(json!({"schema": {"type": "log_level"}, "default": "info"})),
+            )
+            .with_schema("db", schema(json!({"schema": {"type": "string"}})));
+        let mut input: Input =
+            serde_json::from_value(json!({"http": "8080", "db": false})).unwrap();
+        let result_map = schema_set.validate_isolated(&mut input);
+        assert_eq!(result_map["http"], Ok(()));
+        assert_eq!(result_map["log"], Ok(()));
+        assert_eq!(
+            result_map["db"].as_ref().unwrap_err()[0]
+                .position()
+                .to_string(),
+            "db"
+        );
+        let expected: Input =
+            serde_json::from_value(json!({"http": 8080, "log": "info", "db": false})).unwrap();
+        assert_eq!(input, expected);
+    }
+}
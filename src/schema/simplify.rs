@@ -0,0 +1,115 @@
+use crate::schema::InputSchemaType;
+
+// `either` uses the first branch that validates. So a branch after an `any` branch or after the
+// same branch can never be used, and removing it does not change what validates (or how):
+impl InputSchemaType {
+    pub fn simplify(&mut self) {
+        crate::stack::maybe_grow(|| match self {
+            Self::Either(either) => {
+                let mut schema_list = Vec::new();
+                flatten_either(std::mem::take(either.schema_list_mut()), &mut schema_list);
+                let mut simplified_list: Vec<InputSchemaType> = Vec::new();
+                for schema_type in schema_list {
+                    if simplified_list.last().is_some_and(Self::is_any) {
+                        break;
+                    }
+                    if !simplified_list.contains(&schema_type) {
+                        simplified_list.push(schema_type)
+                    }
+                }
+                if simplified_list.len() == 1 {
+                    *self = simplified_list.remove(0);
+                } else {
+                    *either.schema_list_mut() = simplified_list;
+                }
+            }
+            Self::Enum(r#enum) => {
+                let mut value_list = Vec::new();
+                std::mem::take(r#enum.value_list_mut())
+                    .into_iter()
+                    .for_each(|value| {
+                        if !value_list.contains(&value) {
+                            value_list.push(value)
+                        }
+                    });
+                *r#enum.value_list_mut() = value_list;
+            }
+            Self::List(list) => list.item_schema_mut().simplify(),
+            Self::DynamicMap(dynamic_map) => dynamic_map.item_schema_mut().simplify(),
+            Self::StaticMap(static_map) => {
+                static_map
+                    .items_mut()
+                    .values_mut()
+                    .for_each(|schema| schema.schema_type_mut().simplify());
+                if let Some(additional_items) = static_map.maybe_additional_items_mut() {
+                    additional_items.schema_type_mut().simplify()
+                }
+            }
+            Self::Secret(secret) => secret.inner_schema_mut().simplify(),
+            Self::KeyValue(key_value) => key_value.value_schema_mut().simplify(),
+            Self::WeightedList(weighted_list) => weighted_list.value_schema_mut().simplify(),
+            Self::Interval(interval) => interval.item_schema_mut().simplify(),
+            Self::Recursive(recursive) => recursive.inner_schema_mut().simplify(),
+            _ => (),
+        })
+    }
+}
+
+fn flatten_either(schema_list: Vec<InputSchemaType>, flat_schema_list: &mut Vec<InputSchemaType>) {
+    for mut schema_type in schema_list {
+        schema_type.simplify();
+        if let InputSchemaType::Either(either) = &mut schema_type {
+            flat_schema_list.append(either.schema_list_mut())
+        } else {
+            flat_schema_list.push(schema_type)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn simplify() {
+        let mut schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "list",
+            "item_schema": {
+                "type": "either",
+                "schema_list": [
+                    {"type": "either", "schema_list": [{"type": "boolean"}, {"type": "integer"}]},
+                    {"type": "enum", "items": ["a", "b", "a"]},
+                    {"type": "boolean"},
+                    {"type": "any"},
+                    {"type": "any"},
+                    {"type": "string"},
+                ]
+            }
+        }))
+        .unwrap();
+        schema_type.simplify();
+        let expected: InputSchemaType = serde_json::from_value(json!({
+            "type": "list",
+            "item_schema": {
+                "type": "either",
+                "schema_list": [
+                    {"type": "boolean"},
+                    {"type": "integer"},
+                    {"type": "enum", "items": ["a", "b"]},
+                    {"type": "any"},
+                ]
+            }
+        }))
+        .unwrap();
+        assert_eq!(schema_type, expected);
+
+        let mut schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "either",
+            "schema_list": [{"type": "either", "schema_list": [{"type": "port"}]}, {"type": "port"}]
+        }))
+        .unwrap();
+        schema_type.simplify();
+        assert!(schema_type.is_port());
+    }
+}
@@ -0,0 +1,224 @@
+use crate::schema::InputSchemaType;
+use std::fmt::{Display, Formatter};
+
+// Size and complexity of a schema, e.g. to reject plugin-provided schemas that would be too
+// expensive to validate before accepting them:
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InputSchemaStats {
+    node_count: usize,
+    max_depth: usize,
+    regex_count: usize,
+    max_alternative_count: usize,
+    cost_class: InputSchemaCostClass,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum InputSchemaCostClass {
+    // Every value is checked against one schema:
+    #[default]
+    Linear,
+    // `either` schemas check some values against a bounded number of alternatives:
+    Branching,
+    // An `either` inside a recursive schema has more than one branch that refers back to it, so
+    // the number of attempts may double at each nesting level (up to its `max_depth`):
+    Exponential,
+}
+
+impl InputSchemaStats {
+    // Number of schema types (including the root):
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    // Number of nested schema types (the root is `1`):
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn regex_count(&self) -> usize {
+        self.regex_count
+    }
+
+    // An upper bound of the number of schemas a single value may be checked against (the product
+    // of nested `either` branch counts):
+    pub fn max_alternative_count(&self) -> usize {
+        self.max_alternative_count
+    }
+
+    pub fn cost_class(&self) -> InputSchemaCostClass {
+        self.cost_class
+    }
+}
+
+impl InputSchemaType {
+    pub fn stats(&self) -> InputSchemaStats {
+        let mut stats = InputSchemaStats {
+            max_alternative_count: 1,
+            ..Default::default()
+        };
+        collect_stats(self, 1, 1, false, &mut stats);
+        stats
+    }
+
+    // Schemas directly inside this one (in the order of `ordered_items()` for static maps):
+    pub(crate) fn child_list(&self) -> Vec<&InputSchemaType> {
+        match self {
+            Self::Either(either) => either.schema_list().iter().collect(),
+            Self::List(list) => vec![list.item_schema()],
+            Self::DynamicMap(dynamic_map) => vec![dynamic_map.item_schema()],
+            Self::StaticMap(static_map) => static_map
+                .ordered_items()
+                .into_iter()
+                .map(|(_, schema)| schema.schema_type())
+                .chain(
+                    static_map
+                        .maybe_additional_items()
+                        .map(|schema| schema.schema_type()),
+                )
+                .collect(),
+            Self::Secret(secret) => vec![secret.inner_schema()],
+            Self::KeyValue(key_value) => vec![key_value.value_schema()],
+            Self::WeightedList(weighted_list) => vec![weighted_list.value_schema()],
+            Self::Interval(interval) => vec![interval.item_schema()],
+            Self::Recursive(recursive) => vec![recursive.inner_schema()],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl InputSchemaType {
+    pub(crate) fn child_list_mut(&mut self) -> Vec<&mut InputSchemaType> {
+        match self {
+            Self::Either(either) => either.schema_list_mut().iter_mut().collect(),
+            Self::List(list) => vec![list.item_schema_mut()],
+            Self::DynamicMap(dynamic_map) => vec![dynamic_map.item_schema_mut()],
+            Self::StaticMap(static_map) => static_map
+                .schema_list_mut()
+                .into_iter()
+                .map(|schema| schema.schema_type_mut())
+                .collect(),
+            Self::Secret(secret) => vec![secret.inner_schema_mut()],
+            Self::KeyValue(key_value) => vec![key_value.value_schema_mut()],
+            Self::WeightedList(weighted_list) => vec![weighted_list.value_schema_mut()],
+            Self::Interval(interval) => vec![interval.item_schema_mut()],
+            Self::Recursive(recursive) => vec![recursive.inner_schema_mut()],
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn collect_stats(
+    schema_type: &InputSchemaType,
+    depth: usize,
+    alternative_count: usize,
+    is_recursive: bool,
+    stats: &mut InputSchemaStats,
+) {
+    crate::stack::maybe_grow(|| {
+        stats.node_count += 1;
+        stats.max_depth = stats.max_depth.max(depth);
+        let mut alternative_count = alternative_count;
+        let is_recursive = is_recursive || schema_type.is_recursive();
+        match schema_type {
+            InputSchemaType::String(string) if string.maybe_regex().is_some() => {
+                stats.regex_count += 1
+            }
+            InputSchemaType::Either(either) if either.schema_list().len() > 1 => {
+                alternative_count = alternative_count.saturating_mul(either.schema_list().len());
+                stats.max_alternative_count = stats.max_alternative_count.max(alternative_count);
+                let cost_class = if is_recursive
+                    && either
+                        .schema_list()
+                        .iter()
+                        .filter(|schema_type| has_ref(schema_type))
+                        .count()
+                        > 1
+                {
+                    InputSchemaCostClass::Exponential
+                } else {
+                    InputSchemaCostClass::Branching
+                };
+                stats.cost_class = stats.cost_class.max(cost_class);
+            }
+            _ => (),
+        }
+        schema_type.child_list().into_iter().for_each(|child| {
+            collect_stats(child, depth + 1, alternative_count, is_recursive, stats)
+        })
+    })
+}
+
+fn has_ref(schema_type: &InputSchemaType) -> bool {
+    crate::stack::maybe_grow(|| {
+        schema_type.is_reference() || schema_type.child_list().into_iter().any(has_ref)
+    })
+}
+
+impl Display for InputSchemaCostClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Linear => "linear",
+            Self::Branching => "branching",
+            Self::Exponential => "exponential",
+        })
+    }
+}
+
+impl Display for InputSchemaStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} schemas, depth {}, {} regexes, at most {} alternatives per value ({} cost)",
+            self.node_count,
+            self.max_depth,
+            self.regex_count,
+            self.max_alternative_count,
+            self.cost_class
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn stats() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "name": {"schema": {"type": "string", "regex": {"pattern": "^[a-z]+$"}}},
+                "hosts": {"schema": {"type": "list", "item_schema": {"type": "either", "schema_list": [
+                    {"type": "ip"},
+                    {"type": "either", "schema_list": [{"type": "socket_address"}, {"type": "string"}]},
+                ]}}},
+            }
+        }))
+        .unwrap();
+        let stats = schema_type.stats();
+        assert_eq!(stats.node_count(), 8);
+        assert_eq!(stats.max_depth(), 5);
+        assert_eq!(stats.regex_count(), 1);
+        assert_eq!(stats.max_alternative_count(), 4);
+        assert_eq!(stats.cost_class(), InputSchemaCostClass::Branching);
+        assert_eq!(
+            InputSchemaType::new_port().stats().cost_class(),
+            InputSchemaCostClass::Linear
+        );
+
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "recursive",
+            "name": "node",
+            "schema": {"type": "either", "schema_list": [
+                {"type": "list", "item_schema": {"type": "ref", "name": "node"}},
+                {"type": "dynamic_map", "item_schema": {"type": "ref", "name": "node"}},
+            ]}
+        }))
+        .unwrap();
+        assert_eq!(
+            schema_type.stats().cost_class(),
+            InputSchemaCostClass::Exponential
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use crate::{
+    diff::to_patch_with_position,
+    patch::InputPatch,
+    position::InputPosition,
+    schema::{CompiledSchema, InputSchemaError, InputSchemaType, InputSchemaTypeSize},
+    Input, InputMap,
+};
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::Formatter,
+    io::{BufReader, Read},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputSchemaStreamError {
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Schema(Box<InputSchemaError>),
+}
+
+// Validates a JSON document while it's being read, without building an `Input` of the whole
+// document. Lists, dynamic maps, and static maps are read one item at a time (list and map sizes
+// are counted, so too long lists fail as soon as they exceed the maximum) and other values are
+// built and validated one by one, so memory is bounded by the largest of those values. Values of
+// other schema types (e.g. `either` and `secret`, even at the root), lists with `item_map`, and
+// values of dynamic maps with `format` are built whole. The returned patch has the defaults and
+// coercions of validation, so applying it to the document results in the validated input:
+pub fn validate_json_stream<R: Read>(
+    schema: &CompiledSchema,
+    reader: R,
+) -> Result<InputPatch, InputSchemaStreamError> {
+    let maybe_error = RefCell::new(None);
+    let patch = RefCell::new(InputPatch::new());
+    let position = InputPosition::new();
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+    let result = StreamSeed {
+        schema_type: schema.schema_type(),
+        position: &position,
+        maybe_error: &maybe_error,
+        patch: &patch,
+    }
+    .deserialize(&mut deserializer)
+    .and_then(|_| deserializer.end());
+    match (result, maybe_error.take()) {
+        (_, Some(error)) => Err(InputSchemaStreamError::Schema(Box::new(error))),
+        (Err(error), None) => Err(InputSchemaStreamError::Json(error)),
+        (Ok(()), None) => Ok(patch.take()),
+    }
+}
+
+// Validation errors are kept here and the deserializer gets a placeholder error to stop reading:
+#[derive(Clone, Copy)]
+struct StreamSeed<'a> {
+    schema_type: &'a InputSchemaType,
+    position: &'a InputPosition,
+    maybe_error: &'a RefCell<Option<InputSchemaError>>,
+    patch: &'a RefCell<InputPatch>,
+}
+
+impl<'a> StreamSeed<'a> {
+    fn with<'b>(
+        &'b self,
+        schema_type: &'b InputSchemaType,
+        position: &'b InputPosition,
+    ) -> StreamSeed<'b> {
+        StreamSeed {
+            schema_type,
+            position,
+            maybe_error: self.maybe_error,
+            patch: self.patch,
+        }
+    }
+
+    fn check<E: de::Error>(&self, result: Result<(), InputSchemaError>) -> Result<(), E> {
+        result.map_err(|error| {
+            *self.maybe_error.borrow_mut() = Some(error);
+            E::custom("validation failed")
+        })
+    }
+
+    fn validate<E: de::Error>(&self, input: Input) -> Result<(), E> {
+        let mut validated_input = input.clone();
+        self.check(
+            self.schema_type
+                .validate(&mut validated_input, Some(self.position.clone())),
+        )?;
+        to_patch_with_position(
+            &input,
+            &validated_input,
+            &mut self.patch.borrow_mut(),
+            self.position.clone(),
+        );
+        Ok(())
+    }
+
+    fn check_size<E: de::Error>(
+        &self,
+        maybe_size: Option<InputSchemaTypeSize>,
+        size: usize,
+        is_complete: bool,
+    ) -> Result<(), E> {
+        let Some(expected_size) = maybe_size else {
+            return Ok(());
+        };
+        let is_too_large = expected_size.maybe_max().is_some_and(|max| size > max);
+        let is_too_small = is_complete && expected_size.maybe_min().is_some_and(|min| size < min);
+        if !is_too_large && !is_too_small {
+            return Ok(());
+        }
+        self.check(Err(InputSchemaError::Size {
+            position: self.position.clone(),
+            schema_type: match self.schema_type {
+                InputSchemaType::List(_) => InputSchemaType::new_list(),
+                _ => InputSchemaType::new_dynamic_map(),
+            },
+            expected_size,
+            size,
+        }))
+    }
+}
+
+// Lists with `item_map` are built since their items may change:
+fn is_streamed(schema_type: &InputSchemaType) -> bool {
+    match schema_type {
+        InputSchemaType::List(list) => list.maybe_item_map().is_none(),
+        InputSchemaType::DynamicMap(_) | InputSchemaType::StaticMap(_) => true,
+        _ => false,
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for StreamSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        crate::stack::maybe_grow(|| {
+            if is_streamed(self.schema_type) {
+                deserializer.deserialize_any(self)
+            } else {
+                let input = <Input as de::Deserialize>::deserialize(deserializer)?;
+                self.validate(input)
+            }
+        })
+    }
+}
+
+// Values that are not lists or maps are built and validated, so the schema reports the type error:
+impl<'de> Visitor<'de> for StreamSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("null, boolean, integer, float, string, list, or map")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<(), E> {
+        self.validate(Input::from(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<(), E> {
+        self.validate(isize::try_from(value).map_or(Input::from(value as f64), Input::from))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<(), E> {
+        self.validate(isize::try_from(value).map_or(Input::from(value as f64), Input::from))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<(), E> {
+        self.validate(Input::from(value))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<(), E> {
+        self.validate(Input::from(value))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        self.validate(Input::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let InputSchemaType::List(list) = self.schema_type else {
+            let mut input_list: Vec<Input> = Vec::new();
+            while let Some(inner_input) = seq.next_element()? {
+                input_list.push(inner_input)
+            }
+            return self.validate(Input::from(input_list));
+        };
+        let maybe_max = list.maybe_size().and_then(|size| size.maybe_max());
+        let mut index = 0;
+        loop {
+            // Items after the maximum are not validated:
+            if maybe_max == Some(index) {
+                if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                    self.check_size(list.maybe_size().copied(), index + 1, false)?;
+                }
+                break;
+            }
+            let item_position = self.position.new_with_index(index);
+            if seq
+                .next_element_seed(self.with(list.item_schema(), &item_position))?
+                .is_none()
+            {
+                break;
+            }
+            index += 1;
+        }
+        self.check_size(list.maybe_size().copied(), index, true)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map_access: A) -> Result<(), A::Error> {
+        match self.schema_type {
+            InputSchemaType::DynamicMap(dynamic_map) => {
+                let mut key_set = HashSet::new();
+                while let Some(key) = map_access.next_key::<String>()? {
+                    let item_position = self.position.new_with_key(&key);
+                    match dynamic_map.maybe_format() {
+                        // The format checks keys and values together:
+                        Some(format) => {
+                            let inner_input: Input = map_access.next_value()?;
+                            self.check(format.validate(&key, &inner_input, self.position))?;
+                            self.with(dynamic_map.item_schema(), &item_position)
+                                .validate(inner_input)?;
+                        }
+                        None => map_access.next_value_seed(
+                            self.with(dynamic_map.item_schema(), &item_position),
+                        )?,
+                    }
+                    key_set.insert(key);
+                    self.check_size(dynamic_map.maybe_size().copied(), key_set.len(), false)?;
+                }
+                self.check_size(dynamic_map.maybe_size().copied(), key_set.len(), true)
+            }
+            // Items with streamed values are replaced with `null` and validated as `any` with
+            // the rest of the map, so missing and unknown keys and defaults are still checked:
+            InputSchemaType::StaticMap(static_map) => {
+                let mut map = HashMap::new();
+                let mut streamed_key_list = Vec::new();
+                while let Some(key) = map_access.next_key::<String>()? {
+                    match static_map
+                        .items()
+                        .get(&key)
+                        .filter(|schema| is_streamed(schema.schema_type()))
+                    {
+                        Some(schema) => {
+                            let item_position = self.position.new_with_key(&key);
+                            map_access
+                                .next_value_seed(self.with(schema.schema_type(), &item_position))?;
+                            map.insert(key.clone(), Input::Null);
+                            streamed_key_list.push(key);
+                        }
+                        None => {
+                            let inner_input = map_access.next_value()?;
+                            map.insert(key, inner_input);
+                        }
+                    }
+                }
+                if streamed_key_list.is_empty() {
+                    return self.validate(Input::from(map));
+                }
+                let mut static_map = static_map.clone();
+                streamed_key_list.iter().for_each(|key| {
+                    if let Some(schema) = static_map.items_mut().get_mut(key) {
+                        schema.set_schema_type(InputSchemaType::new_any())
+                    }
+                });
+                self.with(&InputSchemaType::StaticMap(static_map), self.position)
+                    .validate(Input::from(map))
+            }
+            _ => {
+                let mut map = InputMap::new();
+                while let Some((key, inner_input)) = map_access.next_entry()? {
+                    map.insert(key, inner_input);
+                }
+                self.validate(Input::from(map))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate_json_stream() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "name": {"schema": {"type": "string"}},
+                "timeout": {"schema": {"type": "integer"}, "default": 30},
+                "routes": {"schema": {"type": "list", "size": {"max": 3}, "item_schema": {
+                    "type": "static_map",
+                    "items": {
+                        "path": {"schema": {"type": "string"}},
+                        "port": {"schema": {"type": "port"}},
+                    }
+                }}},
+            }
+        }))
+        .unwrap();
+        let schema = CompiledSchema::new(schema_type).unwrap();
+        let text = r#"{"name": "app", "routes": [{"path": "/", "port": 80}, {"path": "/a", "port": "8080"}]}"#;
+        let patch = super::validate_json_stream(&schema, text.as_bytes()).unwrap();
+        let mut input: Input = serde_json::from_str(text).unwrap();
+        let mut validated_input = input.clone();
+        schema.validate(&mut validated_input, None).unwrap();
+        input.apply_patch(&patch).unwrap();
+        assert_eq!(input, validated_input);
+        assert_eq!(input.as_map()["timeout"], Input::from(30));
+        assert_eq!(patch.len(), 2);
+
+        for (text, position) in [
+            (
+                r#"{"name": "app", "routes": [{"path": "/", "port": "http"}]}"#,
+                "[routes][0][port]",
+            ),
+            (
+                r#"{"name": "app", "routes": [{"path": "/", "port": 1}, {"path": "/", "port": 2}, {"path": "/", "port": 3}, {}]}"#,
+                "routes",
+            ),
+            (r#"{"name": "app", "routes": {}}"#, "routes"),
+            (r#"{"routes": []}"#, "name"),
+        ] {
+            match super::validate_json_stream(&schema, text.as_bytes()) {
+                Err(InputSchemaStreamError::Schema(error)) => {
+                    assert_eq!(error.position().to_string(), position, "{text}")
+                }
+                result => panic!("{text}: {result:?}"),
+            }
+        }
+        assert!(matches!(
+            super::validate_json_stream(&schema, r#"{"name": "app", "routes": ["#.as_bytes()),
+            Err(InputSchemaStreamError::Json(_))
+        ));
+    }
+}
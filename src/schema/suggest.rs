@@ -0,0 +1,442 @@
+use crate::{
+    position::InputPosition,
+    schema::{InputSchemaError, InputSchemaType, InputSchemaTypeRange},
+    Input,
+};
+use std::time::{Duration, Instant};
+
+impl InputSchemaError {
+    // A value that would fix this error: the closest enum value (by edit distance), the nearest
+    // bound of a range, or the input coerced to another type. `Type` and `Size` errors don't keep
+    // the input so they have no suggestion:
+    pub fn suggestion(&self) -> Option<Input> {
+        let suggestion = match self {
+            Self::Schema {
+                schema_type: InputSchemaType::Enum(r#enum),
+                input,
+                ..
+            } => {
+                let text = text_of(input)?;
+                let value_text_list: Vec<_> = r#enum
+                    .value_list()
+                    .iter()
+                    .filter_map(|value| text_of(value).map(|value_text| (value, value_text)))
+                    .collect();
+                closest(
+                    &text,
+                    value_text_list
+                        .iter()
+                        .map(|(_, value_text)| value_text.as_str()),
+                )
+                .and_then(|closest_text| {
+                    value_text_list
+                        .iter()
+                        .find(|(_, value_text)| value_text == closest_text)
+                        .map(|(value, _)| (*value).clone())
+                })
+            }
+            Self::Schema {
+                schema_type, input, ..
+            } => coerce(schema_type, input),
+            Self::Range {
+                expected_range,
+                input,
+                ..
+            } => clamp(expected_range, input),
+            Self::Context { source, .. } => return source.suggestion(),
+            Self::Type { .. }
+            | Self::Size { .. }
+            | Self::NotFound { .. }
+            | Self::Invalid { .. }
+            | Self::Aborted { .. }
+            | Self::Internal { .. } => None,
+        }?;
+        match self {
+            Self::Schema { input, .. } | Self::Range { input, .. } if input == &suggestion => None,
+            _ => Some(suggestion),
+        }
+    }
+}
+
+// The result of validating an input that can replace the values of failed positions with
+// suggestions (e.g. for "auto-fix" tools). Validation stops at the first error, so each applied
+// suggestion is followed by another validation:
+#[derive(Debug, Clone)]
+pub struct ValidationReport<'a> {
+    schema_type: &'a InputSchemaType,
+    input: Input,
+    maybe_error: Option<InputSchemaError>,
+    applied_list: Vec<(InputPosition, Input)>,
+    // Every error so far (the errors of applied suggestions are followed by `maybe_error`):
+    error_list: Vec<InputSchemaError>,
+    validation_count: usize,
+    duration: Duration,
+}
+
+impl<'a> ValidationReport<'a> {
+    pub fn new(schema_type: &'a InputSchemaType, input: Input) -> Self {
+        let mut report = Self {
+            schema_type,
+            input,
+            maybe_error: None,
+            applied_list: Vec::new(),
+            error_list: Vec::new(),
+            validation_count: 0,
+            duration: Duration::ZERO,
+        };
+        report.validate();
+        report
+    }
+
+    // The validated input if there is no error, otherwise the input with applied suggestions:
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    pub fn into_input(self) -> Input {
+        self.input
+    }
+
+    pub fn maybe_error(&self) -> Option<&InputSchemaError> {
+        self.maybe_error.as_ref()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.maybe_error.is_none()
+    }
+
+    pub fn applied_list(&self) -> &Vec<(InputPosition, Input)> {
+        &self.applied_list
+    }
+
+    pub fn error_list(&self) -> &Vec<InputSchemaError> {
+        &self.error_list
+    }
+
+    pub fn validation_count(&self) -> usize {
+        self.validation_count
+    }
+
+    // Of all validations so far:
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    // One structured record of the outcome (e.g. for INFO-level logs instead of per-value TRACE
+    // logs) with the first `max_error_count` errors as `error.0`, `error.1`, etc:
+    pub fn to_log_kv(&self, max_error_count: usize) -> Vec<(String, String)> {
+        let mut kv_list = vec![
+            ("valid".to_string(), self.is_valid().to_string()),
+            (
+                "validation_count".to_string(),
+                self.validation_count.to_string(),
+            ),
+            (
+                "applied_count".to_string(),
+                self.applied_list.len().to_string(),
+            ),
+            ("error_count".to_string(), self.error_list.len().to_string()),
+            (
+                "duration_us".to_string(),
+                self.duration.as_micros().to_string(),
+            ),
+        ];
+        kv_list.extend(
+            self.error_list
+                .iter()
+                .take(max_error_count)
+                .enumerate()
+                .map(|(index, error)| (format!("error.{index}"), error.to_string())),
+        );
+        kv_list
+    }
+
+    // Emits `to_log_kv()` as one event, at INFO level if the input is valid and WARN otherwise:
+    #[cfg(feature = "tracing")]
+    pub fn to_tracing_event(&self, max_error_count: usize) {
+        let errors = self
+            .error_list
+            .iter()
+            .take(max_error_count)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        let duration_us = self.duration.as_micros() as u64;
+        if self.is_valid() {
+            tracing::info!(
+                valid = true,
+                validation_count = self.validation_count,
+                applied_count = self.applied_list.len(),
+                error_count = self.error_list.len(),
+                duration_us,
+                errors,
+                "validated input"
+            );
+        } else {
+            tracing::warn!(
+                valid = false,
+                validation_count = self.validation_count,
+                applied_count = self.applied_list.len(),
+                error_count = self.error_list.len(),
+                duration_us,
+                errors,
+                "validated input"
+            );
+        }
+    }
+
+    // Applies suggestions until the input is valid or the error has no (new) suggestion and
+    // returns the number of applied suggestions:
+    pub fn apply_suggestions(&mut self) -> usize {
+        let applied_count = self.applied_list.len();
+        while let Some(error) = self.maybe_error.as_ref() {
+            let Some(suggestion) = error.suggestion() else {
+                break;
+            };
+            let position = error.position().clone();
+            if self
+                .applied_list
+                .iter()
+                .any(|applied| applied.0 == position && applied.1 == suggestion)
+            {
+                break;
+            }
+            let Some(inner_input) = self.input.get_at_mut(&position) else {
+                break;
+            };
+            *inner_input = suggestion.clone();
+            self.applied_list.push((position, suggestion));
+            self.validate();
+        }
+        self.applied_list.len() - applied_count
+    }
+
+    fn validate(&mut self) {
+        let mut input = self.input.clone();
+        let start = Instant::now();
+        let result = self.schema_type.validate(&mut input, None);
+        self.duration += start.elapsed();
+        self.validation_count += 1;
+        match result {
+            Ok(()) => {
+                self.input = input;
+                self.maybe_error = None;
+            }
+            Err(error) => {
+                self.error_list.push(error.clone());
+                self.maybe_error = Some(error)
+            }
+        }
+    }
+}
+
+impl InputSchemaType {
+    pub fn validation_report(&self, input: Input) -> ValidationReport<'_> {
+        ValidationReport::new(self, input)
+    }
+}
+
+// Optimal string alignment distance (Levenshtein distance that counts swapping two adjacent
+// characters as one edit):
+pub(crate) fn edit_distance(text: &str, other_text: &str) -> usize {
+    let char_list: Vec<char> = text.chars().collect();
+    let other_char_list: Vec<char> = other_text.chars().collect();
+    let width = other_char_list.len() + 1;
+    let mut distance_list: Vec<usize> = vec![0; (char_list.len() + 1) * width];
+    (0..=char_list.len()).for_each(|index| distance_list[index * width] = index);
+    (0..=other_char_list.len()).for_each(|index| distance_list[index] = index);
+    for index in 1..=char_list.len() {
+        for other_index in 1..=other_char_list.len() {
+            let cost = usize::from(char_list[index - 1] != other_char_list[other_index - 1]);
+            let mut distance = (distance_list[(index - 1) * width + other_index] + 1)
+                .min(distance_list[index * width + other_index - 1] + 1)
+                .min(distance_list[(index - 1) * width + other_index - 1] + cost);
+            if index > 1
+                && other_index > 1
+                && char_list[index - 1] == other_char_list[other_index - 2]
+                && char_list[index - 2] == other_char_list[other_index - 1]
+            {
+                distance = distance.min(distance_list[(index - 2) * width + other_index - 2] + 1);
+            }
+            distance_list[index * width + other_index] = distance;
+        }
+    }
+    distance_list[char_list.len() * width + other_char_list.len()]
+}
+
+// The closest candidate (case-insensitive) if it's close enough to be a typo:
+pub(crate) fn closest<'a, I: IntoIterator<Item = &'a str>>(
+    text: &str,
+    candidate_list: I,
+) -> Option<&'a str> {
+    let text = text.to_lowercase();
+    let max_distance = (text.chars().count() / 3).max(1);
+    candidate_list
+        .into_iter()
+        .map(|candidate| (edit_distance(&text, &candidate.to_lowercase()), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+// `", did you mean `x`?"` or nothing, appended to error messages:
+pub(crate) fn did_you_mean<'a, I: IntoIterator<Item = &'a str>>(
+    text: &str,
+    candidate_list: I,
+) -> String {
+    closest(text, candidate_list)
+        .filter(|candidate| *candidate != text)
+        .map(|candidate| format!(", did you mean `{candidate}`?"))
+        .unwrap_or_default()
+}
+
+pub(crate) fn enum_hint(schema_type: &InputSchemaType, input: &Input) -> String {
+    match (schema_type, text_of(input)) {
+        (InputSchemaType::Enum(r#enum), Some(text)) => {
+            let value_text_list: Vec<_> = r#enum.value_list().iter().filter_map(text_of).collect();
+            did_you_mean(&text, value_text_list.iter().map(String::as_str))
+        }
+        _ => String::new(),
+    }
+}
+
+fn text_of(input: &Input) -> Option<String> {
+    match input {
+        Input::Str(string) => Some(string.clone()),
+        Input::Bool(_) | Input::Int(_) | Input::Float(_) => Some(input.to_string()),
+        _ => None,
+    }
+}
+
+fn coerce(schema_type: &InputSchemaType, input: &Input) -> Option<Input> {
+    let candidate_list = match input {
+        Input::Str(string) => {
+            let string = string.trim();
+            [
+                string.parse::<bool>().ok().map(Input::from),
+                string.parse::<isize>().ok().map(Input::from),
+                string.parse::<f64>().ok().map(Input::from),
+            ]
+            .into_iter()
+            .flatten()
+            .collect()
+        }
+        Input::Bool(_) | Input::Int(_) | Input::Float(_) => vec![Input::from(input.to_string())],
+        _ => Vec::new(),
+    };
+    candidate_list.into_iter().find(|candidate| {
+        let mut validated_candidate = candidate.clone();
+        schema_type.validate(&mut validated_candidate, None).is_ok()
+    })
+}
+
+fn clamp(range: &InputSchemaTypeRange, input: &Input) -> Option<Input> {
+    let (number, is_int) = match input {
+        Input::Int(integer) => (*integer as f64, true),
+        Input::Float(float) => (*float, false),
+        _ => return None,
+    };
+    let clamped_number = match (range.maybe_min(), range.maybe_max()) {
+        (Some(min), _) if number < min.float() => {
+            if is_int {
+                min.float().ceil()
+            } else {
+                min.float()
+            }
+        }
+        (_, Some(max)) if number > max.float() => {
+            if is_int {
+                max.float().floor()
+            } else {
+                max.float()
+            }
+        }
+        _ => return None,
+    };
+    Some(if is_int {
+        Input::from(clamped_number as isize)
+    } else {
+        Input::from(clamped_number)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn edit_distance() {
+        assert_eq!(super::edit_distance("port", "port"), 0);
+        assert_eq!(super::edit_distance("prot", "port"), 1);
+        assert_eq!(super::edit_distance("wrn", "warn"), 1);
+        assert_eq!(super::edit_distance("", "abc"), 3);
+        assert_eq!(closest("WARN", ["error", "warn", "info"]), Some("warn"));
+        assert_eq!(closest("xyz", ["error", "warn", "info"]), None);
+        assert_eq!(
+            did_you_mean("prot", ["host", "port"]),
+            ", did you mean `port`?"
+        );
+        assert_eq!(did_you_mean("port", ["port"]), "");
+    }
+
+    #[test]
+    fn enum_hint() {
+        let schema_type: InputSchemaType =
+            serde_json::from_value(json!({"type": "enum", "items": ["error", "warn", "info"]}))
+                .unwrap();
+        let error = schema_type
+            .validate(&mut Input::from("wran"), None)
+            .unwrap_err();
+        assert!(error.to_string().ends_with(", did you mean `warn`?"));
+    }
+
+    #[test]
+    fn apply_suggestions() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "level": {"schema": {"type": "enum", "items": ["error", "warn", "info"]}},
+                "workers": {"schema": {"type": "integer", "range": {"min": 1, "max": 16}}},
+                "enabled": {"schema": {"type": "enum", "items": [true, false]}},
+                "name": {"schema": {"type": "string"}},
+            }
+        }))
+        .unwrap();
+        let input: Input = serde_json::from_value(json!({
+            "level": "wran",
+            "workers": 64,
+            "enabled": true,
+            "name": "foo",
+        }))
+        .unwrap();
+        let mut report = schema_type.validation_report(input.clone());
+        assert!(!report.is_valid());
+        assert_eq!(report.apply_suggestions(), 2);
+        assert!(report.is_valid());
+        let expected: Input = serde_json::from_value(json!({
+            "level": "warn",
+            "workers": 16,
+            "enabled": true,
+            "name": "foo",
+        }))
+        .unwrap();
+        assert_eq!(report.input(), &expected);
+        assert_eq!(report.validation_count(), 3);
+        let kv_list = report.to_log_kv(1);
+        assert_eq!(kv_list[0], ("valid".to_string(), "true".to_string()));
+        assert_eq!(kv_list[3], ("error_count".to_string(), "2".to_string()));
+        assert_eq!(kv_list.len(), 6);
+        assert_eq!(kv_list[5].0, "error.0");
+
+        let mut input = input;
+        *input.map_mut().get_mut("name").unwrap() = Input::from(1);
+        let mut report = schema_type.validation_report(input);
+        report.apply_suggestions();
+        // `Type` errors don't have suggestions:
+        assert!(matches!(
+            report.maybe_error(),
+            Some(InputSchemaError::Type { .. })
+        ));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{
+    patch::{InputPatch, InputPatchError, InputPatchOperation},
+    position::InputPosition,
+    schema::{InputSchemaError, InputSchemaType},
+    Input,
+};
+use thiserror::Error;
+
+// Mutations are made on a working copy, the original input only changes on a successful commit:
+#[derive(Debug)]
+pub struct InputTransaction<'a> {
+    input: &'a mut Input,
+    schema_type: &'a InputSchemaType,
+    working_input: Input,
+    patch: InputPatch,
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InputTransactionError {
+    #[error(transparent)]
+    Patch(#[from] InputPatchError),
+    #[error(transparent)]
+    Validation(#[from] InputSchemaError),
+}
+
+impl<'a> InputTransaction<'a> {
+    pub fn new(input: &'a mut Input, schema_type: &'a InputSchemaType) -> Self {
+        let working_input = input.clone();
+        Self {
+            input,
+            schema_type,
+            working_input,
+            patch: InputPatch::new(),
+        }
+    }
+
+    pub fn input(&self) -> &Input {
+        &self.working_input
+    }
+
+    pub fn patch(&self) -> &InputPatch {
+        &self.patch
+    }
+
+    // Existing values are replaced with a checked `Replace` operation (where `NaN` is equal to
+    // `NaN` like in all patches):
+    pub fn set_path<I: Into<Input>>(
+        &mut self,
+        position: &InputPosition,
+        value: I,
+    ) -> Result<&mut Self, InputPatchError> {
+        let value = value.into();
+        let operation = if let Some(old_value) = self.working_input.get_at(position) {
+            InputPatchOperation::Replace {
+                position: position.clone(),
+                old_value: old_value.clone(),
+                new_value: value,
+            }
+        } else {
+            InputPatchOperation::Add {
+                position: position.clone(),
+                value,
+            }
+        };
+        self.apply(operation)
+    }
+
+    pub fn remove_path(&mut self, position: &InputPosition) -> Result<&mut Self, InputPatchError> {
+        let old_value = self
+            .working_input
+            .get_at(position)
+            .ok_or_else(|| InputPatchError::NotFound {
+                position: position.clone(),
+            })?
+            .clone();
+        self.apply(InputPatchOperation::Remove {
+            position: position.clone(),
+            old_value,
+        })
+    }
+
+    fn apply(&mut self, operation: InputPatchOperation) -> Result<&mut Self, InputPatchError> {
+        operation.apply(&mut self.working_input)?;
+        self.patch.add_operation(operation);
+        Ok(self)
+    }
+
+    // Returns the patch of all accumulated changes (before any coercion made by validation):
+    pub fn commit(mut self) -> Result<InputPatch, InputTransactionError> {
+        self.schema_type.validate(&mut self.working_input, None)?;
+        self.schema_type
+            .check_immutable(self.input, &self.working_input)?;
+        *self.input = self.working_input;
+        Ok(self.patch)
+    }
+
+    pub fn rollback(self) {}
+}
+
+impl Input {
+    pub fn transaction<'a>(&'a mut self, schema_type: &'a InputSchemaType) -> InputTransaction<'a> {
+        InputTransaction::new(self, schema_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn transaction() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "host": {"schema": {"type": "string"}},
+                "port": {"schema": {"type": "port"}},
+                "user": {"schema": {"type": "string"}, "default": "root"}
+            }
+        }))
+        .unwrap();
+        let mut input: Input =
+            serde_json::from_value(json!({"host": "localhost", "port": 80, "user": "admin"}))
+                .unwrap();
+        let original = input.clone();
+        let port = InputPosition::new().new_with_key("port");
+        let user = InputPosition::new().new_with_key("user");
+
+        let mut transaction = input.transaction(&schema_type);
+        transaction.set_path(&port, "not a port").unwrap();
+        assert!(matches!(
+            transaction.commit(),
+            Err(InputTransactionError::Validation(_))
+        ));
+        assert_eq!(input, original);
+
+        let mut transaction = input.transaction(&schema_type);
+        transaction.set_path(&port, 8080).unwrap();
+        transaction.rollback();
+        assert_eq!(input, original);
+
+        let mut transaction = input.transaction(&schema_type);
+        assert!(transaction
+            .remove_path(&InputPosition::new().new_with_key("unknown"))
+            .is_err());
+        transaction
+            .set_path(&port, 8080)
+            .unwrap()
+            .remove_path(&user)
+            .unwrap();
+        let patch = transaction.commit().unwrap();
+        assert_eq!(patch.len(), 2);
+        assert_eq!(input.as_map().get("port"), Some(&Input::from(8080)));
+        assert_eq!(input.as_map().get("user"), Some(&Input::from("root")));
+    }
+
+    #[test]
+    fn set_path_nan() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {"ratio": {"schema": {"type": "float"}}}
+        }))
+        .unwrap();
+        let mut input = Input::from(std::collections::HashMap::from([(
+            "ratio",
+            Input::from(f64::NAN),
+        )]));
+        let ratio = InputPosition::new().new_with_key("ratio");
+        let mut transaction = input.transaction(&schema_type);
+        transaction.set_path(&ratio, 0.5).unwrap();
+        transaction.set_path(&ratio, f64::NAN).unwrap();
+        transaction.set_path(&ratio, 0.25).unwrap();
+        transaction.commit().unwrap();
+        assert_eq!(input.as_map().get("ratio"), Some(&Input::from(0.25)));
+    }
+}
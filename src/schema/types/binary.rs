@@ -0,0 +1,180 @@
+use crate::{
+    base64::{decode_base64, encode_base64},
+    position::InputPosition,
+    schema::{InputSchemaError, InputSchemaType, InputSchemaTypeSize},
+    Input,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// Binary data (e.g. keys, tokens, and salts) encoded as a string. `size` is checked against the
+// decoded length in bytes. `Input` has no bytes variant, so the value is kept as the encoded string
+// and bytes of loaders (see `utf8::InputUtf8Policy::Bytes`) are encoded:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeBinary {
+    #[serde(default)]
+    encoding: InputSchemaTypeBinaryEncoding,
+    #[serde(rename = "size", skip_serializing_if = "Option::is_none", default)]
+    maybe_size: Option<InputSchemaTypeSize>,
+}
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(
+    rename_all = "snake_case",
+    deny_unknown_fields,
+    expecting = "Expecting binary encodings: `base64` or `hex`"
+)]
+pub enum InputSchemaTypeBinaryEncoding {
+    #[default]
+    Base64,
+    Hex,
+}
+
+impl InputSchemaTypeBinary {
+    pub fn encoding(&self) -> InputSchemaTypeBinaryEncoding {
+        self.encoding
+    }
+
+    pub fn encoding_mut(&mut self) -> &mut InputSchemaTypeBinaryEncoding {
+        &mut self.encoding
+    }
+
+    pub fn set_encoding(&mut self, encoding: InputSchemaTypeBinaryEncoding) {
+        *self.encoding_mut() = encoding;
+    }
+
+    pub fn with_encoding(mut self, encoding: InputSchemaTypeBinaryEncoding) -> Self {
+        self.set_encoding(encoding);
+        self
+    }
+
+    pub fn maybe_size(&self) -> Option<&InputSchemaTypeSize> {
+        self.maybe_size.as_ref()
+    }
+
+    pub fn maybe_size_mut(&mut self) -> &mut Option<InputSchemaTypeSize> {
+        &mut self.maybe_size
+    }
+
+    pub fn set_size<S: Into<InputSchemaTypeSize>>(&mut self, size: S) {
+        *self.maybe_size_mut() = Some(size.into());
+    }
+
+    pub fn with_size<S: Into<InputSchemaTypeSize>>(mut self, size: S) -> Self {
+        self.set_size(size);
+        self
+    }
+}
+
+impl InputSchemaTypeBinary {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        if let Some(bytes) = input.maybe_bytes() {
+            *input = Input::from(match self.encoding {
+                InputSchemaTypeBinaryEncoding::Base64 => encode_base64(&bytes),
+                InputSchemaTypeBinaryEncoding::Hex => bytes
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<String>(),
+            });
+        }
+        if !input.is_str() {
+            return Err(InputSchemaError::Type {
+                position: maybe_position.unwrap_or_default(),
+                expected_type: Input::str_type_name(),
+                input_type: input.type_name(),
+            });
+        }
+        let text = input.as_str().as_str();
+        let maybe_length = match self.encoding {
+            InputSchemaTypeBinaryEncoding::Base64 => decode_base64(text).map(|bytes| bytes.len()),
+            InputSchemaTypeBinaryEncoding::Hex => (text.len().is_multiple_of(2)
+                && text.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .then_some(text.len() / 2),
+        };
+        let length = maybe_length.ok_or_else(|| InputSchemaError::Invalid {
+            description: format!("Could not decode {}", self.encoding),
+            position: maybe_position.clone().unwrap_or_default(),
+            input: input.clone(),
+        })?;
+        if let Some(size) = self.maybe_size {
+            if size.maybe_max().is_some_and(|max| length > max)
+                || size.maybe_min().is_some_and(|min| length < min)
+            {
+                return Err(InputSchemaError::Size {
+                    position: maybe_position.unwrap_or_default(),
+                    schema_type: InputSchemaType::Binary(self.clone()),
+                    expected_size: size,
+                    size: length,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for InputSchemaTypeBinaryEncoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Base64 => "base64",
+            Self::Hex => "hex",
+        })
+    }
+}
+
+impl Display for InputSchemaTypeBinary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            format!(
+                "{} encoded binary data{}",
+                self.encoding,
+                if let Some(size) = self.maybe_size {
+                    format!(" with {size} (in bytes)")
+                } else {
+                    String::new()
+                }
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let base64 = InputSchemaTypeBinary::default()
+            .with_size(InputSchemaTypeSize::new_with_min_max(2usize, 3usize));
+        let hex = base64
+            .clone()
+            .with_encoding(InputSchemaTypeBinaryEncoding::Hex);
+        for (schema, text) in [(&base64, "Zm9v"), (&base64, "Zm8="), (&hex, "C0fFee")] {
+            let mut input = Input::from(text);
+            schema.validate(&mut input, None).unwrap();
+            assert_eq!(input, Input::from(text));
+        }
+        for (schema, text) in [(&base64, "Zm9vYmFy"), (&base64, "Zg=="), (&hex, "00")] {
+            assert!(matches!(
+                schema.validate(&mut Input::from(text), None),
+                Err(InputSchemaError::Size { .. })
+            ));
+        }
+        for (schema, text) in [(&base64, "Zm9"), (&hex, "c0f"), (&hex, "zz00")] {
+            assert!(matches!(
+                schema.validate(&mut Input::from(text), None),
+                Err(InputSchemaError::Invalid { .. })
+            ));
+        }
+        for (schema, text) in [(&base64, "//4="), (&hex, "fffe")] {
+            let mut input = Input::new_bytes(b"\xff\xfe");
+            schema.validate(&mut input, None).unwrap();
+            assert_eq!(input, Input::from(text));
+        }
+    }
+}
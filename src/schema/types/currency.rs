@@ -0,0 +1,74 @@
+use crate::{position::InputPosition, schema::InputSchemaError, Input};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// ISO 4217 currency codes (e.g. `"eur"`) normalized to uppercase:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeCurrency {}
+
+// Active codes (including funds, precious metals, and testing codes), sorted:
+const CURRENCY_CODE_LIST: [&str; 177] = [
+    "AED", "AFN", "ALL", "AMD", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT", "BHD",
+    "BIF", "BMD", "BND", "BOB", "BOV", "BRL", "BSD", "BTN", "BWP", "BYN", "BZD", "CAD", "CDF",
+    "CHE", "CHF", "CHW", "CLF", "CLP", "CNY", "COP", "COU", "CRC", "CUP", "CVE", "CZK", "DJF",
+    "DKK", "DOP", "DZD", "EGP", "ERN", "ETB", "EUR", "FJD", "FKP", "GBP", "GEL", "GHS", "GIP",
+    "GMD", "GNF", "GTQ", "GYD", "HKD", "HNL", "HTG", "HUF", "IDR", "ILS", "INR", "IQD", "IRR",
+    "ISK", "JMD", "JOD", "JPY", "KES", "KGS", "KHR", "KMF", "KPW", "KRW", "KWD", "KYD", "KZT",
+    "LAK", "LBP", "LKR", "LRD", "LSL", "LYD", "MAD", "MDL", "MGA", "MKD", "MMK", "MNT", "MOP",
+    "MRU", "MUR", "MVR", "MWK", "MXN", "MXV", "MYR", "MZN", "NAD", "NGN", "NIO", "NOK", "NPR",
+    "NZD", "OMR", "PAB", "PEN", "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD", "RUB",
+    "RWF", "SAR", "SBD", "SCR", "SDG", "SEK", "SGD", "SHP", "SLE", "SOS", "SRD", "SSP", "STN",
+    "SVC", "SYP", "SZL", "THB", "TJS", "TMT", "TND", "TOP", "TRY", "TTD", "TWD", "TZS", "UAH",
+    "UGX", "USD", "USN", "UYI", "UYU", "UYW", "UZS", "VED", "VES", "VND", "VUV", "WST", "XAF",
+    "XAG", "XAU", "XBA", "XBB", "XBC", "XBD", "XCD", "XCG", "XDR", "XOF", "XPD", "XPF", "XPT",
+    "XSU", "XTS", "XUA", "XXX", "YER", "ZAR", "ZMW", "ZWG",
+];
+
+impl InputSchemaTypeCurrency {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        if !input.is_str() {
+            return Err(InputSchemaError::Type {
+                position: maybe_position.unwrap_or_default(),
+                expected_type: Input::str_type_name(),
+                input_type: input.type_name(),
+            });
+        }
+        let code = input.as_str().trim().to_uppercase();
+        if CURRENCY_CODE_LIST.binary_search(&code.as_str()).is_err() {
+            return Err(InputSchemaError::Invalid {
+                description: "Expected an ISO 4217 currency code".to_string(),
+                position: maybe_position.unwrap_or_default(),
+                input: input.clone(),
+            });
+        }
+        *input = Input::from(code);
+        Ok(())
+    }
+}
+
+impl Display for InputSchemaTypeCurrency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ISO 4217 currency code")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let schema = InputSchemaTypeCurrency::default();
+        let mut input = Input::from("eur");
+        schema.validate(&mut input, None).unwrap();
+        assert_eq!(input, Input::from("EUR"));
+        for text in ["EURO", "ABC", "€"] {
+            assert!(schema.validate(&mut Input::from(text), None).is_err());
+        }
+    }
+}
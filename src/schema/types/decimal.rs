@@ -0,0 +1,164 @@
+use crate::{position::InputPosition, schema::InputSchemaError, Input};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+// Valid values are normalized to a string without trailing zeros (e.g. `"10.50"` to `"10.5"`).
+// `precision` is the maximum number of digits and `scale` is the maximum number of them after the
+// decimal point:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeDecimal {
+    #[serde(rename = "precision", skip_serializing_if = "Option::is_none", default)]
+    maybe_precision: Option<u32>,
+    #[serde(rename = "scale", skip_serializing_if = "Option::is_none", default)]
+    maybe_scale: Option<u32>,
+}
+
+impl InputSchemaTypeDecimal {
+    pub fn maybe_precision(&self) -> Option<u32> {
+        self.maybe_precision
+    }
+
+    pub fn maybe_precision_mut(&mut self) -> &mut Option<u32> {
+        &mut self.maybe_precision
+    }
+
+    pub fn set_precision(&mut self, precision: u32) {
+        *self.maybe_precision_mut() = Some(precision);
+    }
+
+    pub fn with_precision(mut self, precision: u32) -> Self {
+        self.set_precision(precision);
+        self
+    }
+
+    pub fn maybe_scale(&self) -> Option<u32> {
+        self.maybe_scale
+    }
+
+    pub fn maybe_scale_mut(&mut self) -> &mut Option<u32> {
+        &mut self.maybe_scale
+    }
+
+    pub fn set_scale(&mut self, scale: u32) {
+        *self.maybe_scale_mut() = Some(scale);
+    }
+
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        self.set_scale(scale);
+        self
+    }
+}
+
+impl InputSchemaTypeDecimal {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        let maybe_decimal = match input {
+            Input::Str(string) => Decimal::from_str_exact(string.trim()).ok(),
+            Input::Int(integer) => Some(Decimal::from(*integer as i64)),
+            // The shortest representation of the float (e.g. `0.1` and not `0.1000000000000000055`):
+            Input::Float(float) if float.is_finite() => {
+                Decimal::from_str(float.to_string().as_str()).ok()
+            }
+            _ => {
+                return Err(InputSchemaError::Type {
+                    position: maybe_position.unwrap_or_default(),
+                    expected_type: "decimal".to_string(),
+                    input_type: input.type_name(),
+                })
+            }
+        };
+        let invalid = |description: String| InputSchemaError::Invalid {
+            description,
+            position: maybe_position.clone().unwrap_or_default(),
+            input: input.clone(),
+        };
+        let decimal = maybe_decimal
+            .ok_or_else(|| invalid("Could not parse decimal number".to_string()))?
+            .normalize();
+        if let Some(scale) = self.maybe_scale {
+            if decimal.scale() > scale {
+                return Err(invalid(format!(
+                    "Expected at most {scale} digits after the decimal point"
+                )));
+            }
+        }
+        if let Some(precision) = self.maybe_precision {
+            let digit_count = decimal.mantissa().unsigned_abs().to_string().len() as u32;
+            // Leading zeros are not significant (e.g. `0.05` has 1 digit and a scale of 2):
+            let digit_count = digit_count.max(decimal.scale());
+            if digit_count > precision {
+                return Err(invalid(format!("Expected at most {precision} digits")));
+            }
+        }
+        *input = Input::from(decimal.to_string());
+        Ok(())
+    }
+}
+
+impl Display for InputSchemaTypeDecimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut text = "decimal number".to_string();
+        match (self.maybe_precision, self.maybe_scale) {
+            (Some(precision), Some(scale)) => text.push_str(
+                format!(
+                    " with at most {precision} digits and {scale} of them after the decimal point"
+                )
+                .as_str(),
+            ),
+            (Some(precision), None) => {
+                text.push_str(format!(" with at most {precision} digits").as_str())
+            }
+            (None, Some(scale)) => text
+                .push_str(format!(" with at most {scale} digits after the decimal point").as_str()),
+            (None, None) => (),
+        }
+        f.write_str(text.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let schema = InputSchemaTypeDecimal::default()
+            .with_precision(5)
+            .with_scale(2);
+        for (input, expected) in [
+            (Input::from("10.50"), "10.5"),
+            (Input::from(" -123.45 "), "-123.45"),
+            (Input::from(0.1), "0.1"),
+            (Input::from(42), "42"),
+            (Input::from("0.05"), "0.05"),
+        ] {
+            let mut input = input;
+            schema.validate(&mut input, None).unwrap();
+            assert_eq!(input, Input::from(expected));
+        }
+        for input in [
+            Input::from("1.234"),
+            Input::from("123456"),
+            Input::from("1e3"),
+            Input::from("abc"),
+        ] {
+            let mut input = input;
+            assert!(matches!(
+                schema.validate(&mut input, None),
+                Err(InputSchemaError::Invalid { .. })
+            ));
+        }
+        assert!(matches!(
+            schema.validate(&mut Input::from(true), None),
+            Err(InputSchemaError::Type { .. })
+        ));
+    }
+}
@@ -1,20 +1,64 @@
 use crate::{
     position::InputPosition,
-    schema::{InputSchemaError, InputSchemaType, InputSchemaTypeSize},
+    schema::{
+        validate_with_cow, InputSchemaError, InputSchemaType, InputSchemaTypeEither,
+        InputSchemaTypeSize,
+    },
     Input,
 };
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
 
 #[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct InputSchemaTypeDynamicMap {
     #[serde(rename = "size", skip_serializing_if = "Option::is_none", default)]
     maybe_size: Option<InputSchemaTypeSize>,
+    #[serde(default)]
     item_schema: Box<InputSchemaType>,
+    #[serde(rename = "format", skip_serializing_if = "Option::is_none", default)]
+    maybe_format: Option<InputSchemaTypeDynamicMapFormat>,
+}
+
+// Well-known key/value formats checked before `item_schema`:
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(
+    rename_all = "snake_case",
+    deny_unknown_fields,
+    expecting = "Expecting dynamic map formats: `http_headers` or `identifiers`"
+)]
+pub enum InputSchemaTypeDynamicMapFormat {
+    // Names are RFC 7230 tokens and values are visible ASCII (with inner spaces and tabs):
+    HttpHeaders,
+    // Keys start with an ASCII letter or `_` followed by ASCII letters, digits, `_`, `-`, or `.`
+    // (e.g. `new_checkout` or `search.v2`):
+    Identifiers,
 }
 
 impl InputSchemaTypeDynamicMap {
+    // Serialized as `{"type": "dynamic_map", "format": "http_headers"}`:
+    pub fn new_http_headers() -> Self {
+        Self::default()
+            .with_item_schema(InputSchemaType::new_string())
+            .with_format(InputSchemaTypeDynamicMapFormat::HttpHeaders)
+    }
+
+    // Feature flags that are either on/off or rolled out to a ratio (e.g. `{"new_checkout": true,
+    // "search.v2": "25%"}`):
+    pub fn feature_flags() -> Self {
+        Self::default()
+            .with_item_schema(
+                InputSchemaTypeEither::default()
+                    .with_schema(InputSchemaType::new_boolean())
+                    .with_schema(InputSchemaType::new_percent()),
+            )
+            .with_format(InputSchemaTypeDynamicMapFormat::Identifiers)
+    }
+
     pub fn item_schema(&self) -> &InputSchemaType {
         &self.item_schema
     }
@@ -48,6 +92,23 @@ impl InputSchemaTypeDynamicMap {
         self.set_size(size);
         self
     }
+
+    pub fn maybe_format(&self) -> Option<InputSchemaTypeDynamicMapFormat> {
+        self.maybe_format
+    }
+
+    pub fn maybe_format_mut(&mut self) -> &mut Option<InputSchemaTypeDynamicMapFormat> {
+        &mut self.maybe_format
+    }
+
+    pub fn set_format(&mut self, format: InputSchemaTypeDynamicMapFormat) {
+        *self.maybe_format_mut() = Some(format);
+    }
+
+    pub fn with_format(mut self, format: InputSchemaTypeDynamicMapFormat) -> Self {
+        self.set_format(format);
+        self
+    }
 }
 
 // impl InputSchemaTypeDynamicMap {
@@ -95,6 +156,14 @@ impl InputSchemaTypeDynamicMap {
         input: &mut Input,
         maybe_position: Option<InputPosition>,
     ) -> Result<(), InputSchemaError> {
+        validate_with_cow(input, |input| self.validate_cow(input, maybe_position))
+    }
+
+    pub fn validate_cow<'a>(
+        &self,
+        input: &'a Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<Cow<'a, Input>, InputSchemaError> {
         if !input.is_map() {
             return Err(InputSchemaError::Type {
                 position: maybe_position.unwrap_or_default(),
@@ -126,13 +195,110 @@ impl InputSchemaTypeDynamicMap {
             }
         }
         let position = maybe_position.unwrap_or_default();
-        input
-            .map_mut()
-            .iter_mut()
-            .try_for_each(|(key, inner_input)| {
-                self.item_schema
-                    .validate(inner_input, Some(position.new_with_key(key)))
+        let map = input.as_map();
+        let mut changed_list = Vec::new();
+        for (key, inner_input) in map {
+            if let Some(format) = self.maybe_format {
+                format.validate(key, inner_input, &position)?;
+            }
+            if let Cow::Owned(validated_inner_input) = self
+                .item_schema
+                .validate_cow(inner_input, Some(position.new_with_key(key)))?
+            {
+                changed_list.push((key, validated_inner_input));
+            }
+        }
+        if changed_list.is_empty() {
+            return Ok(Cow::Borrowed(input));
+        }
+        // Changed values are not copied from `map`:
+        let mut changed_map: HashMap<_, _> = changed_list.into_iter().collect();
+        let mut new_map: HashMap<_, _> = map
+            .iter()
+            .map(|(key, inner_input)| {
+                (
+                    key.clone(),
+                    changed_map
+                        .remove(key)
+                        .unwrap_or_else(|| inner_input.clone()),
+                )
             })
+            .collect();
+        new_map.extend(
+            changed_map
+                .into_iter()
+                .map(|(key, validated_inner_input)| (key.clone(), validated_inner_input)),
+        );
+        Ok(Cow::Owned(Input::from(new_map)))
+    }
+}
+
+impl InputSchemaTypeDynamicMapFormat {
+    pub(crate) fn validate(
+        &self,
+        key: &str,
+        input: &Input,
+        position: &InputPosition,
+    ) -> Result<(), InputSchemaError> {
+        match self {
+            Self::HttpHeaders => {
+                let is_token_char =
+                    |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+                if key.is_empty() || !key.chars().all(is_token_char) {
+                    return Err(InputSchemaError::Invalid {
+                        description: format!("Invalid HTTP header name `{key}`"),
+                        position: position.new_with_key(key),
+                        input: input.clone(),
+                    });
+                }
+                if !input.is_str() {
+                    return Err(InputSchemaError::Type {
+                        position: position.new_with_key(key),
+                        expected_type: Input::str_type_name(),
+                        input_type: input.type_name(),
+                    });
+                }
+                let value = input.as_str();
+                let is_visible = |c: char| c.is_ascii_graphic();
+                if !value
+                    .chars()
+                    .all(|c| is_visible(c) || c == ' ' || c == '\t')
+                    || value.starts_with([' ', '\t'])
+                    || value.ends_with([' ', '\t'])
+                {
+                    return Err(InputSchemaError::Invalid {
+                        description:
+                            "Invalid HTTP header value (expected visible ASCII characters)"
+                                .to_string(),
+                        position: position.new_with_key(key),
+                        input: input.clone(),
+                    });
+                }
+            }
+            Self::Identifiers => {
+                if !key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                    || !key
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c))
+                {
+                    return Err(InputSchemaError::Invalid {
+                        description: format!("Invalid identifier `{key}`"),
+                        position: position.new_with_key(key),
+                        input: input.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for InputSchemaTypeDynamicMapFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::HttpHeaders => "HTTP headers",
+            Self::Identifiers => "identifiers",
+        })
     }
 }
 
@@ -140,7 +306,12 @@ impl Display for InputSchemaTypeDynamicMap {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(
             format!(
-                "dynamic map {}that each value can be {}",
+                "dynamic map {}{}that each value can be {}",
+                if let Some(format) = self.maybe_format {
+                    format!("of {format} ")
+                } else {
+                    String::new()
+                },
                 if let Some(size) = self.maybe_size {
                     format!(" with {size} ")
                 } else {
@@ -155,6 +326,64 @@ impl Display for InputSchemaTypeDynamicMap {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use serde_json::json;
+
     #[test]
     fn serde() {}
+
+    #[test]
+    fn http_headers() {
+        let schema_type: InputSchemaType =
+            serde_json::from_value(json!({"type": "dynamic_map", "format": "http_headers"}))
+                .unwrap();
+        assert_eq!(
+            schema_type,
+            InputSchemaType::DynamicMap(
+                InputSchemaTypeDynamicMap::new_http_headers()
+                    .with_item_schema(InputSchemaType::new_any())
+            )
+        );
+        let schema = InputSchemaTypeDynamicMap::new_http_headers();
+        let mut input: Input = serde_json::from_value(
+            json!({"X-Request-Id": "abc-123", "Accept": "text/html, */*;q=0.8", "X-Empty": ""}),
+        )
+        .unwrap();
+        schema.validate(&mut input, None).unwrap();
+        for input in [
+            json!({"X Bad": "value"}),
+            json!({"": "value"}),
+            json!({"X-Value": "line\nbreak"}),
+            json!({"X-Value": " padded"}),
+            json!({"X-Value": "caf\u{e9}"}),
+            json!({"X-Value": 1}),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            assert!(schema.validate(&mut input, None).is_err());
+        }
+    }
+
+    #[test]
+    fn feature_flags() {
+        let schema = InputSchemaTypeDynamicMap::feature_flags();
+        let mut input: Input =
+            serde_json::from_value(json!({"new_checkout": true, "search.v2": "25%", "beta": "no"}))
+                .unwrap();
+        schema.validate(&mut input, None).unwrap();
+        assert_eq!(
+            input,
+            serde_json::from_value::<Input>(
+                json!({"new_checkout": true, "search.v2": 0.25, "beta": false})
+            )
+            .unwrap()
+        );
+        for input in [
+            json!({"2fa": true}),
+            json!({"a b": true}),
+            json!({"beta": "x"}),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            assert!(schema.validate(&mut input, None).is_err());
+        }
+    }
 }
@@ -1,10 +1,13 @@
 use crate::{
     position::InputPosition,
-    schema::{InputSchemaError, InputSchemaType},
+    schema::{validate_with_cow, InputSchemaError, InputSchemaType},
     Input,
 };
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter};
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter},
+};
 
 #[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
@@ -49,18 +52,18 @@ impl InputSchemaTypeEither {
         input: &mut Input,
         maybe_position: Option<InputPosition>,
     ) -> Result<(), InputSchemaError> {
+        validate_with_cow(input, |input| self.validate_cow(input, maybe_position))
+    }
+
+    // Branches are tried on the borrowed input, so a failed branch costs no copy:
+    pub fn validate_cow<'a>(
+        &self,
+        input: &'a Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<Cow<'a, Input>, InputSchemaError> {
         for schema in self.schema_list.iter() {
-            let mut new_input = input.clone();
-            // test on clone:
-            if schema
-                .validate(&mut new_input, maybe_position.clone())
-                .is_ok()
-            {
-                if &mut new_input != input {
-                    // trace_update!(maybe_position.unwrap_or_default(), input, new_input);
-                }
-                *input = new_input;
-                return Ok(());
+            if let Ok(validated_input) = schema.validate_cow(input, maybe_position.clone()) {
+                return Ok(validated_input);
             }
         }
         Err(InputSchemaError::Schema {
@@ -73,36 +76,27 @@ impl InputSchemaTypeEither {
 
 impl Display for InputSchemaTypeEither {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let length = self.schema_list.len();
-        if length > 2 {
-            let mut text = String::new();
-            self.schema_list
-                .iter()
-                .enumerate()
-                .for_each(|(index, schema)| {
-                    text += if index + 1 == length {
-                        format!("or {schema}")
-                    } else {
-                        format!("{schema}, ")
-                    }
-                    .as_str()
-                });
-            f.write_str(text.as_str())
-        } else if length == 2 {
-            f.write_str(
-                format!(
-                    "either {} or {}",
-                    self.schema_list.get(0).unwrap(),
-                    self.schema_list.get(2).unwrap()
-                )
-                .as_str(),
-            )
-        } else if length == 1 {
-            f.write_str(format!("{}", self.schema_list.get(0).unwrap()).as_str())
-        } else if length == 0 {
-            f.write_str("misconfigured `Either` type which contains nothing!")
-        } else {
-            unreachable!()
+        match self.schema_list.len() {
+            0 => f.write_str("misconfigured `Either` type which contains nothing!"),
+            1 => f.write_str(format!("{}", self.schema_list[0]).as_str()),
+            2 => f.write_str(
+                format!("either {} or {}", self.schema_list[0], self.schema_list[1]).as_str(),
+            ),
+            length => {
+                let mut text = String::new();
+                self.schema_list
+                    .iter()
+                    .enumerate()
+                    .for_each(|(index, schema)| {
+                        text += if index + 1 == length {
+                            format!("or {schema}")
+                        } else {
+                            format!("{schema}, ")
+                        }
+                        .as_str()
+                    });
+                f.write_str(text.as_str())
+            }
         }
     }
 }
@@ -0,0 +1,334 @@
+use crate::{position::InputPosition, schema::InputSchemaError, Input};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// Predicates for routing and filtering (e.g. `"env == 'prod' and (cpu > 0.8 or !healthy)"`). The
+// grammar is:
+//
+//   or         = and (("or" | "||") and)*
+//   and        = not (("and" | "&&") not)*
+//   not        = ("not" | "!") not | "(" or ")" | comparison
+//   comparison = operand (("==" | "!=" | "<" | "<=" | ">" | ">=") operand)?
+//   operand    = identifier (e.g. `request.path`) | number | string (quoted) | `true` | `false`
+//
+// With `identifiers`, only those identifiers can be used:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeExpr {
+    #[serde(rename = "identifiers", skip_serializing_if = "Vec::is_empty", default)]
+    identifier_list: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    Identifier(&'a str),
+    Literal,
+    Operator,
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+// A syntax error and its byte offset in the expression:
+struct ExprError {
+    offset: usize,
+    description: String,
+}
+
+struct Parser<'a, 'b> {
+    token_list: Vec<(usize, Token<'a>)>,
+    index: usize,
+    length: usize,
+    identifier_list: &'b [String],
+}
+
+impl InputSchemaTypeExpr {
+    pub fn identifier_list(&self) -> &Vec<String> {
+        &self.identifier_list
+    }
+
+    pub fn identifier_list_mut(&mut self) -> &mut Vec<String> {
+        &mut self.identifier_list
+    }
+
+    pub fn set_identifier_list<S: ToString>(&mut self, identifier_list: Vec<S>) {
+        *self.identifier_list_mut() = identifier_list
+            .into_iter()
+            .map(|identifier| identifier.to_string())
+            .collect();
+    }
+
+    pub fn with_identifier_list<S: ToString>(mut self, identifier_list: Vec<S>) -> Self {
+        self.set_identifier_list(identifier_list);
+        self
+    }
+}
+
+impl InputSchemaTypeExpr {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        if !input.is_str() {
+            return Err(InputSchemaError::Type {
+                position: maybe_position.unwrap_or_default(),
+                expected_type: Input::str_type_name(),
+                input_type: input.type_name(),
+            });
+        }
+        let text = input.as_str();
+        tokenize(text)
+            .and_then(|token_list| {
+                Parser {
+                    token_list,
+                    index: 0,
+                    length: text.len(),
+                    identifier_list: &self.identifier_list,
+                }
+                .parse()
+            })
+            .map_err(|error| InputSchemaError::Invalid {
+                description: format!(
+                    "Invalid expression at offset {}: {}",
+                    error.offset, error.description
+                ),
+                position: maybe_position.unwrap_or_default(),
+                input: input.clone(),
+            })
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token<'_>)>, ExprError> {
+    let mut token_list = Vec::new();
+    let mut char_iter = text.char_indices().peekable();
+    while let Some((offset, c)) = char_iter.next() {
+        let next_is =
+            |expected: char, char_iter: &mut std::iter::Peekable<std::str::CharIndices>| {
+                char_iter
+                    .next_if(|(_, next_c)| *next_c == expected)
+                    .is_some()
+            };
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '&' if next_is('&', &mut char_iter) => Token::And,
+            '|' if next_is('|', &mut char_iter) => Token::Or,
+            '=' if next_is('=', &mut char_iter) => Token::Operator,
+            '!' if next_is('=', &mut char_iter) => Token::Operator,
+            '!' => Token::Not,
+            '<' | '>' => {
+                next_is('=', &mut char_iter);
+                Token::Operator
+            }
+            '\'' | '"' => {
+                let mut is_escaped = false;
+                let is_closed = char_iter.by_ref().any(|(_, next_c)| {
+                    let is_end = next_c == c && !is_escaped;
+                    is_escaped = next_c == '\\' && !is_escaped;
+                    is_end
+                });
+                if !is_closed {
+                    return Err(ExprError {
+                        offset,
+                        description: "unterminated string".to_string(),
+                    });
+                }
+                Token::Literal
+            }
+            _ if c.is_ascii_digit()
+                || (c == '-' && char_iter.peek().is_some_and(|(_, c)| c.is_ascii_digit())) =>
+            {
+                while char_iter
+                    .next_if(|(_, next_c)| next_c.is_ascii_alphanumeric() || *next_c == '.')
+                    .is_some()
+                {}
+                let end = char_iter.peek().map_or(text.len(), |(offset, _)| *offset);
+                if text[offset..end].parse::<f64>().is_err() {
+                    return Err(ExprError {
+                        offset,
+                        description: format!("invalid number `{}`", &text[offset..end]),
+                    });
+                }
+                Token::Literal
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                while char_iter
+                    .next_if(|(_, next_c)| next_c.is_alphanumeric() || "_.".contains(*next_c))
+                    .is_some()
+                {}
+                let end = char_iter.peek().map_or(text.len(), |(offset, _)| *offset);
+                match &text[offset..end] {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "true" | "false" => Token::Literal,
+                    identifier => Token::Identifier(identifier),
+                }
+            }
+            _ => {
+                return Err(ExprError {
+                    offset,
+                    description: format!("unexpected `{c}`"),
+                })
+            }
+        };
+        token_list.push((offset, token));
+    }
+    Ok(token_list)
+}
+
+impl Parser<'_, '_> {
+    fn parse(mut self) -> Result<(), ExprError> {
+        self.parse_or()?;
+        match self.token_list.get(self.index) {
+            Some(_) => Err(self.error("expected `and`, `or`, or the end")),
+            None => Ok(()),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<(), ExprError> {
+        self.parse_and()?;
+        while self.next_if(|token| *token == Token::Or) {
+            self.parse_and()?;
+        }
+        Ok(())
+    }
+
+    fn parse_and(&mut self) -> Result<(), ExprError> {
+        self.parse_not()?;
+        while self.next_if(|token| *token == Token::And) {
+            self.parse_not()?;
+        }
+        Ok(())
+    }
+
+    fn parse_not(&mut self) -> Result<(), ExprError> {
+        crate::stack::maybe_grow(|| {
+            if self.next_if(|token| *token == Token::Not) {
+                return self.parse_not();
+            }
+            if self.next_if(|token| *token == Token::Open) {
+                self.parse_or()?;
+                if !self.next_if(|token| *token == Token::Close) {
+                    return Err(self.error("expected `)`"));
+                }
+                return Ok(());
+            }
+            self.parse_operand()?;
+            if self.next_if(|token| *token == Token::Operator) {
+                self.parse_operand()?;
+            }
+            Ok(())
+        })
+    }
+
+    fn parse_operand(&mut self) -> Result<(), ExprError> {
+        match self.token_list.get(self.index) {
+            Some((offset, Token::Identifier(identifier))) => {
+                if !self.identifier_list.is_empty()
+                    && !self.identifier_list.iter().any(|known| known == identifier)
+                {
+                    return Err(ExprError {
+                        offset: *offset,
+                        description: format!("unknown identifier `{identifier}`"),
+                    });
+                }
+                self.index += 1;
+                Ok(())
+            }
+            Some((_, Token::Literal)) => {
+                self.index += 1;
+                Ok(())
+            }
+            _ => Err(self.error("expected an identifier, a literal, `not`, or `(`")),
+        }
+    }
+
+    fn next_if<F: Fn(&Token) -> bool>(&mut self, predicate: F) -> bool {
+        let is_next = self
+            .token_list
+            .get(self.index)
+            .is_some_and(|(_, token)| predicate(token));
+        if is_next {
+            self.index += 1;
+        }
+        is_next
+    }
+
+    fn error(&self, description: &str) -> ExprError {
+        ExprError {
+            offset: self
+                .token_list
+                .get(self.index)
+                .map_or(self.length, |(offset, _)| *offset),
+            description: description.to_string(),
+        }
+    }
+}
+
+impl Display for InputSchemaTypeExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut text = "expression".to_string();
+        if !self.identifier_list.is_empty() {
+            text.push_str(
+                format!(
+                    " over {}",
+                    self.identifier_list
+                        .iter()
+                        .map(|identifier| format!("`{identifier}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+                .as_str(),
+            );
+        }
+        f.write_str(text.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let schema = InputSchemaTypeExpr::default();
+        for text in [
+            "env == 'prod' and (cpu > 0.8 or !healthy)",
+            "not request.path != \"/health\" || retries <= -1",
+            "enabled",
+        ] {
+            schema.validate(&mut Input::from(text), None).unwrap();
+        }
+        for (text, offset) in [
+            ("env == ", 7),
+            ("(a or b", 7),
+            ("a b", 2),
+            ("a == 'x", 5),
+            ("a # b", 2),
+            ("a and or b", 6),
+        ] {
+            let error = schema.validate(&mut Input::from(text), None).unwrap_err();
+            assert!(
+                error
+                    .to_string()
+                    .contains(format!("at offset {offset}:").as_str()),
+                "{error}"
+            );
+        }
+        let schema = schema.with_identifier_list(vec!["env", "cpu"]);
+        schema
+            .validate(&mut Input::from("env == 'prod' and cpu > 1"), None)
+            .unwrap();
+        let error = schema
+            .validate(&mut Input::from("env == 'prod' and mem > 1"), None)
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("offset 18: unknown identifier `mem`"));
+    }
+}
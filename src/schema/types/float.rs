@@ -1,6 +1,9 @@
 use crate::{
     position::InputPosition,
-    schema::{InputSchemaError, InputSchemaType, InputSchemaTypeRange},
+    schema::{
+        common::number::strip_digit_separators, InputSchemaError, InputSchemaType,
+        InputSchemaTypeRange,
+    },
     Input,
 };
 use serde::{Deserialize, Serialize};
@@ -11,9 +14,39 @@ use std::fmt::{Display, Formatter};
 pub struct InputSchemaTypeFloat {
     #[serde(rename = "range", skip_serializing_if = "Option::is_none", default)]
     maybe_range: Option<InputSchemaTypeRange>,
+    // Strings may contain digit separators (e.g. `"1,000"`, `"1_000"`, or `"1 000"`):
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    digit_separators: bool,
 }
 
 impl InputSchemaTypeFloat {
+    // Ranges of the most common constraints. Bounds of ranges are inclusive, so `positive()` starts
+    // from the smallest positive float:
+    pub fn positive() -> Self {
+        Self::default().with_range(InputSchemaTypeRange::new_with_min(f64::MIN_POSITIVE))
+    }
+
+    pub fn non_negative() -> Self {
+        Self::default().with_range(InputSchemaTypeRange::new_with_min(0.0))
+    }
+
+    pub fn negative() -> Self {
+        Self::default().with_range(InputSchemaTypeRange::new_with_max(-f64::MIN_POSITIVE))
+    }
+
+    pub fn digit_separators(&self) -> bool {
+        self.digit_separators
+    }
+
+    pub fn set_digit_separators(&mut self, digit_separators: bool) {
+        self.digit_separators = digit_separators;
+    }
+
+    pub fn with_digit_separators(mut self, digit_separators: bool) -> Self {
+        self.set_digit_separators(digit_separators);
+        self
+    }
+
     pub fn maybe_range(&self) -> Option<&InputSchemaTypeRange> {
         self.maybe_range.as_ref()
     }
@@ -38,6 +71,11 @@ impl InputSchemaTypeFloat {
         input: &mut Input,
         maybe_position: Option<InputPosition>,
     ) -> Result<(), InputSchemaError> {
+        if self.digit_separators && input.is_str() {
+            if let Some(text) = strip_digit_separators(input.as_str()) {
+                *input = Input::from(text)
+            }
+        }
         if input.is_str() {
             if let Ok(float) = input.as_str().parse::<f64>() {
                 *input = Input::from(float)
@@ -99,6 +137,21 @@ impl Display for InputSchemaTypeFloat {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn serde() {}
+
+    #[test]
+    fn positive() {
+        let schema = InputSchemaTypeFloat::positive();
+        schema.validate(&mut Input::from(0.001), None).unwrap();
+        assert!(schema.validate(&mut Input::from(0.0), None).is_err());
+        let schema = InputSchemaTypeFloat::non_negative();
+        schema.validate(&mut Input::from(0.0), None).unwrap();
+        assert!(schema.validate(&mut Input::from(-0.001), None).is_err());
+        let schema = InputSchemaTypeFloat::negative();
+        schema.validate(&mut Input::from(-0.001), None).unwrap();
+        assert!(schema.validate(&mut Input::from(0.0), None).is_err());
+    }
 }
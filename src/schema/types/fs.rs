@@ -154,8 +154,7 @@ impl InputSchemaTypeFs {
                     });
                 };
             }
-            if self.maybe_path_type.is_some() {
-                let path_type = self.maybe_path_type.unwrap();
+            if let Some(path_type) = self.maybe_path_type {
                 let file_type = path
                     .metadata()
                     .map_err(|error| InputSchemaError::Invalid {
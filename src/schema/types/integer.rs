@@ -1,6 +1,9 @@
 use crate::{
     position::InputPosition,
-    schema::{InputSchemaError, InputSchemaType, InputSchemaTypeRange},
+    schema::{
+        common::number::strip_digit_separators, InputSchemaError, InputSchemaType,
+        InputSchemaTypeRange,
+    },
     Input,
 };
 use serde::{Deserialize, Serialize};
@@ -11,9 +14,59 @@ use std::fmt::{Display, Formatter};
 pub struct InputSchemaTypeInteger {
     #[serde(rename = "range", skip_serializing_if = "Option::is_none", default)]
     maybe_range: Option<InputSchemaTypeRange>,
+    // Strings may contain digit separators (e.g. `"1,000"`, `"1_000"`, or `"1 000"`):
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    digit_separators: bool,
+    #[serde(rename = "suffix", skip_serializing_if = "Option::is_none", default)]
+    maybe_suffix: Option<InputSchemaTypeIntegerSuffix>,
+    // Turn off coercions of integral floats (e.g. `3.0`) and strings (e.g. `"3"`) to integers:
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    deny_floats: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    deny_strings: bool,
+}
+
+// How `k`, `M`, `G`, and `T` suffixes of strings (e.g. `"4k"` for worker counts or `"2M"` for
+// buffer sizes) are multiplied. `Ki`, `Mi`, `Gi`, and `Ti` are always binary:
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(
+    rename_all = "snake_case",
+    deny_unknown_fields,
+    expecting = "Expecting integer suffixes: `decimal` (`1k` is `1000`) or `binary` (`1k` is `1024`)"
+)]
+pub enum InputSchemaTypeIntegerSuffix {
+    #[default]
+    Decimal,
+    Binary,
 }
 
 impl InputSchemaTypeInteger {
+    // Ranges of the most common constraints (e.g. `positive()` is `{"range": {"min": 1}}`):
+    pub fn positive() -> Self {
+        Self::default().with_range(InputSchemaTypeRange::new_with_min(1))
+    }
+
+    pub fn non_negative() -> Self {
+        Self::default().with_range(InputSchemaTypeRange::new_with_min(0))
+    }
+
+    pub fn negative() -> Self {
+        Self::default().with_range(InputSchemaTypeRange::new_with_max(-1))
+    }
+
+    pub fn digit_separators(&self) -> bool {
+        self.digit_separators
+    }
+
+    pub fn set_digit_separators(&mut self, digit_separators: bool) {
+        self.digit_separators = digit_separators;
+    }
+
+    pub fn with_digit_separators(mut self, digit_separators: bool) -> Self {
+        self.set_digit_separators(digit_separators);
+        self
+    }
+
     pub fn maybe_range(&self) -> Option<&InputSchemaTypeRange> {
         self.maybe_range.as_ref()
     }
@@ -30,6 +83,77 @@ impl InputSchemaTypeInteger {
         self.set_range(range);
         self
     }
+
+    pub fn maybe_suffix(&self) -> Option<InputSchemaTypeIntegerSuffix> {
+        self.maybe_suffix
+    }
+
+    pub fn maybe_suffix_mut(&mut self) -> &mut Option<InputSchemaTypeIntegerSuffix> {
+        &mut self.maybe_suffix
+    }
+
+    pub fn set_suffix(&mut self, suffix: InputSchemaTypeIntegerSuffix) {
+        *self.maybe_suffix_mut() = Some(suffix);
+    }
+
+    pub fn with_suffix(mut self, suffix: InputSchemaTypeIntegerSuffix) -> Self {
+        self.set_suffix(suffix);
+        self
+    }
+}
+
+impl InputSchemaTypeInteger {
+    pub fn deny_floats(&self) -> bool {
+        self.deny_floats
+    }
+
+    pub fn set_deny_floats(&mut self, deny_floats: bool) {
+        self.deny_floats = deny_floats;
+    }
+
+    pub fn with_deny_floats(mut self, deny_floats: bool) -> Self {
+        self.set_deny_floats(deny_floats);
+        self
+    }
+
+    pub fn deny_strings(&self) -> bool {
+        self.deny_strings
+    }
+
+    pub fn set_deny_strings(&mut self, deny_strings: bool) {
+        self.deny_strings = deny_strings;
+    }
+
+    pub fn with_deny_strings(mut self, deny_strings: bool) -> Self {
+        self.set_deny_strings(deny_strings);
+        self
+    }
+}
+
+impl InputSchemaTypeIntegerSuffix {
+    // `None` if the text has no known suffix or the result doesn't fit in `isize`:
+    pub fn parse(self, text: &str) -> Option<isize> {
+        let text = text.trim();
+        let base: isize = match self {
+            Self::Decimal => 1000,
+            Self::Binary => 1024,
+        };
+        let (number, exponent, base) = [("k", 1), ("K", 1), ("M", 2), ("G", 3), ("T", 4)]
+            .into_iter()
+            .find_map(|(suffix, exponent)| {
+                text.strip_suffix(format!("{suffix}i").as_str())
+                    .map(|number| (number, exponent, 1024))
+                    .or_else(|| {
+                        text.strip_suffix(suffix)
+                            .map(|number| (number, exponent, base))
+                    })
+            })?;
+        number
+            .trim_end()
+            .parse::<isize>()
+            .ok()?
+            .checked_mul(base.checked_pow(exponent)?)
+    }
 }
 
 impl InputSchemaTypeInteger {
@@ -38,11 +162,19 @@ impl InputSchemaTypeInteger {
         input: &mut Input,
         maybe_position: Option<InputPosition>,
     ) -> Result<(), InputSchemaError> {
-        if input.is_str() {
-            if let Ok(integer) = input.as_str().parse::<isize>() {
+        if self.digit_separators && input.is_str() {
+            if let Some(text) = strip_digit_separators(input.as_str()) {
+                *input = Input::from(text)
+            }
+        }
+        if input.is_str() && !self.deny_strings {
+            if let Some(integer) = input.as_str().parse::<isize>().ok().or_else(|| {
+                self.maybe_suffix
+                    .and_then(|suffix| suffix.parse(input.as_str()))
+            }) {
                 *input = Input::from(integer)
             }
-        } else if input.is_float() && input.as_float().fract() == 0.0 {
+        } else if input.is_float() && !self.deny_floats && input.as_float().fract() == 0.0 {
             *input = Input::from(*input.as_float() as isize)
         };
         if !input.is_int() {
@@ -55,7 +187,13 @@ impl InputSchemaTypeInteger {
         if let Some(range) = self.maybe_range {
             let int = *input.as_int();
             if let Some(max) = range.maybe_max() {
-                if int > max.trunc().integer().unwrap() {
+                let Some(max) = max.trunc().integer() else {
+                    return Err(InputSchemaError::Internal {
+                        position: maybe_position.unwrap_or_default(),
+                        description: format!("Truncated range max `{max}` is not an integer"),
+                    });
+                };
+                if int > max {
                     return Err(InputSchemaError::Range {
                         position: maybe_position.unwrap_or_default(),
                         schema_type: InputSchemaType::new_integer(),
@@ -65,7 +203,13 @@ impl InputSchemaTypeInteger {
                 }
             }
             if let Some(min) = range.maybe_min() {
-                if int < min.trunc().integer().unwrap() {
+                let Some(min) = min.trunc().integer() else {
+                    return Err(InputSchemaError::Internal {
+                        position: maybe_position.unwrap_or_default(),
+                        description: format!("Truncated range min `{min}` is not an integer"),
+                    });
+                };
+                if int < min {
                     return Err(InputSchemaError::Range {
                         position: maybe_position.unwrap_or_default(),
                         schema_type: InputSchemaType::new_integer(),
@@ -79,15 +223,29 @@ impl InputSchemaTypeInteger {
     }
 }
 
+impl Display for InputSchemaTypeIntegerSuffix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Decimal => "decimal",
+            Self::Binary => "binary",
+        })
+    }
+}
+
 impl Display for InputSchemaTypeInteger {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(
             format!(
-                "integer {}",
+                "integer {}{}",
                 if let Some(range) = self.maybe_range {
                     format!(" which should be in {range}")
                 } else {
                     String::new()
+                },
+                if let Some(suffix) = self.maybe_suffix {
+                    format!(" (accepts {suffix} `k`, `M`, `G`, and `T` suffixes)")
+                } else {
+                    String::new()
                 }
             )
             .as_str(),
@@ -97,6 +255,91 @@ impl Display for InputSchemaTypeInteger {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use serde_json::json;
+
     #[test]
     fn serde() {}
+
+    #[test]
+    fn validate_suffix() {
+        let schema: InputSchemaTypeInteger =
+            serde_json::from_value(json!({"suffix": "decimal", "range": {"max": 4000000}}))
+                .unwrap();
+        for (text, integer) in [("4k", 4000), ("2M", 2000000), ("1Ki", 1024), ("12", 12)] {
+            let mut input = Input::from(text);
+            schema.validate(&mut input, None).unwrap();
+            assert_eq!(input, Input::from(integer));
+        }
+        assert!(schema.validate(&mut Input::from("5M"), None).is_err());
+        assert!(schema.validate(&mut Input::from("4x"), None).is_err());
+        assert!(schema
+            .validate(&mut Input::from("9999999999999T"), None)
+            .is_err());
+
+        let schema = schema.with_suffix(InputSchemaTypeIntegerSuffix::Binary);
+        let mut input = Input::from("2M");
+        schema.validate(&mut input, None).unwrap();
+        assert_eq!(input, Input::from(2 * 1024 * 1024));
+        assert!(InputSchemaTypeInteger::default()
+            .validate(&mut Input::from("4k"), None)
+            .is_err());
+    }
+
+    #[test]
+    fn deny_coercions() {
+        let schema = InputSchemaTypeInteger::default();
+        for mut input in [Input::from(3.0), Input::from("3")] {
+            schema.validate(&mut input, None).unwrap();
+            assert_eq!(input, Input::from(3));
+        }
+        let schema: InputSchemaTypeInteger =
+            serde_json::from_value(json!({"deny_floats": true, "deny_strings": true})).unwrap();
+        for mut input in [Input::from(3.0), Input::from("3")] {
+            assert!(matches!(
+                schema.validate(&mut input, None),
+                Err(InputSchemaError::Type { .. })
+            ));
+        }
+        schema.validate(&mut Input::from(3), None).unwrap();
+        assert_eq!(
+            serde_json::to_value(InputSchemaTypeInteger::default()).unwrap(),
+            json!({})
+        );
+    }
+
+    #[test]
+    fn positive() {
+        for (schema, valid_list, invalid_list) in [
+            (InputSchemaTypeInteger::positive(), [1, 10], [0, -1]),
+            (InputSchemaTypeInteger::non_negative(), [0, 10], [-1, -10]),
+            (InputSchemaTypeInteger::negative(), [-1, -10], [0, 1]),
+        ] {
+            valid_list
+                .into_iter()
+                .for_each(|integer| schema.validate(&mut Input::from(integer), None).unwrap());
+            invalid_list.into_iter().for_each(|integer| {
+                assert!(schema.validate(&mut Input::from(integer), None).is_err())
+            });
+        }
+    }
+
+    #[test]
+    fn digit_separators() {
+        let schema = InputSchemaTypeInteger::default().with_digit_separators(true);
+        for text in ["1,000", "1_000", "1 000"] {
+            let mut input = Input::from(text);
+            schema.validate(&mut input, None).unwrap();
+            assert_eq!(input, Input::from(1000));
+        }
+        assert!(InputSchemaTypeInteger::default()
+            .validate(&mut Input::from("1,000"), None)
+            .is_err());
+        let mut input = Input::from("1,000.5");
+        crate::schema::InputSchemaTypeFloat::default()
+            .with_digit_separators(true)
+            .validate(&mut input, None)
+            .unwrap();
+        assert_eq!(input, Input::from(1000.5));
+    }
 }
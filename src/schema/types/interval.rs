@@ -0,0 +1,167 @@
+use crate::{
+    position::InputPosition,
+    schema::{suggest::did_you_mean, InputSchemaError, InputSchemaType},
+    Input,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+// A `{"min": ..., "max": ...}` map or a `"MIN..MAX"` string (e.g. port ranges like `"8000..8100"`,
+// time windows, or size windows) where both bounds should be valid for `item_schema` and `min`
+// should not be greater than `max`. Strings are normalized to maps:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeInterval {
+    #[serde(default)]
+    item_schema: Box<InputSchemaType>,
+}
+
+impl InputSchemaTypeInterval {
+    pub fn item_schema(&self) -> &InputSchemaType {
+        &self.item_schema
+    }
+
+    pub fn item_schema_mut(&mut self) -> &mut InputSchemaType {
+        &mut self.item_schema
+    }
+
+    pub fn set_item_schema<S: Into<InputSchemaType>>(&mut self, schema: S) {
+        *self.item_schema_mut() = schema.into();
+    }
+
+    pub fn with_item_schema<S: Into<InputSchemaType>>(mut self, schema: S) -> Self {
+        self.set_item_schema(schema);
+        self
+    }
+}
+
+impl InputSchemaTypeInterval {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        let position = maybe_position.unwrap_or_default();
+        if input.is_str() {
+            let Some((min, max)) = input.as_str().split_once("..") else {
+                return Err(InputSchemaError::Invalid {
+                    description: "Expected `MIN..MAX`".to_string(),
+                    position,
+                    input: input.clone(),
+                });
+            };
+            *input = Input::from(HashMap::from([
+                ("min".to_string(), Input::from(min.trim())),
+                ("max".to_string(), Input::from(max.trim())),
+            ]));
+        }
+        if !input.is_map() {
+            return Err(InputSchemaError::Type {
+                position,
+                expected_type: Input::map_type_name(),
+                input_type: input.type_name(),
+            });
+        }
+        if let Some(key) = input
+            .as_map()
+            .keys()
+            .map(|key| &**key)
+            .find(|key| *key != "min" && *key != "max")
+        {
+            return Err(InputSchemaError::Invalid {
+                description: format!(
+                    "Unknown key `{key}` (expected `min` and `max`){}",
+                    did_you_mean(key, ["min", "max"])
+                ),
+                position,
+                input: input.clone(),
+            });
+        }
+        for key in ["min", "max"] {
+            let bound_position = position.new_with_key(key);
+            let bound = input
+                .map_mut()
+                .get_mut(key)
+                .ok_or_else(|| InputSchemaError::NotFound {
+                    position: bound_position.clone(),
+                    schema_type: self.item_schema().clone(),
+                })?;
+            self.item_schema().validate(bound, Some(bound_position))?;
+        }
+        let map = input.as_map();
+        if compare(&map["min"], &map["max"]) == Some(Ordering::Greater) {
+            return Err(InputSchemaError::Invalid {
+                description: format!(
+                    "Expected `min` ({}) not to be greater than `max` ({})",
+                    map["min"], map["max"]
+                ),
+                position,
+                input: input.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+// Numbers are compared by value and strings lexicographically (e.g. `"09:00"` and `"17:30"`). Other
+// bounds are not ordered so they are not checked:
+fn compare(min: &Input, max: &Input) -> Option<Ordering> {
+    match (min, max) {
+        (Input::Int(min), Input::Int(max)) => Some(min.cmp(max)),
+        (Input::Int(_) | Input::Float(_), Input::Int(_) | Input::Float(_)) => {
+            let float = |input: &Input| match input {
+                Input::Int(integer) => *integer as f64,
+                _ => *input.as_float(),
+            };
+            float(min).partial_cmp(&float(max))
+        }
+        (Input::Str(min), Input::Str(max)) => Some(min.cmp(max)),
+        _ => None,
+    }
+}
+
+impl Display for InputSchemaTypeInterval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            format!(
+                "interval (`{{\"min\": ..., \"max\": ...}}` or `\"MIN..MAX\"`) whose bounds should be {}",
+                self.item_schema
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate() {
+        let schema =
+            InputSchemaTypeInterval::default().with_item_schema(InputSchemaType::new_port());
+        let expected: Input = serde_json::from_value(json!({"min": 8000, "max": 8100})).unwrap();
+        for input in [json!("8000..8100"), json!({"min": "8000", "max": 8100})] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            schema.validate(&mut input, None).unwrap();
+            assert_eq!(input, expected);
+        }
+
+        for (input, error_position) in [
+            (json!("8100..8000"), ""),
+            (json!("8000"), ""),
+            (json!("8000..http"), "max"),
+            (json!({"min": 1}), "max"),
+            (json!({"min": 1, "max": 2, "step": 1}), ""),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            let error = schema.validate(&mut input, None).unwrap_err();
+            assert_eq!(error.position().to_string(), error_position);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{
+    position::InputPosition,
+    schema::{InputSchemaError, InputSchemaType},
+    Input,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+// Accepts a map or a list of `"key=value"` strings (e.g. from CLI arguments or environment
+// variables) which is converted to a map. Values are validated by `value_schema` in both forms:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeKeyValue {
+    #[serde(default)]
+    value_schema: Box<InputSchemaType>,
+}
+
+impl InputSchemaTypeKeyValue {
+    pub fn value_schema(&self) -> &InputSchemaType {
+        &self.value_schema
+    }
+
+    pub fn value_schema_mut(&mut self) -> &mut InputSchemaType {
+        &mut self.value_schema
+    }
+
+    pub fn set_value_schema<S: Into<InputSchemaType>>(&mut self, schema: S) {
+        *self.value_schema_mut() = schema.into();
+    }
+
+    pub fn with_value_schema<S: Into<InputSchemaType>>(mut self, schema: S) -> Self {
+        self.set_value_schema(schema);
+        self
+    }
+}
+
+impl InputSchemaTypeKeyValue {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        let position = maybe_position.unwrap_or_default();
+        if input.is_list() {
+            let mut map = HashMap::with_capacity(input.as_list().len());
+            for (index, item) in input.as_list().iter().enumerate() {
+                let item_position = position.new_with_index(index);
+                if !item.is_str() {
+                    return Err(InputSchemaError::Type {
+                        position: item_position,
+                        expected_type: Input::str_type_name(),
+                        input_type: item.type_name(),
+                    });
+                }
+                let (key, value) = item
+                    .as_str()
+                    .split_once('=')
+                    .filter(|(key, _)| !key.trim().is_empty())
+                    .ok_or_else(|| InputSchemaError::Invalid {
+                        description: "Expected `key=value`".to_string(),
+                        position: item_position.clone(),
+                        input: item.clone(),
+                    })?;
+                if map
+                    .insert(key.trim().to_string(), Input::from(value.trim()))
+                    .is_some()
+                {
+                    return Err(InputSchemaError::Invalid {
+                        description: format!("Duplicate key `{}`", key.trim()),
+                        position: item_position,
+                        input: item.clone(),
+                    });
+                }
+            }
+            *input = Input::from(map);
+        }
+        if !input.is_map() {
+            return Err(InputSchemaError::Type {
+                position,
+                expected_type: Input::map_type_name(),
+                input_type: input.type_name(),
+            });
+        }
+        input.map_mut().iter_mut().try_for_each(|(key, value)| {
+            self.value_schema()
+                .validate(value, Some(position.new_with_key(key)))
+        })
+    }
+}
+
+impl Display for InputSchemaTypeKeyValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            format!(
+                "map or list of `key=value` strings that each value should be {}",
+                self.value_schema
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate() {
+        let schema =
+            InputSchemaTypeKeyValue::default().with_value_schema(InputSchemaType::new_integer());
+        let expected: Input = serde_json::from_value(json!({"a": 1, "b": 2})).unwrap();
+        for input in [json!(["a=1", " b = 2"]), json!({"a": "1", "b": 2})] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            schema.validate(&mut input, None).unwrap();
+            assert_eq!(input, expected);
+        }
+        for input in [
+            json!(["a=1", "a=2"]),
+            json!(["a"]),
+            json!(["=1"]),
+            json!(["a=b"]),
+            json!([1]),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            assert!(schema.validate(&mut input, None).is_err());
+        }
+    }
+}
@@ -1,10 +1,17 @@
 use crate::{
     position::InputPosition,
-    schema::{InputSchemaError, InputSchemaType, InputSchemaTypeSize},
+    schema::{
+        default::default_colon, validate_with_cow, InputSchemaError, InputSchemaType,
+        InputSchemaTypeSize,
+    },
     Input,
 };
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
 
 #[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
@@ -12,6 +19,25 @@ pub struct InputSchemaTypeList {
     #[serde(rename = "size", skip_serializing_if = "Option::is_none", default)]
     maybe_size: Option<InputSchemaTypeSize>,
     item_schema: Box<InputSchemaType>,
+    #[serde(rename = "item_map", skip_serializing_if = "Option::is_none", default)]
+    maybe_item_map: Option<InputSchemaTypeListItemMap>,
+    // Map items with the same value of this key (e.g. `name`) are merged instead of appended by
+    // `merge_with_schema()`:
+    #[serde(rename = "merge_key", skip_serializing_if = "Option::is_none", default)]
+    maybe_merge_key: Option<String>,
+}
+
+// Normalizes items that are not maps after validation (e.g. the `"host:port"` branch of an `either`
+// item schema) to maps, so all items have one shape. The text of the item is split by `separator`
+// from the right into at most one part per key (e.g. `"[::1]:80"` to `{"host": "[::1]", "port":
+// "80"}`), and the map is validated again:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeListItemMap {
+    #[serde(rename = "keys")]
+    key_list: Vec<String>,
+    #[serde(default = "default_colon")]
+    separator: String,
 }
 
 impl InputSchemaTypeList {
@@ -32,6 +58,23 @@ impl InputSchemaTypeList {
         self
     }
 
+    pub fn maybe_item_map(&self) -> Option<&InputSchemaTypeListItemMap> {
+        self.maybe_item_map.as_ref()
+    }
+
+    pub fn maybe_item_map_mut(&mut self) -> &mut Option<InputSchemaTypeListItemMap> {
+        &mut self.maybe_item_map
+    }
+
+    pub fn set_item_map(&mut self, item_map: InputSchemaTypeListItemMap) {
+        *self.maybe_item_map_mut() = Some(item_map);
+    }
+
+    pub fn with_item_map(mut self, item_map: InputSchemaTypeListItemMap) -> Self {
+        self.set_item_map(item_map);
+        self
+    }
+
     pub fn maybe_size(&self) -> Option<&InputSchemaTypeSize> {
         self.maybe_size.as_ref()
     }
@@ -48,6 +91,75 @@ impl InputSchemaTypeList {
         self.set_size(size);
         self
     }
+
+    pub fn maybe_merge_key(&self) -> Option<&String> {
+        self.maybe_merge_key.as_ref()
+    }
+
+    pub fn maybe_merge_key_mut(&mut self) -> &mut Option<String> {
+        &mut self.maybe_merge_key
+    }
+
+    pub fn set_merge_key<K: ToString>(&mut self, merge_key: K) {
+        *self.maybe_merge_key_mut() = Some(merge_key.to_string());
+    }
+
+    pub fn with_merge_key<K: ToString>(mut self, merge_key: K) -> Self {
+        self.set_merge_key(merge_key);
+        self
+    }
+}
+
+impl InputSchemaTypeListItemMap {
+    pub fn new<K: ToString>(key_list: Vec<K>) -> Self {
+        Self {
+            key_list: key_list.into_iter().map(|key| key.to_string()).collect(),
+            separator: default_colon(),
+        }
+    }
+
+    pub fn key_list(&self) -> &Vec<String> {
+        &self.key_list
+    }
+
+    pub fn separator(&self) -> &String {
+        &self.separator
+    }
+
+    pub fn set_separator<S: ToString>(&mut self, separator: S) {
+        self.separator = separator.to_string();
+    }
+
+    pub fn with_separator<S: ToString>(mut self, separator: S) -> Self {
+        self.set_separator(separator);
+        self
+    }
+
+    // `None` for lists and maps (and if there are no keys):
+    fn to_map(&self, input: &Input) -> Option<Input> {
+        let text = match input {
+            Input::Str(text) => text.clone(),
+            Input::Bool(_) | Input::Int(_) | Input::Float(_) => input.to_string(),
+            _ => return None,
+        };
+        if self.key_list.is_empty() {
+            return None;
+        }
+        let mut part_list: Vec<&str> = if self.separator.is_empty() {
+            vec![text.as_str()]
+        } else {
+            text.rsplitn(self.key_list.len(), self.separator.as_str())
+                .collect()
+        };
+        part_list.reverse();
+        Some(Input::from(
+            self.key_list
+                .iter()
+                .zip(part_list)
+                .map(|(key, part)| (key.clone(), Input::from(part)))
+                .collect::<HashMap<_, _>>(),
+        ))
+    }
 }
 
 impl InputSchemaTypeList {
@@ -56,6 +168,14 @@ impl InputSchemaTypeList {
         input: &mut Input,
         maybe_position: Option<InputPosition>,
     ) -> Result<(), InputSchemaError> {
+        validate_with_cow(input, |input| self.validate_cow(input, maybe_position))
+    }
+
+    pub fn validate_cow<'a>(
+        &self,
+        input: &'a Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<Cow<'a, Input>, InputSchemaError> {
         if !input.is_list() {
             return Err(InputSchemaError::Type {
                 position: maybe_position.unwrap_or_default(),
@@ -87,17 +207,38 @@ impl InputSchemaTypeList {
             }
         }
         let position = maybe_position.unwrap_or_default();
-        let mut new_input = input.clone();
-        new_input
-            .list_mut()
-            .iter_mut()
-            .enumerate()
-            .try_for_each(|(index, inner_input)| {
+        let list = input.as_list();
+        // Only created once an item changes:
+        let mut maybe_new_list: Option<Vec<Input>> = None;
+        for (index, inner_input) in list.iter().enumerate() {
+            let mut validated_inner_input = self
+                .item_schema()
+                .validate_cow(inner_input, Some(position.new_with_index(index)))?;
+            if let Some(mut map) = self
+                .maybe_item_map
+                .as_ref()
+                .filter(|_| !validated_inner_input.is_map())
+                .and_then(|item_map| item_map.to_map(&validated_inner_input))
+            {
                 self.item_schema()
-                    .validate(inner_input, Some(position.new_with_index(index)))
-            })?;
-        *input = new_input;
-        Ok(())
+                    .validate(&mut map, Some(position.new_with_index(index)))?;
+                validated_inner_input = Cow::Owned(map);
+            }
+            match (validated_inner_input, maybe_new_list.as_mut()) {
+                (Cow::Owned(validated_inner_input), None) => {
+                    let mut new_list = list[..index].to_vec();
+                    new_list.push(validated_inner_input);
+                    maybe_new_list = Some(new_list);
+                }
+                (validated_inner_input, Some(new_list)) => {
+                    new_list.push(validated_inner_input.into_owned())
+                }
+                (Cow::Borrowed(_), None) => (),
+            }
+        }
+        Ok(maybe_new_list.map_or(Cow::Borrowed(input), |new_list| {
+            Cow::Owned(Input::from(new_list))
+        }))
     }
 }
 
@@ -120,6 +261,44 @@ impl Display for InputSchemaTypeList {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use serde_json::json;
+
     #[test]
     fn serde() {}
+
+    #[test]
+    fn item_map() {
+        let schema: InputSchemaTypeList = serde_json::from_value(json!({
+            "item_schema": {
+                "type": "either",
+                "schema_list": [
+                    {"type": "socket_address"},
+                    {
+                        "type": "static_map",
+                        "items": {
+                            "host": {"schema": {"type": "string"}},
+                            "port": {"schema": {"type": "port"}, "default": 80}
+                        }
+                    }
+                ]
+            },
+            "item_map": {"keys": ["host", "port"]}
+        }))
+        .unwrap();
+        let mut input: Input = serde_json::from_value(json!([
+            "127.0.0.1:8080",
+            "[::1]:8081",
+            {"host": "localhost"},
+        ]))
+        .unwrap();
+        schema.validate(&mut input, None).unwrap();
+        let expected: Input = serde_json::from_value(json!([
+            {"host": "127.0.0.1", "port": 8080},
+            {"host": "[::1]", "port": 8081},
+            {"host": "localhost", "port": 80},
+        ]))
+        .unwrap();
+        assert_eq!(input, expected);
+    }
 }
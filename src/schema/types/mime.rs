@@ -0,0 +1,211 @@
+use crate::{position::InputPosition, schema::InputSchemaError, Input};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// `type/subtype` media types (e.g. `"image/png"` or `"text/plain; charset=utf-8"`) normalized to
+// lowercase, or common file extensions (e.g. `".png"`) converted to their media types. With an
+// `allow` list (e.g. `["image/*", "application/pdf"]`), the media type should match one of them:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeMime {
+    #[serde(rename = "allow", skip_serializing_if = "Vec::is_empty", default)]
+    allow_list: Vec<String>,
+}
+
+const EXTENSION_LIST: [(&str, &str); 24] = [
+    ("avif", "image/avif"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("js", "text/javascript"),
+    ("json", "application/json"),
+    ("md", "text/markdown"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("svg", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("toml", "application/toml"),
+    ("txt", "text/plain"),
+    ("wasm", "application/wasm"),
+    ("webp", "image/webp"),
+    ("xml", "application/xml"),
+    ("zip", "application/zip"),
+];
+
+impl InputSchemaTypeMime {
+    pub fn allow_list(&self) -> &Vec<String> {
+        &self.allow_list
+    }
+
+    pub fn allow_list_mut(&mut self) -> &mut Vec<String> {
+        &mut self.allow_list
+    }
+
+    pub fn set_allow_list<S: ToString>(&mut self, allow_list: Vec<S>) {
+        *self.allow_list_mut() = allow_list
+            .into_iter()
+            .map(|pattern| pattern.to_string())
+            .collect();
+    }
+
+    pub fn with_allow_list<S: ToString>(mut self, allow_list: Vec<S>) -> Self {
+        self.set_allow_list(allow_list);
+        self
+    }
+
+    // `*/*`, `type/*`, or an exact `type/subtype` (parameters are ignored):
+    pub fn matches(pattern: &str, mime: &str) -> bool {
+        let essence = |text: &str| {
+            text.split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase()
+        };
+        let (pattern, mime) = (essence(pattern), essence(mime));
+        match (pattern.split_once('/'), mime.split_once('/')) {
+            (Some(("*", "*")), Some(_)) => true,
+            (Some((pattern_type, "*")), Some((mime_type, _))) => pattern_type == mime_type,
+            _ => pattern == mime,
+        }
+    }
+}
+
+impl InputSchemaTypeMime {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        if !input.is_str() {
+            return Err(InputSchemaError::Type {
+                position: maybe_position.unwrap_or_default(),
+                expected_type: Input::str_type_name(),
+                input_type: input.type_name(),
+            });
+        }
+        let invalid = |description: String| InputSchemaError::Invalid {
+            description,
+            position: maybe_position.clone().unwrap_or_default(),
+            input: input.clone(),
+        };
+        let text = input.as_str().trim();
+        let mime = if let Some(extension) = text.strip_prefix('.') {
+            let extension = extension.to_lowercase();
+            EXTENSION_LIST
+                .iter()
+                .find(|(known_extension, _)| *known_extension == extension)
+                .map(|(_, mime)| mime.to_string())
+                .ok_or_else(|| invalid(format!("Unknown file extension `.{extension}`")))?
+        } else {
+            let mut part_iter = text.split(';').map(str::trim);
+            let essence = part_iter.next().unwrap_or_default().to_lowercase();
+            let parameter_list = part_iter
+                .map(|parameter| match parameter.split_once('=') {
+                    Some((name, value)) if is_name(name.trim()) && !value.trim().is_empty() => {
+                        Some(format!("{}={}", name.trim().to_lowercase(), value.trim()))
+                    }
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            match (essence.split_once('/'), parameter_list) {
+                (Some((r#type, subtype)), Some(parameter_list))
+                    if is_name(r#type) && is_name(subtype) =>
+                {
+                    [essence.clone()]
+                        .into_iter()
+                        .chain(parameter_list)
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                }
+                _ => {
+                    return Err(invalid(
+                        "Expected `type/subtype` (e.g. `image/png`)".to_string(),
+                    ))
+                }
+            }
+        };
+        if !self.allow_list.is_empty()
+            && !self
+                .allow_list
+                .iter()
+                .any(|pattern| Self::matches(pattern, &mime))
+        {
+            return Err(invalid(format!(
+                "Media type `{mime}` is not allowed (expected {})",
+                self.allow_list
+                    .iter()
+                    .map(|pattern| format!("`{pattern}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        *input = Input::from(mime);
+        Ok(())
+    }
+}
+
+// RFC 6838 restricted names:
+fn is_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 127
+        && name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+}
+
+impl Display for InputSchemaTypeMime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut text = "media type (`type/subtype` or file extension)".to_string();
+        if !self.allow_list.is_empty() {
+            text.push_str(format!(" which should match {}", self.allow_list.join(" or ")).as_str());
+        }
+        f.write_str(text.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let schema =
+            InputSchemaTypeMime::default().with_allow_list(vec!["image/*", "application/pdf"]);
+        for (text, mime) in [
+            ("image/PNG", "image/png"),
+            (".jpg", "image/jpeg"),
+            (
+                "Application/PDF;Version=1.7",
+                "application/pdf; version=1.7",
+            ),
+            (
+                "image/svg+xml; Charset=UTF-8",
+                "image/svg+xml; charset=UTF-8",
+            ),
+        ] {
+            let mut input = Input::from(text);
+            schema.validate(&mut input, None).unwrap();
+            assert_eq!(input, Input::from(mime));
+        }
+        for text in [
+            "text/plain",
+            ".exe",
+            "image",
+            "image/",
+            "image/png; charset",
+        ] {
+            assert!(schema.validate(&mut Input::from(text), None).is_err());
+        }
+        assert!(InputSchemaTypeMime::matches("*/*", "text/plain"));
+        assert!(!InputSchemaTypeMime::matches("image/*", "text/plain"));
+    }
+}
@@ -1,17 +1,41 @@
 pub(crate) mod any;
+pub(crate) mod binary;
 pub(crate) mod boolean;
+#[cfg(feature = "iso-codes")]
+pub(crate) mod country_code;
+#[cfg(feature = "iso-codes")]
+pub(crate) mod currency;
+#[cfg(feature = "rust_decimal")]
+pub(crate) mod decimal;
+pub(crate) mod dsn;
 pub(crate) mod dynamic_map;
 pub(crate) mod either;
 pub(crate) mod r#enum;
+#[cfg(feature = "expr")]
+pub(crate) mod expr;
 pub(crate) mod float;
 pub(crate) mod fs;
 pub(crate) mod integer;
+pub(crate) mod interval;
 pub(crate) mod ip;
+pub(crate) mod key_value;
 pub(crate) mod list;
 pub(crate) mod log_level;
 pub(crate) mod log_level_filter;
+pub(crate) mod mime;
 pub(crate) mod number;
+pub(crate) mod percent;
 pub(crate) mod port;
+pub(crate) mod rate;
+pub(crate) mod recursive;
+pub(crate) mod reference;
+pub(crate) mod regex_value;
+pub(crate) mod secret;
 pub(crate) mod socket_address;
 pub(crate) mod static_map;
 pub(crate) mod string;
+pub(crate) mod switch;
+pub(crate) mod time_window;
+pub(crate) mod tls;
+pub(crate) mod unknown;
+pub(crate) mod weighted_list;
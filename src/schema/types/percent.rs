@@ -0,0 +1,137 @@
+use crate::{position::InputPosition, schema::InputSchemaError, Input};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// Accepts integers in `0..=100` (e.g. `75`), floats in `0.0..=1.0` (e.g. `0.75`), and strings with
+// a `%` suffix (e.g. `"75%"`) and normalizes all of them to the configured `form`:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypePercent {
+    #[serde(default)]
+    form: InputSchemaTypePercentForm,
+}
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(
+    rename_all = "snake_case",
+    deny_unknown_fields,
+    expecting = "Expecting percent forms: `ratio` (`0.75`), `percent` (`75.0`), or `string` (`\"75%\"`)"
+)]
+pub enum InputSchemaTypePercentForm {
+    #[default]
+    Ratio,
+    Percent,
+    String,
+}
+
+impl InputSchemaTypePercent {
+    pub fn form(&self) -> InputSchemaTypePercentForm {
+        self.form
+    }
+
+    pub fn form_mut(&mut self) -> &mut InputSchemaTypePercentForm {
+        &mut self.form
+    }
+
+    pub fn set_form(&mut self, form: InputSchemaTypePercentForm) {
+        *self.form_mut() = form;
+    }
+
+    pub fn with_form(mut self, form: InputSchemaTypePercentForm) -> Self {
+        self.set_form(form);
+        self
+    }
+}
+
+impl InputSchemaTypePercent {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        let maybe_percent = match input {
+            Input::Int(integer) => Some(*integer as f64),
+            Input::Float(float) => Some(*float * 100.0),
+            Input::Str(string) => {
+                let string = string.trim();
+                if let Some(percent) = string.strip_suffix('%') {
+                    percent.trim_end().parse::<f64>().ok()
+                } else if let Ok(integer) = string.parse::<isize>() {
+                    Some(integer as f64)
+                } else {
+                    string.parse::<f64>().ok().map(|ratio| ratio * 100.0)
+                }
+            }
+            _ => {
+                return Err(InputSchemaError::Type {
+                    position: maybe_position.unwrap_or_default(),
+                    expected_type: "percent".to_string(),
+                    input_type: input.type_name(),
+                })
+            }
+        };
+        let percent = maybe_percent
+            .filter(|percent| (0.0..=100.0).contains(percent))
+            .ok_or_else(|| InputSchemaError::Invalid {
+                description: "Expected a percentage between 0 and 100 (`75`, `0.75`, or `\"75%\"`)"
+                    .to_string(),
+                position: maybe_position.unwrap_or_default(),
+                input: input.clone(),
+            })?;
+        // `0.07 * 100.0` is `7.000000000000001`:
+        let percent = (percent * 1e9).round() / 1e9;
+        *input = match self.form {
+            InputSchemaTypePercentForm::Ratio => Input::from(percent / 100.0),
+            InputSchemaTypePercentForm::Percent => Input::from(percent),
+            InputSchemaTypePercentForm::String => Input::from(format!("{percent}%")),
+        };
+        Ok(())
+    }
+}
+
+impl Display for InputSchemaTypePercent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("percentage (e.g. `75`, `0.75`, or `\"75%\"`)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        for (form, expected) in [
+            (InputSchemaTypePercentForm::Ratio, Input::from(0.07)),
+            (InputSchemaTypePercentForm::Percent, Input::from(7.0)),
+            (InputSchemaTypePercentForm::String, Input::from("7%")),
+        ] {
+            let schema = InputSchemaTypePercent::default().with_form(form);
+            for input in [
+                Input::from(7),
+                Input::from(0.07),
+                Input::from("7%"),
+                Input::from("7 %"),
+                Input::from("7"),
+                Input::from("0.07"),
+            ] {
+                let mut input = input;
+                schema.validate(&mut input, None).unwrap();
+                assert_eq!(input, expected);
+            }
+        }
+        let schema = InputSchemaTypePercent::default();
+        for input in [
+            Input::from(101),
+            Input::from(1.5),
+            Input::from("-1%"),
+            Input::from("abc"),
+        ] {
+            let mut input = input;
+            assert!(matches!(
+                schema.validate(&mut input, None),
+                Err(InputSchemaError::Invalid { .. })
+            ));
+        }
+    }
+}
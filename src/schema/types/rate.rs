@@ -0,0 +1,201 @@
+use crate::{
+    position::InputPosition,
+    schema::{
+        InputSchemaError, InputSchemaType, InputSchemaTypeIntegerSuffix, InputSchemaTypeRange,
+    },
+    Input,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+// `"COUNT/UNIT"` strings (e.g. `"100/s"`, `"5k/min"`, or `"10/5m"`) for rate limits, normalized to
+// `{"count": ..., "per_seconds": ...}` maps. The `range` is checked against the rate per second:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeRate {
+    #[serde(rename = "range", skip_serializing_if = "Option::is_none", default)]
+    maybe_range: Option<InputSchemaTypeRange>,
+}
+
+impl InputSchemaTypeRate {
+    pub fn maybe_range(&self) -> Option<&InputSchemaTypeRange> {
+        self.maybe_range.as_ref()
+    }
+
+    pub fn maybe_range_mut(&mut self) -> &mut Option<InputSchemaTypeRange> {
+        &mut self.maybe_range
+    }
+
+    pub fn set_range<R: Into<InputSchemaTypeRange>>(&mut self, range: R) {
+        *self.maybe_range_mut() = Some(range.into());
+    }
+
+    pub fn with_range<R: Into<InputSchemaTypeRange>>(mut self, range: R) -> Self {
+        self.set_range(range);
+        self
+    }
+
+    // The rate per second of a validated input:
+    pub fn per_second(input: &Input) -> Option<f64> {
+        let Input::Map(map) = input else {
+            return None;
+        };
+        let count = match map.get("count")? {
+            Input::Int(count) => *count as f64,
+            Input::Float(count) => *count,
+            _ => return None,
+        };
+        match map.get("per_seconds")? {
+            Input::Int(per_seconds) => Some(count / *per_seconds as f64),
+            _ => None,
+        }
+    }
+}
+
+impl InputSchemaTypeRate {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        let position = maybe_position.unwrap_or_default();
+        let invalid = |input: &Input| {
+            InputSchemaError::Invalid {
+            description: "Expected `COUNT/UNIT` (e.g. `100/s` or `5k/min`) or a map with `count` and positive `per_seconds`".to_string(),
+            position: position.clone(),
+            input: input.clone(),
+        }
+        };
+        let (count, per_seconds) = match input {
+            Input::Str(rate) => parse(rate).ok_or_else(|| invalid(input))?,
+            Input::Map(map)
+                if map.len() == 2
+                    && matches!(map.get("count"), Some(Input::Int(_) | Input::Float(_)))
+                    && matches!(map.get("per_seconds"), Some(Input::Int(per_seconds)) if *per_seconds > 0) =>
+            {
+                (map["count"].clone(), *map["per_seconds"].as_int())
+            }
+            Input::Map(_) => return Err(invalid(input)),
+            _ => {
+                return Err(InputSchemaError::Type {
+                    position,
+                    expected_type: Input::str_type_name(),
+                    input_type: input.type_name(),
+                })
+            }
+        };
+        *input = Input::from(HashMap::from([
+            ("count".to_string(), count),
+            ("per_seconds".to_string(), Input::from(per_seconds)),
+        ]));
+        let per_second = Self::per_second(input).unwrap_or_default();
+        if !per_second.is_finite() || per_second < 0.0 {
+            return Err(invalid(input));
+        }
+        if let Some(range) = self.maybe_range {
+            if range
+                .maybe_min()
+                .is_some_and(|min| per_second < min.float())
+                || range
+                    .maybe_max()
+                    .is_some_and(|max| per_second > max.float())
+            {
+                return Err(InputSchemaError::Range {
+                    position,
+                    schema_type: InputSchemaType::from(self.clone()),
+                    expected_range: range,
+                    input: input.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+// The count (with an optional `k`, `M`, ... suffix) and the unit in seconds:
+fn parse(rate: &str) -> Option<(Input, isize)> {
+    let (count, unit) = rate.split_once('/')?;
+    let count = count.trim();
+    let count = if let Some(count) = InputSchemaTypeIntegerSuffix::Decimal
+        .parse(count)
+        .or_else(|| count.parse::<isize>().ok())
+    {
+        Input::from(count)
+    } else {
+        Input::from(count.parse::<f64>().ok()?)
+    };
+    let unit = unit.trim();
+    let unit_start = unit
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(unit.len());
+    let multiplier = match &unit[..unit_start] {
+        "" => 1,
+        multiplier => multiplier.parse::<isize>().ok().filter(|m| *m > 0)?,
+    };
+    let seconds = match unit[unit_start..].trim() {
+        "s" | "sec" | "second" => 1,
+        "m" | "min" | "minute" => 60,
+        "h" | "hour" => 60 * 60,
+        "d" | "day" => 24 * 60 * 60,
+        _ => return None,
+    };
+    Some((count, multiplier.checked_mul(seconds)?))
+}
+
+impl Display for InputSchemaTypeRate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            format!(
+                "rate (`COUNT/UNIT`){}",
+                if let Some(range) = self.maybe_range {
+                    format!(" which should be in {range} per second")
+                } else {
+                    String::new()
+                }
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate() {
+        let schema =
+            InputSchemaTypeRate::default().with_range(InputSchemaTypeRange::new_with_max(1000));
+        for (input, count, per_seconds) in [
+            (json!("100/s"), json!(100), 1),
+            (json!("5k/min"), json!(5000), 60),
+            (json!("2.5 / 5m"), json!(2.5), 300),
+            (json!({"count": 1, "per_seconds": 3600}), json!(1), 3600),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            schema.validate(&mut input, None).unwrap();
+            let expected: Input =
+                serde_json::from_value(json!({"count": count, "per_seconds": per_seconds}))
+                    .unwrap();
+            assert_eq!(input, expected);
+        }
+        let mut input = Input::from("600/min");
+        schema.validate(&mut input, None).unwrap();
+        assert_eq!(InputSchemaTypeRate::per_second(&input), Some(10.0));
+
+        for input in [
+            json!("2k/s"),
+            json!("100"),
+            json!("100/week"),
+            json!("-1/s"),
+            json!({"count": 1, "per_seconds": 0}),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            assert!(schema.validate(&mut input, None).is_err());
+        }
+    }
+}
@@ -0,0 +1,431 @@
+use crate::{
+    position::InputPosition,
+    resolution::{ResolutionGuard, DEFAULT_MAX_DEPTH},
+    schema::{InputSchemaError, InputSchemaType},
+    Input,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    fmt::{Display, Formatter},
+    rc::Rc,
+};
+use thiserror::Error;
+
+// A named schema that `ref` schemas inside it can refer to, for recursive structures (e.g. nested
+// filter expressions where a `not` contains another filter). Each `ref` that is followed counts
+// as one level, and validation fails after `max_depth` levels:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeRecursive {
+    name: String,
+    #[serde(rename = "schema", default)]
+    inner_schema: Box<InputSchemaType>,
+    #[serde(rename = "max_depth", skip_serializing_if = "Option::is_none", default)]
+    maybe_max_depth: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InputSchemaRefError {
+    #[error("{position} refers to `{name}` which is not an enclosing recursive schema")]
+    Unresolved {
+        position: InputPosition,
+        name: String,
+    },
+    #[error("{position} refers to `{name}` without a list or map in between, so it never ends")]
+    Cycle {
+        position: InputPosition,
+        name: String,
+    },
+}
+
+// Enclosing recursive schemas of the current validation and the positions of followed refs
+// (nested values are validated with the same ref, so cycles are allowed):
+struct Definition {
+    name: String,
+    schema_type: Rc<InputSchemaType>,
+    guard: ResolutionGuard<String>,
+}
+
+thread_local! {
+    static DEFINITION_LIST: RefCell<Vec<Definition>> = const { RefCell::new(Vec::new()) };
+}
+
+// Pops the definition even if validation fails:
+struct DefinitionGuard;
+
+impl Drop for DefinitionGuard {
+    fn drop(&mut self) {
+        DEFINITION_LIST.with_borrow_mut(|definition_list| definition_list.pop());
+    }
+}
+
+impl InputSchemaTypeRecursive {
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn set_name<N: ToString>(&mut self, name: N) {
+        self.name = name.to_string();
+    }
+
+    pub fn with_name<N: ToString>(mut self, name: N) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    pub fn inner_schema(&self) -> &InputSchemaType {
+        &self.inner_schema
+    }
+
+    pub fn inner_schema_mut(&mut self) -> &mut InputSchemaType {
+        &mut self.inner_schema
+    }
+
+    pub fn set_inner_schema<S: Into<InputSchemaType>>(&mut self, schema: S) {
+        *self.inner_schema_mut() = schema.into();
+    }
+
+    pub fn with_inner_schema<S: Into<InputSchemaType>>(mut self, schema: S) -> Self {
+        self.set_inner_schema(schema);
+        self
+    }
+
+    pub fn maybe_max_depth(&self) -> Option<usize> {
+        self.maybe_max_depth
+    }
+
+    pub fn maybe_max_depth_mut(&mut self) -> &mut Option<usize> {
+        &mut self.maybe_max_depth
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        *self.maybe_max_depth_mut() = Some(max_depth);
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.set_max_depth(max_depth);
+        self
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.maybe_max_depth.unwrap_or(DEFAULT_MAX_DEPTH)
+    }
+}
+
+impl InputSchemaTypeRecursive {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        DEFINITION_LIST.with_borrow_mut(|definition_list| {
+            definition_list.push(Definition {
+                name: self.name.clone(),
+                schema_type: Rc::new(self.inner_schema().clone()),
+                guard: ResolutionGuard::new()
+                    .with_max_depth(self.max_depth())
+                    .with_allow_cycles(true),
+            })
+        });
+        let _guard = DefinitionGuard;
+        self.inner_schema().validate(input, maybe_position)
+    }
+}
+
+// Validates against the innermost enclosing recursive schema with the name:
+pub(crate) fn validate_ref(
+    name: &str,
+    input: &mut Input,
+    maybe_position: Option<InputPosition>,
+) -> Result<(), InputSchemaError> {
+    let invalid = |description: String| InputSchemaError::Invalid {
+        description,
+        position: maybe_position.clone().unwrap_or_default(),
+        input: input.clone(),
+    };
+    let maybe_found = DEFINITION_LIST.with_borrow_mut(|definition_list| {
+        let definition = definition_list
+            .iter_mut()
+            .rev()
+            .find(|definition| definition.name == name)?;
+        let position = maybe_position.clone().unwrap_or_default().to_string();
+        Some((
+            definition.schema_type.clone(),
+            definition.guard.max_depth(),
+            definition.guard.enter(position),
+        ))
+    });
+    let Some((schema_type, max_depth, entered)) = maybe_found else {
+        return Err(invalid(format!("Unknown recursive schema `{name}`")));
+    };
+    if let Err(error) = entered {
+        return Err(invalid(format!(
+            "Expected at most {max_depth} nested levels of `{name}` ({})",
+            error.chain_text()
+        )));
+    }
+    let result = schema_type.validate(input, maybe_position.clone());
+    DEFINITION_LIST.with_borrow_mut(|definition_list| {
+        if let Some(definition) = definition_list
+            .iter_mut()
+            .rev()
+            .find(|definition| definition.name == name)
+        {
+            definition.guard.leave();
+        }
+    });
+    result
+}
+
+// Enclosing recursive schemas of a walk over an input and its schema outside of validation (e.g.
+// to find secrets), so that refs are followed with the same depth limits as in validation:
+#[derive(Default)]
+pub(crate) struct RefScope<'a> {
+    definition_list: Vec<(&'a InputSchemaTypeRecursive, ResolutionGuard<String>)>,
+}
+
+impl<'a> RefScope<'a> {
+    pub(crate) fn with_recursive<T, F: FnOnce(&mut Self) -> T>(
+        &mut self,
+        recursive: &'a InputSchemaTypeRecursive,
+        walk: F,
+    ) -> T {
+        let guard = ResolutionGuard::new()
+            .with_max_depth(recursive.max_depth())
+            .with_allow_cycles(true);
+        self.definition_list.push((recursive, guard));
+        let result = walk(self);
+        self.definition_list.pop();
+        result
+    }
+
+    pub(crate) fn find(&self, name: &str) -> Option<&'a InputSchemaTypeRecursive> {
+        self.definition_list
+            .iter()
+            .rev()
+            .find(|(recursive, _)| recursive.name() == name)
+            .map(|(recursive, _)| *recursive)
+    }
+
+    // Walks the schema of the innermost enclosing recursive schema with the name. The error
+    // describes an unknown name or a depth beyond `max_depth`:
+    pub(crate) fn with_ref<T, F: FnOnce(&mut Self, &'a InputSchemaType) -> T>(
+        &mut self,
+        name: &str,
+        walk: F,
+    ) -> Result<T, String> {
+        let index = self
+            .definition_list
+            .iter()
+            .rposition(|(recursive, _)| recursive.name() == name)
+            .ok_or_else(|| format!("Unknown recursive schema `{name}`"))?;
+        let (recursive, guard) = &mut self.definition_list[index];
+        let recursive = *recursive;
+        guard.enter(name.to_string()).map_err(|error| {
+            format!(
+                "Expected at most {} nested levels of `{name}` ({})",
+                recursive.max_depth(),
+                error.chain_text()
+            )
+        })?;
+        let result = walk(self, recursive.inner_schema());
+        self.definition_list[index].1.leave();
+        Ok(result)
+    }
+
+    // Like `InputSchemaType::validate_cow()` but refs of the schema are resolved by the scope:
+    pub(crate) fn is_valid(&self, schema_type: &InputSchemaType, input: &Input) -> bool {
+        let guard_list: Vec<_> = self
+            .definition_list
+            .iter()
+            .map(|(recursive, _)| {
+                DEFINITION_LIST.with_borrow_mut(|definition_list| {
+                    definition_list.push(Definition {
+                        name: recursive.name.clone(),
+                        schema_type: Rc::new(recursive.inner_schema().clone()),
+                        guard: ResolutionGuard::new()
+                            .with_max_depth(recursive.max_depth())
+                            .with_allow_cycles(true),
+                    })
+                });
+                DefinitionGuard
+            })
+            .collect();
+        let is_valid = schema_type.validate_cow(input, None).is_ok();
+        drop(guard_list);
+        is_valid
+    }
+}
+
+impl InputSchemaType {
+    // Every `ref` should refer to an enclosing `recursive` schema, and there should be a list or
+    // map between them (otherwise validation would follow refs without reading the input):
+    pub fn check_refs(&self) -> Result<(), InputSchemaRefError> {
+        check_refs(self, InputPosition::new(), &mut Vec::new())
+    }
+}
+
+// Names of enclosing recursive schemas and whether a list or map is in between:
+fn check_refs(
+    schema_type: &InputSchemaType,
+    position: InputPosition,
+    name_list: &mut Vec<(String, bool)>,
+) -> Result<(), InputSchemaRefError> {
+    crate::stack::maybe_grow(|| match schema_type {
+        InputSchemaType::Recursive(recursive) => {
+            name_list.push((recursive.name.clone(), false));
+            let result = check_refs(recursive.inner_schema(), position, name_list);
+            name_list.pop();
+            result
+        }
+        InputSchemaType::Ref(reference) => {
+            match name_list
+                .iter()
+                .rev()
+                .find(|(name, _)| name == reference.name())
+            {
+                None => Err(InputSchemaRefError::Unresolved {
+                    position,
+                    name: reference.name().clone(),
+                }),
+                Some((_, false)) => Err(InputSchemaRefError::Cycle {
+                    position,
+                    name: reference.name().clone(),
+                }),
+                Some(_) => Ok(()),
+            }
+        }
+        InputSchemaType::Either(either) => either
+            .schema_list()
+            .iter()
+            .try_for_each(|schema_type| check_refs(schema_type, position.clone(), name_list)),
+        InputSchemaType::Secret(secret) => check_refs(secret.inner_schema(), position, name_list),
+        InputSchemaType::List(list) => {
+            check_guarded_refs(list.item_schema(), position.new_with_index(0), name_list)
+        }
+        InputSchemaType::DynamicMap(dynamic_map) => check_guarded_refs(
+            dynamic_map.item_schema(),
+            position.new_with_key("*"),
+            name_list,
+        ),
+        InputSchemaType::KeyValue(key_value) => check_guarded_refs(
+            key_value.value_schema(),
+            position.new_with_key("*"),
+            name_list,
+        ),
+        InputSchemaType::WeightedList(weighted_list) => check_guarded_refs(
+            weighted_list.value_schema(),
+            position.new_with_index(0).new_with_key("value"),
+            name_list,
+        ),
+        InputSchemaType::Interval(interval) => check_guarded_refs(
+            interval.item_schema(),
+            position.new_with_key("min"),
+            name_list,
+        ),
+        InputSchemaType::StaticMap(static_map) => {
+            static_map
+                .ordered_items()
+                .into_iter()
+                .try_for_each(|(key, schema)| {
+                    check_guarded_refs(schema.schema_type(), position.new_with_key(key), name_list)
+                })?;
+            static_map
+                .maybe_additional_items()
+                .map_or(Ok(()), |schema| {
+                    check_guarded_refs(schema.schema_type(), position.new_with_key("*"), name_list)
+                })
+        }
+        _ => Ok(()),
+    })
+}
+
+// Inside a list or map, every enclosing recursive schema is guarded:
+fn check_guarded_refs(
+    schema_type: &InputSchemaType,
+    position: InputPosition,
+    name_list: &[(String, bool)],
+) -> Result<(), InputSchemaRefError> {
+    let mut inner_name_list: Vec<_> = name_list
+        .iter()
+        .map(|(name, _)| (name.clone(), true))
+        .collect();
+    check_refs(schema_type, position, &mut inner_name_list)
+}
+
+impl Display for InputSchemaTypeRecursive {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (recursive `{}`)", self.inner_schema, self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn filter_schema() -> InputSchemaType {
+        serde_json::from_value(json!({
+            "type": "recursive",
+            "name": "filter",
+            "max_depth": 3,
+            "schema": {
+                "type": "either",
+                "schema_list": [
+                    {"type": "string"},
+                    {
+                        "type": "static_map",
+                        "items": {"not": {"schema": {"type": "ref", "name": "filter"}}}
+                    },
+                    {"type": "list", "item_schema": {"type": "ref", "name": "filter"}}
+                ]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn validate() {
+        let schema_type = filter_schema();
+        schema_type.check_refs().unwrap();
+        for input in [
+            json!("a"),
+            json!({"not": "a"}),
+            json!([{"not": ["a", "b"]}, "c"]),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            schema_type.validate(&mut input, None).unwrap();
+        }
+        for input in [
+            json!({"not": 1}),
+            json!({"not": {"not": {"not": {"not": "a"}}}}),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            assert!(schema_type.validate(&mut input, None).is_err());
+        }
+    }
+
+    #[test]
+    fn check_refs() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "recursive",
+            "name": "loop",
+            "schema": {"type": "either", "schema_list": [{"type": "ref", "name": "loop"}]}
+        }))
+        .unwrap();
+        assert!(matches!(
+            schema_type.check_refs(),
+            Err(InputSchemaRefError::Cycle { .. })
+        ));
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "list",
+            "item_schema": {"type": "ref", "name": "filter"}
+        }))
+        .unwrap();
+        assert_eq!(
+            schema_type.check_refs().unwrap_err().to_string(),
+            "0 refers to `filter` which is not an enclosing recursive schema"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    position::InputPosition,
+    schema::{types::recursive::validate_ref, InputSchemaError},
+    Input,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// Refers to the innermost enclosing `recursive` schema with the same name (see
+// `InputSchemaType::check_refs()`):
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeRef {
+    name: String,
+}
+
+impl InputSchemaTypeRef {
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn set_name<N: ToString>(&mut self, name: N) {
+        self.name = name.to_string();
+    }
+
+    pub fn with_name<N: ToString>(mut self, name: N) -> Self {
+        self.set_name(name);
+        self
+    }
+}
+
+impl InputSchemaTypeRef {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        validate_ref(&self.name, input, maybe_position)
+    }
+}
+
+impl Display for InputSchemaTypeRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "recursive `{}`", self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let error = InputSchemaTypeRef::default()
+            .with_name("filter")
+            .validate(&mut Input::from("a"), None)
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Unknown recursive schema `filter`"));
+    }
+}
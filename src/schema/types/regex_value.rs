@@ -0,0 +1,121 @@
+use crate::{position::InputPosition, schema::InputSchemaError, Input};
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// Strings that compile as regular expressions (e.g. user-supplied match patterns). The
+// `size_limit` (in bytes of the compiled regex) and `nest_limit` (depth of groups and repetitions)
+// limit the complexity of accepted patterns, otherwise the defaults of `regex` crate are used:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeRegexValue {
+    #[serde(
+        rename = "size_limit",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    maybe_size_limit: Option<usize>,
+    #[serde(
+        rename = "nest_limit",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    maybe_nest_limit: Option<u32>,
+}
+
+impl InputSchemaTypeRegexValue {
+    pub fn maybe_size_limit(&self) -> Option<usize> {
+        self.maybe_size_limit
+    }
+
+    pub fn maybe_size_limit_mut(&mut self) -> &mut Option<usize> {
+        &mut self.maybe_size_limit
+    }
+
+    pub fn set_size_limit(&mut self, size_limit: usize) {
+        *self.maybe_size_limit_mut() = Some(size_limit);
+    }
+
+    pub fn with_size_limit(mut self, size_limit: usize) -> Self {
+        self.set_size_limit(size_limit);
+        self
+    }
+
+    pub fn maybe_nest_limit(&self) -> Option<u32> {
+        self.maybe_nest_limit
+    }
+
+    pub fn maybe_nest_limit_mut(&mut self) -> &mut Option<u32> {
+        &mut self.maybe_nest_limit
+    }
+
+    pub fn set_nest_limit(&mut self, nest_limit: u32) {
+        *self.maybe_nest_limit_mut() = Some(nest_limit);
+    }
+
+    pub fn with_nest_limit(mut self, nest_limit: u32) -> Self {
+        self.set_nest_limit(nest_limit);
+        self
+    }
+}
+
+impl InputSchemaTypeRegexValue {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        if !input.is_str() {
+            return Err(InputSchemaError::Type {
+                position: maybe_position.unwrap_or_default(),
+                expected_type: Input::str_type_name(),
+                input_type: input.type_name(),
+            });
+        }
+        let mut builder = RegexBuilder::new(input.as_str());
+        if let Some(size_limit) = self.maybe_size_limit {
+            builder.size_limit(size_limit);
+        }
+        if let Some(nest_limit) = self.maybe_nest_limit {
+            builder.nest_limit(nest_limit);
+        }
+        builder
+            .build()
+            .map(|_| ())
+            .map_err(|error| InputSchemaError::Invalid {
+                description: match error {
+                    regex::Error::CompiledTooBig(size_limit) => {
+                        format!("Expected a simpler regular expression (compiled size limit is {size_limit} bytes)")
+                    }
+                    error => format!("Expected a valid regular expression: {error}"),
+                },
+                position: maybe_position.unwrap_or_default(),
+                input: input.clone(),
+            })
+    }
+}
+
+impl Display for InputSchemaTypeRegexValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("regular expression")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let schema = InputSchemaTypeRegexValue::default()
+            .with_size_limit(100_000)
+            .with_nest_limit(4);
+        for text in ["^[a-z]+$", "(foo|bar)\\d{2}"] {
+            schema.validate(&mut Input::from(text), None).unwrap();
+        }
+        for text in ["[a-z", "((((((a))))))", "\\w{1000}"] {
+            assert!(schema.validate(&mut Input::from(text), None).is_err());
+        }
+        assert!(schema.validate(&mut Input::from(1), None).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{
+    position::InputPosition,
+    schema::{InputSchemaError, InputSchemaType},
+    Input,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter},
+};
+
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeSecret {
+    #[serde(rename = "schema", default)]
+    inner_schema: Box<InputSchemaType>,
+}
+
+impl InputSchemaTypeSecret {
+    pub fn inner_schema(&self) -> &InputSchemaType {
+        &self.inner_schema
+    }
+
+    pub fn inner_schema_mut(&mut self) -> &mut InputSchemaType {
+        &mut self.inner_schema
+    }
+
+    pub fn set_inner_schema<S: Into<InputSchemaType>>(&mut self, schema: S) {
+        *self.inner_schema_mut() = schema.into();
+    }
+
+    pub fn with_inner_schema<S: Into<InputSchemaType>>(mut self, schema: S) -> Self {
+        self.set_inner_schema(schema);
+        self
+    }
+
+    pub fn placeholder() -> Input {
+        Input::from("<secret>")
+    }
+}
+
+impl InputSchemaTypeSecret {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        self.inner_schema
+            .validate(input, maybe_position)
+            .map_err(Self::hide_input)
+    }
+
+    pub fn validate_cow<'a>(
+        &self,
+        input: &'a Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<Cow<'a, Input>, InputSchemaError> {
+        self.inner_schema
+            .validate_cow(input, maybe_position)
+            .map_err(Self::hide_input)
+    }
+
+    // Secret values should never be rendered in error messages:
+    fn hide_input(mut error: InputSchemaError) -> InputSchemaError {
+        match &mut error {
+            InputSchemaError::Schema { input, .. }
+            | InputSchemaError::Range { input, .. }
+            | InputSchemaError::Invalid { input, .. } => *input = Self::placeholder(),
+            InputSchemaError::Context { source, .. } => {
+                **source = Self::hide_input(*source.clone())
+            }
+            _ => (),
+        };
+        error
+    }
+}
+
+impl Display for InputSchemaTypeSecret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(format!("secret {}", self.inner_schema).as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::InputSchemaTypeInteger;
+
+    #[test]
+    fn serde() {
+        let json = serde_json::json!({"type": "secret", "schema": {"type": "string"}});
+        let schema: InputSchemaType = serde_json::from_value(json).unwrap();
+        assert!(schema.is_secret());
+        assert!(schema.as_secret().inner_schema().is_string());
+    }
+
+    #[test]
+    fn validate() {
+        let schema = InputSchemaTypeSecret::default().with_inner_schema(InputSchemaType::Integer(
+            InputSchemaTypeInteger::default().with_range(10),
+        ));
+        assert!(schema.validate(&mut Input::from(5), None).is_ok());
+        let error = schema.validate(&mut Input::from(50), None).unwrap_err();
+        assert!(!error.to_string().contains("50"));
+        assert!(error.to_string().contains("<secret>"));
+    }
+}
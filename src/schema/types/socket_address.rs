@@ -8,8 +8,9 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     fmt::{Display, Formatter},
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
 };
 
 #[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
@@ -21,6 +22,21 @@ pub struct InputSchemaTypeSocketAddress {
     v6: bool,
     #[serde(default = "default_port_zero")]
     port_start: u16,
+    #[serde(default)]
+    form: InputSchemaTypeSocketAddressForm,
+}
+
+// Both forms are accepted and validated values are rewritten to the configured one:
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(
+    rename_all = "snake_case",
+    deny_unknown_fields,
+    expecting = "Expecting socket address forms: `string` (`\"127.0.0.1:80\"`) or `map` (`{\"ip\": \"127.0.0.1\", \"port\": 80}`)"
+)]
+pub enum InputSchemaTypeSocketAddressForm {
+    #[default]
+    String,
+    Map,
 }
 
 impl InputSchemaTypeSocketAddress {
@@ -74,6 +90,23 @@ impl InputSchemaTypeSocketAddress {
         self.set_port_start(start);
         self
     }
+
+    pub fn form(&self) -> InputSchemaTypeSocketAddressForm {
+        self.form
+    }
+
+    pub fn form_mut(&mut self) -> &mut InputSchemaTypeSocketAddressForm {
+        &mut self.form
+    }
+
+    pub fn set_form(&mut self, form: InputSchemaTypeSocketAddressForm) {
+        *self.form_mut() = form;
+    }
+
+    pub fn with_form(mut self, form: InputSchemaTypeSocketAddressForm) -> Self {
+        self.set_form(form);
+        self
+    }
 }
 
 impl InputSchemaTypeSocketAddress {
@@ -82,22 +115,47 @@ impl InputSchemaTypeSocketAddress {
         input: &mut Input,
         maybe_position: Option<InputPosition>,
     ) -> Result<(), InputSchemaError> {
-        if !input.is_str() {
-            return Err(InputSchemaError::Type {
-                position: maybe_position.unwrap_or_default(),
-                expected_type: Input::map_type_name(),
-                input_type: input.type_name(),
-            });
-        }
-        let socket_address_str = input.as_str();
-        let socket_address = match socket_address_str.parse::<SocketAddr>() {
-            Ok(socket_address) => socket_address,
-            Err(error) => {
-                return Err(InputSchemaError::Invalid {
-                    description: format!("Could not parse socket address: {error}"),
+        let invalid = |description: String| InputSchemaError::Invalid {
+            description,
+            position: maybe_position.clone().unwrap_or_default(),
+            input: input.clone(),
+        };
+        let socket_address = match &*input {
+            Input::Str(socket_address) => socket_address
+                .parse::<SocketAddr>()
+                .map_err(|error| invalid(format!("Could not parse socket address: {error}")))?,
+            Input::Map(map) => {
+                if let Some(key) = map
+                    .keys()
+                    .map(|key| &**key)
+                    .find(|key| *key != "ip" && *key != "port")
+                {
+                    return Err(invalid(format!(
+                        "Unknown key `{key}` (expected `ip` and `port`)"
+                    )));
+                }
+                let ip = match map.get("ip") {
+                    Some(Input::Str(ip)) => ip
+                        .trim_start_matches('[')
+                        .trim_end_matches(']')
+                        .parse::<IpAddr>()
+                        .map_err(|error| invalid(format!("Could not parse IP address: {error}")))?,
+                    _ => return Err(invalid("Expected `ip` to be an IP address".to_string())),
+                };
+                let port = match map.get("port") {
+                    Some(Input::Int(port)) => u16::try_from(*port).ok(),
+                    Some(Input::Str(port)) => port.parse::<u16>().ok(),
+                    _ => None,
+                }
+                .ok_or_else(|| invalid("Expected `port` to be a port number".to_string()))?;
+                SocketAddr::new(ip, port)
+            }
+            _ => {
+                return Err(InputSchemaError::Type {
                     position: maybe_position.unwrap_or_default(),
-                    input: input.clone(),
-                });
+                    expected_type: Input::str_type_name(),
+                    input_type: input.type_name(),
+                })
             }
         };
         let ip = socket_address.ip();
@@ -116,10 +174,37 @@ impl InputSchemaTypeSocketAddress {
             });
         }
         // TODO: port
+        match self.form {
+            InputSchemaTypeSocketAddressForm::String if input.is_map() => {
+                *input = Input::from(socket_address.to_string())
+            }
+            InputSchemaTypeSocketAddressForm::String => (),
+            InputSchemaTypeSocketAddressForm::Map => {
+                *input = Input::from(HashMap::from([
+                    (
+                        "ip".to_string(),
+                        Input::from(socket_address.ip().to_string()),
+                    ),
+                    (
+                        "port".to_string(),
+                        Input::from(socket_address.port() as isize),
+                    ),
+                ]))
+            }
+        }
         Ok(())
     }
 }
 
+impl Display for InputSchemaTypeSocketAddressForm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::String => "string",
+            Self::Map => "map",
+        })
+    }
+}
+
 impl Display for InputSchemaTypeSocketAddress {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let ip = if !self.v4 && self.v6 {
@@ -134,12 +219,45 @@ impl Display for InputSchemaTypeSocketAddress {
         } else {
             String::new()
         };
-        f.write_str(format!("`<{ip}>:<Port>`{port}").as_str())
+        let form = if self.form == InputSchemaTypeSocketAddressForm::Map {
+            " (normalized to a `{\"ip\": ..., \"port\": ...}` map)"
+        } else {
+            ""
+        };
+        f.write_str(format!("`<{ip}>:<Port>`{port}{form}").as_str())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use serde_json::json;
+
     #[test]
     fn serde() {}
+
+    #[test]
+    fn form() {
+        let map_schema = InputSchemaTypeSocketAddress::default()
+            .with_form(InputSchemaTypeSocketAddressForm::Map);
+        let mut input = Input::from("[::1]:8080");
+        map_schema.validate(&mut input, None).unwrap();
+        let expected: Input = serde_json::from_value(json!({"ip": "::1", "port": 8080})).unwrap();
+        assert_eq!(input, expected);
+        map_schema.validate(&mut input, None).unwrap();
+        assert_eq!(input, expected);
+
+        let schema = InputSchemaTypeSocketAddress::default();
+        schema.validate(&mut input, None).unwrap();
+        assert_eq!(input, Input::from("[::1]:8080"));
+        for input in [
+            json!({"ip": "::1"}),
+            json!({"ip": "::1", "port": 70000}),
+            json!({"ip": "localhost", "port": 80}),
+            json!({"ip": "::1", "port": 80, "scope": 1}),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            assert!(schema.validate(&mut input, None).is_err());
+        }
+    }
 }
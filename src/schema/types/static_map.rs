@@ -1,17 +1,73 @@
 use crate::{
+    naming::NamingConvention,
     position::InputPosition,
-    schema::{InputSchema, InputSchemaError},
-    Input,
+    schema::{validate_with_cow, InputSchema, InputSchemaError},
+    Input, InputKey, InputMap,
 };
 use cfg_if::cfg_if;
-use serde::{Deserialize, Serialize};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
-#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+// `Serialize` is implemented by hand so that `items` follow `ordered_items()`:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct InputSchemaTypeStaticMap {
     items: HashMap<String, InputSchema>,
+    // Input keys that only differ in case (e.g. `Port` or `PORT` for `port`) are renamed to the
+    // keys of `items`:
+    #[serde(default)]
+    case_insensitive_keys: bool,
+    // Like `case_insensitive_keys` but input keys in any naming convention (e.g.
+    // `maxConnections` or `max-connections` for `max_connections`) are renamed:
+    #[serde(default)]
+    naming_insensitive_keys: bool,
+    // The logical order of keys for generated output (e.g. docs and prompts). Other keys follow
+    // in alphabetical order:
+    #[serde(default)]
+    order: Vec<String>,
+    // Checked after all items are validated. Items of groups without defaults may be missing:
+    #[serde(rename = "groups", default)]
+    group_list: Vec<InputSchemaTypeStaticMapGroup>,
+    // Validates input keys that are not in `items` (like `additionalProperties` of JSON Schema).
+    // Without it, they are kept as they are:
+    #[serde(rename = "additional_items", default)]
+    maybe_additional_items: Option<Box<InputSchema>>,
+    // How items that are given as `null` differ from missing items:
+    #[serde(default)]
+    null_policy: InputSchemaTypeStaticMapNullPolicy,
+}
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(
+    rename_all = "snake_case",
+    deny_unknown_fields,
+    expecting = "Expecting null policies: `validate`, `as_missing`, `allow_if_optional`, or `forbid`"
+)]
+pub enum InputSchemaTypeStaticMapNullPolicy {
+    // `null` is validated with the schema of the item like any other value:
+    #[default]
+    Validate,
+    // `null` is replaced by the default or removed, and is not given for groups:
+    AsMissing,
+    // `null` is kept for items with defaults or in groups (and is given for groups). Other items
+    // are validated:
+    AllowIfOptional,
+    // Items can not be `null`:
+    Forbid,
+}
+
+// Constraints on which keys of a group are given in the input (defaults don't count):
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(
+    rename_all = "snake_case",
+    deny_unknown_fields,
+    expecting = "Expecting `exactly_one_of` or `at_most_one_of` with a list of keys"
+)]
+pub enum InputSchemaTypeStaticMapGroup {
+    ExactlyOneOf(Vec<String>),
+    AtMostOneOf(Vec<String>),
 }
 
 impl InputSchemaTypeStaticMap {
@@ -31,6 +87,203 @@ impl InputSchemaTypeStaticMap {
         self.set_item(key, schema);
         self
     }
+
+    pub fn case_insensitive_keys(&self) -> bool {
+        self.case_insensitive_keys
+    }
+
+    pub fn set_case_insensitive_keys(&mut self, case_insensitive_keys: bool) {
+        self.case_insensitive_keys = case_insensitive_keys;
+    }
+
+    pub fn with_case_insensitive_keys(mut self, case_insensitive_keys: bool) -> Self {
+        self.set_case_insensitive_keys(case_insensitive_keys);
+        self
+    }
+
+    pub fn naming_insensitive_keys(&self) -> bool {
+        self.naming_insensitive_keys
+    }
+
+    pub fn set_naming_insensitive_keys(&mut self, naming_insensitive_keys: bool) {
+        self.naming_insensitive_keys = naming_insensitive_keys;
+    }
+
+    pub fn with_naming_insensitive_keys(mut self, naming_insensitive_keys: bool) -> Self {
+        self.set_naming_insensitive_keys(naming_insensitive_keys);
+        self
+    }
+
+    pub fn order(&self) -> &Vec<String> {
+        &self.order
+    }
+
+    pub fn order_mut(&mut self) -> &mut Vec<String> {
+        &mut self.order
+    }
+
+    pub fn set_order<K: ToString>(&mut self, order: Vec<K>) {
+        *self.order_mut() = order.into_iter().map(|key| key.to_string()).collect();
+    }
+
+    pub fn with_order<K: ToString>(mut self, order: Vec<K>) -> Self {
+        self.set_order(order);
+        self
+    }
+
+    pub fn group_list(&self) -> &Vec<InputSchemaTypeStaticMapGroup> {
+        &self.group_list
+    }
+
+    pub fn group_list_mut(&mut self) -> &mut Vec<InputSchemaTypeStaticMapGroup> {
+        &mut self.group_list
+    }
+
+    pub fn add_group(&mut self, group: InputSchemaTypeStaticMapGroup) {
+        self.group_list_mut().push(group);
+    }
+
+    pub fn with_group(mut self, group: InputSchemaTypeStaticMapGroup) -> Self {
+        self.add_group(group);
+        self
+    }
+
+    pub fn with_exactly_one_of<K: ToString>(self, key_list: Vec<K>) -> Self {
+        self.with_group(InputSchemaTypeStaticMapGroup::ExactlyOneOf(
+            key_list.into_iter().map(|key| key.to_string()).collect(),
+        ))
+    }
+
+    pub fn with_at_most_one_of<K: ToString>(self, key_list: Vec<K>) -> Self {
+        self.with_group(InputSchemaTypeStaticMapGroup::AtMostOneOf(
+            key_list.into_iter().map(|key| key.to_string()).collect(),
+        ))
+    }
+
+    pub fn maybe_additional_items(&self) -> Option<&InputSchema> {
+        self.maybe_additional_items.as_deref()
+    }
+
+    pub fn maybe_additional_items_mut(&mut self) -> &mut Option<Box<InputSchema>> {
+        &mut self.maybe_additional_items
+    }
+
+    pub fn set_additional_items<S: Into<InputSchema>>(&mut self, schema: S) {
+        *self.maybe_additional_items_mut() = Some(Box::new(schema.into()));
+    }
+
+    pub fn with_additional_items<S: Into<InputSchema>>(mut self, schema: S) -> Self {
+        self.set_additional_items(schema);
+        self
+    }
+
+    pub fn null_policy(&self) -> InputSchemaTypeStaticMapNullPolicy {
+        self.null_policy
+    }
+
+    pub fn set_null_policy(&mut self, null_policy: InputSchemaTypeStaticMapNullPolicy) {
+        self.null_policy = null_policy;
+    }
+
+    pub fn with_null_policy(mut self, null_policy: InputSchemaTypeStaticMapNullPolicy) -> Self {
+        self.set_null_policy(null_policy);
+        self
+    }
+
+    // Items in `order` (unknown keys are ignored) followed by the rest sorted by key:
+    pub fn ordered_items(&self) -> Vec<(&String, &InputSchema)> {
+        let mut item_list: Vec<_> = self
+            .order
+            .iter()
+            .filter_map(|key| self.items.get_key_value(key))
+            .collect();
+        let mut rest_item_list: Vec<_> = self
+            .items
+            .iter()
+            .filter(|(key, _)| !self.order.contains(key))
+            .collect();
+        rest_item_list.sort_by_key(|(key, _)| *key);
+        item_list.append(&mut rest_item_list);
+        item_list
+    }
+
+    // The item that validation renames the input key `key` to (see `case_insensitive_keys` and
+    // `naming_insensitive_keys`):
+    pub(crate) fn item(&self, key: &str) -> Option<(&String, &InputSchema)> {
+        if let Some(item) = self.items.get_key_value(key) {
+            return Some(item);
+        }
+        let normalize: fn(&str) -> String = if self.naming_insensitive_keys {
+            |key| NamingConvention::Snake.convert(key)
+        } else if self.case_insensitive_keys {
+            str::to_lowercase
+        } else {
+            return None;
+        };
+        let normalized_key = normalize(key);
+        self.items
+            .iter()
+            .find(|(item_key, _)| normalize(item_key) == normalized_key)
+    }
+
+    // The schema of the value of the input key `key` (its item or else `additional_items`):
+    pub(crate) fn item_schema(&self, key: &str) -> Option<&InputSchema> {
+        self.item(key)
+            .map(|(_, schema)| schema)
+            .or(self.maybe_additional_items())
+    }
+
+    // Item schemas and the additional items schema, in no particular order:
+    pub(crate) fn schema_list_mut(&mut self) -> Vec<&mut InputSchema> {
+        self.items
+            .values_mut()
+            .chain(self.maybe_additional_items.as_deref_mut())
+            .collect()
+    }
+}
+
+impl Serialize for InputSchemaTypeStaticMap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Items<'a>(&'a InputSchemaTypeStaticMap);
+
+        impl Serialize for Items<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_map(self.0.ordered_items())
+            }
+        }
+
+        let mut state = serializer.serialize_struct(
+            "InputSchemaTypeStaticMap",
+            3 + usize::from(!self.order.is_empty())
+                + usize::from(!self.group_list.is_empty())
+                + usize::from(self.maybe_additional_items.is_some())
+                + usize::from(self.null_policy != Default::default()),
+        )?;
+        state.serialize_field("items", &Items(self))?;
+        state.serialize_field("case_insensitive_keys", &self.case_insensitive_keys)?;
+        state.serialize_field("naming_insensitive_keys", &self.naming_insensitive_keys)?;
+        if self.order.is_empty() {
+            state.skip_field("order")?;
+        } else {
+            state.serialize_field("order", &self.order)?;
+        }
+        if self.group_list.is_empty() {
+            state.skip_field("groups")?;
+        } else {
+            state.serialize_field("groups", &self.group_list)?;
+        }
+        if let Some(additional_items) = &self.maybe_additional_items {
+            state.serialize_field("additional_items", additional_items)?;
+        } else {
+            state.skip_field("additional_items")?;
+        }
+        if self.null_policy == Default::default() {
+            state.skip_field("null_policy")?;
+        } else {
+            state.serialize_field("null_policy", &self.null_policy)?;
+        }
+        state.end()
+    }
 }
 
 impl InputSchemaTypeStaticMap {
@@ -39,6 +292,14 @@ impl InputSchemaTypeStaticMap {
         input: &mut Input,
         maybe_position: Option<InputPosition>,
     ) -> Result<(), InputSchemaError> {
+        validate_with_cow(input, |input| self.validate_cow(input, maybe_position))
+    }
+
+    pub fn validate_cow<'a>(
+        &self,
+        input: &'a Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<Cow<'a, Input>, InputSchemaError> {
         if !input.is_map() {
             return Err(InputSchemaError::Type {
                 position: maybe_position.unwrap_or_default(),
@@ -46,63 +307,241 @@ impl InputSchemaTypeStaticMap {
                 input_type: input.type_name(),
             });
         };
-        let mut new_input = input.clone();
-        let map = new_input.map_mut();
+        let map = input.as_map();
         let position = maybe_position.unwrap_or_default();
+        // Values that validation changed (or defaults), applied to a copy of the map at the end:
+        let mut changed_list = Vec::new();
+        // Input keys that are renamed to the keys of `items`:
+        let mut renamed_key_list = Vec::new();
+        // Keys of `items` that are given in the input:
+        let mut given_key_list = Vec::new();
         for (key, inner_schema) in self.items.iter() {
             let inner_position = position.new_with_key(key);
-            if let Some(inner_input) = map.get_mut(key) {
-                inner_schema
+            let maybe_inner_input = match map.get_key_value(key.as_str()) {
+                None if self.naming_insensitive_keys => {
+                    find_similar_key(map, key, &inner_position, |key| {
+                        NamingConvention::Snake.convert(key)
+                    })?
+                }
+                None if self.case_insensitive_keys => {
+                    find_similar_key(map, key, &inner_position, str::to_lowercase)?
+                }
+                maybe_inner_input => maybe_inner_input,
+            };
+            let is_optional = inner_schema.maybe_default().is_some()
+                || self
+                    .group_list
+                    .iter()
+                    .any(|group| group.key_list().contains(key));
+            let maybe_inner_input = match (maybe_inner_input, self.null_policy) {
+                (Some((input_key, Input::Null)), InputSchemaTypeStaticMapNullPolicy::AsMissing) => {
+                    // Removed unless there's a default:
+                    renamed_key_list.push(input_key);
+                    None
+                }
+                (
+                    Some((input_key, Input::Null)),
+                    InputSchemaTypeStaticMapNullPolicy::AllowIfOptional,
+                ) if is_optional => {
+                    given_key_list.push(key);
+                    if **input_key != **key {
+                        renamed_key_list.push(input_key);
+                        changed_list.push((key.as_str(), Input::Null));
+                    }
+                    continue;
+                }
+                (Some((_, Input::Null)), InputSchemaTypeStaticMapNullPolicy::Forbid) => {
+                    return Err(InputSchemaError::Invalid {
+                        description: "Expected a value instead of `null`".to_string(),
+                        position: inner_position,
+                        input: Input::Null,
+                    }
+                    .with_maybe_context(inner_schema.maybe_context()));
+                }
+                (maybe_inner_input, _) => maybe_inner_input,
+            };
+            if let Some((input_key, inner_input)) = maybe_inner_input {
+                given_key_list.push(key);
+                let validated_inner_input = inner_schema
                     .schema_type()
-                    .validate(inner_input, Some(inner_position))?;
+                    .validate_cow(inner_input, Some(inner_position))
+                    .map_err(|error| error.with_maybe_context(inner_schema.maybe_context()))?;
+                if **input_key != **key {
+                    renamed_key_list.push(input_key);
+                    changed_list.push((key.as_str(), validated_inner_input.into_owned()));
+                } else if let Cow::Owned(validated_inner_input) = validated_inner_input {
+                    changed_list.push((key.as_str(), validated_inner_input));
+                }
             } else if let Some(default) = inner_schema.maybe_default() {
                 cfg_if! {
                     if #[cfg(feature = "tracing")] {
                         tracing::trace!(
                             position = %inner_position,
-                            default = %default,
+                            default = %default.display_compact(crate::logging::TRACE_MAX_LEN),
                             "using default value"
                         );
                     } else if #[cfg(feature = "logging")] {
                         log::trace!(
                             "position={:?} default={:?} message=\"using default value\"",
                             inner_position.to_string(),
-                            default.to_string(),
+                            default.display_compact(crate::logging::TRACE_MAX_LEN).to_string(),
                         );
                     }
                 }
-                map.insert(key.clone(), default.clone());
-            } else {
+                changed_list.push((key.as_str(), default.clone()));
+            } else if !is_optional {
                 return Err(InputSchemaError::NotFound {
                     position: inner_position,
                     schema_type: inner_schema.schema_type().clone(),
-                });
+                }
+                .with_maybe_context(inner_schema.maybe_context()));
             }
         }
-        *input = new_input;
-        Ok(())
+        if let Some(additional_items) = &self.maybe_additional_items {
+            for (key, inner_input) in map.iter() {
+                if self.items.contains_key(&**key) || renamed_key_list.contains(&key) {
+                    continue;
+                }
+                let validated_inner_input = additional_items
+                    .schema_type()
+                    .validate_cow(inner_input, Some(position.new_with_key(key)))
+                    .map_err(|error| error.with_maybe_context(additional_items.maybe_context()))?;
+                if let Cow::Owned(validated_inner_input) = validated_inner_input {
+                    changed_list.push((&**key, validated_inner_input));
+                }
+            }
+        }
+        for group in self.group_list.iter() {
+            group.validate(
+                |key| {
+                    given_key_list.contains(&key)
+                        || (!self.items.contains_key(key) && map.contains_key(key.as_str()))
+                },
+                input,
+                &position,
+            )?;
+        }
+        if changed_list.is_empty() && renamed_key_list.is_empty() {
+            return Ok(Cow::Borrowed(input));
+        }
+        // Changed values are not copied from `map`:
+        let mut changed_map: HashMap<_, _> = changed_list.into_iter().collect();
+        let mut new_map: HashMap<_, _> = map
+            .iter()
+            .filter(|(key, _)| !renamed_key_list.contains(key))
+            .map(|(key, inner_input)| {
+                (
+                    key.clone(),
+                    changed_map
+                        .remove(&**key)
+                        .unwrap_or_else(|| inner_input.clone()),
+                )
+            })
+            .collect();
+        new_map.extend(
+            changed_map.into_iter().map(|(key, validated_inner_input)| {
+                (crate::intern_key(key), validated_inner_input)
+            }),
+        );
+        Ok(Cow::Owned(Input::from(new_map)))
+    }
+}
+
+impl InputSchemaTypeStaticMapGroup {
+    pub fn key_list(&self) -> &Vec<String> {
+        match self {
+            Self::ExactlyOneOf(key_list) | Self::AtMostOneOf(key_list) => key_list,
+        }
+    }
+
+    fn validate<F: Fn(&String) -> bool>(
+        &self,
+        is_given: F,
+        input: &Input,
+        position: &InputPosition,
+    ) -> Result<(), InputSchemaError> {
+        let given_key_list: Vec<_> = self.key_list().iter().filter(|key| is_given(key)).collect();
+        let is_valid = match self {
+            Self::ExactlyOneOf(_) => given_key_list.len() == 1,
+            Self::AtMostOneOf(_) => given_key_list.len() <= 1,
+        };
+        if is_valid {
+            return Ok(());
+        }
+        let given = if given_key_list.is_empty() {
+            "none".to_string()
+        } else {
+            key_list_text(given_key_list)
+        };
+        Err(InputSchemaError::Invalid {
+            description: format!("Expected {self}, got {given}"),
+            position: position.clone(),
+            input: input.clone(),
+        })
+    }
+}
+
+fn key_list_text<'a, I: IntoIterator<Item = &'a String>>(key_list: I) -> String {
+    key_list
+        .into_iter()
+        .map(|key| format!("`{key}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Display for InputSchemaTypeStaticMapGroup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let key_list = key_list_text(self.key_list());
+        match self {
+            Self::ExactlyOneOf(_) => write!(f, "exactly one of {key_list}"),
+            Self::AtMostOneOf(_) => write!(f, "at most one of {key_list}"),
+        }
     }
 }
 
+// The only input key that is the same as `key` after `normalize`:
+fn find_similar_key<'a, F: Fn(&str) -> String>(
+    map: &'a InputMap,
+    key: &str,
+    position: &InputPosition,
+    normalize: F,
+) -> Result<Option<(&'a InputKey, &'a Input)>, InputSchemaError> {
+    let normalized_key = normalize(key);
+    let mut found_iter = map
+        .iter()
+        .filter(|(input_key, _)| normalize(input_key) == normalized_key);
+    let maybe_found = found_iter.next();
+    if let Some((other_input_key, _)) = found_iter.next() {
+        return Err(InputSchemaError::Invalid {
+            description: format!(
+                "Keys `{}` and `{other_input_key}` both match `{key}`",
+                maybe_found.unwrap().0
+            ),
+            position: position.clone(),
+            input: Input::from(map.clone()),
+        });
+    }
+    Ok(maybe_found)
+}
+
 impl Display for InputSchemaTypeStaticMap {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let length = self.items.len();
+        let item_list = self.ordered_items();
         if length == 1 {
-            let (key, schema) = self.items.iter().next().unwrap();
+            let (key, schema) = item_list[0];
             f.write_str(
                 format!("static map with key `{key}` and value that should be {schema}").as_str(),
             )
         } else if length == 2 {
-            let mut iter = self.items.iter();
-            let (key1, schema1) = iter.next().unwrap();
-            let (key2, schema2) = iter.next().unwrap();
+            let ((key1, schema1), (key2, schema2)) = (item_list[0], item_list[1]);
             f.write_str(format!("static map with key `{key1}` and its value that should be {schema1} and another key `{key2}` and its value that should be {schema2}").as_str())
         } else if length == 0 {
             f.write_str("static map that should contain nothing")
         } else {
             let mut text = format!("static map with {length} different keys;");
-            self.items
-                .iter()
+            item_list
+                .into_iter()
                 .enumerate()
                 .for_each(|(index, (key, schema))| {
                     text += if index + 1 == length {
@@ -119,6 +558,212 @@ impl Display for InputSchemaTypeStaticMap {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::schema::InputSchemaType;
+    use serde_json::json;
+
     #[test]
     fn serde() {}
+
+    #[test]
+    fn error_context() {
+        let schema: InputSchemaTypeStaticMap = serde_json::from_value(json!({
+            "items": {
+                "server": {"schema": {"type": "static_map", "items": {
+                    "port": {
+                        "schema": {"type": "port", "start": 1024},
+                        "title": "listen port",
+                        "description": "the TCP port the plugin binds to"
+                    }
+                }}, "title": "server"}
+            }
+        }))
+        .unwrap();
+        let mut input: Input = serde_json::from_value(json!({"server": {"port": 80}})).unwrap();
+        let error = schema.validate(&mut input, None).unwrap_err();
+        // Only the nearest item is mentioned:
+        assert!(error
+            .to_string()
+            .starts_with("listen port (the TCP port the plugin binds to): [server][port] "));
+        assert_eq!(error.position().to_string(), "[server][port]");
+        assert!(matches!(error.inner(), InputSchemaError::Range { .. }));
+    }
+
+    #[test]
+    fn case_insensitive_keys() {
+        let schema: InputSchemaTypeStaticMap = serde_json::from_value(json!({
+            "items": {
+                "port": {"schema": {"type": "port"}},
+                "Host": {"schema": {"type": "string"}, "default": "localhost"},
+            },
+            "case_insensitive_keys": true
+        }))
+        .unwrap();
+        let mut input: Input =
+            serde_json::from_value(json!({"PORT": "8080", "host": "example.com"})).unwrap();
+        schema.validate(&mut input, None).unwrap();
+        let expected: Input =
+            serde_json::from_value(json!({"port": 8080, "Host": "example.com"})).unwrap();
+        assert_eq!(input, expected);
+
+        let mut input: Input = serde_json::from_value(json!({"PORT": 1, "Port": 2})).unwrap();
+        assert!(schema.validate(&mut input, None).is_err());
+        assert!(schema
+            .clone()
+            .with_case_insensitive_keys(false)
+            .validate(&mut Input::from(HashMap::from([("PORT", 1)])), None)
+            .is_err());
+    }
+
+    #[test]
+    fn order() {
+        let schema = InputSchemaTypeStaticMap::default()
+            .with_item("port", InputSchemaType::new_port())
+            .with_item("host", InputSchemaType::new_string())
+            .with_item("user", InputSchemaType::new_string())
+            .with_item("password", InputSchemaType::new_string())
+            .with_order(vec!["host", "port", "unknown"]);
+        let key_list: Vec<_> = schema
+            .ordered_items()
+            .into_iter()
+            .map(|(key, _)| key.as_str())
+            .collect();
+        assert_eq!(key_list, ["host", "port", "password", "user"]);
+        let json = serde_json::to_string(&schema).unwrap();
+        assert!(json.starts_with(r#"{"items":{"host":"#));
+        assert!(json.contains(r#""order":["host","port","unknown"]"#));
+        assert_eq!(
+            serde_json::from_str::<InputSchemaTypeStaticMap>(&json).unwrap(),
+            schema
+        );
+        assert!(schema
+            .to_string()
+            .starts_with("static map with 4 different keys;key `host`"));
+    }
+
+    #[test]
+    fn groups() {
+        let schema: InputSchemaTypeStaticMap = serde_json::from_value(json!({
+            "items": {
+                "password": {"schema": {"type": "string"}},
+                "password_file": {"schema": {"type": "string"}},
+                "token": {"schema": {"type": "string"}},
+                "cert": {"schema": {"type": "string"}},
+                "key": {"schema": {"type": "string"}, "default": "key.pem"},
+            },
+            "groups": [
+                {"exactly_one_of": ["password", "password_file", "token"]},
+                {"at_most_one_of": ["token", "cert"]},
+            ]
+        }))
+        .unwrap();
+        for input in [json!({"password": "a"}), json!({"token": "a", "key": "b"})] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            schema.validate(&mut input, None).unwrap();
+        }
+        for (input, description) in [
+            (
+                json!({}),
+                "Expected exactly one of `password`, `password_file`, `token`, got none",
+            ),
+            (
+                json!({"password": "a", "token": "b"}),
+                "Expected exactly one of `password`, `password_file`, `token`, got `password`, `token`",
+            ),
+            (
+                json!({"token": "a", "cert": "b"}),
+                "Expected at most one of `token`, `cert`, got `token`, `cert`",
+            ),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            match schema.validate(&mut input, None) {
+                Err(InputSchemaError::Invalid {
+                    description: error_description,
+                    ..
+                }) => assert_eq!(error_description, description),
+                result => panic!("{result:?}"),
+            }
+        }
+        let json_schema = InputSchemaType::StaticMap(schema).to_json_schema();
+        assert!(!json_schema.as_map().contains_key("required"));
+        assert_eq!(json_schema.as_map()["allOf"].as_list().len(), 2);
+    }
+
+    #[test]
+    fn naming_insensitive_keys() {
+        let schema = InputSchemaTypeStaticMap::default()
+            .with_item("max_connections", InputSchemaType::new_integer())
+            .with_naming_insensitive_keys(true);
+        for key in ["maxConnections", "max-connections", "MAX_CONNECTIONS"] {
+            let mut input = Input::from(HashMap::from([(key, 10)]));
+            schema.validate(&mut input, None).unwrap();
+            assert_eq!(input, Input::from(HashMap::from([("max_connections", 10)])));
+        }
+    }
+
+    #[test]
+    fn additional_items() {
+        let schema: InputSchemaTypeStaticMap = serde_json::from_value(json!({
+            "items": {"name": {"schema": {"type": "string"}}},
+            "additional_items": {"schema": {"type": "integer"}},
+        }))
+        .unwrap();
+        let mut input: Input =
+            serde_json::from_value(json!({"name": "foo", "a": "1", "b": 2})).unwrap();
+        schema.validate(&mut input, None).unwrap();
+        assert_eq!(
+            input,
+            serde_json::from_value::<Input>(json!({"name": "foo", "a": 1, "b": 2})).unwrap()
+        );
+        let mut input: Input = serde_json::from_value(json!({"name": "foo", "a": "x"})).unwrap();
+        let error = schema.validate(&mut input, None).unwrap_err();
+        assert_eq!(error.position().to_string(), "a");
+        assert_eq!(
+            serde_json::to_value(&schema).unwrap()["additional_items"],
+            json!({"schema": {"type": "integer"}})
+        );
+    }
+
+    #[test]
+    fn null_policy() {
+        let schema_json = |null_policy: &str| {
+            json!({
+                "items": {
+                    "port": {"schema": {"type": "port"}, "default": 8080},
+                    "host": {"schema": {"type": "string"}},
+                },
+                "null_policy": null_policy,
+            })
+        };
+        let validate = |null_policy: &str, input: serde_json::Value| {
+            let schema: InputSchemaTypeStaticMap =
+                serde_json::from_value(schema_json(null_policy)).unwrap();
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            schema.validate(&mut input, None).map(|_| input)
+        };
+        let null_port = json!({"host": "localhost", "port": null});
+        assert!(validate("validate", null_port.clone()).is_err());
+        assert_eq!(
+            validate("as_missing", null_port.clone()).unwrap(),
+            serde_json::from_value::<Input>(json!({"host": "localhost", "port": 8080})).unwrap()
+        );
+        assert_eq!(
+            validate("allow_if_optional", null_port.clone()).unwrap(),
+            serde_json::from_value::<Input>(null_port.clone()).unwrap()
+        );
+        let error = validate("forbid", null_port).unwrap_err();
+        assert_eq!(error.position().to_string(), "port");
+        let null_host = json!({"host": null});
+        assert!(matches!(
+            validate("as_missing", null_host.clone()),
+            Err(InputSchemaError::NotFound { .. })
+        ));
+        assert!(validate("allow_if_optional", null_host).is_err());
+        let schema: InputSchemaTypeStaticMap =
+            serde_json::from_value(schema_json("forbid")).unwrap();
+        assert_eq!(
+            serde_json::to_value(&schema).unwrap()["null_policy"],
+            json!("forbid")
+        );
+    }
 }
@@ -88,6 +88,25 @@ impl InputSchemaTypeString {
                 }
             }
         }
+        if let Some(regex) = &self.maybe_regex {
+            let is_match = regex
+                .compile()
+                .map_err(|error| InputSchemaError::Internal {
+                    position: maybe_position.clone().unwrap_or_default(),
+                    description: format!(
+                        "Invalid regular expression `{}`: {error}",
+                        regex.pattern()
+                    ),
+                })?
+                .is_match(input.as_str());
+            if !is_match {
+                return Err(InputSchemaError::Schema {
+                    position: maybe_position.unwrap_or_default(),
+                    schema_type: InputSchemaType::String(self.clone()),
+                    input: input.clone(),
+                });
+            }
+        }
         Ok(())
     }
 }
@@ -0,0 +1,164 @@
+use crate::{
+    position::InputPosition,
+    schema::{InputSchemaError, InputSchemaType},
+    Input,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+// Kill-switches that record why something was turned off: a boolean (including strings like
+// `"no"`), another string (the reason, which turns the switch off), or the normalized
+// `{"enabled": ..., "reason": ...}` map. With `require_reason`, turning the switch off requires a
+// reason:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeSwitch {
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    require_reason: bool,
+}
+
+impl InputSchemaTypeSwitch {
+    pub fn require_reason(&self) -> bool {
+        self.require_reason
+    }
+
+    pub fn set_require_reason(&mut self, require_reason: bool) {
+        self.require_reason = require_reason;
+    }
+
+    pub fn with_require_reason(mut self, require_reason: bool) -> Self {
+        self.set_require_reason(require_reason);
+        self
+    }
+}
+
+impl InputSchemaTypeSwitch {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        let position = maybe_position.unwrap_or_default();
+        let mut enabled = input.clone();
+        let (enabled, maybe_reason) = if InputSchemaType::new_boolean()
+            .validate(&mut enabled, None)
+            .is_ok()
+        {
+            (*enabled.as_bool(), None)
+        } else {
+            match input {
+                Input::Str(reason) => (false, Some(reason.clone())),
+                Input::Map(map) => {
+                    if let Some(key) = map
+                        .keys()
+                        .map(|key| &**key)
+                        .find(|key| *key != "enabled" && *key != "reason")
+                    {
+                        return Err(InputSchemaError::Invalid {
+                            description: format!(
+                                "Unknown key `{key}` (expected `enabled` and `reason`)"
+                            ),
+                            position,
+                            input: input.clone(),
+                        });
+                    }
+                    let mut enabled =
+                        map.get("enabled")
+                            .cloned()
+                            .ok_or_else(|| InputSchemaError::NotFound {
+                                position: position.new_with_key("enabled"),
+                                schema_type: InputSchemaType::new_boolean(),
+                            })?;
+                    InputSchemaType::new_boolean()
+                        .validate(&mut enabled, Some(position.new_with_key("enabled")))?;
+                    let maybe_reason = match map.get("reason") {
+                        Some(Input::Str(reason)) => Some(reason.clone()),
+                        Some(reason) => {
+                            return Err(InputSchemaError::Type {
+                                position: position.new_with_key("reason"),
+                                expected_type: Input::str_type_name(),
+                                input_type: reason.type_name(),
+                            })
+                        }
+                        None => None,
+                    };
+                    (*enabled.as_bool(), maybe_reason)
+                }
+                _ => {
+                    return Err(InputSchemaError::Type {
+                        position,
+                        expected_type: Input::bool_type_name(),
+                        input_type: input.type_name(),
+                    })
+                }
+            }
+        };
+        let maybe_reason = maybe_reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty());
+        if self.require_reason && !enabled && maybe_reason.is_none() {
+            return Err(InputSchemaError::Invalid {
+                description: "Expected a reason for turning off the switch".to_string(),
+                position,
+                input: input.clone(),
+            });
+        }
+        let mut map = HashMap::from([("enabled".to_string(), Input::from(enabled))]);
+        if let Some(reason) = maybe_reason {
+            map.insert("reason".to_string(), Input::from(reason));
+        }
+        *input = Input::from(map);
+        Ok(())
+    }
+}
+
+impl Display for InputSchemaTypeSwitch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.require_reason {
+            "switch (a boolean, or a reason for turning it off which is required)"
+        } else {
+            "switch (a boolean, or a reason for turning it off)"
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate() {
+        let schema = InputSchemaTypeSwitch::default();
+        for (input, expected) in [
+            (json!(true), json!({"enabled": true})),
+            (json!("no"), json!({"enabled": false})),
+            (
+                json!("Broken upstream, see INC-42"),
+                json!({"enabled": false, "reason": "Broken upstream, see INC-42"}),
+            ),
+            (
+                json!({"enabled": true, "reason": "Fixed"}),
+                json!({"enabled": true, "reason": "Fixed"}),
+            ),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            schema.validate(&mut input, None).unwrap();
+            assert_eq!(input, serde_json::from_value::<Input>(expected).unwrap());
+        }
+
+        let schema = schema.with_require_reason(true);
+        for input in [
+            json!(false),
+            json!({"enabled": false, "reason": " "}),
+            json!({"reason": "x"}),
+            json!(1),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            assert!(schema.validate(&mut input, None).is_err());
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{position::InputPosition, schema::InputSchemaError, Input};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// `"HH:MM-HH:MM"` strings (e.g. `"22:00-06:00"` for a maintenance window) or lists of them. A window
+// whose end is before its start wraps around midnight. Times are normalized to two-digit hours
+// (e.g. `"9:00-17:00"` to `"09:00-17:00"`):
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeTimeWindow {}
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+impl InputSchemaTypeTimeWindow {
+    // Whether a validated input (a window or a list of windows) contains the minute of the day
+    // (`0..1440`). The start is inclusive and the end is exclusive:
+    pub fn contains(input: &Input, minute_of_day: u16) -> bool {
+        let minute_of_day = minute_of_day % MINUTES_PER_DAY;
+        match input {
+            Input::Str(window) => parse(window).is_some_and(|(start, end)| {
+                if start < end {
+                    (start..end).contains(&minute_of_day)
+                } else {
+                    minute_of_day >= start || minute_of_day < end
+                }
+            }),
+            Input::List(list) => list
+                .iter()
+                .any(|window| Self::contains(window, minute_of_day)),
+            _ => false,
+        }
+    }
+}
+
+impl InputSchemaTypeTimeWindow {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        let position = maybe_position.unwrap_or_default();
+        if let Input::List(list) = input {
+            return list.iter_mut().enumerate().try_for_each(|(index, window)| {
+                validate_window(window, position.new_with_index(index))
+            });
+        }
+        validate_window(input, position)
+    }
+}
+
+fn validate_window(input: &mut Input, position: InputPosition) -> Result<(), InputSchemaError> {
+    if !input.is_str() {
+        return Err(InputSchemaError::Type {
+            position,
+            expected_type: Input::str_type_name(),
+            input_type: input.type_name(),
+        });
+    }
+    let (start, end) = parse(input.as_str()).ok_or_else(|| InputSchemaError::Invalid {
+        description: "Expected `HH:MM-HH:MM` (e.g. `22:00-06:00`)".to_string(),
+        position: position.clone(),
+        input: input.clone(),
+    })?;
+    if start == end {
+        return Err(InputSchemaError::Invalid {
+            description: "Expected the start and end of the window to be different".to_string(),
+            position,
+            input: input.clone(),
+        });
+    }
+    *input = Input::from(format!(
+        "{:02}:{:02}-{:02}:{:02}",
+        start / 60,
+        start % 60,
+        end / 60,
+        end % 60
+    ));
+    Ok(())
+}
+
+// Start and end minutes of the day:
+fn parse(window: &str) -> Option<(u16, u16)> {
+    let (start, end) = window.split_once('-')?;
+    Some((parse_time(start)?, parse_time(end)?))
+}
+
+// `24:00` is accepted as the end of the day:
+fn parse_time(time: &str) -> Option<u16> {
+    let (hour, minute) = time.trim().split_once(':')?;
+    if hour.is_empty() || hour.len() > 2 || minute.len() != 2 {
+        return None;
+    }
+    let hour = hour.parse::<u16>().ok()?;
+    let minute = minute.parse::<u16>().ok()?;
+    match (hour, minute) {
+        (24, 0) => Some(0),
+        (0..=23, 0..=59) => Some(hour * 60 + minute),
+        _ => None,
+    }
+}
+
+impl Display for InputSchemaTypeTimeWindow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("time window (`HH:MM-HH:MM`) or list of time windows")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate() {
+        let schema = InputSchemaTypeTimeWindow::default();
+        let mut input: Input =
+            serde_json::from_value(json!(["9:00-17:30", "22:00-06:00"])).unwrap();
+        schema.validate(&mut input, None).unwrap();
+        assert_eq!(
+            input,
+            serde_json::from_value::<Input>(json!(["09:00-17:30", "22:00-06:00"])).unwrap()
+        );
+        assert!(InputSchemaTypeTimeWindow::contains(&input, 10 * 60));
+        assert!(InputSchemaTypeTimeWindow::contains(&input, 23 * 60));
+        assert!(InputSchemaTypeTimeWindow::contains(&input, 60));
+        assert!(!InputSchemaTypeTimeWindow::contains(&input, 20 * 60));
+        assert!(!InputSchemaTypeTimeWindow::contains(&input, 17 * 60 + 30));
+
+        for (input, error_position) in [
+            (json!("25:00-06:00"), ""),
+            (json!("10:00-10:00"), ""),
+            (json!("22:00"), ""),
+            (json!(["22:00-06:00", "9-17"]), "1"),
+            (json!(2200), ""),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            let error = schema.validate(&mut input, None).unwrap_err();
+            assert_eq!(error.position().to_string(), error_position);
+        }
+    }
+}
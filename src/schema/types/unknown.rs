@@ -0,0 +1,153 @@
+use crate::{
+    deserialize::InputDeserializer,
+    position::InputPosition,
+    schema::{InputSchemaError, InputSchemaType},
+    Input,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    cell::Cell,
+    fmt::{Display, Formatter},
+};
+
+// A schema type that this version doesn't know (e.g. from a schema written for a newer version)
+// which was deserialized inside `with_unknown_types()`. It's kept as it was, so it's serialized
+// back unchanged, but validating anything with it fails:
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct InputSchemaTypeUnknown {
+    type_name: String,
+    raw: Input,
+}
+
+thread_local! {
+    static UNKNOWN_TYPES: Cell<bool> = const { Cell::new(false) };
+}
+
+// Runs `f` (e.g. `|| serde_json::from_str::<InputSchema>(text)`) so that unknown schema types
+// deserialize to `InputSchemaType::Unknown` instead of failing the whole schema:
+pub fn with_unknown_types<T, F: FnOnce() -> T>(f: F) -> T {
+    struct Guard(bool);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            UNKNOWN_TYPES.set(self.0)
+        }
+    }
+    let _guard = Guard(UNKNOWN_TYPES.replace(true));
+    f()
+}
+
+impl InputSchemaTypeUnknown {
+    pub fn type_name(&self) -> &String {
+        &self.type_name
+    }
+
+    // The whole schema map including its `type`:
+    pub fn raw(&self) -> &Input {
+        &self.raw
+    }
+}
+
+impl InputSchemaTypeUnknown {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        Err(InputSchemaError::Invalid {
+            description: format!(
+                "Could not validate with unknown schema type `{}` (it may need a newer version)",
+                self.type_name
+            ),
+            position: maybe_position.unwrap_or_default(),
+            input: input.clone(),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for InputSchemaType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !UNKNOWN_TYPES.get() {
+            return InputSchemaType::deserialize(deserializer);
+        }
+        let raw = Input::deserialize(deserializer)?;
+        let maybe_type_name = match &raw {
+            Input::Map(map) => match map.get("type") {
+                Some(Input::Str(type_name)) => Some(type_name.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        // Serde reports unknown tags with this message (other errors are kept):
+        InputSchemaType::deserialize(InputDeserializer::new(&raw, InputPosition::new())).or_else(
+            |error| match maybe_type_name {
+                Some(type_name)
+                    if error
+                        .description()
+                        .starts_with(format!("unknown variant `{type_name}`").as_str()) =>
+                {
+                    Ok(Self::Unknown(InputSchemaTypeUnknown { type_name, raw }))
+                }
+                _ => Err(de::Error::custom(error)),
+            },
+        )
+    }
+}
+
+impl Serialize for InputSchemaType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Unknown(unknown) => unknown.raw.serialize(serializer),
+            _ => InputSchemaType::serialize(self, serializer),
+        }
+    }
+}
+
+impl Display for InputSchemaTypeUnknown {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(format!("unknown schema type `{}`", self.type_name).as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::InputSchema;
+    use serde_json::json;
+
+    #[test]
+    fn with_unknown_types() {
+        let value = json!({
+            "schema": {"type": "static_map", "items": {
+                "port": {"schema": {"type": "port"}},
+                "token": {"schema": {"type": "jwt", "issuer": "example.com"}},
+            }}
+        });
+        assert!(serde_json::from_value::<InputSchema>(value.clone()).is_err());
+        let schema: InputSchema =
+            super::with_unknown_types(|| serde_json::from_value(value.clone())).unwrap();
+        let token_schema = schema.schema_type().as_static_map().items()["token"].schema_type();
+        assert_eq!(token_schema.as_unknown().type_name(), "jwt");
+        assert_eq!(
+            serde_json::to_value(token_schema).unwrap(),
+            value["schema"]["items"]["token"]["schema"]
+        );
+        let mut input: Input = serde_json::from_value(json!({"port": 80, "token": "x"})).unwrap();
+        assert_eq!(
+            schema
+                .schema_type()
+                .validate(&mut input, None)
+                .unwrap_err()
+                .position()
+                .to_string(),
+            "token"
+        );
+
+        // Errors of known types are kept:
+        assert!(
+            super::with_unknown_types(|| serde_json::from_value::<InputSchemaType>(
+                json!({"type": "port", "end": 1})
+            ))
+            .is_err()
+        );
+    }
+}
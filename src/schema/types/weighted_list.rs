@@ -0,0 +1,199 @@
+use crate::{
+    position::InputPosition,
+    schema::{suggest::did_you_mean, InputSchemaError, InputSchemaType},
+    Input,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// A list of `{"value": ..., "weight": ...}` maps (e.g. load-balancing backends or sampling rates).
+// Every weight should be positive and, with `weight_sum` (e.g. `1.0` or `100.0`), the weights
+// should add up to it. Weights are normalized to floats:
+#[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSchemaTypeWeightedList {
+    #[serde(default)]
+    value_schema: Box<InputSchemaType>,
+    #[serde(
+        rename = "weight_sum",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    maybe_weight_sum: Option<f64>,
+}
+
+// Floats rarely add up exactly (e.g. `0.1 + 0.2`):
+const WEIGHT_SUM_TOLERANCE: f64 = 1e-9;
+
+impl InputSchemaTypeWeightedList {
+    pub fn value_schema(&self) -> &InputSchemaType {
+        &self.value_schema
+    }
+
+    pub fn value_schema_mut(&mut self) -> &mut InputSchemaType {
+        &mut self.value_schema
+    }
+
+    pub fn set_value_schema<S: Into<InputSchemaType>>(&mut self, schema: S) {
+        *self.value_schema_mut() = schema.into();
+    }
+
+    pub fn with_value_schema<S: Into<InputSchemaType>>(mut self, schema: S) -> Self {
+        self.set_value_schema(schema);
+        self
+    }
+
+    pub fn maybe_weight_sum(&self) -> Option<f64> {
+        self.maybe_weight_sum
+    }
+
+    pub fn maybe_weight_sum_mut(&mut self) -> &mut Option<f64> {
+        &mut self.maybe_weight_sum
+    }
+
+    pub fn set_weight_sum(&mut self, weight_sum: f64) {
+        *self.maybe_weight_sum_mut() = Some(weight_sum);
+    }
+
+    pub fn with_weight_sum(mut self, weight_sum: f64) -> Self {
+        self.set_weight_sum(weight_sum);
+        self
+    }
+}
+
+impl InputSchemaTypeWeightedList {
+    pub fn validate(
+        &self,
+        input: &mut Input,
+        maybe_position: Option<InputPosition>,
+    ) -> Result<(), InputSchemaError> {
+        let position = maybe_position.unwrap_or_default();
+        if !input.is_list() {
+            return Err(InputSchemaError::Type {
+                position,
+                expected_type: Input::list_type_name(),
+                input_type: input.type_name(),
+            });
+        };
+        let mut weight_sum = 0.0;
+        for (index, item) in input.list_mut().iter_mut().enumerate() {
+            let item_position = position.new_with_index(index);
+            if !item.is_map() {
+                return Err(InputSchemaError::Type {
+                    position: item_position,
+                    expected_type: Input::map_type_name(),
+                    input_type: item.type_name(),
+                });
+            }
+            if let Some(key) = item
+                .as_map()
+                .keys()
+                .map(|key| &**key)
+                .find(|key| *key != "value" && *key != "weight")
+            {
+                return Err(InputSchemaError::Invalid {
+                    description: format!(
+                        "Unknown key `{key}` (expected `value` and `weight`){}",
+                        did_you_mean(key, ["value", "weight"])
+                    ),
+                    position: item_position,
+                    input: item.clone(),
+                });
+            }
+            let map = item.map_mut();
+            let value_position = item_position.new_with_key("value");
+            let value = map
+                .get_mut("value")
+                .ok_or_else(|| InputSchemaError::NotFound {
+                    position: value_position.clone(),
+                    schema_type: self.value_schema().clone(),
+                })?;
+            self.value_schema().validate(value, Some(value_position))?;
+            let weight_position = item_position.new_with_key("weight");
+            let weight = map
+                .get_mut("weight")
+                .ok_or_else(|| InputSchemaError::NotFound {
+                    position: weight_position.clone(),
+                    schema_type: InputSchemaType::new_float(),
+                })?;
+            InputSchemaType::new_float().validate(weight, Some(weight_position.clone()))?;
+            if !(*weight.as_float() > 0.0 && weight.as_float().is_finite()) {
+                return Err(InputSchemaError::Invalid {
+                    description: "Expected a positive weight".to_string(),
+                    position: weight_position,
+                    input: weight.clone(),
+                });
+            }
+            weight_sum += *weight.as_float();
+        }
+        if let Some(expected_weight_sum) = self.maybe_weight_sum {
+            if (weight_sum - expected_weight_sum).abs()
+                > WEIGHT_SUM_TOLERANCE * expected_weight_sum.abs().max(1.0)
+            {
+                return Err(InputSchemaError::Invalid {
+                    description: format!(
+                        "Expected weights to add up to {expected_weight_sum}, got {weight_sum}"
+                    ),
+                    position,
+                    input: input.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for InputSchemaTypeWeightedList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            format!(
+                "list of maps with a `value` that should be {} and a positive `weight`{}",
+                self.value_schema,
+                if let Some(weight_sum) = self.maybe_weight_sum {
+                    format!(" (weights should add up to {weight_sum})")
+                } else {
+                    String::new()
+                }
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate() {
+        let schema = InputSchemaTypeWeightedList::default()
+            .with_value_schema(InputSchemaType::new_socket_address())
+            .with_weight_sum(1.0);
+        let mut input: Input = serde_json::from_value(json!([
+            {"value": "127.0.0.1:8080", "weight": 0.1},
+            {"value": "127.0.0.1:8081", "weight": "0.2"},
+            {"value": "127.0.0.1:8082", "weight": 0.7},
+        ]))
+        .unwrap();
+        schema.validate(&mut input, None).unwrap();
+        assert_eq!(input.as_list()[1].as_map()["weight"], Input::from(0.2));
+
+        for (input, error_position) in [
+            (json!([{"value": "127.0.0.1:8080", "weight": 0.5}]), ""),
+            (
+                json!([{"value": "127.0.0.1:8080", "weight": 0}, {"value": "127.0.0.1:8081", "weight": 1}]),
+                "[0][weight]",
+            ),
+            (json!([{"weight": 1}]), "[0][value]"),
+            (
+                json!([{"value": "127.0.0.1:8080", "weight": 1, "name": "a"}]),
+                "0",
+            ),
+        ] {
+            let mut input: Input = serde_json::from_value(input).unwrap();
+            let error = schema.validate(&mut input, None).unwrap_err();
+            assert_eq!(error.position().to_string(), error_position);
+        }
+    }
+}
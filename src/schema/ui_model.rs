@@ -0,0 +1,267 @@
+use crate::{
+    schema::{InputSchema, InputSchemaType, InputSchemaTypeNumberValue, InputSchemaTypeRange},
+    Input,
+};
+use serde::Serialize;
+
+// A form for editing values of a schema, e.g. for web frontends that render config editors from
+// plugin schemas. It's serialized as a tree of `{"widget": ..., ...}` maps:
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UiModel {
+    #[serde(rename = "title", skip_serializing_if = "Option::is_none")]
+    maybe_title: Option<String>,
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    maybe_description: Option<String>,
+    #[serde(rename = "default", skip_serializing_if = "Option::is_none")]
+    maybe_default: Option<Input>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    required: bool,
+    #[serde(flatten)]
+    widget: UiWidget,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "widget", rename_all = "snake_case")]
+pub enum UiWidget {
+    Text {
+        // Answers should not be echoed:
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        secret: bool,
+        #[serde(rename = "pattern", skip_serializing_if = "Option::is_none")]
+        maybe_pattern: Option<String>,
+        // What the text should be for types other than `string` (e.g. `IP address`):
+        #[serde(rename = "hint", skip_serializing_if = "Option::is_none")]
+        maybe_hint: Option<String>,
+    },
+    Number {
+        integer: bool,
+        #[serde(rename = "min", skip_serializing_if = "Option::is_none")]
+        maybe_min: Option<Input>,
+        #[serde(rename = "max", skip_serializing_if = "Option::is_none")]
+        maybe_max: Option<Input>,
+    },
+    Select {
+        #[serde(rename = "options")]
+        option_list: Vec<Input>,
+    },
+    Toggle,
+    FilePicker {
+        directory: bool,
+    },
+    // Static maps, in the order of `ordered_items()`:
+    Group {
+        #[serde(rename = "fields")]
+        field_list: Vec<UiField>,
+    },
+    List {
+        item: Box<UiModel>,
+    },
+    // Maps with any keys (the values are `item`):
+    Map {
+        item: Box<UiModel>,
+    },
+    // One of `either`:
+    Choice {
+        #[serde(rename = "alternatives")]
+        alternative_list: Vec<UiModel>,
+    },
+    // A raw (e.g. JSON) editor for everything else (e.g. `any` or references):
+    Raw,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UiField {
+    key: String,
+    #[serde(flatten)]
+    model: UiModel,
+}
+
+impl UiModel {
+    pub fn maybe_title(&self) -> Option<&String> {
+        self.maybe_title.as_ref()
+    }
+
+    pub fn maybe_description(&self) -> Option<&String> {
+        self.maybe_description.as_ref()
+    }
+
+    pub fn maybe_default(&self) -> Option<&Input> {
+        self.maybe_default.as_ref()
+    }
+
+    // Static map items without defaults:
+    pub fn required(&self) -> bool {
+        self.required
+    }
+
+    pub fn widget(&self) -> &UiWidget {
+        &self.widget
+    }
+}
+
+impl UiField {
+    pub fn key(&self) -> &String {
+        &self.key
+    }
+
+    pub fn model(&self) -> &UiModel {
+        &self.model
+    }
+}
+
+pub fn ui_model(schema_type: &InputSchemaType) -> UiModel {
+    model(schema_type, None)
+}
+
+fn model(schema_type: &InputSchemaType, maybe_schema: Option<&InputSchema>) -> UiModel {
+    UiModel {
+        maybe_title: maybe_schema.and_then(|schema| schema.maybe_title().cloned()),
+        maybe_description: maybe_schema.and_then(|schema| schema.maybe_description().cloned()),
+        maybe_default: maybe_schema.and_then(|schema| schema.maybe_default().cloned()),
+        required: maybe_schema.is_some_and(|schema| schema.maybe_default().is_none()),
+        widget: crate::stack::maybe_grow(|| widget(schema_type)),
+    }
+}
+
+fn widget(schema_type: &InputSchemaType) -> UiWidget {
+    match schema_type {
+        InputSchemaType::Boolean(_) => UiWidget::Toggle,
+        InputSchemaType::Integer(integer) => number(true, integer.maybe_range()),
+        InputSchemaType::Number(number_type) => number(false, number_type.maybe_range()),
+        InputSchemaType::Float(float) => number(false, float.maybe_range()),
+        InputSchemaType::Port(port) => UiWidget::Number {
+            integer: true,
+            maybe_min: Some((port.start() as isize).into()),
+            maybe_max: Some((u16::MAX as isize).into()),
+        },
+        InputSchemaType::String(string) => UiWidget::Text {
+            secret: false,
+            maybe_pattern: string
+                .maybe_regex()
+                .map(|regex| regex.pattern().as_str().to_string()),
+            maybe_hint: None,
+        },
+        InputSchemaType::Enum(r#enum) => UiWidget::Select {
+            option_list: r#enum.value_list().clone(),
+        },
+        InputSchemaType::LogLevel(_) => select(&["error", "warn", "info", "debug", "trace"]),
+        InputSchemaType::LogLevelFilter(_) => {
+            select(&["off", "error", "warn", "info", "debug", "trace"])
+        }
+        InputSchemaType::Fs(fs) => UiWidget::FilePicker {
+            directory: fs
+                .maybe_path_type()
+                .is_some_and(|path_type| path_type.is_directory()),
+        },
+        InputSchemaType::Secret(secret) => match widget(secret.inner_schema()) {
+            UiWidget::Text {
+                maybe_pattern,
+                maybe_hint,
+                ..
+            } => UiWidget::Text {
+                secret: true,
+                maybe_pattern,
+                maybe_hint,
+            },
+            widget => widget,
+        },
+        InputSchemaType::List(list) => UiWidget::List {
+            item: Box::new(ui_model(list.item_schema())),
+        },
+        InputSchemaType::DynamicMap(dynamic_map) => UiWidget::Map {
+            item: Box::new(ui_model(dynamic_map.item_schema())),
+        },
+        InputSchemaType::KeyValue(key_value) => UiWidget::Map {
+            item: Box::new(ui_model(key_value.value_schema())),
+        },
+        InputSchemaType::StaticMap(static_map) => UiWidget::Group {
+            field_list: static_map
+                .ordered_items()
+                .into_iter()
+                .map(|(key, schema)| UiField {
+                    key: key.clone(),
+                    model: model(schema.schema_type(), Some(schema)),
+                })
+                .collect(),
+        },
+        InputSchemaType::Either(either) => UiWidget::Choice {
+            alternative_list: either.schema_list().iter().map(ui_model).collect(),
+        },
+        InputSchemaType::Recursive(recursive) => widget(recursive.inner_schema()),
+        // Other types that validate strings (e.g. IP addresses) get a text with a hint:
+        _ if schema_type.to_json_schema().as_map().get("type") == Some(&"string".into()) => {
+            UiWidget::Text {
+                secret: false,
+                maybe_pattern: None,
+                maybe_hint: Some(schema_type.to_string()),
+            }
+        }
+        _ => UiWidget::Raw,
+    }
+}
+
+fn number(integer: bool, maybe_range: Option<&InputSchemaTypeRange>) -> UiWidget {
+    let to_input = |value| match value {
+        InputSchemaTypeNumberValue::Integer(integer) => Input::from(integer),
+        InputSchemaTypeNumberValue::Float(float) => Input::from(float),
+    };
+    UiWidget::Number {
+        integer,
+        maybe_min: maybe_range
+            .and_then(|range| range.maybe_min())
+            .map(to_input),
+        maybe_max: maybe_range
+            .and_then(|range| range.maybe_max())
+            .map(to_input),
+    }
+}
+
+fn select(option_list: &[&str]) -> UiWidget {
+    UiWidget::Select {
+        option_list: option_list
+            .iter()
+            .map(|option| Input::from(*option))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn ui_model() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "port": {"schema": {"type": "port", "start": 1024}, "default": 8080, "title": "Port"},
+                "level": {"schema": {"type": "enum", "items": ["info", "debug"]}},
+                "password": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                "root": {"schema": {"type": "fs", "access": "rw"}},
+                "hosts": {"schema": {"type": "list", "item_schema": {"type": "ip"}}},
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(super::ui_model(&schema_type)).unwrap(),
+            json!({"widget": "group", "fields": [
+                {"key": "hosts", "required": true, "widget": "list", "item": {
+                    "widget": "text", "hint": "IP address"
+                }},
+                {"key": "level", "required": true, "widget": "select", "options": ["info", "debug"]},
+                {"key": "password", "required": true, "widget": "text", "secret": true},
+                {
+                    "key": "port",
+                    "title": "Port",
+                    "default": 8080,
+                    "widget": "number",
+                    "integer": true,
+                    "min": 1024,
+                    "max": 65535
+                },
+                {"key": "root", "required": true, "widget": "file_picker", "directory": false},
+            ]})
+        );
+    }
+}
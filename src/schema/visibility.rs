@@ -0,0 +1,159 @@
+use crate::{schema::InputSchemaType, Input};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// Who may see a value. Items inherit the visibility of their parent item and values of `secret`
+// schemas are `Secret`:
+#[derive(Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Deserialize, Serialize)]
+#[serde(
+    rename_all = "snake_case",
+    deny_unknown_fields,
+    expecting = "Expecting visibility `public`, `internal`, or `secret`"
+)]
+pub enum InputSchemaVisibility {
+    // e.g. a UI:
+    #[default]
+    Public,
+    // e.g. logs and the plugin itself:
+    Internal,
+    // Only the owner of the secret:
+    Secret,
+}
+
+impl Input {
+    // A copy without the values that are not visible at `level` (e.g. a `Public` view of a
+    // document for a UI). Keys that are not declared by the schema are kept:
+    pub fn filter_visibility(
+        &self,
+        level: InputSchemaVisibility,
+        schema_type: &InputSchemaType,
+    ) -> Input {
+        let mut input = self.clone();
+        filter_visibility(&mut input, level, schema_type);
+        input
+    }
+}
+
+fn filter_visibility(
+    input: &mut Input,
+    level: InputSchemaVisibility,
+    schema_type: &InputSchemaType,
+) {
+    crate::stack::maybe_grow(|| match (schema_type, input) {
+        (InputSchemaType::StaticMap(static_map), Input::Map(map)) => {
+            map.retain(|key, inner_input| match static_map.item(key) {
+                Some((_, schema)) if schema.visibility() > level => false,
+                Some((_, schema)) => {
+                    filter_visibility(inner_input, level, schema.schema_type());
+                    true
+                }
+                None => true,
+            })
+        }
+        (InputSchemaType::DynamicMap(dynamic_map), Input::Map(map)) => {
+            filter_map(map, level, dynamic_map.item_schema())
+        }
+        (InputSchemaType::KeyValue(key_value), Input::Map(map)) => {
+            filter_map(map, level, key_value.value_schema())
+        }
+        (InputSchemaType::List(list), Input::List(inner_input_list)) => {
+            if is_secret(list.item_schema()) && level < InputSchemaVisibility::Secret {
+                inner_input_list.clear()
+            } else {
+                inner_input_list.iter_mut().for_each(|inner_input| {
+                    filter_visibility(inner_input, level, list.item_schema())
+                })
+            }
+        }
+        (InputSchemaType::Secret(secret), input) => {
+            filter_visibility(input, level, secret.inner_schema())
+        }
+        _ => (),
+    })
+}
+
+fn filter_map(
+    map: &mut crate::InputMap,
+    level: InputSchemaVisibility,
+    item_schema_type: &InputSchemaType,
+) {
+    if is_secret(item_schema_type) && level < InputSchemaVisibility::Secret {
+        map.clear()
+    } else {
+        map.values_mut()
+            .for_each(|inner_input| filter_visibility(inner_input, level, item_schema_type))
+    }
+}
+
+fn is_secret(schema_type: &InputSchemaType) -> bool {
+    matches!(schema_type, InputSchemaType::Secret(_))
+}
+
+impl Display for InputSchemaVisibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Public => "public",
+            Self::Internal => "internal",
+            Self::Secret => "secret",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn filter_visibility() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {
+                "name": {"schema": {"type": "string"}},
+                "debug": {"schema": {"type": "static_map", "items": {
+                    "trace": {"schema": {"type": "boolean"}},
+                }}, "visibility": "internal"},
+                "password": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+                "tokens": {"schema": {"type": "dynamic_map", "item_schema": {"type": "secret", "schema": {"type": "string"}}}},
+            }
+        }))
+        .unwrap();
+        let input: Input = serde_json::from_value(json!({
+            "name": "app",
+            "debug": {"trace": true},
+            "password": "hunter2",
+            "tokens": {"github": "x"},
+            "extra": 1,
+        }))
+        .unwrap();
+        assert_eq!(
+            input.filter_visibility(InputSchemaVisibility::Public, &schema_type),
+            serde_json::from_value::<Input>(json!({"name": "app", "tokens": {}, "extra": 1}))
+                .unwrap()
+        );
+        let internal_input = input.filter_visibility(InputSchemaVisibility::Internal, &schema_type);
+        assert!(internal_input.as_map().contains_key("debug"));
+        assert!(!internal_input.as_map().contains_key("password"));
+        assert_eq!(
+            input.filter_visibility(InputSchemaVisibility::Secret, &schema_type),
+            input
+        );
+    }
+
+    #[test]
+    fn filter_visibility_case_insensitive_keys() {
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "case_insensitive_keys": true,
+            "items": {
+                "password": {"schema": {"type": "secret", "schema": {"type": "string"}}},
+            }
+        }))
+        .unwrap();
+        let input: Input = serde_json::from_value(json!({"PASSWORD": "hunter2"})).unwrap();
+        assert!(input
+            .filter_visibility(InputSchemaVisibility::Public, &schema_type)
+            .as_map()
+            .is_empty());
+    }
+}
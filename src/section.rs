@@ -0,0 +1,280 @@
+#[cfg(feature = "schema")]
+use crate::schema::InputSchemaError;
+use crate::{
+    position::{InputPosition, InputPositionPattern, InputPositionType},
+    Input,
+};
+use std::collections::HashMap;
+use thiserror::Error;
+
+// Owns a section that is handed to a plugin and remembers where it came from:
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputSectionGuard {
+    position: InputPosition,
+    input: Input,
+}
+
+// Both variants give the section back so that it is not lost:
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InputSectionError {
+    #[error("{position} is not the position of a section")]
+    Position {
+        position: InputPosition,
+        section: Input,
+    },
+    #[error("Could not put section `{key}` into {input_type} (expected a map)")]
+    NotMap {
+        key: String,
+        input_type: String,
+        section: Input,
+    },
+}
+
+impl InputSectionError {
+    pub fn into_section(self) -> Input {
+        match self {
+            Self::Position { section, .. } | Self::NotMap { section, .. } => section,
+        }
+    }
+}
+
+impl InputSectionGuard {
+    pub fn new<I: Into<Input>>(position: InputPosition, input: I) -> Self {
+        Self {
+            position,
+            input: input.into(),
+        }
+    }
+
+    pub fn position(&self) -> &InputPosition {
+        &self.position
+    }
+
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    pub fn input_mut(&mut self) -> &mut Input {
+        &mut self.input
+    }
+
+    pub fn into_input(self) -> Input {
+        self.input
+    }
+
+    pub fn take_section(&mut self, key: &str) -> Option<InputSectionGuard> {
+        self.input
+            .take_section(key)
+            .map(|input| Self::new(self.position.new_with_key(key), input))
+    }
+
+    pub fn reanchor(&self, position: &InputPosition) -> InputPosition {
+        self.position.new_with_position(position)
+    }
+
+    #[cfg(feature = "schema")]
+    pub fn reanchor_error(&self, mut error: InputSchemaError) -> InputSchemaError {
+        *error.position_mut() = self.reanchor(error.position());
+        error
+    }
+
+    // `input` should be the parent of this section. Returns the replaced section, if any:
+    pub fn put_back(self, input: &mut Input) -> Result<Option<Input>, InputSectionError> {
+        if let Some(InputPositionType::Key(key)) = self.position.last() {
+            let key = key.clone();
+            input.put_section(key, self.input)
+        } else {
+            Err(InputSectionError::Position {
+                position: self.position,
+                section: self.input,
+            })
+        }
+    }
+}
+
+impl Input {
+    pub fn take_section(&mut self, key: &str) -> Option<Input> {
+        if let Self::Map(map) = self {
+            map.remove(key)
+        } else {
+            None
+        }
+    }
+
+    // Returns the replaced section, if any:
+    pub fn put_section<K: ToString>(
+        &mut self,
+        key: K,
+        section: Input,
+    ) -> Result<Option<Input>, InputSectionError> {
+        if let Self::Map(map) = self {
+            Ok(map.insert(crate::intern_key(key.to_string()), section))
+        } else {
+            Err(InputSectionError::NotMap {
+                key: key.to_string(),
+                input_type: self.type_name(),
+                section,
+            })
+        }
+    }
+
+    pub fn take_section_guard(&mut self, key: &str) -> Option<InputSectionGuard> {
+        self.take_section(key)
+            .map(|input| InputSectionGuard::new(InputPosition::new().new_with_key(key), input))
+    }
+
+    // A copy of the values at positions that match any of the patterns, inside their parent maps
+    // and lists (e.g. `plugins.http.**` keeps `{"plugins": {"http": ...}}`). Kept list items are
+    // not padded, so their indexes may change. Nothing matching is an empty map:
+    pub fn slice(&self, pattern_list: &[InputPositionPattern]) -> Input {
+        slice(self, InputPosition::new(), pattern_list).unwrap_or_else(Input::new_map)
+    }
+}
+
+fn slice(
+    input: &Input,
+    position: InputPosition,
+    pattern_list: &[InputPositionPattern],
+) -> Option<Input> {
+    crate::stack::maybe_grow(|| {
+        if pattern_list
+            .iter()
+            .any(|pattern| pattern.matches(&position))
+        {
+            return Some(input.clone());
+        }
+        if !pattern_list
+            .iter()
+            .any(|pattern| pattern.overlaps(&position))
+        {
+            return None;
+        }
+        let inner_input = match input {
+            Input::Map(map) => Input::from(
+                map.iter()
+                    .filter_map(|(key, inner_input)| {
+                        slice(inner_input, position.new_with_key(key), pattern_list)
+                            .map(|inner_input| (key.clone(), inner_input))
+                    })
+                    .collect::<HashMap<_, _>>(),
+            ),
+            Input::List(list) => Input::from(
+                list.iter()
+                    .enumerate()
+                    .filter_map(|(index, inner_input)| {
+                        slice(inner_input, position.new_with_index(index), pattern_list)
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            _ => return None,
+        };
+        match &inner_input {
+            Input::Map(map) if map.is_empty() => None,
+            Input::List(list) if list.is_empty() => None,
+            _ => Some(inner_input),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn section() {
+        let mut input: Input =
+            serde_json::from_value(json!({"plugins": {"http": {"port": 80}}, "name": "host"}))
+                .unwrap();
+        let original = input.clone();
+        let mut plugins = input.take_section_guard("plugins").unwrap();
+        let mut http = plugins.take_section("http").unwrap();
+        assert_eq!(http.position().to_string(), "[plugins][http]");
+        assert_eq!(
+            http.reanchor(&InputPosition::new().new_with_key("port"))
+                .to_string(),
+            "[plugins][http][port]"
+        );
+        assert!(input.take_section("unknown").is_none());
+        assert!(input.take_section_guard("plugins").is_none());
+        http.input_mut()
+            .put_section("port", Input::from(80))
+            .unwrap();
+        http.put_back(plugins.input_mut()).unwrap();
+        plugins.put_back(&mut input).unwrap();
+        assert_eq!(input, original);
+    }
+
+    #[test]
+    fn put_section_errors() {
+        let error = Input::from(1)
+            .put_section("port", Input::from(80))
+            .unwrap_err();
+        assert!(matches!(error, InputSectionError::NotMap { .. }));
+        assert_eq!(error.into_section(), Input::from(80));
+        let mut list: Input = serde_json::from_value(json!([{"port": 80}])).unwrap();
+        let guard =
+            InputSectionGuard::new(InputPosition::new().new_with_index(0), Input::new_map());
+        assert!(matches!(
+            guard.put_back(&mut list),
+            Err(InputSectionError::Position { .. })
+        ));
+        let guard = InputSectionGuard::new(InputPosition::new(), Input::new_map());
+        assert!(matches!(
+            guard.put_back(&mut Input::new_map()),
+            Err(InputSectionError::Position { .. })
+        ));
+    }
+
+    #[test]
+    fn slice() {
+        let input: Input = serde_json::from_value(json!({
+            "plugins": {"http": {"port": 80}, "db": {"password": "x"}},
+            "servers": [{"host": "a", "port": 1}, {"host": "b"}],
+            "name": "host",
+        }))
+        .unwrap();
+        let pattern_list =
+            ["plugins.http", "servers.*.port", "name"].map(InputPositionPattern::new);
+        assert_eq!(
+            input.slice(&pattern_list),
+            serde_json::from_value::<Input>(json!({
+                "plugins": {"http": {"port": 80}},
+                "servers": [{"port": 1}],
+                "name": "host",
+            }))
+            .unwrap()
+        );
+        assert_eq!(
+            input.slice(&[InputPositionPattern::new("user")]),
+            Input::new_map()
+        );
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn reanchor_error() {
+        use crate::schema::InputSchemaType;
+
+        let mut input: Input =
+            serde_json::from_value(json!({"plugins": {"http": {"port": "foo"}}})).unwrap();
+        let http = input
+            .take_section_guard("plugins")
+            .unwrap()
+            .take_section("http")
+            .unwrap();
+        let schema_type: InputSchemaType = serde_json::from_value(json!({
+            "type": "static_map",
+            "items": {"port": {"schema": {"type": "port"}}}
+        }))
+        .unwrap();
+        let error = schema_type
+            .validate(&mut http.input().clone(), None)
+            .unwrap_err();
+        assert_eq!(error.position().to_string(), "port");
+        assert_eq!(
+            http.reanchor_error(error).position().to_string(),
+            "[plugins][http][port]"
+        );
+    }
+}
@@ -0,0 +1,268 @@
+#[cfg(feature = "schema")]
+use crate::schema::{InputSchemaError, InputSchemaType};
+use crate::{
+    diff::{diff, to_patch, InputDiff},
+    patch::{InputPatch, InputPatchError},
+    subscription::InputSubscriptions,
+    Input,
+};
+use std::sync::{
+    mpsc::{channel, Receiver, Sender},
+    Arc, Mutex, RwLock, RwLockReadGuard,
+};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Default)]
+pub struct SharedInput {
+    inner: Arc<SharedInputInner>,
+}
+
+#[derive(Debug, Default)]
+struct SharedInputInner {
+    input: RwLock<Input>,
+    #[cfg(feature = "schema")]
+    maybe_schema_type: Option<InputSchemaType>,
+    subscriber_list: Mutex<Vec<Sender<Vec<InputDiff>>>>,
+    patch_subscriber_list: Mutex<Vec<Sender<InputPatch>>>,
+    subscriptions: InputSubscriptions,
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SharedInputError {
+    #[cfg(feature = "schema")]
+    #[error("Could not validate updated input: {0}")]
+    Validation(#[from] InputSchemaError),
+    #[error("Could not apply patch: {0}")]
+    Patch(#[from] InputPatchError),
+}
+
+impl SharedInput {
+    pub fn new<I: Into<Input>>(input: I) -> Self {
+        Self {
+            inner: Arc::new(SharedInputInner {
+                input: RwLock::new(input.into()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[cfg(feature = "schema")]
+    pub fn new_with_schema<I: Into<Input>, S: Into<InputSchemaType>>(
+        input: I,
+        schema_type: S,
+    ) -> Result<Self, SharedInputError> {
+        let mut input = input.into();
+        let schema_type = schema_type.into();
+        schema_type.validate(&mut input, None)?;
+        Ok(Self {
+            inner: Arc::new(SharedInputInner {
+                input: RwLock::new(input),
+                maybe_schema_type: Some(schema_type),
+                subscriber_list: Default::default(),
+                patch_subscriber_list: Default::default(),
+                subscriptions: Default::default(),
+            }),
+        })
+    }
+
+    #[cfg(feature = "schema")]
+    pub fn maybe_schema_type(&self) -> Option<&InputSchemaType> {
+        self.inner.maybe_schema_type.as_ref()
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, Input> {
+        self.inner
+            .input
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn get(&self) -> Input {
+        self.read().clone()
+    }
+
+    pub fn update<F>(&self, update_function: F) -> Result<Vec<InputDiff>, SharedInputError>
+    where
+        F: FnOnce(&mut Input),
+    {
+        self.try_update(|input| {
+            update_function(input);
+            Ok(())
+        })
+    }
+
+    // e.g. to replay the patches of `subscribe_patches()` of another handle. Nothing changes if
+    // the patch conflicts with the current input:
+    pub fn apply_patch(&self, patch: &InputPatch) -> Result<Vec<InputDiff>, SharedInputError> {
+        self.try_update(|input| patch.apply(input).map_err(SharedInputError::from))
+    }
+
+    fn try_update<F>(&self, update_function: F) -> Result<Vec<InputDiff>, SharedInputError>
+    where
+        F: FnOnce(&mut Input) -> Result<(), SharedInputError>,
+    {
+        let diff_list = self.replace(update_function)?;
+        // Callbacks run after the write lock is released, so they can read this handle:
+        self.inner.subscriptions.notify(&diff_list);
+        Ok(diff_list)
+    }
+
+    fn replace<F>(&self, update_function: F) -> Result<Vec<InputDiff>, SharedInputError>
+    where
+        F: FnOnce(&mut Input) -> Result<(), SharedInputError>,
+    {
+        let mut input = self
+            .inner
+            .input
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut new_input = input.clone();
+        update_function(&mut new_input)?;
+        #[cfg(feature = "schema")]
+        if let Some(schema_type) = self.inner.maybe_schema_type.as_ref() {
+            schema_type.validate(&mut new_input, None)?;
+        }
+        let mut diff_list = Vec::new();
+        diff(&input, &new_input, &mut |diff| diff_list.push(diff));
+        // Notify while holding the write lock, so subscribers receive batches in order:
+        if !diff_list.is_empty() {
+            self.inner
+                .subscriber_list
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .retain(|subscriber| subscriber.send(diff_list.clone()).is_ok());
+            let mut patch_subscriber_list = self
+                .inner
+                .patch_subscriber_list
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if !patch_subscriber_list.is_empty() {
+                let patch = to_patch(&input, &new_input);
+                patch_subscriber_list.retain(|subscriber| subscriber.send(patch.clone()).is_ok());
+            }
+        }
+        *input = new_input;
+        Ok(diff_list)
+    }
+
+    pub fn subscriptions(&self) -> &InputSubscriptions {
+        &self.inner.subscriptions
+    }
+
+    pub fn subscribe(&self) -> Receiver<Vec<InputDiff>> {
+        let (sender, receiver) = channel();
+        self.inner
+            .subscriber_list
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(sender);
+        receiver
+    }
+
+    // Unlike diffs (whose list indexes are of both the old and the new list), patches of updates
+    // can be applied in order to a copy of the input (e.g. with `apply_patch()` of a replica):
+    pub fn subscribe_patches(&self) -> Receiver<InputPatch> {
+        let (sender, receiver) = channel();
+        self.inner
+            .patch_subscriber_list
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(sender);
+        receiver
+    }
+}
+
+impl From<Input> for SharedInput {
+    fn from(input: Input) -> Self {
+        Self::new(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashMap, thread};
+
+    #[test]
+    fn update() {
+        let shared = SharedInput::new(HashMap::from([("foo", 1)]));
+        let receiver = shared.subscribe();
+        let cloned = shared.clone();
+        thread::spawn(move || {
+            cloned
+                .update(|input| *input.map_mut().get_mut("foo").unwrap() = Input::from(2))
+                .unwrap();
+        })
+        .join()
+        .unwrap();
+        assert_eq!(shared.read().as_map().get("foo"), Some(&Input::from(2)));
+        let diff_list = receiver.recv().unwrap();
+        assert_eq!(diff_list.len(), 1);
+        assert_eq!(diff_list[0].maybe_new_value(), Some(&Input::from(2)));
+        assert!(shared.update(|_| ()).unwrap().is_empty());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn subscriptions() {
+        let shared = SharedInput::new(HashMap::from([("a", 1), ("b", 2)]));
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let cloned = shared.clone();
+        shared
+            .subscriptions()
+            .register("a", move |diff_list: &[InputDiff]| {
+                sender.send((diff_list.len(), cloned.get())).unwrap()
+            });
+        shared
+            .update(|input| *input.map_mut().get_mut("b").unwrap() = Input::from(3))
+            .unwrap();
+        assert!(receiver.try_recv().is_err());
+        shared
+            .update(|input| *input.map_mut().get_mut("a").unwrap() = Input::from(3))
+            .unwrap();
+        let (length, input) = receiver.try_recv().unwrap();
+        assert_eq!(length, 1);
+        assert_eq!(input.as_map().get("a"), Some(&Input::from(3)));
+    }
+
+    #[test]
+    fn subscribe_patches() {
+        let shared = SharedInput::new(Input::from([1, 2, 3]));
+        let replica = SharedInput::new(shared.get());
+        let receiver = shared.subscribe_patches();
+        shared
+            .update(|input| {
+                input.list_mut().remove(0);
+                input.list_mut().push(Input::from(4));
+            })
+            .unwrap();
+        shared.update(|input| input.list_mut().clear()).unwrap();
+        receiver
+            .try_iter()
+            .for_each(|patch| assert!(!replica.apply_patch(&patch).unwrap().is_empty()));
+        assert_eq!(replica.get(), shared.get());
+
+        let patch = to_patch(&Input::from(1), &Input::from(2));
+        assert!(matches!(
+            replica.apply_patch(&patch),
+            Err(SharedInputError::Patch(InputPatchError::Conflict { .. }))
+        ));
+        assert_eq!(replica.get(), Input::new_list());
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn validate() {
+        let schema_type = InputSchemaType::Integer(
+            crate::schema::InputSchemaTypeInteger::default().with_range(10),
+        );
+        let shared = SharedInput::new_with_schema("5", schema_type).unwrap();
+        assert_eq!(shared.get(), Input::from(5));
+        let receiver = shared.subscribe();
+        assert!(shared.update(|input| *input = Input::from(50)).is_err());
+        assert_eq!(shared.get(), Input::from(5));
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(shared.update(|input| *input = "6".into()).unwrap().len(), 1);
+        assert_eq!(shared.get(), Input::from(6));
+    }
+}
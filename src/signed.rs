@@ -0,0 +1,219 @@
+use crate::Input;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+
+pub trait InputSigner {
+    fn algorithm(&self) -> &str;
+
+    fn sign(&self, data: &[u8]) -> Vec<u8>;
+
+    // Implementations should compare in constant time:
+    fn verify(&self, data: &[u8], signature: &[u8]) -> bool;
+}
+
+#[derive(Clone)]
+pub struct InputHmacSha256Signer {
+    key: Vec<u8>,
+}
+
+// The signature is computed over `Input::to_canonical_json()` of `input`:
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InputSignedEnvelope {
+    input: Input,
+    algorithm: String,
+    // Lowercase hex:
+    signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InputSignedError {
+    #[error("Expected signature algorithm `{expected}`, got `{found}`")]
+    Algorithm { expected: String, found: String },
+    #[error("Signature is not valid hex")]
+    Encoding,
+    #[error("Signature does not match the input")]
+    Signature,
+    #[error("Expected a map with `input`, `algorithm`, and `signature` keys, got `{input}`")]
+    Invalid { input: Input },
+}
+
+impl InputHmacSha256Signer {
+    pub fn new<K: AsRef<[u8]>>(key: K) -> Self {
+        Self {
+            key: key.as_ref().to_vec(),
+        }
+    }
+
+    fn mac(&self) -> Hmac<Sha256> {
+        Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts keys of any size")
+    }
+}
+
+impl InputSigner for InputHmacSha256Signer {
+    fn algorithm(&self) -> &str {
+        "hmac-sha256"
+    }
+
+    fn sign(&self, data: &[u8]) -> Vec<u8> {
+        let mut mac = self.mac();
+        mac.update(data);
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    fn verify(&self, data: &[u8], signature: &[u8]) -> bool {
+        let mut mac = self.mac();
+        mac.update(data);
+        mac.verify_slice(signature).is_ok()
+    }
+}
+
+impl std::fmt::Debug for InputHmacSha256Signer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputHmacSha256Signer")
+            .field("key", &"<secret>")
+            .finish()
+    }
+}
+
+impl InputSignedEnvelope {
+    pub fn sign<S: InputSigner + ?Sized>(input: Input, signer: &S) -> Self {
+        let signature = to_hex(&signer.sign(input.to_canonical_json().as_bytes()));
+        Self {
+            input,
+            algorithm: signer.algorithm().to_string(),
+            signature,
+        }
+    }
+
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    pub fn algorithm(&self) -> &String {
+        &self.algorithm
+    }
+
+    pub fn signature(&self) -> &String {
+        &self.signature
+    }
+
+    pub fn verify<S: InputSigner + ?Sized>(&self, signer: &S) -> Result<(), InputSignedError> {
+        if self.algorithm != signer.algorithm() {
+            return Err(InputSignedError::Algorithm {
+                expected: signer.algorithm().to_string(),
+                found: self.algorithm.clone(),
+            });
+        }
+        let signature = from_hex(&self.signature).ok_or(InputSignedError::Encoding)?;
+        if signer.verify(self.input.to_canonical_json().as_bytes(), &signature) {
+            Ok(())
+        } else {
+            Err(InputSignedError::Signature)
+        }
+    }
+
+    pub fn verify_and_unwrap<S: InputSigner + ?Sized>(
+        self,
+        signer: &S,
+    ) -> Result<Input, InputSignedError> {
+        self.verify(signer)?;
+        Ok(self.input)
+    }
+
+    pub fn to_input(&self) -> Input {
+        Input::from(std::collections::HashMap::from([
+            ("input", self.input.clone()),
+            ("algorithm", Input::from(self.algorithm.as_str())),
+            ("signature", Input::from(self.signature.as_str())),
+        ]))
+    }
+
+    pub fn from_input(input: Input) -> Result<Self, InputSignedError> {
+        let invalid = |input: &Input| InputSignedError::Invalid {
+            input: input.clone(),
+        };
+        let mut map = match input {
+            Input::Map(ref map) if map.len() == 3 => map.clone(),
+            _ => return Err(invalid(&input)),
+        };
+        match (
+            map.remove("input"),
+            map.remove("algorithm"),
+            map.remove("signature"),
+        ) {
+            (Some(inner_input), Some(algorithm), Some(signature))
+                if algorithm.is_str() && signature.is_str() =>
+            {
+                Ok(Self {
+                    input: inner_input,
+                    algorithm: algorithm.into_str(),
+                    signature: signature.into_str(),
+                })
+            }
+            _ => Err(invalid(&input)),
+        }
+    }
+}
+
+impl Input {
+    pub fn sign<S: InputSigner + ?Sized>(self, signer: &S) -> InputSignedEnvelope {
+        InputSignedEnvelope::sign(self, signer)
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(text.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn verify_and_unwrap() {
+        let signer = InputHmacSha256Signer::new("key");
+        let input: Input = serde_json::from_value(json!({"b": 2, "a": [1.5, "x"]})).unwrap();
+        let envelope = input.clone().sign(&signer);
+        assert_eq!(envelope.algorithm(), "hmac-sha256");
+        assert_eq!(envelope.signature().len(), 64);
+        let transferred = InputSignedEnvelope::from_input(
+            serde_json::from_str(&serde_json::to_string(&envelope.to_input()).unwrap()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(transferred.verify_and_unwrap(&signer).unwrap(), input);
+
+        let mut tampered = envelope.to_input();
+        *tampered
+            .map_mut()
+            .get_mut("input")
+            .unwrap()
+            .map_mut()
+            .get_mut("b")
+            .unwrap() = Input::from(3);
+        assert_eq!(
+            InputSignedEnvelope::from_input(tampered)
+                .unwrap()
+                .verify(&signer),
+            Err(InputSignedError::Signature)
+        );
+        assert_eq!(
+            envelope.verify(&InputHmacSha256Signer::new("other key")),
+            Err(InputSignedError::Signature)
+        );
+        assert!(InputSignedEnvelope::from_input(Input::from(1)).is_err());
+    }
+}
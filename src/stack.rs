@@ -0,0 +1,152 @@
+use cfg_if::cfg_if;
+
+// With the `stacker` feature, a new stack segment is allocated once less than this is left:
+#[cfg(feature = "stacker")]
+const RED_ZONE: usize = 64 * 1024;
+#[cfg(feature = "stacker")]
+const STACK_SIZE: usize = 1024 * 1024;
+
+// Called at every level of recursive walks over inputs (and schemas) so that deeply nested
+// inputs don't overflow the stack:
+#[inline]
+pub(crate) fn maybe_grow<R, F: FnOnce() -> R>(function: F) -> R {
+    cfg_if! {
+        if #[cfg(feature = "stacker")] {
+            stacker::maybe_grow(RED_ZONE, STACK_SIZE, function)
+        } else {
+            function()
+        }
+    }
+}
+
+// Dropping is recursive too, so lists and maps with nested lists or maps are emptied into a work
+// list first and deep inputs are dropped one level at a time:
+impl Drop for crate::Input {
+    fn drop(&mut self) {
+        let is_nested = |input: &crate::Input| input.is_list() || input.is_map();
+        let mut input_list: Vec<crate::Input> = match self {
+            crate::Input::List(list) if list.iter().any(is_nested) => std::mem::take(list),
+            crate::Input::Map(map) if map.values().any(is_nested) => {
+                std::mem::take(map).into_values().collect()
+            }
+            _ => return,
+        };
+        while let Some(mut input) = input_list.pop() {
+            match &mut input {
+                crate::Input::List(list) => input_list.append(list),
+                crate::Input::Map(map) => input_list.extend(std::mem::take(map).into_values()),
+                _ => (),
+            }
+        }
+    }
+}
+
+// Same for schemas, whose children are replaced with the default (`any`):
+#[cfg(feature = "schema")]
+impl Drop for crate::schema::InputSchemaType {
+    fn drop(&mut self) {
+        let take_child_list = |schema_type: &mut crate::schema::InputSchemaType| {
+            schema_type
+                .child_list_mut()
+                .into_iter()
+                .filter(|child| !child.child_list().is_empty())
+                .map(std::mem::take)
+                .collect::<Vec<_>>()
+        };
+        let mut schema_type_list = take_child_list(self);
+        while let Some(mut schema_type) = schema_type_list.pop() {
+            schema_type_list.extend(take_child_list(&mut schema_type));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Input;
+    use std::collections::HashMap;
+
+    const DEPTH: usize = 10_000;
+
+    fn deep_map(leaf: Input) -> Input {
+        (0..DEPTH).fold(leaf, |input, _| Input::from(HashMap::from([("a", input)])))
+    }
+
+    fn deep_list(leaf: Input) -> Input {
+        (0..DEPTH).fold(leaf, |input, _| Input::from([input]))
+    }
+
+    #[test]
+    fn drop_deep() {
+        drop(deep_map(Input::from(1)));
+        drop(deep_list(Input::from(1)));
+        #[cfg(feature = "schema")]
+        drop((0..DEPTH).fold(
+            crate::schema::InputSchemaType::new_any(),
+            |schema_type, _| {
+                crate::schema::InputSchemaType::List(
+                    crate::schema::InputSchemaTypeList::default().with_item_schema(schema_type),
+                )
+            },
+        ));
+    }
+
+    // Walks only handle deep inputs with the `stacker` feature:
+    #[cfg(feature = "stacker")]
+    #[test]
+    fn merge_deep() {
+        let (mut input, other_input) = (deep_map(Input::from(1)), deep_map(Input::from(2)));
+        crate::merge::merge(&mut input, &other_input);
+        assert!(input == other_input);
+    }
+
+    #[cfg(feature = "stacker")]
+    #[test]
+    fn diff_deep() {
+        for (input, other_input) in [
+            (deep_map(Input::from(1)), deep_map(Input::from(2))),
+            (deep_list(Input::from(1)), deep_list(Input::from(2))),
+        ] {
+            let mut diff_list = Vec::new();
+            crate::diff::diff(&input, &other_input, &mut |diff| diff_list.push(diff));
+            assert_eq!(diff_list.len(), 1);
+            assert_eq!(diff_list[0].position().len(), DEPTH);
+        }
+    }
+
+    #[cfg(feature = "stacker")]
+    #[test]
+    fn fingerprint_deep() {
+        for (input, other_input) in [
+            (deep_map(Input::from(1)), deep_map(Input::from(2))),
+            (deep_list(Input::from(1)), deep_list(Input::from(2))),
+        ] {
+            assert_eq!(input.fingerprint(), input.clone().fingerprint());
+            assert_ne!(input.fingerprint(), other_input.fingerprint());
+            assert_eq!(
+                input.to_canonical_json().len(),
+                other_input.to_canonical_json().len()
+            );
+        }
+    }
+
+    #[cfg(all(feature = "schema", feature = "stacker"))]
+    #[test]
+    fn validate_deep() {
+        use crate::schema::{InputSchemaType, InputSchemaTypeDynamicMap};
+
+        let schema_type = (0..DEPTH).fold(InputSchemaType::new_integer(), |schema_type, _| {
+            InputSchemaType::DynamicMap(
+                InputSchemaTypeDynamicMap::default().with_item_schema(schema_type),
+            )
+        });
+        let (mut input, expected_input) = (deep_map(Input::from("1")), deep_map(Input::from(1)));
+        schema_type.validate(&mut input, None).unwrap();
+        assert!(input == expected_input);
+
+        let cache = crate::schema::ValidationCache::new(schema_type, 1);
+        let mut input = deep_map(Input::from("1"));
+        cache.validate(&mut input).unwrap();
+        assert!(input == expected_input);
+        assert_eq!(cache.entry_count(), 1);
+    }
+}
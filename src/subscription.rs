@@ -0,0 +1,175 @@
+#[cfg(feature = "schema")]
+use crate::{diff::diff_with_schema, schema::InputSchemaType};
+use crate::{
+    diff::{diff, InputDiff},
+    position::InputPositionPattern,
+    Input,
+};
+use std::{
+    fmt::{Debug, Formatter},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        RwLock,
+    },
+};
+
+pub type InputSubscriptionCallback = Box<dyn Fn(&[InputDiff]) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InputSubscriptionId(usize);
+
+#[derive(Default)]
+pub struct InputSubscriptions {
+    subscription_list: RwLock<Vec<InputSubscription>>,
+    next_id: AtomicUsize,
+}
+
+struct InputSubscription {
+    id: InputSubscriptionId,
+    pattern: InputPositionPattern,
+    callback: InputSubscriptionCallback,
+}
+
+impl InputSubscriptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn register<P, F>(&self, pattern: P, callback: F) -> InputSubscriptionId
+    where
+        P: Into<InputPositionPattern>,
+        F: Fn(&[InputDiff]) + Send + Sync + 'static,
+    {
+        let id = InputSubscriptionId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.subscription_list
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(InputSubscription {
+                id,
+                pattern: pattern.into(),
+                callback: Box::new(callback),
+            });
+        id
+    }
+
+    pub fn unregister(&self, id: InputSubscriptionId) -> bool {
+        let mut subscription_list = self
+            .subscription_list
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let length = subscription_list.len();
+        subscription_list.retain(|subscription| subscription.id != id);
+        subscription_list.len() != length
+    }
+
+    pub fn len(&self) -> usize {
+        self.subscription_list
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Every callback only receives the diffs that overlap its pattern and is not called at all if
+    // there is none:
+    pub fn notify(&self, diff_list: &[InputDiff]) {
+        if diff_list.is_empty() {
+            return;
+        }
+        self.subscription_list
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .for_each(|subscription| {
+                let matched_diff_list: Vec<_> = diff_list
+                    .iter()
+                    .filter(|diff| subscription.pattern.overlaps(diff.position()))
+                    .cloned()
+                    .collect();
+                if !matched_diff_list.is_empty() {
+                    (subscription.callback)(&matched_diff_list)
+                }
+            })
+    }
+
+    pub fn notify_changes(&self, old_input: &Input, new_input: &Input) -> Vec<InputDiff> {
+        let mut diff_list = Vec::new();
+        diff(old_input, new_input, &mut |diff| diff_list.push(diff));
+        self.notify(&diff_list);
+        diff_list
+    }
+
+    // Uses `diff::diff_with_schema()`, so changes of `volatile` items don't notify anyone:
+    #[cfg(feature = "schema")]
+    pub fn notify_changes_with_schema(
+        &self,
+        old_input: &Input,
+        new_input: &Input,
+        schema_type: &InputSchemaType,
+    ) -> Vec<InputDiff> {
+        let mut diff_list = Vec::new();
+        diff_with_schema(old_input, new_input, schema_type, &mut |diff| {
+            diff_list.push(diff)
+        });
+        self.notify(&diff_list);
+        diff_list
+    }
+}
+
+impl Debug for InputSubscriptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputSubscriptions")
+            .field(
+                "pattern_list",
+                &self
+                    .subscription_list
+                    .read()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .iter()
+                    .map(|subscription| subscription.pattern.to_string())
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn notify() {
+        let old_input: Input =
+            serde_json::from_value(serde_json::json!({"plugins": {"a": {"port": 80}, "b": {}}}))
+                .unwrap();
+        let new_input: Input = serde_json::from_value(
+            serde_json::json!({"plugins": {"a": {"port": 8080}, "b": {"enabled": true}}}),
+        )
+        .unwrap();
+        let subscriptions = InputSubscriptions::new();
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified_a = notified.clone();
+        let id = subscriptions.register("plugins.a.*", move |diff_list| {
+            notified_a
+                .lock()
+                .unwrap()
+                .extend(diff_list.iter().map(|diff| diff.position().to_string()))
+        });
+        let notified_c = notified.clone();
+        subscriptions.register("plugins.c.*", move |_| {
+            notified_c.lock().unwrap().push("c".to_string())
+        });
+        assert_eq!(
+            subscriptions.notify_changes(&old_input, &new_input).len(),
+            2
+        );
+        assert_eq!(*notified.lock().unwrap(), vec!["[plugins][a][port]"]);
+        assert!(subscriptions.unregister(id));
+        assert!(!subscriptions.unregister(id));
+        assert_eq!(subscriptions.len(), 1);
+    }
+}
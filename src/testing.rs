@@ -0,0 +1,117 @@
+#[cfg(feature = "schema")]
+use crate::schema::InputSchemaType;
+use crate::{
+    diff::{diff, InputDiffAction},
+    Input,
+};
+use std::path::Path;
+
+// Snapshots are (re)written instead of compared if this variable is set:
+pub const UPDATE_SNAPSHOTS_VARIABLE: &str = "PLUGX_INPUT_UPDATE_SNAPSHOTS";
+
+// One line per difference (`-` for old and `+` for new values), colored unless `NO_COLOR` is set:
+pub fn diff_text(left: &Input, right: &Input) -> String {
+    let (red, green, reset) = if std::env::var_os("NO_COLOR").is_some() {
+        ("", "", "")
+    } else {
+        ("\x1b[31m", "\x1b[32m", "\x1b[0m")
+    };
+    let mut line_list = Vec::new();
+    diff(left, right, &mut |diff| {
+        let position = if diff.position().is_empty() {
+            "<root>".to_string()
+        } else {
+            diff.position().to_string()
+        };
+        if let Some(old_value) = diff.maybe_old_value() {
+            line_list.push(format!("{red}- {position}: {old_value}{reset}"))
+        }
+        if let Some(new_value) = diff.maybe_new_value() {
+            line_list.push(format!("{green}+ {position}: {new_value}{reset}"))
+        }
+        if let InputDiffAction::TypeChanged { .. } = diff.action() {
+            line_list.push(format!("  {position}: {}", diff.action()))
+        }
+    });
+    line_list.join("\n")
+}
+
+#[track_caller]
+pub fn assert_input_eq(left: &Input, right: &Input) {
+    if left != right {
+        panic!(
+            "inputs are not equal (- left, + right):\n{}",
+            diff_text(left, right)
+        )
+    }
+}
+
+// Loads `.json`, `.env` (with `__` nesting), and (with the `ini` feature) `.ini` files:
+#[track_caller]
+pub fn load_fixture<P: AsRef<Path>>(path: P) -> Input {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+        .unwrap_or_else(|error| panic!("could not read fixture {path:?}: {error}"));
+    let result = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => serde_json::from_str(&text).map_err(|error| error.to_string()),
+        Some("env") => Input::from_dotenv_str(&text, "__").map_err(|error| error.to_string()),
+        #[cfg(feature = "ini")]
+        Some("ini") => Input::from_ini_str(&text).map_err(|error| error.to_string()),
+        _ => Err("unsupported fixture extension".to_string()),
+    };
+    result.unwrap_or_else(|error| panic!("could not load fixture {path:?}: {error}"))
+}
+
+// Compares the input with the JSON snapshot file, or writes the file if it doesn't exist (or
+// `UPDATE_SNAPSHOTS_VARIABLE` is set). Keys are written in order so snapshots are stable:
+#[track_caller]
+pub fn assert_snapshot<P: AsRef<Path>>(path: P, input: &Input) {
+    let path = path.as_ref();
+    if path.exists() && std::env::var_os(UPDATE_SNAPSHOTS_VARIABLE).is_none() {
+        return assert_input_eq(&load_fixture(path), input);
+    }
+    let text = serde_json::to_value(input)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .unwrap_or_else(|error| panic!("could not serialize snapshot {path:?}: {error}"));
+    std::fs::write(path, text + "\n")
+        .unwrap_or_else(|error| panic!("could not write snapshot {path:?}: {error}"));
+}
+
+// Snapshots the validated input (with coerced values and defaults):
+#[cfg(feature = "schema")]
+#[track_caller]
+pub fn assert_validated_snapshot<P: AsRef<Path>>(
+    path: P,
+    schema_type: &InputSchemaType,
+    input: &Input,
+) {
+    let mut validated_input = input.clone();
+    if let Err(error) = schema_type.validate(&mut validated_input, None) {
+        panic!("input is not valid: {error}")
+    }
+    assert_snapshot(path, &validated_input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn assert_snapshot() {
+        let dir = tempdir::TempDir::new("plugx-input").unwrap();
+        let path = dir.path().join("snapshot.json");
+        let input: Input = serde_json::from_value(json!({"b": [1, 2], "a": {"c": null}})).unwrap();
+        super::assert_snapshot(&path, &input);
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("{\n  \"a\""));
+        super::assert_snapshot(&path, &input);
+        assert_input_eq(&load_fixture(&path), &input);
+
+        let other_input: Input = serde_json::from_value(json!({"b": [1], "a": 1})).unwrap();
+        let text = diff_text(&input, &other_input);
+        assert!(text.contains("- [b][1]: 2"));
+        assert!(text.contains("+ a: 1"));
+    }
+}
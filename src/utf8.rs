@@ -0,0 +1,163 @@
+use crate::{
+    base64::{decode_base64, encode_base64},
+    Input,
+};
+use std::{borrow::Cow, collections::HashMap};
+
+// The only key of maps that hold bytes which are not valid UTF-8, e.g. `{"$bytes": "/w=="}` with
+// the base64 string of the bytes, so they can't be mistaken for strings:
+pub const BYTES_KEY: &str = "$bytes";
+
+// How loaders (dotenv, INI, and env) handle text that is not valid UTF-8:
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputUtf8Policy {
+    #[default]
+    Error,
+    // Invalid sequences are replaced with `U+FFFD`:
+    Lossy,
+    // Values that are not valid UTF-8 are kept as bytes (see `Input::new_bytes()`) after quotes,
+    // escapes, and comments are handled. Keys and section headers still have to be valid UTF-8:
+    Bytes,
+}
+
+// With `Bytes`, every invalid byte of a line is replaced with a character of this private use
+// range (`U+10FF00` plus the byte), so the format parser can handle the line as text:
+const BYTE_MARKER_RANGE: std::ops::RangeInclusive<u32> = 0x10FF00..=0x10FFFF;
+
+impl Input {
+    pub fn new_bytes(bytes: &[u8]) -> Self {
+        Input::from(HashMap::from([(
+            BYTES_KEY.to_string(),
+            Input::from(encode_base64(bytes)),
+        )]))
+    }
+
+    pub fn is_bytes(&self) -> bool {
+        self.maybe_bytes().is_some()
+    }
+
+    pub fn maybe_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            Input::Map(map) if map.len() == 1 => match map.get(BYTES_KEY)? {
+                Input::Str(text) => decode_base64(text),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl InputUtf8Policy {
+    // `None` if the value is rejected (e.g. for custom loaders of `include::InputIncludeResolver`):
+    pub fn decode_value(self, bytes: &[u8]) -> Option<Input> {
+        match (std::str::from_utf8(bytes), self) {
+            (Ok(text), _) => Some(Input::from(text)),
+            (Err(_), Self::Error) => None,
+            (Err(_), Self::Lossy) => Some(Input::from(String::from_utf8_lossy(bytes).into_owned())),
+            (Err(_), Self::Bytes) => Some(Input::new_bytes(bytes)),
+        }
+    }
+
+    // Decodes `KEY=VALUE` lines. With `Bytes`, the parsed input has to be passed to
+    // `restore_bytes()`. On error, returns the line number of the first invalid line:
+    pub(crate) fn decode_lines(self, bytes: &[u8]) -> Result<String, usize> {
+        let line_list = bytes
+            .split(|byte| *byte == b'\n')
+            .enumerate()
+            .map(|(index, line)| match (std::str::from_utf8(line), self) {
+                (Ok(text), Self::Bytes) if has_byte_marker(text) => Err(index + 1),
+                (Ok(text), _) => Ok(Cow::Borrowed(text)),
+                (Err(_), Self::Error) => Err(index + 1),
+                (Err(_), Self::Lossy) => Ok(String::from_utf8_lossy(line)),
+                (Err(_), Self::Bytes) => mark_bytes(line).map(Cow::Owned).ok_or(index + 1),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(line_list.join("\n"))
+    }
+
+    // Replaces the strings that contain invalid bytes of `decode_lines()` with bytes:
+    pub(crate) fn restore_bytes(self, input: &mut Input) {
+        if self != Self::Bytes {
+            return;
+        }
+        crate::stack::maybe_grow(|| match input {
+            Input::Str(text) if has_byte_marker(text) => {
+                let mut bytes = Vec::with_capacity(text.len());
+                text.chars().for_each(|character| {
+                    if BYTE_MARKER_RANGE.contains(&(character as u32)) {
+                        bytes.push((character as u32 - BYTE_MARKER_RANGE.start()) as u8)
+                    } else {
+                        let mut buffer = [0; 4];
+                        bytes.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes())
+                    }
+                });
+                *input = Input::new_bytes(&bytes)
+            }
+            Input::List(list) => list.iter_mut().for_each(|input| self.restore_bytes(input)),
+            Input::Map(map) => map.values_mut().for_each(|input| self.restore_bytes(input)),
+            _ => (),
+        })
+    }
+}
+
+fn has_byte_marker(text: &str) -> bool {
+    text.chars()
+        .any(|character| BYTE_MARKER_RANGE.contains(&(character as u32)))
+}
+
+// `None` if the key (before the first `=`) or a section header is not valid UTF-8:
+fn mark_bytes(line: &[u8]) -> Option<String> {
+    let separator_index = line.iter().position(|byte| *byte == b'=')?;
+    std::str::from_utf8(&line[..separator_index]).ok()?;
+    let mut text = String::with_capacity(line.len());
+    for chunk in line.utf8_chunks() {
+        if has_byte_marker(chunk.valid()) {
+            return None;
+        }
+        text.push_str(chunk.valid());
+        chunk.invalid().iter().for_each(|byte| {
+            text.push(char::from_u32(BYTE_MARKER_RANGE.start() + *byte as u32).unwrap())
+        });
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_lines() {
+        let bytes = b"A=1\nB=\xff\xfe\nC=3";
+        assert_eq!(InputUtf8Policy::Error.decode_lines(bytes), Err(2));
+        assert_eq!(
+            InputUtf8Policy::Lossy.decode_lines(bytes).unwrap(),
+            "A=1\nB=\u{fffd}\u{fffd}\nC=3"
+        );
+        let mut input = Input::from(
+            InputUtf8Policy::Bytes
+                .decode_lines(bytes)
+                .unwrap()
+                .split('\n')
+                .map(Input::from)
+                .collect::<Vec<_>>(),
+        );
+        InputUtf8Policy::Bytes.restore_bytes(&mut input);
+        assert_eq!(input.as_list()[0], Input::from("A=1"));
+        assert_eq!(input.as_list()[1].maybe_bytes().unwrap(), b"B=\xff\xfe");
+        assert_eq!(InputUtf8Policy::Bytes.decode_lines(b"\xff=1"), Err(1));
+        assert_eq!(
+            InputUtf8Policy::Bytes.decode_lines("A=\u{10ff00}".as_bytes()),
+            Err(1)
+        );
+        assert_eq!(
+            InputUtf8Policy::Bytes.decode_value(b"\xff"),
+            Some(Input::new_bytes(b"\xff"))
+        );
+        assert!(!Input::from("/w==").is_bytes());
+        assert_eq!(
+            Input::new_bytes(b"\xff").to_string(),
+            r#"{"$bytes": "/w=="}"#
+        );
+    }
+}